@@ -0,0 +1,80 @@
+//! Compare rendering mostly static pages with and without statements.
+//!
+//! Run with `cargo bench --bench render`.
+use bracket::Registry;
+use criterion::{
+    criterion_group, criterion_main, BenchmarkId, Criterion, Throughput,
+};
+use serde_json::{json, Value};
+use std::hint::black_box;
+
+fn page(paragraphs: usize) -> String {
+    let mut page = String::from("<!doctype html>\n<html>\n<body>\n");
+    for index in 0..paragraphs {
+        page.push_str(&format!(
+            "<p id=\"p{}\">Lorem ipsum dolor sit amet, consectetur \
+            adipiscing elit, sed do eiusmod tempor incididunt.</p>\n",
+            index
+        ));
+    }
+    page.push_str("</body>\n</html>\n");
+    page
+}
+
+fn data() -> Value {
+    json!({
+        "title": "Benchmark",
+        "items": (0..100).collect::<Vec<_>>(),
+    })
+}
+
+/// Register the same page as plain text, with a comment
+/// and with a statement that requires the render loop.
+fn registry(page: &str) -> Registry<'static> {
+    let mut registry = Registry::new();
+    registry.insert("text", page).unwrap();
+    registry
+        .insert("comment", format!("{{{{! page }}}}{}", page))
+        .unwrap();
+    registry
+        .insert("statement", format!("{}{{{{title}}}}", page))
+        .unwrap();
+    registry
+}
+
+fn static_page(c: &mut Criterion) {
+    let reg = registry(&page(200));
+    let data = data();
+
+    let mut group = c.benchmark_group("static_page");
+    for name in ["text", "comment", "statement"] {
+        group.bench_function(name, |b| {
+            b.iter(|| reg.render(black_box(name), &data).unwrap())
+        });
+    }
+    group.finish();
+}
+
+fn page_size(c: &mut Criterion) {
+    let data = data();
+
+    let mut group = c.benchmark_group("page_size");
+    for paragraphs in [10, 100, 1_000] {
+        let page = page(paragraphs);
+        let reg = registry(&page);
+        group.throughput(Throughput::Bytes(page.len() as u64));
+        for name in ["text", "statement"] {
+            group.bench_with_input(
+                BenchmarkId::new(name, paragraphs),
+                &name,
+                |b, name| {
+                    b.iter(|| reg.render(black_box(name), &data).unwrap())
+                },
+            );
+        }
+    }
+    group.finish();
+}
+
+criterion_group!(benches, static_page, page_size);
+criterion_main!(benches);
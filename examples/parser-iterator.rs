@@ -10,6 +10,7 @@ fn main() -> Result<()> {
         file_name: String::from("document.md"),
         line_offset: 0,
         byte_offset: 0,
+        ..Default::default()
     };
     let parser = Parser::new(content, options);
     for node in parser {
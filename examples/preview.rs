@@ -0,0 +1,17 @@
+extern crate log;
+extern crate pretty_env_logger;
+
+use bracket::preview::Preview;
+
+fn main() {
+    std::env::set_var("RUST_LOG", "info");
+    pretty_env_logger::init();
+
+    // NOTE: Pass the directory of templates as the first argument
+    let dir = std::env::args()
+        .nth(1)
+        .unwrap_or_else(|| String::from("examples/files/partials"));
+    if let Err(e) = Preview::new(dir).serve(([127, 0, 0, 1], 8080)) {
+        log::error!("{:?}", e);
+    }
+}
@@ -0,0 +1,14 @@
+#![no_main]
+use libfuzzer_sys::fuzz_target;
+
+use bracket::{parser::ParserOptions, Registry};
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(source) = std::str::from_utf8(data) {
+        let registry = Registry::new();
+        // Syntax errors are expected, panics are not
+        if let Err(e) = registry.compile(source, ParserOptions::default()) {
+            let _ = format!("{:?}", e);
+        }
+    }
+});
@@ -0,0 +1,17 @@
+#![no_main]
+use libfuzzer_sys::fuzz_target;
+
+use bracket::Registry;
+use serde_json::json;
+
+fuzz_target!(|data: &[u8]| {
+    if let Ok(source) = std::str::from_utf8(data) {
+        let registry = Registry::new();
+        let data = json!({
+            "title": "Fuzz",
+            "list": [1, 2, 3],
+            "map": {"foo": "bar", "qux": null},
+        });
+        let _ = registry.once("fuzz.hbs", source, &data);
+    }
+});
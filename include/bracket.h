@@ -0,0 +1,86 @@
+#ifndef BRACKET_H
+#define BRACKET_H
+
+/* Generated with cbindgen, do not edit by hand. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+/**
+ * Opaque registry handle.
+ */
+typedef struct BracketRegistry BracketRegistry;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+/**
+ * Create a new registry.
+ *
+ * The registry must be released with `bracket_registry_free()`.
+ */
+BracketRegistry *bracket_registry_new(void);
+
+/**
+ * Release a registry.
+ *
+ * # Safety
+ *
+ * The registry must have been created with `bracket_registry_new()`
+ * and must not be used after it has been released.
+ */
+void bracket_registry_free(BracketRegistry *registry);
+
+/**
+ * Compile a template and add it to the registry.
+ *
+ * Returns zero on success or `-1` when an argument is invalid or
+ * the template has a syntax error.
+ *
+ * # Safety
+ *
+ * The registry must be a valid handle and `name` and `source` must
+ * be valid NUL-terminated strings.
+ */
+int bracket_register_template(BracketRegistry *registry, const char *name, const char *source);
+
+/**
+ * Render a named template with data encoded as a JSON string.
+ *
+ * Returns the rendered string or `NULL` on error; the returned string
+ * must be released with `bracket_string_free()`.
+ *
+ * # Safety
+ *
+ * The registry must be a valid handle and `name` and `json` must
+ * be valid NUL-terminated strings.
+ */
+char *bracket_render_json(const BracketRegistry *registry, const char *name, const char *json);
+
+/**
+ * Release a string returned by `bracket_render_json()`.
+ *
+ * # Safety
+ *
+ * The string must have been returned by this library and must not
+ * be used after it has been released.
+ */
+void bracket_string_free(char *value);
+
+/**
+ * Get the message for the last error on the calling thread.
+ *
+ * Returns `NULL` when no error has occurred; the pointer is owned
+ * by the library and is valid until the next failing call on the
+ * same thread.
+ */
+const char *bracket_last_error(void);
+
+#ifdef __cplusplus
+} // extern "C"
+#endif // __cplusplus
+
+#endif /* BRACKET_H */
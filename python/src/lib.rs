@@ -0,0 +1,314 @@
+//! Python bindings for the bracket template engine.
+//!
+//! Build and install the module with [maturin][]:
+//!
+//! ```ignore
+//! cd python && maturin develop
+//! ```
+//!
+//! Data is converted from Python values the same way as JSON so
+//! dictionaries, lists, strings, numbers, booleans and `None` are
+//! supported:
+//!
+//! ```ignore
+//! import bracket
+//!
+//! registry = bracket.Registry()
+//! registry.insert("greeting", "Hello {{upper name}}!")
+//! registry.register_helper("upper", lambda value: value.upper())
+//! print(registry.render("greeting", {"name": "world"}))
+//! ```
+//!
+//! Helpers are called with the helper arguments as positional
+//! arguments and the hash parameters as keyword arguments; the
+//! return value is converted back and written to the output unless
+//! it is `None`.
+//!
+//! [maturin]: https://github.com/PyO3/maturin
+use pyo3::create_exception;
+use pyo3::exceptions::{PyException, PyTypeError};
+use pyo3::prelude::*;
+use pyo3::types::{PyBool, PyDict, PyFloat, PyInt, PyList, PyString, PyTuple};
+use serde_json::{Map, Number, Value};
+use std::collections::BTreeSet;
+use std::sync::Mutex;
+
+use bracket::helper::prelude::*;
+
+create_exception!(bracket, BracketError, PyException);
+
+/// Helper names registered from Python.
+///
+/// Helper names are borrowed for the lifetime of the registry which
+/// is static here so each distinct name is leaked once and reused.
+static HELPER_NAMES: Mutex<BTreeSet<&'static str>> =
+    Mutex::new(BTreeSet::new());
+
+/// Get the static helper name for a name.
+fn intern(name: &str) -> &'static str {
+    let mut names = HELPER_NAMES.lock().unwrap_or_else(|e| e.into_inner());
+    match names.get(name) {
+        Some(name) => name,
+        None => {
+            let name: &'static str =
+                Box::leak(name.to_string().into_boxed_str());
+            names.insert(name);
+            name
+        }
+    }
+}
+
+/// Convert a Python object to a JSON value.
+pub fn to_value(obj: &Bound<'_, PyAny>) -> PyResult<Value> {
+    if obj.is_none() {
+        Ok(Value::Null)
+    } else if obj.is_instance_of::<PyBool>() {
+        Ok(Value::Bool(obj.extract()?))
+    } else if obj.is_instance_of::<PyInt>() {
+        if let Ok(value) = obj.extract::<i64>() {
+            Ok(Value::Number(value.into()))
+        } else if let Ok(value) = obj.extract::<u64>() {
+            Ok(Value::Number(value.into()))
+        } else {
+            float(obj.extract()?)
+        }
+    } else if obj.is_instance_of::<PyFloat>() {
+        float(obj.extract()?)
+    } else if let Ok(value) = obj.downcast::<PyString>() {
+        Ok(Value::String(value.to_str()?.to_string()))
+    } else if let Ok(dict) = obj.downcast::<PyDict>() {
+        let mut map = Map::new();
+        for (key, value) in dict.iter() {
+            map.insert(key.str()?.to_str()?.to_string(), to_value(&value)?);
+        }
+        Ok(Value::Object(map))
+    } else if obj.is_instance_of::<PyList>() || obj.is_instance_of::<PyTuple>()
+    {
+        let mut list = Vec::new();
+        for value in obj.try_iter()? {
+            list.push(to_value(&value?)?);
+        }
+        Ok(Value::Array(list))
+    } else {
+        Err(PyTypeError::new_err(format!(
+            "Unsupported type '{}' for template data",
+            obj.get_type().name()?
+        )))
+    }
+}
+
+fn float(value: f64) -> PyResult<Value> {
+    Number::from_f64(value).map(Value::Number).ok_or_else(|| {
+        PyTypeError::new_err("Non-finite numbers are not supported")
+    })
+}
+
+/// Convert a JSON value to a Python object.
+pub fn from_value(py: Python<'_>, value: &Value) -> PyResult<PyObject> {
+    Ok(match value {
+        Value::Null => py.None(),
+        Value::Bool(b) => b.into_pyobject(py)?.to_owned().into_any().unbind(),
+        Value::Number(n) => {
+            if let Some(n) = n.as_i64() {
+                n.into_pyobject(py)?.into_any().unbind()
+            } else if let Some(n) = n.as_u64() {
+                n.into_pyobject(py)?.into_any().unbind()
+            } else {
+                n.as_f64()
+                    .unwrap_or_default()
+                    .into_pyobject(py)?
+                    .into_any()
+                    .unbind()
+            }
+        }
+        Value::String(s) => s.into_pyobject(py)?.into_any().unbind(),
+        Value::Array(list) => {
+            let items = PyList::empty(py);
+            for value in list {
+                items.append(from_value(py, value)?)?;
+            }
+            items.into_any().unbind()
+        }
+        Value::Object(map) => {
+            let dict = PyDict::new(py);
+            for (key, value) in map {
+                dict.set_item(key, from_value(py, value)?)?;
+            }
+            dict.into_any().unbind()
+        }
+    })
+}
+
+fn data(data: Option<&Bound<'_, PyAny>>) -> PyResult<Value> {
+    match data {
+        Some(data) => to_value(data),
+        None => Ok(Value::Object(Map::new())),
+    }
+}
+
+fn error(e: bracket::Error) -> PyErr {
+    BracketError::new_err(format!("{:?}", e))
+}
+
+/// Helper that calls a Python function.
+struct PythonHelper {
+    function: PyObject,
+}
+
+impl Helper for PythonHelper {
+    fn call<'render, 'call>(
+        &self,
+        _rc: &mut Render<'render>,
+        ctx: &Context<'call>,
+        _template: Option<&'render Node<'render>>,
+    ) -> HelperValue {
+        Python::with_gil(|py| {
+            let result = (|| -> PyResult<Value> {
+                let mut args = Vec::new();
+                for value in ctx.arguments() {
+                    args.push(from_value(py, value)?);
+                }
+                let kwargs = PyDict::new(py);
+                for (key, value) in ctx.parameters() {
+                    kwargs.set_item(key, from_value(py, value)?)?;
+                }
+                let args = PyTuple::new(py, args)?;
+                let result = self.function.call(py, args, Some(&kwargs))?;
+                to_value(result.bind(py))
+            })();
+            match result {
+                Ok(Value::Null) => Ok(None),
+                Ok(value) => Ok(Some(value)),
+                Err(e) => Err(HelperError::Message(format!(
+                    "Helper '{}' raised {}",
+                    ctx.name(),
+                    e
+                ))),
+            }
+        })
+    }
+}
+
+/// Compiled template.
+#[pyclass(module = "bracket")]
+pub struct Template {
+    name: String,
+    template: bracket::Template,
+}
+
+#[pymethods]
+impl Template {
+    /// The name given when the template was compiled.
+    #[getter]
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The source string for the template.
+    #[getter]
+    fn source(&self) -> &str {
+        self.template.source()
+    }
+
+    fn __str__(&self) -> String {
+        self.template.to_string()
+    }
+}
+
+/// Registry of templates and helpers.
+#[pyclass(module = "bracket")]
+pub struct Registry {
+    registry: bracket::Registry<'static>,
+}
+
+#[pymethods]
+impl Registry {
+    #[new]
+    fn new() -> Self {
+        Self {
+            registry: bracket::Registry::new(),
+        }
+    }
+
+    /// Set the strict mode flag.
+    fn set_strict(&mut self, strict: bool) {
+        self.registry.set_strict(strict);
+    }
+
+    /// Compile a template and add it to the registry.
+    fn insert(&mut self, name: &str, source: &str) -> PyResult<()> {
+        self.registry.insert(name, source).map_err(error)
+    }
+
+    /// Compile a template without adding it to the registry.
+    fn parse(&self, name: &str, source: &str) -> PyResult<Template> {
+        let template = self.registry.parse(name, source).map_err(error)?;
+        Ok(Template {
+            name: name.to_string(),
+            template,
+        })
+    }
+
+    /// Register a Python function as a helper.
+    fn register_helper(&mut self, name: &str, function: PyObject) {
+        self.registry
+            .helpers_mut()
+            .insert(intern(name), Box::new(PythonHelper { function }));
+    }
+
+    /// Render a named template.
+    #[pyo3(signature = (name, data=None))]
+    fn render(
+        &self,
+        py: Python<'_>,
+        name: &str,
+        data: Option<&Bound<'_, PyAny>>,
+    ) -> PyResult<String> {
+        let data = self::data(data)?;
+        py.allow_threads(|| self.registry.render(name, &data))
+            .map_err(error)
+    }
+
+    /// Render a compiled template.
+    #[pyo3(signature = (template, data=None))]
+    fn render_template(
+        &self,
+        py: Python<'_>,
+        template: &Template,
+        data: Option<&Bound<'_, PyAny>>,
+    ) -> PyResult<String> {
+        let data = self::data(data)?;
+        py.allow_threads(|| {
+            self.registry.render_template(
+                &template.name,
+                &template.template,
+                &data,
+            )
+        })
+        .map_err(error)
+    }
+
+    /// Compile and render a template string.
+    #[pyo3(signature = (name, source, data=None))]
+    fn once(
+        &self,
+        py: Python<'_>,
+        name: &str,
+        source: &str,
+        data: Option<&Bound<'_, PyAny>>,
+    ) -> PyResult<String> {
+        let data = self::data(data)?;
+        py.allow_threads(|| self.registry.once(name, source, &data))
+            .map_err(error)
+    }
+}
+
+/// Python module definition.
+#[pymodule]
+#[pyo3(name = "bracket")]
+pub fn init(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<Registry>()?;
+    m.add_class::<Template>()?;
+    m.add("BracketError", m.py().get_type::<BracketError>())?;
+    Ok(())
+}
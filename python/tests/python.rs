@@ -0,0 +1,62 @@
+use pyo3::prelude::*;
+use pyo3::types::PyDict;
+use std::ffi::CString;
+
+fn run(code: &str) -> PyResult<String> {
+    Python::with_gil(|py| {
+        let module = PyModule::new(py, "bracket")?;
+        bracket_python::init(&module)?;
+        let locals = PyDict::new(py);
+        locals.set_item("bracket", module)?;
+        let code = CString::new(code).unwrap();
+        py.run(&code, None, Some(&locals))?;
+        locals.get_item("result")?.unwrap().extract()
+    })
+}
+
+#[test]
+fn python_render() -> PyResult<()> {
+    let result = run(r#"
+registry = bracket.Registry()
+registry.insert("greeting", "Hello {{name}}, {{#each list}}{{this}}{{/each}}")
+result = registry.render("greeting", {"name": "world", "list": [1, 2.5, True]})
+"#)?;
+    assert_eq!("Hello world, 12.5true", result);
+    Ok(())
+}
+
+#[test]
+fn python_template() -> PyResult<()> {
+    let result = run(r#"
+registry = bracket.Registry()
+template = registry.parse("page", "{{title}}")
+result = registry.render_template(template, {"title": "<Title>"})
+"#)?;
+    assert_eq!("&lt;Title&gt;", result);
+    Ok(())
+}
+
+#[test]
+fn python_helper() -> PyResult<()> {
+    let result = run(r#"
+registry = bracket.Registry()
+registry.register_helper("join", lambda *args, sep="": sep.join(args))
+result = registry.once("join", "{{join a b sep=\"-\"}}", {"a": "x", "b": "y"})
+"#)?;
+    assert_eq!("x-y", result);
+    Ok(())
+}
+
+#[test]
+fn python_syntax_error() -> PyResult<()> {
+    let result = run(r#"
+registry = bracket.Registry()
+try:
+    registry.insert("bad", "{{}}")
+    result = "no error"
+except bracket.BracketError as e:
+    result = str(e).splitlines()[0]
+"#)?;
+    assert_eq!("Syntax error, expecting identifier", result);
+    Ok(())
+}
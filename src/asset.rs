@@ -0,0 +1,140 @@
+//! Manifests that map asset paths to fingerprinted URLs.
+//!
+//! The `asset` helper resolves a logical path such as `css/app.css`
+//! using the manifest assigned to the registry so templates do not
+//! need to know the hashed file names generated by a build tool:
+//!
+//! ```ignore
+//! use bracket::asset::JsonManifest;
+//! let mut manifest = JsonManifest::load("dist/manifest.json")?;
+//! manifest.set_base("/static/");
+//! registry.set_manifest(Box::new(manifest));
+//! ```
+//!
+//! Then `{{asset "css/app.css"}}` renders `/static/css/app.3f2a9c.css`.
+//!
+//! Closures can also be assigned as a manifest:
+//!
+//! ```ignore
+//! registry.set_manifest(Box::new(|path: &str| {
+//!     Some(format!("/static/{}?v={}", path, VERSION))
+//! }));
+//! ```
+use alloc::{
+    format,
+    string::{String, ToString},
+};
+
+use serde_json::Value;
+
+use crate::{collections::HashMap, error::RenderError, Error, Result};
+
+/// Trait for types that map asset paths to URLs.
+pub trait Manifest: Send + Sync {
+    /// Get the URL for a logical asset path.
+    fn url(&self, path: &str) -> Option<String>;
+}
+
+impl<F> Manifest for F
+where
+    F: Fn(&str) -> Option<String> + Send + Sync,
+{
+    fn url(&self, path: &str) -> Option<String> {
+        (self)(path)
+    }
+}
+
+/// Manifest of asset paths parsed from JSON.
+///
+/// Accepts the manifest formats written by common build tools; an
+/// object where the values are the fingerprinted file names:
+///
+/// ```json
+/// {"css/app.css": "css/app.3f2a9c.css"}
+/// ```
+///
+/// Or an object where the values are objects with a `file` field:
+///
+/// ```json
+/// {"src/main.js": {"file": "assets/main.4b1e7d.js"}}
+/// ```
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct JsonManifest {
+    base: String,
+    entries: HashMap<String, String>,
+}
+
+impl JsonManifest {
+    /// Create an empty manifest.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Parse a manifest from JSON.
+    pub fn parse(content: &str) -> Result<Self> {
+        let value: Value = serde_json::from_str(content)
+            .map_err(|e| Error::from(RenderError::from(e)))?;
+        let mut manifest = Self::new();
+        if let Value::Object(map) = value {
+            for (path, entry) in map {
+                let file = match entry {
+                    Value::String(file) => Some(file),
+                    Value::Object(ref entry) => entry
+                        .get("file")
+                        .and_then(|f| f.as_str())
+                        .map(|f| f.to_string()),
+                    _ => None,
+                };
+                if let Some(file) = file {
+                    manifest.insert(path, file);
+                }
+            }
+        }
+        Ok(manifest)
+    }
+
+    /// Load a manifest from a JSON file.
+    #[cfg(feature = "std")]
+    pub fn load<P: AsRef<std::path::Path>>(file: P) -> Result<Self> {
+        let content = std::fs::read_to_string(file)?;
+        Self::parse(&content)
+    }
+
+    /// Set the prefix for URLs, for example `/static/`.
+    pub fn set_base<S: Into<String>>(&mut self, base: S) {
+        self.base = base.into();
+    }
+
+    /// Get the prefix for URLs.
+    pub fn base(&self) -> &str {
+        &self.base
+    }
+
+    /// Insert the file for an asset path.
+    pub fn insert<P, F>(&mut self, path: P, file: F)
+    where
+        P: Into<String>,
+        F: Into<String>,
+    {
+        self.entries.insert(path.into(), file.into());
+    }
+
+    /// Get the file for an asset path without the prefix.
+    pub fn get(&self, path: &str) -> Option<&str> {
+        self.entries.get(path).map(|f| f.as_str())
+    }
+}
+
+impl Manifest for JsonManifest {
+    fn url(&self, path: &str) -> Option<String> {
+        let path = path.trim_start_matches('/');
+        self.get(path).map(|file| {
+            let file = file.trim_start_matches('/');
+            if self.base.is_empty() || self.base.ends_with('/') {
+                format!("{}{}", self.base, file)
+            } else {
+                format!("{}/{}", self.base, file)
+            }
+        })
+    }
+}
@@ -0,0 +1,418 @@
+//! Build templates in code without writing template source.
+//!
+//! A [DocumentBuilder](DocumentBuilder) appends text, statements and
+//! blocks to a document and compiles the result into a
+//! [Template](crate::Template); text is always rendered verbatim so
+//! it may contain characters that are special in template source:
+//!
+//! ```ignore
+//! use bracket::builder::{Argument, CallBuilder, DocumentBuilder};
+//! let mut doc = DocumentBuilder::new();
+//! doc.text("<ul>")
+//!     .block(CallBuilder::new("each").arg(Argument::path("items")), |b| {
+//!         b.text("<li>{{")
+//!             .statement(CallBuilder::new("this"))
+//!             .text("}}</li>");
+//!     })
+//!     .text("</ul>");
+//! registry.templates_mut().insert("list".to_string(), doc.build()?);
+//! ```
+//!
+//! Literal values are passed to helpers exactly as given, values that
+//! have no literal syntax such as objects, arrays and strings that
+//! contain a backslash are written as `null` in the template source.
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::ops::Range;
+use serde_json::Value;
+
+use crate::{
+    error::{ErrorInfo, SourcePos, SyntaxError},
+    parser::{
+        ast::{
+            Block, Call, Document, Element, Lines, Node, ParameterValue, Slice,
+            Text,
+        },
+        Parser, ParserOptions, UNKNOWN,
+    },
+    SyntaxResult, Template,
+};
+
+/// Argument or hash parameter value for a call.
+#[derive(Clone, Debug)]
+pub enum Argument {
+    /// Path to a variable resolved at runtime.
+    Path(String),
+    /// Literal JSON value.
+    Value(Value),
+    /// Sub-expression invoked at runtime to determine the value.
+    Call(CallBuilder),
+}
+
+impl Argument {
+    /// Create a path argument.
+    pub fn path<S: Into<String>>(path: S) -> Self {
+        Self::Path(path.into())
+    }
+
+    /// Create a literal argument.
+    pub fn value<V: Into<Value>>(value: V) -> Self {
+        Self::Value(value.into())
+    }
+
+    fn write(&self, out: &mut String) {
+        match *self {
+            Self::Path(ref path) => out.push_str(path),
+            Self::Value(ref value) => write_value(value, out),
+            Self::Call(ref call) => {
+                out.push('(');
+                call.write(out);
+                out.push(')');
+            }
+        }
+    }
+
+    fn fill(&self, param: &mut ParameterValue<'_>) {
+        match (self, param) {
+            (Self::Value(ref value), ParameterValue::Json { value: v, .. }) => {
+                *v = value.clone();
+            }
+            (
+                Self::Call(ref builder),
+                ParameterValue::SubExpr(ref mut call),
+            ) => {
+                builder.fill(call);
+            }
+            _ => {}
+        }
+    }
+}
+
+impl From<Value> for Argument {
+    fn from(value: Value) -> Self {
+        Self::Value(value)
+    }
+}
+
+impl From<CallBuilder> for Argument {
+    fn from(call: CallBuilder) -> Self {
+        Self::Call(call)
+    }
+}
+
+/// Build a call to a helper, partial or variable.
+#[derive(Clone, Debug)]
+pub struct CallBuilder {
+    target: String,
+    arguments: Vec<Argument>,
+    parameters: Vec<(String, Argument)>,
+}
+
+impl CallBuilder {
+    /// Create a call for a target path.
+    pub fn new<S: Into<String>>(target: S) -> Self {
+        Self {
+            target: target.into(),
+            arguments: Vec::new(),
+            parameters: Vec::new(),
+        }
+    }
+
+    /// Add an argument.
+    pub fn arg<A: Into<Argument>>(mut self, argument: A) -> Self {
+        self.arguments.push(argument.into());
+        self
+    }
+
+    /// Add a hash parameter.
+    pub fn hash<K: Into<String>, A: Into<Argument>>(
+        mut self,
+        key: K,
+        value: A,
+    ) -> Self {
+        self.parameters.push((key.into(), value.into()));
+        self
+    }
+
+    fn write(&self, out: &mut String) {
+        out.push_str(&self.target);
+        for arg in self.arguments.iter() {
+            out.push(' ');
+            arg.write(out);
+        }
+        for (key, value) in self.parameters.iter() {
+            out.push(' ');
+            out.push_str(key);
+            out.push('=');
+            value.write(out);
+        }
+    }
+
+    /// Replace placeholder literals with the exact values.
+    fn fill(&self, call: &mut Call<'_>) {
+        for (arg, param) in
+            self.arguments.iter().zip(call.arguments_mut().iter_mut())
+        {
+            arg.fill(param);
+        }
+        for (key, value) in self.parameters.iter() {
+            if let Some(param) = call.parameters_mut().get_mut(key) {
+                value.fill(param);
+            }
+        }
+    }
+}
+
+#[derive(Debug)]
+enum Item {
+    Text(Range<usize>, usize),
+    Statement(Range<usize>, usize, CallBuilder),
+    Block {
+        open: Range<usize>,
+        line: usize,
+        call: CallBuilder,
+        content: Vec<Item>,
+        inverse: Option<(Range<usize>, usize, Vec<Item>)>,
+        close: Range<usize>,
+        end: usize,
+    },
+}
+
+/// Build a document from text, statements and blocks.
+#[derive(Debug, Default)]
+pub struct DocumentBuilder {
+    source: String,
+    items: Vec<Item>,
+    line: usize,
+}
+
+impl DocumentBuilder {
+    /// Create an empty document.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Append text that is rendered verbatim.
+    pub fn text(&mut self, text: &str) -> &mut Self {
+        let start = self.source.len();
+        self.source.push_str(text);
+        self.items
+            .push(Item::Text(start..self.source.len(), self.line));
+        self.line += text.matches('\n').count();
+        self
+    }
+
+    /// Append a statement; the result of the call is escaped.
+    pub fn statement(&mut self, call: CallBuilder) -> &mut Self {
+        self.tag("{{", &call, "}}");
+        self
+    }
+
+    /// Append a statement; the result of the call is not escaped.
+    pub fn unescaped(&mut self, call: CallBuilder) -> &mut Self {
+        self.tag("{{{", &call, "}}}");
+        self
+    }
+
+    /// Append a statement that renders a partial.
+    pub fn partial(&mut self, call: CallBuilder) -> &mut Self {
+        self.tag("{{> ", &call, "}}");
+        self
+    }
+
+    /// Append a block; the content for the block is added
+    /// to the builder passed to the function.
+    pub fn block<F>(&mut self, call: CallBuilder, content: F) -> &mut Self
+    where
+        F: FnOnce(&mut DocumentBuilder),
+    {
+        let (open, line) = self.block_tag(&call);
+        let content = self.nested(content);
+        let (close, end) = self.close_tag(&call);
+        self.items.push(Item::Block {
+            open,
+            line,
+            call,
+            content,
+            inverse: None,
+            close,
+            end,
+        });
+        self
+    }
+
+    /// Append a block with an `else` branch.
+    pub fn block_else<F, E>(
+        &mut self,
+        call: CallBuilder,
+        content: F,
+        inverse: E,
+    ) -> &mut Self
+    where
+        F: FnOnce(&mut DocumentBuilder),
+        E: FnOnce(&mut DocumentBuilder),
+    {
+        let (open, line) = self.block_tag(&call);
+        let content = self.nested(content);
+        let start = self.source.len();
+        self.source.push_str("{{else}}");
+        let inverse =
+            Some((start..self.source.len(), self.line, self.nested(inverse)));
+        let (close, end) = self.close_tag(&call);
+        self.items.push(Item::Block {
+            open,
+            line,
+            call,
+            content,
+            inverse,
+            close,
+            end,
+        });
+        self
+    }
+
+    /// Compile the document into a template.
+    pub fn build(self) -> SyntaxResult<Template> {
+        let items = self.items;
+        Template::build(self.source, None, move |s| {
+            let nodes = nodes(s, items)?;
+            Ok(Node::Document(Document(s, nodes)))
+        })
+    }
+
+    fn tag(&mut self, open: &str, call: &CallBuilder, close: &str) {
+        let start = self.source.len();
+        self.source.push_str(open);
+        call.write(&mut self.source);
+        self.source.push_str(close);
+        let span = start..self.source.len();
+        self.items
+            .push(Item::Statement(span, self.line, call.clone()));
+    }
+
+    fn block_tag(&mut self, call: &CallBuilder) -> (Range<usize>, usize) {
+        let start = self.source.len();
+        self.source.push_str("{{#");
+        call.write(&mut self.source);
+        self.source.push_str("}}");
+        (start..self.source.len(), self.line)
+    }
+
+    fn close_tag(&mut self, call: &CallBuilder) -> (Range<usize>, usize) {
+        let start = self.source.len();
+        self.source.push_str(&format!("{{{{/{}}}}}", call.target));
+        (start..self.source.len(), self.line)
+    }
+
+    fn nested<F>(&mut self, content: F) -> Vec<Item>
+    where
+        F: FnOnce(&mut DocumentBuilder),
+    {
+        let outer = core::mem::take(&mut self.items);
+        content(self);
+        core::mem::replace(&mut self.items, outer)
+    }
+}
+
+fn write_value(value: &Value, out: &mut String) {
+    match value {
+        Value::String(ref s) if !s.contains('\\') => {
+            out.push('"');
+            for c in s.chars() {
+                match c {
+                    '"' => out.push_str("\\\""),
+                    '\n' => out.push_str("\\n"),
+                    _ => out.push(c),
+                }
+            }
+            out.push('"');
+        }
+        Value::Bool(_) | Value::Number(_) => out.push_str(&value.to_string()),
+        _ => out.push_str("null"),
+    }
+}
+
+/// Parse a single tag from the source for an item.
+fn parse<'a>(
+    source: &'a str,
+    span: &Range<usize>,
+    line: usize,
+    call: &CallBuilder,
+) -> SyntaxResult<Node<'a>> {
+    let options = ParserOptions::new(UNKNOWN.to_string(), line, span.start);
+    if let Some(node) = Parser::tag(source, options, span.start)? {
+        let tag = match node {
+            Node::Block(ref block) => block.call().as_str(),
+            _ => node.as_str(),
+        };
+        if tag.len() == span.len() {
+            return Ok(node);
+        }
+    }
+    let pos = SourcePos(line, span.start);
+    Err(SyntaxError::BuilderTag(
+        call.target.clone(),
+        ErrorInfo::new(source, UNKNOWN, pos, Vec::new()).into(),
+    ))
+}
+
+/// Create the nodes for a list of items.
+fn nodes(source: &str, items: Vec<Item>) -> SyntaxResult<Vec<Node<'_>>> {
+    let mut nodes = Vec::with_capacity(items.len());
+    for item in items {
+        let node = match item {
+            Item::Text(span, line) => {
+                let lines =
+                    line..line + source[span.clone()].matches('\n').count();
+                Node::Text(Text::new(source, span, lines))
+            }
+            Item::Statement(span, line, call) => {
+                let mut node = parse(source, &span, line, &call)?;
+                if let Node::Statement(ref mut statement) = node {
+                    call.fill(statement);
+                }
+                node
+            }
+            Item::Block {
+                open,
+                line,
+                call,
+                content,
+                inverse,
+                close,
+                end,
+            } => {
+                let mut node = parse(source, &open, line, &call)?;
+                if let Node::Block(ref mut block) = node {
+                    call.fill(block.call_mut());
+                    block.nodes_mut().extend(self::nodes(source, content)?);
+                    if let Some((span, line, inverse)) = inverse {
+                        if let Node::Statement(condition) =
+                            parse(source, &span, line, &call)?
+                        {
+                            let mut inner = Block::new(
+                                source,
+                                condition.open_span().clone(),
+                                false,
+                                line..line + 1,
+                            );
+                            inner.set_call(condition);
+                            block.add_condition(inner);
+                        }
+                        for node in self::nodes(source, inverse)? {
+                            block.push(node);
+                        }
+                    }
+                    block.exit(close);
+                    block.lines_end(&end);
+                }
+                node
+            }
+        };
+        nodes.push(node);
+    }
+    Ok(nodes)
+}
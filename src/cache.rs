@@ -0,0 +1,190 @@
+//! Cache for rendered partial output.
+//!
+//! Pages that render the same partial for repeated data can reuse
+//! the output of a previous render by assigning a cache to the
+//! registry using
+//! [set_partial_cache()](crate::Registry#method.set_partial_cache).
+//!
+//! Caching is opt-in for each partial because the cache key is the
+//! partial name and the context passed to the partial; a partial that
+//! reads values from a parent scope, the root data or local variables,
+//! or calls a helper that is not [pure](crate::helper::Helper#method.pure),
+//! is rendered without the cache.
+//!
+//! Variables that are not found in the context are resolved using the
+//! outer scopes and the root data, so a partial that reads variables
+//! other than `this` is only cached when it is called with
+//! `isolated=true`:
+//!
+//! ```ignore
+//! let mut cache = PartialCache::new(256);
+//! cache.allow_partials(&["card", "avatar"]);
+//! registry.set_partial_cache(cache);
+//! registry.insert("page", "{{#each users}}{{> card this isolated=true}}{{/each}}")?;
+//! ```
+//!
+//! Rendered output is shared between renders until it is evicted or
+//! the cache is cleared; call [clear()](PartialCache#method.clear)
+//! after changing a cached partial, a partial it calls or a helper.
+//!
+//! Block partials and partials registered with the renderer are never
+//! cached; output written using
+//! [out()](crate::render::Render#method.out) bypasses the cache so
+//! helpers that write to the output destination directly should not
+//! be called from a cached partial.
+use std::sync::Mutex;
+
+use crate::collections::{HashMap, HashSet};
+use alloc::string::{String, ToString};
+
+/// Least recently used cache of rendered partials.
+#[derive(Debug)]
+pub struct PartialCache {
+    capacity: usize,
+    partials: HashSet<String>,
+    inner: Mutex<Inner>,
+}
+
+#[derive(Debug, Default)]
+struct Inner {
+    entries: HashMap<String, Entry>,
+    cacheable: HashMap<String, [Option<bool>; 2]>,
+    tick: u64,
+    hits: u64,
+    misses: u64,
+}
+
+#[derive(Debug)]
+struct Entry {
+    output: String,
+    used: u64,
+}
+
+impl PartialCache {
+    /// Create a cache that holds at most `capacity` rendered partials.
+    pub fn new(capacity: usize) -> Self {
+        Self {
+            capacity,
+            partials: Default::default(),
+            inner: Mutex::new(Default::default()),
+        }
+    }
+
+    /// Allow the output of the named partials to be cached.
+    pub fn allow_partials<S>(&mut self, names: &[S])
+    where
+        S: AsRef<str>,
+    {
+        for name in names {
+            self.partials.insert(name.as_ref().to_string());
+        }
+    }
+
+    /// Determine if the output of a partial may be cached.
+    pub fn is_cached(&self, name: &str) -> bool {
+        self.capacity > 0 && self.partials.contains(name)
+    }
+
+    /// Maximum number of entries in the cache.
+    pub fn capacity(&self) -> usize {
+        self.capacity
+    }
+
+    /// Number of entries in the cache.
+    pub fn len(&self) -> usize {
+        self.lock().entries.len()
+    }
+
+    /// Determine if the cache is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Number of partial renders that used cached output.
+    pub fn hits(&self) -> u64 {
+        self.lock().hits
+    }
+
+    /// Number of partial renders that were not in the cache.
+    pub fn misses(&self) -> u64 {
+        self.lock().misses
+    }
+
+    /// Remove all entries and reset the statistics.
+    ///
+    /// Whether each partial may be cached is also determined
+    /// again on the next render.
+    pub fn clear(&self) {
+        *self.lock() = Default::default();
+    }
+
+    /// Determine if the output of a partial may be cached, computing
+    /// the answer only the first time the partial is rendered with
+    /// or without isolation.
+    pub(crate) fn is_cacheable<F>(
+        &self,
+        name: &str,
+        isolated: bool,
+        compute: F,
+    ) -> bool
+    where
+        F: FnOnce() -> bool,
+    {
+        let index = isolated as usize;
+        if let Some(Some(cacheable)) =
+            self.lock().cacheable.get(name).map(|c| c[index])
+        {
+            return cacheable;
+        }
+        let cacheable = compute();
+        self.lock()
+            .cacheable
+            .entry(name.to_string())
+            .or_default()[index] = Some(cacheable);
+        cacheable
+    }
+
+    /// Get the cached output for a key.
+    pub(crate) fn get(&self, key: &str) -> Option<String> {
+        let mut inner = self.lock();
+        inner.tick += 1;
+        let tick = inner.tick;
+        let output = inner.entries.get_mut(key).map(|entry| {
+            entry.used = tick;
+            entry.output.clone()
+        });
+        if output.is_some() {
+            inner.hits += 1;
+        } else {
+            inner.misses += 1;
+        }
+        output
+    }
+
+    /// Add rendered output for a key evicting the least
+    /// recently used entry when the cache is full.
+    pub(crate) fn insert(&self, key: String, output: String) {
+        let mut inner = self.lock();
+        if !inner.entries.contains_key(&key)
+            && inner.entries.len() >= self.capacity
+        {
+            let oldest = inner
+                .entries
+                .iter()
+                .min_by_key(|(_, entry)| entry.used)
+                .map(|(key, _)| key.clone());
+            if let Some(oldest) = oldest {
+                inner.entries.remove(&oldest);
+            }
+        }
+        inner.tick += 1;
+        let used = inner.tick;
+        inner.entries.insert(key, Entry { output, used });
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Inner> {
+        // NOTE: entries are always left in a consistent state
+        // NOTE: so a poisoned lock can be recovered
+        self.inner.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
@@ -0,0 +1,69 @@
+//! Clocks that provide the current time for helpers.
+//!
+//! Helpers such as `now` read the time from the clock assigned to
+//! the registry; freeze the clock so that tests and reproducible
+//! builds render the same output:
+//!
+//! ```ignore
+//! use bracket::clock::FixedClock;
+//! registry.set_clock(Box::new(FixedClock::new(1_700_000_000)));
+//! ```
+//!
+//! When no clock is assigned and the `std` feature is enabled
+//! helpers use the [SystemClock](SystemClock) unless the registry is
+//! sandboxed; a sandboxed registry must be assigned a clock because
+//! the system clock is ambient authority.
+
+/// Trait for types that provide the current time.
+pub trait Clock: Send + Sync {
+    /// Get the number of seconds since the Unix epoch.
+    fn now(&self) -> i64;
+}
+
+/// Clock that always returns the same time.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct FixedClock {
+    timestamp: i64,
+}
+
+impl FixedClock {
+    /// Create a clock frozen at a number of seconds since the Unix epoch.
+    pub fn new(timestamp: i64) -> Self {
+        Self { timestamp }
+    }
+}
+
+impl Clock for FixedClock {
+    fn now(&self) -> i64 {
+        self.timestamp
+    }
+}
+
+/// Clock that reads the system time.
+#[cfg(feature = "std")]
+#[derive(Clone, Copy, Debug, Default)]
+pub struct SystemClock;
+
+#[cfg(feature = "std")]
+impl Clock for SystemClock {
+    fn now(&self) -> i64 {
+        use std::time::{SystemTime, UNIX_EPOCH};
+        match SystemTime::now().duration_since(UNIX_EPOCH) {
+            Ok(elapsed) => elapsed.as_secs() as i64,
+            Err(e) => -(e.duration().as_secs() as i64),
+        }
+    }
+}
+
+/// Get the time from the clock for a registry falling back to the
+/// system clock when the `std` feature is enabled and the registry
+/// is not sandboxed.
+#[cfg(any(feature = "now-helper", feature = "timeago-helper"))]
+pub(crate) fn now(registry: &crate::Registry<'_>) -> Option<i64> {
+    match registry.clock() {
+        Some(clock) => Some(clock.now()),
+        #[cfg(feature = "std")]
+        None if !registry.sandboxed() => Some(SystemClock.now()),
+        None => None,
+    }
+}
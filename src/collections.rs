@@ -0,0 +1,7 @@
+//! Hash collections from the standard library or `hashbrown` when
+//! building without `std`.
+#[cfg(feature = "std")]
+pub use std::collections::{HashMap, HashSet};
+
+#[cfg(not(feature = "std"))]
+pub use hashbrown::{HashMap, HashSet};
@@ -0,0 +1,165 @@
+//! Contracts declare the hash parameters expected by a partial.
+//!
+//! Partials written as components receive their properties as hash
+//! parameters; a typo at a call site renders a blank rather than an
+//! error. Assign a contract to a partial so that call sites are
+//! validated by [lint()](crate::Registry#method.lint) and, in strict
+//! mode, when the partial is rendered:
+//!
+//! ```ignore
+//! use bracket::{contract::Contract, render::Type};
+//! registry.insert("button", r#"<a class="{{kind}}">{{label}}</a>"#)?;
+//! registry.set_contract(
+//!     "button",
+//!     Contract::new()
+//!         .required("label", &[Type::String])
+//!         .optional("kind", &[Type::String]),
+//! );
+//! ```
+//!
+//! A parameter declared without any types accepts any value.
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::fmt;
+
+use serde_json::Value;
+
+use crate::render::Type;
+
+/// Hash parameter names that are handled by the renderer and
+/// are permitted for every partial.
+const RESERVED: [&str; 1] = ["isolated"];
+
+/// Parameter declared by a contract.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Param {
+    /// Name of the hash parameter.
+    pub name: String,
+    /// Whether call sites must pass the parameter.
+    pub required: bool,
+    /// Types accepted for the value; empty to accept any type.
+    pub types: Vec<Type>,
+}
+
+/// Violation of a contract at a call site.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Violation {
+    /// Parameter that is not declared by the contract.
+    Unknown(String),
+    /// Required parameter that was not passed.
+    Missing(String),
+    /// Parameter with a value of the wrong type; contains the
+    /// parameter name, the expected types and the actual type.
+    Type(String, String, Type),
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::Unknown(ref name) => {
+                write!(f, "unknown parameter '{}'", name)
+            }
+            Self::Missing(ref name) => {
+                write!(f, "missing required parameter '{}'", name)
+            }
+            Self::Type(ref name, ref expected, ref actual) => write!(
+                f,
+                "parameter '{}' expects {}, got {}",
+                name, expected, actual
+            ),
+        }
+    }
+}
+
+/// Declares the hash parameters expected by a partial.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct Contract {
+    params: Vec<Param>,
+}
+
+impl Contract {
+    /// Create a contract that does not accept any parameters.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Declare a parameter that call sites must pass.
+    pub fn required(mut self, name: &str, types: &[Type]) -> Self {
+        self.declare(name, true, types);
+        self
+    }
+
+    /// Declare a parameter that call sites may pass.
+    pub fn optional(mut self, name: &str, types: &[Type]) -> Self {
+        self.declare(name, false, types);
+        self
+    }
+
+    /// Get the declared parameters.
+    pub fn params(&self) -> &[Param] {
+        &self.params
+    }
+
+    /// Get a declared parameter by name.
+    pub fn param(&self, name: &str) -> Option<&Param> {
+        self.params.iter().find(|p| p.name == name)
+    }
+
+    fn declare(&mut self, name: &str, required: bool, types: &[Type]) {
+        let param = Param {
+            name: name.to_string(),
+            required,
+            types: types.to_vec(),
+        };
+        match self.params.iter_mut().find(|p| p.name == name) {
+            Some(existing) => *existing = param,
+            None => self.params.push(param),
+        }
+    }
+
+    /// Check the parameters passed at a call site.
+    ///
+    /// Each parameter is a name and the value when it is known,
+    /// values are `None` when they can only be determined by
+    /// rendering the template which skips the type check.
+    pub fn check<'a, I>(&self, params: I) -> Vec<Violation>
+    where
+        I: IntoIterator<Item = (&'a str, Option<&'a Value>)>,
+    {
+        let mut violations = Vec::new();
+        let mut passed = Vec::new();
+        for (name, value) in params {
+            if RESERVED.contains(&name) {
+                continue;
+            }
+            passed.push(name);
+            let param = match self.param(name) {
+                Some(param) => param,
+                None => {
+                    violations.push(Violation::Unknown(name.to_string()));
+                    continue;
+                }
+            };
+            if let (Some(value), false) = (value, param.types.is_empty()) {
+                let actual = Type::from(value);
+                if !param.types.contains(&actual) {
+                    let expected: Vec<String> =
+                        param.types.iter().map(|t| t.to_string()).collect();
+                    violations.push(Violation::Type(
+                        name.to_string(),
+                        expected.join(" or "),
+                        actual,
+                    ));
+                }
+            }
+        }
+        for param in self.params.iter().filter(|p| p.required) {
+            if !passed.contains(&param.name.as_str()) {
+                violations.push(Violation::Missing(param.name.clone()));
+            }
+        }
+        violations
+    }
+}
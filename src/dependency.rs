@@ -0,0 +1,183 @@
+//! Dependency graph for templates and the partials they reference.
+//!
+//! Use [dependencies()](crate::Registry#method.dependencies) to
+//! statically walk a template and collect the partials it renders
+//! including block partials (`{{#> layout}}`):
+//!
+//! ```ignore
+//! let graph = registry.dependencies("index")?;
+//! if graph.contains("header") {
+//!     // Invalidate the cached output for index
+//! }
+//! ```
+//!
+//! Partials whose name is computed at runtime using a sub-expression
+//! (`{{> (name)}}`) cannot be resolved statically so they are not
+//! included in the graph.
+use alloc::{
+    collections::{BTreeMap, BTreeSet},
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use crate::parser::ast::{Call, CallTarget, Node, Slice};
+
+const PARTIAL_BLOCK: &str = "@partial-block";
+
+/// Graph of the partials referenced by a template.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct Dependencies {
+    root: String,
+    edges: BTreeMap<String, Vec<String>>,
+    missing: BTreeSet<String>,
+}
+
+impl Dependencies {
+    /// Create a graph for the named root template.
+    pub(crate) fn new(root: &str) -> Self {
+        Self {
+            root: root.to_string(),
+            edges: Default::default(),
+            missing: Default::default(),
+        }
+    }
+
+    /// Add a template and the partials it references.
+    pub(crate) fn insert(&mut self, name: &str, partials: Vec<String>) {
+        self.edges.insert(name.to_string(), partials);
+    }
+
+    /// Add a partial that does not exist in the registry.
+    pub(crate) fn insert_missing(&mut self, name: &str) {
+        self.missing.insert(name.to_string());
+    }
+
+    /// Determine if a template has already been visited.
+    pub(crate) fn visited(&self, name: &str) -> bool {
+        self.edges.contains_key(name) || self.missing.contains(name)
+    }
+
+    /// The name of the root template.
+    pub fn root(&self) -> &str {
+        &self.root
+    }
+
+    /// Partials referenced directly by a template in the order they
+    /// are first referenced.
+    pub fn get(&self, name: &str) -> Option<&[String]> {
+        self.edges.get(name).map(|v| v.as_slice())
+    }
+
+    /// Determine if the graph contains a template or partial.
+    pub fn contains(&self, name: &str) -> bool {
+        self.visited(name)
+    }
+
+    /// Iterate the names of the templates in the graph.
+    ///
+    /// Missing partials are not included.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        self.edges.keys().map(|k| k.as_str())
+    }
+
+    /// Iterate the names of referenced partials that do not
+    /// exist in the registry.
+    pub fn missing(&self) -> impl Iterator<Item = &str> {
+        self.missing.iter().map(|k| k.as_str())
+    }
+
+    /// Find a cycle in the graph.
+    ///
+    /// The returned path starts and ends with the same name,
+    /// for example `["a", "b", "a"]`.
+    pub fn cycle(&self) -> Option<Vec<&str>> {
+        self.walk().err()
+    }
+
+    /// Determine if the graph contains a cycle.
+    pub fn is_cyclic(&self) -> bool {
+        self.cycle().is_some()
+    }
+
+    /// Topological order of the graph.
+    ///
+    /// Partials are listed before the templates that reference
+    /// them so the root template is always last; returns `None`
+    /// when the graph contains a cycle.
+    pub fn order(&self) -> Option<Vec<&str>> {
+        self.walk().ok()
+    }
+
+    fn walk(&self) -> Result<Vec<&str>, Vec<&str>> {
+        let mut order = Vec::new();
+        let mut done = BTreeSet::new();
+        let mut path = Vec::new();
+        self.visit(&self.root, &mut path, &mut done, &mut order)?;
+        Ok(order)
+    }
+
+    fn visit<'a>(
+        &'a self,
+        name: &'a str,
+        path: &mut Vec<&'a str>,
+        done: &mut BTreeSet<&'a str>,
+        order: &mut Vec<&'a str>,
+    ) -> Result<(), Vec<&'a str>> {
+        if done.contains(name) {
+            return Ok(());
+        }
+
+        if let Some(pos) = path.iter().position(|n| *n == name) {
+            let mut cycle = path[pos..].to_vec();
+            cycle.push(name);
+            return Err(cycle);
+        }
+
+        if let Some(partials) = self.edges.get(name) {
+            path.push(name);
+            for partial in partials {
+                self.visit(partial, path, done, order)?;
+            }
+            path.pop();
+            done.insert(name);
+            order.push(name);
+        }
+
+        Ok(())
+    }
+}
+
+/// Collect the names of the partials referenced by a node
+/// in the order they are first referenced.
+pub(crate) fn partials(node: &Node<'_>) -> Vec<String> {
+    let mut names: Vec<String> = Vec::new();
+    for node in node.descendants() {
+        let call = match node {
+            Node::Statement(ref call) => call,
+            Node::Block(ref block) => block.call(),
+            _ => continue,
+        };
+        if let Some(name) = partial_name(call) {
+            if !names.iter().any(|n| n == name) {
+                names.push(name.to_string());
+            }
+        }
+    }
+    names
+}
+
+pub(crate) fn partial_name<'a>(call: &'a Call<'_>) -> Option<&'a str> {
+    if !call.is_partial() {
+        return None;
+    }
+    match call.target() {
+        CallTarget::Path(ref path) => {
+            if path.is_simple() && path.as_str() != PARTIAL_BLOCK {
+                Some(path.as_str())
+            } else {
+                None
+            }
+        }
+        CallTarget::SubExpr(_) => None,
+    }
+}
@@ -0,0 +1,287 @@
+//! Compare the structure of two templates.
+//!
+//! Use [diff()](crate::diff()) to list the statements, blocks and text
+//! that were added, removed or changed between two versions of a
+//! template, for example when reviewing a change or comparing the
+//! variants of a template:
+//!
+//! ```ignore
+//! let before = registry.parse("page", old)?;
+//! let after = registry.parse("page", new)?;
+//! for change in bracket::diff(&before, &after) {
+//!     println!("{}", change);
+//! }
+//! ```
+//!
+//! Whitespace is not significant so reformatting a template, for
+//! example changing the indentation or writing `{{ title }}` as
+//! `{{title}}`, does not produce any changes. Whitespace in string
+//! literals and whitespace control characters (`~`) are significant.
+//!
+//! Nodes are compared in document order including the nodes of
+//! blocks and their `else` branches; a removed node followed by an
+//! added node of the same kind that calls the same helper or partial
+//! is reported as a change.
+use alloc::{
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use core::{fmt, ops::Range};
+
+use crate::{
+    parser::ast::{Element, Lines, Node, Slice},
+    template::Template,
+};
+
+/// Characters that open a tag before the call target.
+const OPEN: &[char] = &['{', '~', '#', '>', '^', '/', '&', '*', '!', '\\'];
+/// Characters that close a tag.
+const CLOSE: &[char] = &['}', '~'];
+
+/// Node that was compared.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Item {
+    /// Type of node, for example `statement` or `block`.
+    pub kind: &'static str,
+    /// Source for the node; for blocks the open tag.
+    pub source: String,
+    /// Byte range of the node in the template.
+    pub span: Range<usize>,
+    /// Zero-based line range of the node in the template.
+    pub lines: Range<usize>,
+    /// Source with insignificant whitespace removed.
+    key: String,
+}
+
+impl Item {
+    /// Name of the helper, partial or variable for statements and
+    /// blocks used to pair removed and added nodes.
+    fn name(&self) -> &str {
+        match self.kind {
+            "text" => "",
+            _ => self
+                .key
+                .trim_start_matches(OPEN)
+                .split(|c: char| c.is_whitespace() || CLOSE.contains(&c))
+                .next()
+                .unwrap_or(""),
+        }
+    }
+}
+
+/// Difference between two templates.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub enum Change {
+    /// Node that only exists in the second template.
+    Added(Item),
+    /// Node that only exists in the first template.
+    Removed(Item),
+    /// Node in the first template that was changed
+    /// to a node in the second template.
+    Changed(Item, Item),
+}
+
+impl fmt::Display for Change {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match *self {
+            Self::Added(ref item) => {
+                write!(f, "+ {}: {}", item.lines.start + 1, item.source)
+            }
+            Self::Removed(ref item) => {
+                write!(f, "- {}: {}", item.lines.start + 1, item.source)
+            }
+            Self::Changed(ref before, ref after) => write!(
+                f,
+                "~ {}:{}: {} -> {}",
+                before.lines.start + 1,
+                after.lines.start + 1,
+                before.source,
+                after.source
+            ),
+        }
+    }
+}
+
+/// Compare two templates and list the changes.
+///
+/// An empty list means the templates only differ by whitespace.
+pub fn diff(before: &Template, after: &Template) -> Vec<Change> {
+    let a = items(before.node());
+    let b = items(after.node());
+
+    // NOTE: longest common subsequence of the normalized nodes
+    let keys_a: Vec<&str> = a.iter().map(|item| item.key.as_str()).collect();
+    let keys_b: Vec<&str> = b.iter().map(|item| item.key.as_str()).collect();
+
+    let mut changes = Vec::new();
+    let mut removed = Vec::new();
+    let mut added = Vec::new();
+    for edit in lcs(&keys_a, &keys_b) {
+        match edit {
+            Edit::Same(..) => pair(&mut removed, &mut added, &mut changes),
+            Edit::Removed(i) => removed.push(a[i].clone()),
+            Edit::Added(j) => added.push(b[j].clone()),
+        }
+    }
+    pair(&mut removed, &mut added, &mut changes);
+    changes
+}
+
+/// Step in the edit script between two sequences.
+#[derive(Debug, Clone, Copy, Eq, PartialEq)]
+pub(crate) enum Edit {
+    /// Element at the index in the first sequence equals the
+    /// element at the index in the second sequence.
+    Same(usize, usize),
+    /// Element at the index only exists in the first sequence.
+    Removed(usize),
+    /// Element at the index only exists in the second sequence.
+    Added(usize),
+}
+
+/// Compute the edit script from the longest common subsequence
+/// of two sequences.
+///
+/// When a run of elements is replaced the removed elements are
+/// listed before the added elements.
+pub(crate) fn lcs<T: PartialEq>(a: &[T], b: &[T]) -> Vec<Edit> {
+    let mut table = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+    for i in (0..a.len()).rev() {
+        for j in (0..b.len()).rev() {
+            table[i][j] = if a[i] == b[j] {
+                table[i + 1][j + 1] + 1
+            } else {
+                table[i + 1][j].max(table[i][j + 1])
+            };
+        }
+    }
+
+    let mut edits = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() || j < b.len() {
+        if i < a.len() && j < b.len() && a[i] == b[j] {
+            edits.push(Edit::Same(i, j));
+            i += 1;
+            j += 1;
+        } else if i < a.len()
+            && (j == b.len() || table[i + 1][j] >= table[i][j + 1])
+        {
+            edits.push(Edit::Removed(i));
+            i += 1;
+        } else {
+            edits.push(Edit::Added(j));
+            j += 1;
+        }
+    }
+    edits
+}
+
+/// Flush a run of removed and added nodes pairing nodes of the
+/// same kind and name as changes.
+fn pair(
+    removed: &mut Vec<Item>,
+    added: &mut Vec<Item>,
+    changes: &mut Vec<Change>,
+) {
+    let mut added: Vec<Option<Item>> = added.drain(..).map(Some).collect();
+    for item in removed.drain(..) {
+        let other = added.iter_mut().find(|other| match other {
+            Some(other) => {
+                other.kind == item.kind && other.name() == item.name()
+            }
+            None => false,
+        });
+        match other.and_then(|other| other.take()) {
+            Some(other) => changes.push(Change::Changed(item, other)),
+            None => changes.push(Change::Removed(item)),
+        }
+    }
+    changes.extend(added.into_iter().flatten().map(Change::Added));
+}
+
+/// Collect the nodes to compare in document order.
+fn items(node: &Node<'_>) -> Vec<Item> {
+    let mut out = Vec::new();
+    for node in core::iter::once(node).chain(node.descendants()) {
+        let (kind, span, lines, source) = match node {
+            Node::Text(ref n) => {
+                ("text", n.span().clone(), n.lines().clone(), n.value())
+            }
+            Node::Statement(ref n) => {
+                ("statement", n.span(), n.lines().clone(), n.as_str())
+            }
+            Node::Block(ref n) => {
+                let call = n.call();
+                ("block", call.span(), call.lines().clone(), call.as_str())
+            }
+            Node::RawStatement(ref n) => {
+                ("raw_statement", n.span(), n.lines().clone(), n.as_str())
+            }
+            Node::Link(ref n) => {
+                ("link", n.span(), n.lines().clone(), n.as_str())
+            }
+            Node::Document(_) | Node::RawComment(_) | Node::Comment(_) => {
+                continue
+            }
+        };
+        let key = match kind {
+            "text" => collapse(source),
+            _ => normalize(source),
+        };
+        if key.is_empty() {
+            continue;
+        }
+        out.push(Item {
+            kind,
+            source: source.to_string(),
+            span,
+            lines,
+            key,
+        });
+    }
+    out
+}
+
+/// Collapse runs of whitespace to a single space and trim.
+fn collapse(value: &str) -> String {
+    value.split_whitespace().collect::<Vec<_>>().join(" ")
+}
+
+/// Remove insignificant whitespace from a tag.
+///
+/// Whitespace after the open delimiter and before the close
+/// delimiter is removed and other runs of whitespace outside of
+/// string literals are collapsed to a single space.
+fn normalize(tag: &str) -> String {
+    let inner = tag.trim_start_matches(OPEN).trim_end_matches(CLOSE);
+    let open = &tag[..tag.len() - tag.trim_start_matches(OPEN).len()];
+    let close = &tag[tag.trim_end_matches(CLOSE).len()..];
+    let mut out = String::with_capacity(tag.len());
+    out.push_str(open);
+    let mut quote: Option<char> = None;
+    let mut space = false;
+    for c in inner.trim().chars() {
+        match quote {
+            Some(q) => {
+                out.push(c);
+                if c == q {
+                    quote = None;
+                }
+            }
+            None if c.is_whitespace() => space = true,
+            None => {
+                if space {
+                    out.push(' ');
+                    space = false;
+                }
+                if c == '"' || c == '\'' || c == '[' {
+                    quote = Some(if c == '[' { ']' } else { c });
+                }
+                out.push(c);
+            }
+        }
+    }
+    out.push_str(close);
+    out
+}
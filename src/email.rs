@@ -0,0 +1,90 @@
+//! Render plain text and HTML variants of a message.
+//!
+//! Transactional emails are usually sent with both an HTML and a
+//! plain text part; keeping the templates for both parts side by
+//! side and rendering them with the same data in one call ensures
+//! they stay in sync.
+//!
+//! Templates are resolved by appending the `.html` and `.txt`
+//! extensions to the message name; the HTML variant is escaped using
+//! the registry escape function and the text variant is not escaped.
+//!
+//! ```ignore
+//! registry.insert("welcome.html", "<p>Hello {{name}}</p>")?;
+//! registry.insert("welcome.txt", "Hello {{name}}")?;
+//! let email = registry.render_email("welcome", &data)?;
+//! println!("{}", email.text);
+//! ```
+use alloc::{
+    boxed::Box,
+    format,
+    string::{String, ToString},
+};
+use serde::Serialize;
+
+use crate::{
+    escape::{self, EscapeWriteFn},
+    output::StringOutput,
+    Error, Registry, Result,
+};
+
+/// Extension for the HTML template of a message.
+pub const HTML: &str = "html";
+
+/// Extension for the plain text template of a message.
+pub const TEXT: &str = "txt";
+
+/// Rendered HTML and plain text variants of a message.
+#[derive(Debug, Default, Clone, Eq, PartialEq)]
+pub struct Email {
+    /// The rendered HTML variant.
+    pub html: String,
+    /// The rendered plain text variant.
+    pub text: String,
+}
+
+impl<'reg> Registry<'reg> {
+    /// Render the HTML and plain text templates for a message.
+    ///
+    /// Both the `name.html` and `name.txt` templates must exist
+    /// in the templates collection.
+    pub fn render_email<T>(&self, name: &str, data: &T) -> Result<Email>
+    where
+        T: Serialize,
+    {
+        let html_name = format!("{}.{}", name, HTML);
+        let text_name = format!("{}.{}", name, TEXT);
+        let noop: EscapeWriteFn = Box::new(escape::noop_write);
+        let html =
+            self.render_with_escape(&html_name, data, self.escape_write())?;
+        let text = self.render_with_escape(&text_name, data, &noop)?;
+        Ok(Email { html, text })
+    }
+
+    fn render_with_escape<T>(
+        &self,
+        name: &str,
+        data: &T,
+        escape: &EscapeWriteFn,
+    ) -> Result<String>
+    where
+        T: Serialize,
+    {
+        let template = self
+            .get(name)
+            .ok_or_else(|| Error::TemplateNotFound(name.to_string()))?;
+        let mut writer = StringOutput::with_capacity(template.size_hint());
+        template.render_with(
+            self,
+            name,
+            data,
+            &mut writer,
+            escape,
+            |rc, node, escape| {
+                rc.set_escape(escape);
+                rc.render(node)
+            },
+        )?;
+        Ok(writer.into())
+    }
+}
@@ -0,0 +1,121 @@
+//! Machine readable diagnostics for errors.
+//!
+//! Syntax, render and helper errors convert to a
+//! [Diagnostic](Diagnostic) with a stable code so that build tools
+//! and editors can consume errors without parsing messages; errors
+//! serialize as their diagnostic and `to_json()` returns the value:
+//!
+//! ```ignore
+//! if let Err(e) = registry.insert("page", source) {
+//!     println!("{}", e.to_json());
+//! }
+//! ```
+//!
+//! The JSON for a diagnostic is an object with the fields:
+//!
+//! * `kind`: One of `syntax`, `warning`, `render`, `helper`, `template`
+//!   or `io`.
+//! * `code`: Identifier for the error such as `expected_identifier`.
+//! * `message`: The error message.
+//! * `file`: Name of the template or `null`.
+//! * `line`: Zero-based line number or `null`.
+//! * `span`: Object with the `start` and `end` byte offsets or `null`.
+//! * `notes`: Array of notes.
+use alloc::{string::String, vec::Vec};
+use core::ops::Range;
+
+use serde::{Serialize, Serializer};
+use serde_json::{json, Value};
+
+/// Kind of error for a diagnostic.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum DiagnosticKind {
+    /// Error compiling a template.
+    Syntax,
+    /// Warning from a lint rule such as a call to a deprecated helper.
+    Warning,
+    /// Error rendering a template.
+    Render,
+    /// Error returned by a helper.
+    Helper,
+    /// Error when a template or fragment does not exist.
+    Template,
+    /// Error reading or writing.
+    Io,
+}
+
+impl DiagnosticKind {
+    /// Name of the kind used in JSON.
+    pub fn as_str(&self) -> &'static str {
+        match *self {
+            Self::Syntax => "syntax",
+            Self::Warning => "warning",
+            Self::Render => "render",
+            Self::Helper => "helper",
+            Self::Template => "template",
+            Self::Io => "io",
+        }
+    }
+}
+
+/// Machine readable description of an error.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Diagnostic {
+    /// Kind of error.
+    pub kind: DiagnosticKind,
+    /// Stable identifier for the error.
+    pub code: &'static str,
+    /// Error message.
+    pub message: String,
+    /// Name of the template when known.
+    pub file: Option<String>,
+    /// Zero-based line number when known.
+    pub line: Option<usize>,
+    /// Byte range in the template source when known.
+    pub span: Option<Range<usize>>,
+    /// Notes for the error.
+    pub notes: Vec<String>,
+}
+
+impl Diagnostic {
+    /// Create a diagnostic without a location.
+    pub(crate) fn new(
+        kind: DiagnosticKind,
+        code: &'static str,
+        message: String,
+    ) -> Self {
+        Self {
+            kind,
+            code,
+            message,
+            file: None,
+            line: None,
+            span: None,
+            notes: Vec::new(),
+        }
+    }
+
+    /// Convert this diagnostic to JSON.
+    pub fn to_json(&self) -> Value {
+        json!({
+            "kind": self.kind.as_str(),
+            "code": self.code,
+            "message": self.message,
+            "file": self.file,
+            "line": self.line,
+            "span": self.span.as_ref().map(|span| {
+                json!({"start": span.start, "end": span.end})
+            }),
+            "notes": self.notes,
+        })
+    }
+}
+
+impl Serialize for Diagnostic {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.to_json().serialize(serializer)
+    }
+}
@@ -0,0 +1,21 @@
+//! Errors generated when editing template source.
+use alloc::string::String;
+use core::ops::Range;
+use thiserror::Error;
+
+/// Errors generated by the source rewriter.
+#[derive(Error, Debug, Eq, PartialEq)]
+pub enum EditError {
+    /// Error when a span is outside of the source string.
+    #[error("Edit span {0:?} is out of bounds")]
+    OutOfBounds(Range<usize>),
+    /// Error when a span does not start and end on a character boundary.
+    #[error("Edit span {0:?} is not on a character boundary")]
+    CharBoundary(Range<usize>),
+    /// Error when a span overlaps the span of an existing edit.
+    #[error("Edit span {0:?} overlaps existing edit {1:?}")]
+    Overlap(Range<usize>, Range<usize>),
+    /// Error when the target of a call cannot be renamed.
+    #[error("Call target '{0}' cannot be renamed")]
+    Rename(String),
+}
@@ -2,7 +2,18 @@
 //!
 //! The renderer will wrap these in `RenderError` so you only
 //! need to use this type when implementing helpers.
-use crate::error::{render::RenderError, syntax::SyntaxError, IoError};
+use crate::error::{
+    diagnostic::{Diagnostic, DiagnosticKind},
+    render::RenderError,
+    syntax::SyntaxError,
+    IoError,
+};
+use alloc::{
+    boxed::Box,
+    string::{String, ToString},
+};
+use serde::{Serialize, Serializer};
+use serde_json::Value;
 use thiserror::Error;
 
 /// Errors generated by helpers.
@@ -23,6 +34,9 @@ pub enum HelperError {
     /// Error when supplied arguments do not match an arity range.
     #[error("Helper '{0}' got invalid arity expects {1}-{2} argument(s)")]
     ArityRange(String, usize, usize),
+    /// Error when supplied arguments are fewer than an open-ended arity.
+    #[error("Helper '{0}' got invalid arity expects at least {1} argument(s)")]
+    ArityMinimum(String, usize),
     /// Error when a helper expects a string argument.
     #[error("Helper '{0}' got invalid argument at index {1}, string expected")]
     ArgumentTypeString(String, usize),
@@ -72,10 +86,69 @@ impl HelperError {
     pub fn new<S: AsRef<str>>(msg: S) -> Self {
         HelperError::Message(msg.as_ref().to_string())
     }
+
+    /// Stable identifier for the kind of error.
+    ///
+    /// Proxied syntax and render errors use the code of the
+    /// proxied error.
+    pub fn code(&self) -> &'static str {
+        match *self {
+            Self::Message(..) => "message",
+            Self::BlockTemplate(..) => "block_template",
+            Self::BlockTemplateNotAllowed(..) => "block_template_not_allowed",
+            Self::ArityExact(..) => "arity_exact",
+            Self::ArityRange(..) => "arity_range",
+            Self::ArityMinimum(..) => "arity_minimum",
+            Self::ArgumentTypeString(..) => "argument_type_string",
+            Self::LookupField(..) => "lookup_field",
+            Self::InvalidNumericalOperand(..) => "invalid_numerical_operand",
+            Self::TypeAssert(..) => "type_assert",
+            Self::Syntax(ref e) => e.code(),
+            Self::Render(ref e) => e.code(),
+            Self::Io(..) => "io",
+            Self::Json(..) => "json",
+        }
+    }
+
+    /// Convert this error to a diagnostic.
+    ///
+    /// Proxied syntax and render errors use the diagnostic for
+    /// the proxied error.
+    pub fn diagnostic(&self) -> Diagnostic {
+        match *self {
+            Self::Syntax(ref e) => e.diagnostic(),
+            Self::Render(ref e) => e.diagnostic(),
+            Self::Io(..) => Diagnostic::new(
+                DiagnosticKind::Io,
+                self.code(),
+                self.to_string(),
+            ),
+            _ => Diagnostic::new(
+                DiagnosticKind::Helper,
+                self.code(),
+                self.to_string(),
+            ),
+        }
+    }
+
+    /// Convert this error to JSON; see the
+    /// [diagnostic](crate::error::diagnostic) module for the fields.
+    pub fn to_json(&self) -> Value {
+        self.diagnostic().to_json()
+    }
+}
+
+impl Serialize for HelperError {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.diagnostic().serialize(serializer)
+    }
 }
 
-impl From<std::io::Error> for HelperError {
-    fn from(err: std::io::Error) -> Self {
+impl From<crate::output::io::Error> for HelperError {
+    fn from(err: crate::output::io::Error) -> Self {
         Self::Io(IoError::Io(err))
     }
 }
@@ -1,15 +1,24 @@
 //! Error types.
-use std::fmt;
+use alloc::string::{String, ToString};
+use core::fmt;
+use serde::{Serialize, Serializer};
+use serde_json::Value;
 use thiserror::Error;
 
+pub mod diagnostic;
+pub mod edit;
 pub mod helper;
+pub mod path;
 pub mod render;
 pub mod source;
 pub mod syntax;
 
+pub use diagnostic::{Diagnostic, DiagnosticKind};
+pub use edit::EditError;
 pub use helper::HelperError;
+pub use path::PathError;
 pub use render::RenderError;
-pub use source::{ErrorInfo, SourcePos};
+pub use source::{ErrorInfo, Snippet, SourcePos};
 pub use syntax::SyntaxError;
 
 /// Generic error type that wraps more specific types and is
@@ -25,6 +34,9 @@ pub enum Error {
     /// Error when a named template does not exist.
     #[error("Template not found '{0}'")]
     TemplateNotFound(String),
+    /// Error when a template does not have a fragment with an id.
+    #[error("Fragment '{1}' not found in template '{0}'")]
+    FragmentNotFound(String, String),
     /// Proxy IO errors.
     #[error(transparent)]
     Io(#[from] IoError),
@@ -35,14 +47,53 @@ impl fmt::Debug for Error {
         match *self {
             Self::Syntax(ref e) => fmt::Debug::fmt(e, f),
             Self::Render(ref e) => fmt::Debug::fmt(e, f),
-            Self::TemplateNotFound(_) => fmt::Display::fmt(self, f),
+            Self::TemplateNotFound(_) | Self::FragmentNotFound(_, _) => {
+                fmt::Display::fmt(self, f)
+            }
             Self::Io(ref e) => fmt::Debug::fmt(e, f),
         }
     }
 }
 
-impl From<std::io::Error> for Error {
-    fn from(err: std::io::Error) -> Self {
+impl Error {
+    /// Convert this error to a diagnostic.
+    pub fn diagnostic(&self) -> Diagnostic {
+        match *self {
+            Self::Syntax(ref e) => e.diagnostic(),
+            Self::Render(ref e) => e.diagnostic(),
+            Self::TemplateNotFound(_) => Diagnostic::new(
+                DiagnosticKind::Template,
+                "template_not_found",
+                self.to_string(),
+            ),
+            Self::FragmentNotFound(_, _) => Diagnostic::new(
+                DiagnosticKind::Template,
+                "fragment_not_found",
+                self.to_string(),
+            ),
+            Self::Io(_) => {
+                Diagnostic::new(DiagnosticKind::Io, "io", self.to_string())
+            }
+        }
+    }
+
+    /// Convert this error to JSON; see the
+    /// [diagnostic](crate::error::diagnostic) module for the fields.
+    pub fn to_json(&self) -> Value {
+        self.diagnostic().to_json()
+    }
+}
+impl Serialize for Error {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.diagnostic().serialize(serializer)
+    }
+}
+
+impl From<crate::output::io::Error> for Error {
+    fn from(err: crate::output::io::Error) -> Self {
         Self::Io(IoError::Io(err))
     }
 }
@@ -53,7 +104,7 @@ impl From<std::io::Error> for Error {
 pub enum IoError {
     /// Proxy IO errors.
     #[error(transparent)]
-    Io(#[from] std::io::Error),
+    Io(#[from] crate::output::io::Error),
 }
 
 impl PartialEq for IoError {
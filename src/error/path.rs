@@ -0,0 +1,23 @@
+//! Errors generated when parsing value paths.
+use thiserror::Error;
+
+/// Errors generated when parsing a [ValuePath](crate::query::ValuePath).
+///
+/// Each variant holds the byte offset in the path where
+/// the error was detected.
+#[derive(Error, Debug, Eq, PartialEq)]
+pub enum PathError {
+    /// Error when a path has an empty segment such as `a..b`.
+    #[error("Empty path segment at offset {0}")]
+    EmptySegment(usize),
+    /// Error when a bracket segment is not closed.
+    #[error("Unterminated bracket segment at offset {0}")]
+    UnterminatedBracket(usize),
+    /// Error when a bracket segment is followed by
+    /// characters other than a delimiter.
+    #[error("Expected '.' or '[' after bracket segment at offset {0}")]
+    ExpectedDelimiter(usize),
+    /// Error when a path ends with an escape character.
+    #[error("Trailing escape character at offset {0}")]
+    TrailingEscape(usize),
+}
@@ -1,22 +1,107 @@
 //! Errors generated when rendering templates.
-use crate::error::{HelperError, IoError};
-use std::fmt;
+use crate::error::{
+    diagnostic::{Diagnostic, DiagnosticKind},
+    HelperError, IoError,
+};
+use alloc::{
+    boxed::Box,
+    string::{String, ToString},
+};
+use core::fmt;
+use core::ops::Range;
+use serde::{Serialize, Serializer};
+use serde_json::Value;
 use thiserror::Error;
 
+/// Location in a template where a render error occurred.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Location {
+    name: String,
+    span: Range<usize>,
+    lines: Range<usize>,
+    source: String,
+}
+
+impl Location {
+    /// Create a new location.
+    ///
+    /// The `name` is the name of the template, `span` is the byte range
+    /// of the node, `lines` is the zero-based line range and `source`
+    /// is the template source for the node.
+    pub fn new(
+        name: String,
+        span: Range<usize>,
+        lines: Range<usize>,
+        source: String,
+    ) -> Self {
+        Self {
+            name,
+            span,
+            lines,
+            source,
+        }
+    }
+
+    /// The name of the template.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The byte range of the node.
+    pub fn span(&self) -> &Range<usize> {
+        &self.span
+    }
+
+    /// The zero-based line range.
+    pub fn lines(&self) -> &Range<usize> {
+        &self.lines
+    }
+
+    /// The template source for the node.
+    pub fn source(&self) -> &str {
+        &self.source
+    }
+}
+
+impl fmt::Display for Location {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.name, self.lines.start + 1)
+    }
+}
+
 /// Errors generated during rendering.
+///
+/// Errors that occur whilst rendering a node are assigned the
+/// [Location](Location) of the node; the location is not included
+/// in the display message but is shown in the debug output.
 #[derive(Error)]
 pub enum RenderError {
     /// Error when a partial could not be found.
     #[error("Partial '{0}' not found")]
-    PartialNotFound(String),
+    PartialNotFound(String, Option<Box<Location>>),
+
+    /// Error when the `isolated` hash parameter for a partial
+    /// is not a boolean.
+    #[error("Partial '{0}' expects a boolean for 'isolated', got {1}")]
+    PartialIsolated(String, String, Option<Box<Location>>),
+
+    /// Error when a partial call does not satisfy the contract
+    /// for the partial in strict mode.
+    #[error("Partial '{0}' contract violation, {1}")]
+    PartialContract(String, String, Option<Box<Location>>),
 
     /// Error when a variable could not be resolved.
     #[error("Variable '{0}' not found in {1}, check the variable path and verify the template data")]
-    VariableNotFound(String, String),
+    VariableNotFound(String, String, Option<Box<Location>>),
+
+    /// Error when a statement is null or missing and the
+    /// null policy does not allow it.
+    #[error("Variable '{0}' is null or missing in {1}")]
+    NullValue(String, String, Option<Box<Location>>),
 
     /// Error when a helper could not be found.
     #[error("Helper '{0}' not found, check the name")]
-    HelperNotFound(String),
+    HelperNotFound(String, Option<Box<Location>>),
 
     /// Error when evaluating a path and a syntax error occurs.
     ///
@@ -24,62 +109,240 @@ pub enum RenderError {
     /// [evaluate()](crate::render::Render#method.evaluate) function is called
     /// inside a helper.
     #[error("Syntax error while evaluating path '{0}'")]
-    EvaluatePath(String),
+    EvaluatePath(String, Option<Box<Location>>),
 
     /// Error when a cycle is detected whilst handling a partial.
     #[error("Cycle detected whilst processing partial '{0}'")]
-    PartialCycle(String),
+    PartialCycle(String, Option<Box<Location>>),
 
     /// Error when a cycle is detected whilst handling a helper.
     #[error("Cycle detected whilst processing helper '{0}'")]
-    HelperCycle(String),
+    HelperCycle(String, Option<Box<Location>>),
+
+    /// Error when a helper that uses ambient authority is called
+    /// and the registry is sandboxed.
+    #[error("Helper '{0}' is not allowed in a sandbox")]
+    HelperSandboxed(String, Option<Box<Location>>),
+
+    /// Error when the sandbox partial depth is exceeded.
+    #[error("Partial '{0}' exceeds the maximum partial depth {1}")]
+    PartialDepth(String, usize, Option<Box<Location>>),
+
+    /// Error when nodes are nested deeper than the maximum render depth.
+    #[error("Render depth exceeds the maximum of {0}")]
+    DepthLimit(usize, Option<Box<Location>>),
+
+    /// Error when the sandbox output limit is exceeded.
+    #[error("Output exceeds the maximum size of {0} bytes")]
+    OutputLimit(usize, Option<Box<Location>>),
+
+    /// Error when unescaped interpolation is not allowed by the sandbox.
+    #[error("Unescaped output is not allowed in a sandbox, got '{0}'")]
+    RawNotAllowed(String, Option<Box<Location>>),
 
     /// Error when a partial is not a simple identifier.
     #[error("Partial names must be simple identifiers, got path '{0}'")]
-    PartialIdentifier(String),
+    PartialIdentifier(String, Option<Box<Location>>),
     /// Error when a block is not a simple identifier.
     #[error("Block names must be simple identifiers, got path '{0}'")]
-    BlockIdentifier(String),
+    BlockIdentifier(String, Option<Box<Location>>),
     /// Error attempting to invoke a sub-expression outside of a partial target context.
     #[error("Block target sub expressions are only supported for partials")]
-    BlockTargetSubExpr,
+    BlockTargetSubExpr(Option<Box<Location>>),
 
     /// Wrap a helper error.
-    #[error(transparent)]
-    Helper(#[from] HelperError),
+    ///
+    /// When the error was returned by a helper call the location
+    /// is the call rather than the node being rendered.
+    #[error("{0}")]
+    Helper(#[source] HelperError, Option<Box<Location>>),
 
     /// Wrap a syntax error.
     //#[error(transparent)]
     //Syntax(#[from] Box<SyntaxError>),
 
     /// Proxy for IO errors.
-    #[error(transparent)]
-    Io(#[from] IoError),
+    #[error("{0}")]
+    Io(#[source] IoError, Option<Box<Location>>),
 
     /// Proxy for JSON errors.
-    #[error(transparent)]
-    Json(#[from] serde_json::Error),
+    #[error("{0}")]
+    Json(#[source] serde_json::Error, Option<Box<Location>>),
+}
+
+impl RenderError {
+    /// The location in the template where this error occurred.
+    pub fn location(&self) -> Option<&Location> {
+        match *self {
+            Self::PartialNotFound(_, ref location)
+            | Self::PartialIsolated(_, _, ref location)
+            | Self::PartialContract(_, _, ref location)
+            | Self::VariableNotFound(_, _, ref location)
+            | Self::NullValue(_, _, ref location)
+            | Self::HelperNotFound(_, ref location)
+            | Self::EvaluatePath(_, ref location)
+            | Self::PartialCycle(_, ref location)
+            | Self::HelperCycle(_, ref location)
+            | Self::HelperSandboxed(_, ref location)
+            | Self::PartialDepth(_, _, ref location)
+            | Self::DepthLimit(_, ref location)
+            | Self::OutputLimit(_, ref location)
+            | Self::RawNotAllowed(_, ref location)
+            | Self::PartialIdentifier(_, ref location)
+            | Self::BlockIdentifier(_, ref location)
+            | Self::BlockTargetSubExpr(ref location)
+            | Self::Helper(_, ref location)
+            | Self::Io(_, ref location)
+            | Self::Json(_, ref location) => location.as_deref(),
+        }
+    }
+
+    /// Stable identifier for the kind of error.
+    ///
+    /// Errors returned by helpers use the code of the helper error.
+    pub fn code(&self) -> &'static str {
+        match *self {
+            Self::PartialNotFound(..) => "partial_not_found",
+            Self::PartialIsolated(..) => "partial_isolated",
+            Self::PartialContract(..) => "partial_contract",
+            Self::VariableNotFound(..) => "variable_not_found",
+            Self::NullValue(..) => "null_value",
+            Self::HelperNotFound(..) => "helper_not_found",
+            Self::EvaluatePath(..) => "evaluate_path",
+            Self::PartialCycle(..) => "partial_cycle",
+            Self::HelperCycle(..) => "helper_cycle",
+            Self::HelperSandboxed(..) => "helper_sandboxed",
+            Self::PartialDepth(..) => "partial_depth",
+            Self::DepthLimit(..) => "depth_limit",
+            Self::OutputLimit(..) => "output_limit",
+            Self::RawNotAllowed(..) => "raw_not_allowed",
+            Self::PartialIdentifier(..) => "partial_identifier",
+            Self::BlockIdentifier(..) => "block_identifier",
+            Self::BlockTargetSubExpr(..) => "block_target_sub_expr",
+            Self::Helper(ref e, _) => e.code(),
+            Self::Io(..) => "io",
+            Self::Json(..) => "json",
+        }
+    }
+
+    /// Convert this error to a diagnostic.
+    ///
+    /// The diagnostic for an error returned by a helper is the
+    /// diagnostic for the helper error with the location of the call
+    /// when the helper error does not have a location.
+    pub fn diagnostic(&self) -> Diagnostic {
+        let mut diagnostic = match *self {
+            Self::Helper(ref e, _) => e.diagnostic(),
+            Self::Io(..) => Diagnostic::new(
+                DiagnosticKind::Io,
+                self.code(),
+                self.to_string(),
+            ),
+            _ => Diagnostic::new(
+                DiagnosticKind::Render,
+                self.code(),
+                self.to_string(),
+            ),
+        };
+        if let (None, Some(location)) = (&diagnostic.file, self.location()) {
+            diagnostic.file = Some(location.name().to_string());
+            diagnostic.line = Some(location.lines().start);
+            diagnostic.span = Some(location.span().clone());
+        }
+        diagnostic
+    }
+
+    /// Convert this error to JSON; see the
+    /// [diagnostic](crate::error::diagnostic) module for the fields.
+    pub fn to_json(&self) -> Value {
+        self.diagnostic().to_json()
+    }
+
+    /// Assign a location to this error if it does not already
+    /// have a location.
+    pub(crate) fn locate<F>(mut self, location: F) -> Self
+    where
+        F: FnOnce() -> Location,
+    {
+        let target = match self {
+            Self::PartialNotFound(_, ref mut location)
+            | Self::PartialIsolated(_, _, ref mut location)
+            | Self::PartialContract(_, _, ref mut location)
+            | Self::VariableNotFound(_, _, ref mut location)
+            | Self::NullValue(_, _, ref mut location)
+            | Self::HelperNotFound(_, ref mut location)
+            | Self::EvaluatePath(_, ref mut location)
+            | Self::PartialCycle(_, ref mut location)
+            | Self::HelperCycle(_, ref mut location)
+            | Self::HelperSandboxed(_, ref mut location)
+            | Self::PartialDepth(_, _, ref mut location)
+            | Self::DepthLimit(_, ref mut location)
+            | Self::OutputLimit(_, ref mut location)
+            | Self::RawNotAllowed(_, ref mut location)
+            | Self::PartialIdentifier(_, ref mut location)
+            | Self::BlockIdentifier(_, ref mut location)
+            | Self::BlockTargetSubExpr(ref mut location)
+            | Self::Helper(_, ref mut location)
+            | Self::Io(_, ref mut location)
+            | Self::Json(_, ref mut location) => location,
+        };
+        if target.is_none() {
+            *target = Some(Box::new(location()));
+        }
+        self
+    }
 }
 
 impl fmt::Debug for RenderError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}", self.to_string())
-        // TODO: support source code snippets
+        write!(f, "{}", self.to_string())?;
+        if let Some(location) = self.location() {
+            write!(f, "\n --> {}\n  |\n  | {}", location, location.source())?;
+        }
+        Ok(())
+    }
+}
+
+impl Serialize for RenderError {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.diagnostic().serialize(serializer)
+    }
+}
+
+impl From<HelperError> for RenderError {
+    fn from(err: HelperError) -> Self {
+        Self::Helper(err, None)
+    }
+}
+
+impl From<crate::output::io::Error> for RenderError {
+    fn from(err: crate::output::io::Error) -> Self {
+        Self::Io(IoError::Io(err), None)
+    }
+}
+
+impl From<IoError> for RenderError {
+    fn from(err: IoError) -> Self {
+        Self::Io(err, None)
     }
 }
 
-impl From<std::io::Error> for RenderError {
-    fn from(err: std::io::Error) -> Self {
-        Self::Io(IoError::Io(err))
+impl From<serde_json::Error> for RenderError {
+    fn from(err: serde_json::Error) -> Self {
+        Self::Json(err, None)
     }
 }
 
 impl PartialEq for RenderError {
     fn eq(&self, other: &Self) -> bool {
         match (self, other) {
-            (Self::PartialNotFound(ref s), Self::PartialNotFound(ref o)) => {
-                s == o
-            }
+            (
+                Self::PartialNotFound(ref s, _),
+                Self::PartialNotFound(ref o, _),
+            ) => s == o,
             _ => false,
         }
     }
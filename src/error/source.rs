@@ -1,5 +1,15 @@
 //! Utilties for generating error messages with source code.
-use std::fmt;
+use alloc::{
+    boxed::Box,
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+use core::{
+    fmt,
+    ops::{Deref, Range},
+};
 use unicode_width::UnicodeWidthStr;
 
 use crate::parser::ParseState;
@@ -52,29 +62,22 @@ impl<'source> ErrorInfo<'source> {
     }
 
     fn find_prev_line_offset(&self, s: &str, pos: &SourcePos) -> Option<usize> {
-        let mut counter: usize = pos.byte_offset().clone();
-        while counter > 0 {
-            // TODO: clamp end range to string length!
-            let slice = &s[counter..counter + 1];
-            if slice == "\n" {
-                return Some(counter);
-            }
-            counter -= 1;
-        }
-        None
+        let offset = self.clamp_offset(s, pos);
+        s.as_bytes()[..offset].iter().rposition(|b| *b == b'\n')
     }
 
     fn find_next_line_offset(&self, s: &str, pos: &SourcePos) -> Option<usize> {
-        let mut counter: usize = pos.byte_offset().clone();
-        while counter < s.len() {
-            // TODO: clamp end range to string length!
-            let slice = &s[counter..counter + 1];
-            if slice == "\n" {
-                return Some(counter);
-            }
-            counter += 1;
-        }
-        None
+        let offset = self.clamp_offset(s, pos);
+        s.as_bytes()[offset..]
+            .iter()
+            .position(|b| *b == b'\n')
+            .map(|i| offset + i)
+    }
+
+    /// Byte offset for the source position constrained to the
+    /// source length.
+    fn clamp_offset(&self, s: &str, pos: &SourcePos) -> usize {
+        core::cmp::min(*pos.byte_offset(), s.len())
     }
 }
 
@@ -120,16 +123,22 @@ impl fmt::Debug for ErrorInfo<'_> {
             s.len()
         };
 
-        let line_slice = &s[prev_line_offset..next_line_offset];
+        // NOTE: exclude the carriage return for CRLF line endings
+        let line_slice =
+            s[prev_line_offset..next_line_offset].trim_end_matches('\r');
         let line_number = pos.line();
 
         let line_prefix = format!(" {} | ", line_number + 1);
         let line_padding = " ".repeat(line_prefix.len() - 3);
 
-        let diff = (pos.byte_offset() - prev_line_offset) + 1;
-        let diff_start = prev_line_offset;
-        let diff_end = prev_line_offset + diff;
-        let diff_str = &s[diff_start..diff_end];
+        // NOTE: include the character at the error position but
+        // NOTE: ensure we do not slice inside a multi-byte character.
+        let mut diff_end =
+            core::cmp::min(self.clamp_offset(s, pos) + 1, next_line_offset);
+        while !s.is_char_boundary(diff_end) {
+            diff_end += 1;
+        }
+        let diff_str = &s[prev_line_offset..diff_end];
 
         let cols = UnicodeWidthStr::width(diff_str);
 
@@ -148,11 +157,8 @@ impl fmt::Debug for ErrorInfo<'_> {
         write!(f, "{}{}\n", line_prefix, line_slice)?;
         write!(f, "{} | {}", line_padding, err_pointer)?;
 
-        if !self.notes.is_empty() {
-            write!(f, "\n")?;
-            for n in self.notes.iter() {
-                write!(f, "{} = note: {}", line_padding, n)?;
-            }
+        for n in self.notes.iter() {
+            write!(f, "\n{} = note: {}", line_padding, n)?;
         }
 
         Ok(())
@@ -164,3 +170,79 @@ impl Into<String> for ErrorInfo<'_> {
         format!("{:?}", self)
     }
 }
+
+/// Source code snippet and position for a syntax error.
+///
+/// Displays as and dereferences to the snippet generated by
+/// [ErrorInfo](ErrorInfo); the position is kept so that errors can
+/// be reported as [diagnostics](crate::error::Diagnostic).
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Snippet(Box<SnippetFields>);
+
+// Boxed so that syntax errors stay small as they are returned
+// through the recursive parser and render functions.
+#[derive(Clone, Debug, Eq, PartialEq)]
+struct SnippetFields {
+    snippet: String,
+    file_name: String,
+    line: usize,
+    span: Range<usize>,
+    notes: Vec<String>,
+}
+
+impl Snippet {
+    /// The file name for the template.
+    pub fn file_name(&self) -> &str {
+        &self.0.file_name
+    }
+
+    /// The zero-based line number.
+    pub fn line(&self) -> usize {
+        self.0.line
+    }
+
+    /// The byte range of the character at the error position.
+    pub fn span(&self) -> &Range<usize> {
+        &self.0.span
+    }
+
+    /// The notes for the error.
+    pub fn notes(&self) -> &[String] {
+        &self.0.notes
+    }
+}
+
+impl From<ErrorInfo<'_>> for Snippet {
+    fn from(info: ErrorInfo<'_>) -> Self {
+        let mut start = info.clamp_offset(info.source, &info.source_pos);
+        while !info.source.is_char_boundary(start) {
+            start -= 1;
+        }
+        let end = info.source[start..]
+            .chars()
+            .next()
+            .map(|c| start + c.len_utf8())
+            .unwrap_or(start);
+        Self(Box::new(SnippetFields {
+            snippet: format!("{:?}", info),
+            line: *info.source_pos.line(),
+            span: start..end,
+            file_name: info.file_name,
+            notes: info.notes,
+        }))
+    }
+}
+
+impl Deref for Snippet {
+    type Target = str;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0.snippet
+    }
+}
+
+impl fmt::Display for Snippet {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.0.snippet)
+    }
+}
@@ -1,35 +1,43 @@
 //! Errors generated when compiling templates.
-use std::fmt;
+use alloc::string::{String, ToString};
+use core::fmt;
+use serde::{Serialize, Serializer};
+use serde_json::Value;
 use thiserror::Error;
 
+use crate::error::{
+    diagnostic::{Diagnostic, DiagnosticKind},
+    source::Snippet,
+};
+
 /// Errors generated when compiling a template.
 #[derive(Error, Eq, PartialEq)]
 pub enum SyntaxError {
     /// Error when an identifier is expected.
     #[error("Syntax error, expecting identifier")]
-    ExpectedIdentifier(String),
+    ExpectedIdentifier(Snippet),
 
     /// Error when a path is expected.
     #[error("Syntax error, expecting path")]
-    ExpectedPath(String),
+    ExpectedPath(Snippet),
 
     /// Error if a block name is not a simple identifier.
     #[error("Syntax error, block name must be an identifier")]
-    BlockName(String),
+    BlockName(Snippet),
 
     /// Error when a newline is enccountered in a raw literal.
     #[error("Syntax error, new lines in raw literals must be escaped (\\n)")]
-    LiteralNewline(String),
+    LiteralNewline(Snippet),
 
     /// Error when the partial operator is not the first token in a call statement.
     #[error("Syntax error, partial operator (>) must come first")]
-    PartialPosition(String),
+    PartialPosition(Snippet),
 
     /// Error when a sub-expression is closed by no sub-expression is open.
     #[error(
         "Syntax error, got close sub-expression but no sub-expression is open"
     )]
-    SubExprNotOpen(String),
+    SubExprNotOpen(Snippet),
 
     /// Error when a sub-expression attempts to use a sub-expression for it's target.
     ///
@@ -38,184 +46,395 @@ pub enum SyntaxError {
     #[error(
         "Syntax error, sub-expression must use an identifier for the target"
     )]
-    SubExprTargetNotAllowed(String),
+    SubExprTargetNotAllowed(Snippet),
 
     /// Error when a path delimiter is encountered in an invalid position.
     #[error("Syntax error, path delimiter (.) not allowed here")]
-    PathDelimiterNotAllowed(String),
+    PathDelimiterNotAllowed(Snippet),
 
     /// Error when the `else` keyword is encountered in an invalid position.
     #[error("Syntax error, 'else' keyword is not allowed here")]
-    ElseNotAllowed(String),
+    ElseNotAllowed(Snippet),
 
     /// Error when the `this` keywords is not at the start of a path.
     #[error(
         "Syntax error, explicit this reference must be at the start of a path"
     )]
-    UnexpectedPathExplicitThis(String),
+    UnexpectedPathExplicitThis(Snippet),
 
     /// Error when a parent path reference (../) is not at the start of a path.
     #[error("Syntax error, parent scopes must be at the start of a path")]
-    UnexpectedPathParent(String),
+    UnexpectedPathParent(Snippet),
 
     /// Error when a local identifier is not at the start of a path.
     #[error(
         "Syntax error, local scope identifiers must be at the start of a path"
     )]
-    UnexpectedPathLocal(String),
+    UnexpectedPathLocal(Snippet),
 
     /// Error when an identifier is expected but a path delimiter was encountered.
     #[error("Syntax error, expected identifier but got path delimiter")]
-    UnexpectedPathDelimiter(String),
+    UnexpectedPathDelimiter(Snippet),
 
     /// Error when parent scope references and local identifiers are combined illegally.
     #[error("Syntax error, parent scopes and local identifiers are mutually exclusive")]
-    UnexpectedPathParentWithLocal(String),
+    UnexpectedPathParentWithLocal(Snippet),
 
     /// Error attempting to mix parent scope references and explicit this.
     #[error(
         "Syntax error, parent scopes and explicit this are mutually exclusive"
     )]
-    UnexpectedPathParentWithExplicit(String),
+    UnexpectedPathParentWithExplicit(Snippet),
+
+    /// Error when a number literal is not a valid JSON number.
+    #[error("Syntax error, invalid number literal")]
+    NumberLiteral(Snippet),
 
     /// Error when a path delimiter is expected.
     #[error("Syntax error, expected path delimiter (.)")]
-    ExpectedPathDelimiter(String),
+    ExpectedPathDelimiter(Snippet),
 
     /// Error when a sub-expression was not terminated.
     #[error("Syntax error, sub-expression not terminated")]
-    OpenSubExpression(String),
+    OpenSubExpression(Snippet),
 
     /// Error when a closing tag name does not match the opening name.
     #[error("Syntax error, closing name does not match")]
-    TagNameMismatch(String),
+    TagNameMismatch(Snippet),
 
     /// Error when an end tag is encountered but no block is open.
     #[error("Syntax error, got a closing tag but no block is open")]
-    BlockNotOpen(String),
+    BlockNotOpen(Snippet),
+
+    /// Error when a statement or the open tag for a block
+    /// is not terminated.
+    #[error("Syntax error, statement was not terminated")]
+    StatementNotTerminated(Snippet),
 
     /// Error when a sub-expression is not terminated.
     #[error("Syntax error, sub-expression was not terminated")]
-    SubExpressionNotTerminated(String),
+    SubExpressionNotTerminated(Snippet),
     /// Erro when a link is not terminated.
     #[error("Syntax error, link was not terminated")]
-    LinkNotTerminated(String),
+    LinkNotTerminated(Snippet),
 
     /// Error when the opening tag for a raw block is not terminated.
     #[error("Syntax error, raw block open tag was not terminated")]
-    RawBlockOpenNotTerminated(String),
+    RawBlockOpenNotTerminated(Snippet),
+
+    /// Error when a block is not terminated.
+    #[error("Syntax error, block was not terminated")]
+    BlockNotTerminated(Snippet),
 
     /// Error when a raw block is not terminated.
     #[error("Syntax error, raw block was not terminated")]
-    RawBlockNotTerminated(String),
+    RawBlockNotTerminated(Snippet),
     /// Error when a raw comment is not terminated.
     #[error("Syntax error, raw comment was not terminated")]
-    RawCommentNotTerminated(String),
+    RawCommentNotTerminated(Snippet),
     /// Error when a raw statement is not terminated.
     #[error("Syntax error, raw statement was not terminated")]
-    RawStatementNotTerminated(String),
+    RawStatementNotTerminated(Snippet),
     /// Error when a comment is not terminated.
     #[error("Syntax error, comment was not terminated")]
-    CommentNotTerminated(String),
+    CommentNotTerminated(Snippet),
 
     /// Error attempting to use a sub-expression outside of a partial target context.
     #[error("Syntax error, block target sub expressions are only supported for partials")]
-    BlockTargetSubExpr(String),
+    BlockTargetSubExpr(Snippet),
     /// Error when an empty path is encountered.
     #[error("Syntax error, path is empty")]
-    EmptyPath(String),
+    EmptyPath(Snippet),
     /// Error if we could not identify the type of a path component (internal error).
     #[error("Syntax error, path component type could not be identified")]
-    ComponentType(String),
+    ComponentType(Snippet),
     /// Error attempting to combine partials with conditionals.
     #[error("Syntax error, partials and conditionals may not be combined")]
-    MixedPartialConditional(String),
+    MixedPartialConditional(Snippet),
+    /// Error when a filter pipe is used in a partial, conditional or block.
+    #[error("Syntax error, filter pipes (|) are not allowed here")]
+    PipeNotAllowed(Snippet),
+    /// Error when the block parameters of an open block are invalid.
+    #[error("Syntax error, block parameters must be identifiers between pipes (as |name|)")]
+    BlockParams(Snippet),
+
+    /// Error when an identifier contains a character that is not
+    /// allowed by the Unicode identifier syntax (UAX #31).
+    #[error("Syntax error, character '{0}' is not allowed in an identifier")]
+    IdentifierCharacter(char, Snippet),
+
+    /// Lint warning when a deprecated helper is called.
+    #[error("Helper '{0}' is deprecated, {1}")]
+    DeprecatedHelper(String, String, Snippet),
+
+    /// Lint warning when a whitespace control character has no effect.
+    #[error("Whitespace control has no effect, {0}")]
+    TrimNoEffect(String, Snippet),
+
+    /// Lint warning when a block template can never be rendered.
+    #[error("Unreachable template in block '{0}', {1}")]
+    UnreachableBlock(String, String, Snippet),
+
+    /// Lint warning when whitespace control removes the space
+    /// between text and the output of a statement.
+    #[error("Whitespace control joins text and the statement output")]
+    TrimJoinsText(Snippet),
+
+    /// Error when a call added to a document builder does not
+    /// produce a single tag.
+    #[error("Syntax error, call '{0}' must produce a single tag")]
+    BuilderTag(String, Snippet),
+
+    /// Error when a template references a partial that does not exist.
+    #[error("Partial '{0}' does not exist")]
+    MissingPartial(String, Snippet),
+
+    /// Error when a partial call does not satisfy the contract
+    /// for the partial.
+    #[error("Partial '{0}' contract violation, {1}")]
+    PartialContract(String, String, Snippet),
+
+    /// Error when a template calls a helper that does not exist.
+    #[error("Helper '{0}' does not exist")]
+    MissingHelper(String, Snippet),
+
+    /// Error when blocks or sub-expressions are nested deeper
+    /// than the maximum depth.
+    #[error("Syntax error, nesting depth exceeds the maximum of {0}")]
+    NestingDepth(usize, Snippet),
 
     /// Invalid token error (internal error).
     #[error("Syntax error, unexpected error token for context '{0}'")]
-    TokenError(String, String),
+    TokenError(String, Snippet),
     /// Invalid token error (internal error).
     #[error("Syntax error, expecting path or sub-expression for call target")]
-    TokenCallTarget(String),
+    TokenCallTarget(Snippet),
     /// Invalid token error (internal error).
     #[error("Syntax error, expecting JSON literal token")]
-    TokenJsonLiteral(String),
+    TokenJsonLiteral(Snippet),
     /// Invalid token error (internal error).
     #[error("Syntax error, expecting parameter token")]
-    TokenParameter(String),
+    TokenParameter(Snippet),
     /// Invalid token error (internal error).
     #[error("Syntax error, expecting key/value token")]
-    TokenHashKeyValue(String),
+    TokenHashKeyValue(Snippet),
     /// Invalid token error (internal error).
     #[error("Syntax error, expecting raw literal token")]
-    TokenRawLiteral(String),
+    TokenRawLiteral(Snippet),
     /// Invalid token error (internal error).
     #[error("Syntax error, unexpected token parsing quoted literal (\"\")")]
-    TokenDoubleQuoteLiteral(String),
+    TokenDoubleQuoteLiteral(Snippet),
     /// Invalid token error (internal error).
     #[error("Syntax error, unexpected token parsing quoted literal ('')")]
-    TokenSingleQuoteLiteral(String),
+    TokenSingleQuoteLiteral(Snippet),
     /// Invalid token error (internal error).
     #[error("Syntax error, unexpected token parsing quoted literal ([])")]
-    TokenArrayLiteral(String),
+    TokenArrayLiteral(Snippet),
     /// Invalid token error (internal error).
     #[error("Syntax error, unexpected token parsing link")]
-    TokenLink(String),
+    TokenLink(Snippet),
     /// Invalid token error (internal error).
     #[error("Syntax error, unexpected token parsing path")]
-    TokenParameterPath(String),
+    TokenParameterPath(Snippet),
     /// Invalid token error (internal error).
     #[error("Syntax error, unexpected token, expecting end of raw block")]
-    TokenEndRawBlock(String),
+    TokenEndRawBlock(Snippet),
+}
+
+impl SyntaxError {
+    /// The source snippet and position for this error.
+    pub fn snippet(&self) -> &Snippet {
+        match *self {
+            Self::ExpectedIdentifier(ref snippet)
+            | Self::ExpectedPath(ref snippet)
+            | Self::BlockName(ref snippet)
+            | Self::LiteralNewline(ref snippet)
+            | Self::PartialPosition(ref snippet)
+            | Self::SubExprNotOpen(ref snippet)
+            | Self::SubExprTargetNotAllowed(ref snippet)
+            | Self::PathDelimiterNotAllowed(ref snippet)
+            | Self::ElseNotAllowed(ref snippet)
+            | Self::UnexpectedPathExplicitThis(ref snippet)
+            | Self::UnexpectedPathParent(ref snippet)
+            | Self::UnexpectedPathLocal(ref snippet)
+            | Self::UnexpectedPathDelimiter(ref snippet)
+            | Self::UnexpectedPathParentWithLocal(ref snippet)
+            | Self::UnexpectedPathParentWithExplicit(ref snippet)
+            | Self::NumberLiteral(ref snippet)
+            | Self::ExpectedPathDelimiter(ref snippet)
+            | Self::OpenSubExpression(ref snippet)
+            | Self::TagNameMismatch(ref snippet)
+            | Self::BlockNotOpen(ref snippet)
+            | Self::StatementNotTerminated(ref snippet)
+            | Self::SubExpressionNotTerminated(ref snippet)
+            | Self::LinkNotTerminated(ref snippet)
+            | Self::RawBlockOpenNotTerminated(ref snippet)
+            | Self::BlockNotTerminated(ref snippet)
+            | Self::RawBlockNotTerminated(ref snippet)
+            | Self::RawCommentNotTerminated(ref snippet)
+            | Self::RawStatementNotTerminated(ref snippet)
+            | Self::CommentNotTerminated(ref snippet)
+            | Self::BlockTargetSubExpr(ref snippet)
+            | Self::EmptyPath(ref snippet)
+            | Self::ComponentType(ref snippet)
+            | Self::MixedPartialConditional(ref snippet)
+            | Self::PipeNotAllowed(ref snippet)
+            | Self::BlockParams(ref snippet)
+            | Self::IdentifierCharacter(_, ref snippet)
+            | Self::DeprecatedHelper(_, _, ref snippet)
+            | Self::TrimNoEffect(_, ref snippet)
+            | Self::UnreachableBlock(_, _, ref snippet)
+            | Self::TrimJoinsText(ref snippet)
+            | Self::BuilderTag(_, ref snippet)
+            | Self::MissingPartial(_, ref snippet)
+            | Self::PartialContract(_, _, ref snippet)
+            | Self::MissingHelper(_, ref snippet)
+            | Self::NestingDepth(_, ref snippet)
+            | Self::TokenError(_, ref snippet)
+            | Self::TokenCallTarget(ref snippet)
+            | Self::TokenJsonLiteral(ref snippet)
+            | Self::TokenParameter(ref snippet)
+            | Self::TokenHashKeyValue(ref snippet)
+            | Self::TokenRawLiteral(ref snippet)
+            | Self::TokenDoubleQuoteLiteral(ref snippet)
+            | Self::TokenSingleQuoteLiteral(ref snippet)
+            | Self::TokenArrayLiteral(ref snippet)
+            | Self::TokenLink(ref snippet)
+            | Self::TokenParameterPath(ref snippet)
+            | Self::TokenEndRawBlock(ref snippet) => snippet,
+        }
+    }
+
+    /// Stable identifier for the kind of error.
+    pub fn code(&self) -> &'static str {
+        match *self {
+            Self::ExpectedIdentifier(..) => "expected_identifier",
+            Self::ExpectedPath(..) => "expected_path",
+            Self::BlockName(..) => "block_name",
+            Self::LiteralNewline(..) => "literal_newline",
+            Self::PartialPosition(..) => "partial_position",
+            Self::SubExprNotOpen(..) => "sub_expr_not_open",
+            Self::SubExprTargetNotAllowed(..) => "sub_expr_target_not_allowed",
+            Self::PathDelimiterNotAllowed(..) => "path_delimiter_not_allowed",
+            Self::ElseNotAllowed(..) => "else_not_allowed",
+            Self::UnexpectedPathExplicitThis(..) => {
+                "unexpected_path_explicit_this"
+            }
+            Self::UnexpectedPathParent(..) => "unexpected_path_parent",
+            Self::UnexpectedPathLocal(..) => "unexpected_path_local",
+            Self::UnexpectedPathDelimiter(..) => "unexpected_path_delimiter",
+            Self::UnexpectedPathParentWithLocal(..) => {
+                "unexpected_path_parent_with_local"
+            }
+            Self::UnexpectedPathParentWithExplicit(..) => {
+                "unexpected_path_parent_with_explicit"
+            }
+            Self::NumberLiteral(..) => "number_literal",
+            Self::ExpectedPathDelimiter(..) => "expected_path_delimiter",
+            Self::OpenSubExpression(..) => "open_sub_expression",
+            Self::TagNameMismatch(..) => "tag_name_mismatch",
+            Self::BlockNotOpen(..) => "block_not_open",
+            Self::StatementNotTerminated(..) => "statement_not_terminated",
+            Self::SubExpressionNotTerminated(..) => {
+                "sub_expression_not_terminated"
+            }
+            Self::LinkNotTerminated(..) => "link_not_terminated",
+            Self::RawBlockOpenNotTerminated(..) => {
+                "raw_block_open_not_terminated"
+            }
+            Self::BlockNotTerminated(..) => "block_not_terminated",
+            Self::RawBlockNotTerminated(..) => "raw_block_not_terminated",
+            Self::RawCommentNotTerminated(..) => "raw_comment_not_terminated",
+            Self::RawStatementNotTerminated(..) => {
+                "raw_statement_not_terminated"
+            }
+            Self::CommentNotTerminated(..) => "comment_not_terminated",
+            Self::BlockTargetSubExpr(..) => "block_target_sub_expr",
+            Self::EmptyPath(..) => "empty_path",
+            Self::ComponentType(..) => "component_type",
+            Self::MixedPartialConditional(..) => "mixed_partial_conditional",
+            Self::PipeNotAllowed(..) => "pipe_not_allowed",
+            Self::BlockParams(..) => "block_params",
+            Self::IdentifierCharacter(..) => "identifier_character",
+            Self::DeprecatedHelper(..) => "deprecated_helper",
+            Self::TrimNoEffect(..) => "trim_no_effect",
+            Self::UnreachableBlock(..) => "unreachable_block",
+            Self::TrimJoinsText(..) => "trim_joins_text",
+            Self::BuilderTag(..) => "builder_tag",
+            Self::MissingPartial(..) => "missing_partial",
+            Self::PartialContract(..) => "partial_contract",
+            Self::MissingHelper(..) => "missing_helper",
+            Self::NestingDepth(..) => "nesting_depth",
+            Self::TokenError(..) => "token_error",
+            Self::TokenCallTarget(..) => "token_call_target",
+            Self::TokenJsonLiteral(..) => "token_json_literal",
+            Self::TokenParameter(..) => "token_parameter",
+            Self::TokenHashKeyValue(..) => "token_hash_key_value",
+            Self::TokenRawLiteral(..) => "token_raw_literal",
+            Self::TokenDoubleQuoteLiteral(..) => "token_double_quote_literal",
+            Self::TokenSingleQuoteLiteral(..) => "token_single_quote_literal",
+            Self::TokenArrayLiteral(..) => "token_array_literal",
+            Self::TokenLink(..) => "token_link",
+            Self::TokenParameterPath(..) => "token_parameter_path",
+            Self::TokenEndRawBlock(..) => "token_end_raw_block",
+        }
+    }
+
+    /// Determine if this is a lint warning rather than an error.
+    ///
+    /// Warnings such as calls to deprecated helpers are only
+    /// returned by [lint()](crate::Registry#method.lint) and do
+    /// not prevent a template from compiling.
+    pub fn is_warning(&self) -> bool {
+        matches!(
+            *self,
+            Self::DeprecatedHelper(..)
+                | Self::TrimNoEffect(..)
+                | Self::UnreachableBlock(..)
+                | Self::TrimJoinsText(..)
+        )
+    }
+
+    /// Convert this error to a diagnostic.
+    ///
+    /// Lint warnings use the `warning` kind.
+    pub fn diagnostic(&self) -> Diagnostic {
+        let snippet = self.snippet();
+        let kind = if self.is_warning() {
+            DiagnosticKind::Warning
+        } else {
+            DiagnosticKind::Syntax
+        };
+        Diagnostic {
+            kind,
+            code: self.code(),
+            message: self.to_string(),
+            file: Some(snippet.file_name().to_string()),
+            line: Some(snippet.line()),
+            span: Some(snippet.span().clone()),
+            notes: snippet.notes().to_vec(),
+        }
+    }
+
+    /// Convert this error to JSON; see the
+    /// [diagnostic](crate::error::diagnostic) module for the fields.
+    pub fn to_json(&self) -> Value {
+        self.diagnostic().to_json()
+    }
+}
+
+impl Serialize for SyntaxError {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        self.diagnostic().serialize(serializer)
+    }
 }
 
 impl fmt::Debug for SyntaxError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         write!(f, "{}\n", self.to_string())?;
-        match *self {
-            Self::ExpectedIdentifier(ref source)
-            | Self::ExpectedPath(ref source)
-            | Self::BlockName(ref source)
-            | Self::LiteralNewline(ref source)
-            | Self::PartialPosition(ref source)
-            | Self::SubExprNotOpen(ref source)
-            | Self::SubExprTargetNotAllowed(ref source)
-            | Self::PathDelimiterNotAllowed(ref source)
-            | Self::ElseNotAllowed(ref source)
-            | Self::UnexpectedPathExplicitThis(ref source)
-            | Self::UnexpectedPathParent(ref source)
-            | Self::UnexpectedPathLocal(ref source)
-            | Self::UnexpectedPathDelimiter(ref source)
-            | Self::UnexpectedPathParentWithLocal(ref source)
-            | Self::UnexpectedPathParentWithExplicit(ref source)
-            | Self::ExpectedPathDelimiter(ref source)
-            | Self::OpenSubExpression(ref source)
-            | Self::TagNameMismatch(ref source)
-            | Self::SubExpressionNotTerminated(ref source)
-            | Self::LinkNotTerminated(ref source)
-            | Self::RawBlockNotTerminated(ref source)
-            | Self::RawCommentNotTerminated(ref source)
-            | Self::RawStatementNotTerminated(ref source)
-            | Self::CommentNotTerminated(ref source)
-            | Self::BlockTargetSubExpr(ref source)
-            | Self::EmptyPath(ref source)
-            | Self::ComponentType(ref source)
-            | Self::MixedPartialConditional(ref source)
-            | Self::RawBlockOpenNotTerminated(ref source)
-            | Self::TokenError(_, ref source)
-            | Self::TokenCallTarget(ref source)
-            | Self::TokenJsonLiteral(ref source)
-            | Self::TokenParameter(ref source)
-            | Self::TokenHashKeyValue(ref source)
-            | Self::TokenRawLiteral(ref source)
-            | Self::TokenDoubleQuoteLiteral(ref source)
-            | Self::TokenSingleQuoteLiteral(ref source)
-            | Self::TokenArrayLiteral(ref source)
-            | Self::TokenLink(ref source)
-            | Self::TokenParameterPath(ref source)
-            | Self::TokenEndRawBlock(ref source)
-            | Self::BlockNotOpen(ref source) => write!(f, "{}", source)?,
-        }
-        Ok(())
+        write!(f, "{}", self.snippet())
     }
 }
@@ -1,27 +1,293 @@
 //! Escape function trait and default functions.
 //!
-//! The default is to escape for HTML content using `escape_html`.
+//! The default is to escape for HTML content using `html_write`
+//! which writes escaped content directly to the output destination.
+//!
+//! Functions that return an escaped `String` (`EscapeFn`) are still
+//! supported; they are converted with `adapt()` when assigned to
+//! a registry.
+//!
+//! Escape functions that write to an output destination may emit
+//! bytes that are not UTF-8 using `write_bytes()`, for example to
+//! encode a binary-safe protocol; render to a
+//! [BytesOutput](crate::output::BytesOutput) to accept such output,
+//! string outputs reject it with an error.
+use crate::output::io::Result;
+use alloc::{
+    borrow::{Cow, ToOwned},
+    boxed::Box,
+    string::String,
+    sync::Arc,
+};
+
+use crate::output::{Output, StringOutput};
 
-/// Type for escape functions.
+/// Type for escape functions that return a new string.
 pub type EscapeFn = Box<dyn Fn(&str) -> String + Send + Sync>;
 
+/// Type for escape functions that write to an output destination.
+pub type EscapeWriteFn =
+    Box<dyn Fn(&str, &mut dyn Output) -> Result<usize> + Send + Sync>;
+
+type SharedEscapeFn = Arc<dyn Fn(&str) -> String + Send + Sync>;
+
+type SharedEscapeWriteFn =
+    Arc<dyn Fn(&str, &mut dyn Output) -> Result<usize> + Send + Sync>;
+
+/// Convert an escape function that returns a string into
+/// an escape function that writes to an output destination.
+pub fn adapt(escape: EscapeFn) -> EscapeWriteFn {
+    Box::new(move |s: &str, out: &mut dyn Output| out.write_str(&escape(s)))
+}
+
+/// Share an escape function that returns a string as both an
+/// `EscapeFn` and an `EscapeWriteFn`.
+pub(crate) fn share(escape: EscapeFn) -> (EscapeFn, EscapeWriteFn) {
+    let escape: SharedEscapeFn = Arc::from(escape);
+    let write = Arc::clone(&escape);
+    (
+        Box::new(move |s: &str| escape(s)),
+        adapt(Box::new(move |s: &str| write(s))),
+    )
+}
+
+/// Share an escape function that writes to an output destination
+/// as both an `EscapeFn` and an `EscapeWriteFn`.
+///
+/// The returned `EscapeFn` buffers to a string so any bytes
+/// that are not UTF-8 are dropped.
+pub(crate) fn share_write(escape: EscapeWriteFn) -> (EscapeFn, EscapeWriteFn) {
+    let write: SharedEscapeWriteFn = Arc::from(escape);
+    let escape = Arc::clone(&write);
+    (
+        Box::new(move |s: &str| {
+            let mut out = StringOutput::new();
+            let _ = escape(s, &mut out);
+            out.into()
+        }),
+        Box::new(move |s: &str, out: &mut dyn Output| write(s, out)),
+    )
+}
+
+/// Call a function with each chunk of escaped output.
+///
+/// Runs of characters that do not need escaping are passed
+/// as slices of the input so no intermediate allocation is required.
+fn entity_chunks(
+    s: &str,
+    entity: impl Fn(u8) -> Option<&'static str>,
+    mut chunk: impl FnMut(&str) -> Result<()>,
+) -> Result<()> {
+    let mut start = 0;
+    for (i, b) in s.bytes().enumerate() {
+        let entity = match entity(b) {
+            Some(entity) => entity,
+            None => continue,
+        };
+        if start < i {
+            chunk(&s[start..i])?;
+        }
+        chunk(entity)?;
+        start = i + 1;
+    }
+    if start < s.len() {
+        chunk(&s[start..])?;
+    }
+    Ok(())
+}
+
+fn html_entity(b: u8) -> Option<&'static str> {
+    match b {
+        b'<' => Some("&lt;"),
+        b'>' => Some("&gt;"),
+        b'"' => Some("&quot;"),
+        b'&' => Some("&amp;"),
+        b'\'' => Some("&#x27;"),
+        _ => None,
+    }
+}
+
+fn xml_entity(b: u8) -> Option<&'static str> {
+    match b {
+        b'<' => Some("&lt;"),
+        b'>' => Some("&gt;"),
+        b'"' => Some("&quot;"),
+        b'&' => Some("&amp;"),
+        b'\'' => Some("&apos;"),
+        _ => None,
+    }
+}
+
 /// Escape for HTML output.
 pub fn html(s: &str) -> String {
-    let mut output = String::new();
-    for c in s.chars() {
-        match c {
-            '<' => output.push_str("&lt;"),
-            '>' => output.push_str("&gt;"),
-            '"' => output.push_str("&quot;"),
-            '&' => output.push_str("&amp;"),
-            '\'' => output.push_str("&#x27;"),
-            _ => output.push(c),
-        }
+    let mut output = String::with_capacity(s.len());
+    html_into(s, &mut output);
+    output
+}
+
+/// Escape for HTML output appending to an existing buffer.
+pub fn html_into(s: &str, output: &mut String) {
+    let _ = entity_chunks(s, html_entity, |chunk| {
+        output.push_str(chunk);
+        Ok(())
+    });
+}
+
+/// Escape for HTML output writing to an output destination.
+pub fn html_write(s: &str, out: &mut dyn Output) -> Result<usize> {
+    let mut written = 0;
+    entity_chunks(s, html_entity, |chunk| {
+        written += out.write_str(chunk)?;
+        Ok(())
+    })?;
+    Ok(written)
+}
+
+/// Escape for XML output.
+///
+/// Uses the predefined XML entities so the output is valid
+/// in element content and in attributes using either quote.
+pub fn xml(s: &str) -> String {
+    let mut output = String::with_capacity(s.len());
+    let _ = entity_chunks(s, xml_entity, |chunk| {
+        output.push_str(chunk);
+        Ok(())
+    });
+    output
+}
+
+/// Escape for XML output writing to an output destination.
+pub fn xml_write(s: &str, out: &mut dyn Output) -> Result<usize> {
+    let mut written = 0;
+    entity_chunks(s, xml_entity, |chunk| {
+        written += out.write_str(chunk)?;
+        Ok(())
+    })?;
+    Ok(written)
+}
+
+fn latex_entity(b: u8) -> Option<&'static str> {
+    match b {
+        b'\\' => Some("\\textbackslash{}"),
+        b'%' => Some("\\%"),
+        b'&' => Some("\\&"),
+        b'#' => Some("\\#"),
+        b'_' => Some("\\_"),
+        b'{' => Some("\\{"),
+        b'}' => Some("\\}"),
+        b'$' => Some("\\$"),
+        b'~' => Some("\\textasciitilde{}"),
+        b'^' => Some("\\textasciicircum{}"),
+        _ => None,
     }
+}
+
+/// Escape for LaTeX output.
+///
+/// Escapes the characters that are special in LaTeX text:
+/// `%`, `&`, `#`, `_`, `{`, `}`, `$`, `~`, `^` and backslash.
+pub fn latex(s: &str) -> String {
+    let mut output = String::with_capacity(s.len());
+    let _ = entity_chunks(s, latex_entity, |chunk| {
+        output.push_str(chunk);
+        Ok(())
+    });
     output
 }
 
+/// Escape for LaTeX output writing to an output destination.
+pub fn latex_write(s: &str, out: &mut dyn Output) -> Result<usize> {
+    let mut written = 0;
+    entity_chunks(s, latex_entity, |chunk| {
+        written += out.write_str(chunk)?;
+        Ok(())
+    })?;
+    Ok(written)
+}
+
 /// Do not escape output.
 pub fn noop(s: &str) -> String {
     s.to_owned()
 }
+
+/// Do not escape output writing to an output destination.
+pub fn noop_write(s: &str, out: &mut dyn Output) -> Result<usize> {
+    out.write_str(s)
+}
+
+/// Characters that start a formula when a field is opened
+/// by a spreadsheet application.
+const FORMULA: &[char] = &['=', '+', '-', '@', '\t', '\r'];
+
+/// Escape a field for delimited output.
+///
+/// Fields that start with a formula character and are not numbers are
+/// prefixed with a single quote; fields that contain the delimiter, a
+/// quote or a line break are quoted with embedded quotes doubled.
+fn delimited(s: &str, delimiter: char) -> Cow<'_, str> {
+    let formula = s.starts_with(FORMULA) && s.parse::<f64>().is_err();
+    let quote = s.contains([delimiter, '"', '\n', '\r']);
+    if !formula && !quote {
+        return Cow::Borrowed(s);
+    }
+    let mut output = String::with_capacity(s.len() + 3);
+    if quote {
+        output.push('"');
+    }
+    if formula {
+        output.push('\'');
+    }
+    for c in s.chars() {
+        if c == '"' {
+            output.push('"');
+        }
+        output.push(c);
+    }
+    if quote {
+        output.push('"');
+    }
+    Cow::Owned(output)
+}
+
+/// Escape a field for comma-separated values (CSV) output.
+///
+/// Guards against formula injection by prefixing fields that start
+/// with `=`, `+`, `-`, `@`, a tab or a carriage return with a single
+/// quote unless the field is a number such as `-5`; fields that
+/// contain a comma, quote or line break are quoted.
+pub fn csv(s: &str) -> String {
+    delimited(s, ',').into_owned()
+}
+
+/// Escape a field for CSV output writing to an output destination.
+pub fn csv_write(s: &str, out: &mut dyn Output) -> Result<usize> {
+    out.write_str(&delimited(s, ','))
+}
+
+/// Escape a field for tab-separated values (TSV) output.
+///
+/// Uses the same rules as [csv()](csv) with a tab delimiter.
+pub fn tsv(s: &str) -> String {
+    delimited(s, '\t').into_owned()
+}
+
+/// Escape a field for TSV output writing to an output destination.
+pub fn tsv_write(s: &str, out: &mut dyn Output) -> Result<usize> {
+    out.write_str(&delimited(s, '\t'))
+}
+
+/// Escape a list of fields as a delimited row.
+#[cfg(feature = "csv-helper")]
+pub(crate) fn delimited_row<'a, I>(fields: I, delimiter: char) -> String
+where
+    I: IntoIterator<Item = &'a str>,
+{
+    let mut output = String::new();
+    for (index, field) in fields.into_iter().enumerate() {
+        if index > 0 {
+            output.push(delimiter);
+        }
+        output.push_str(&delimited(field, delimiter));
+    }
+    output
+}
@@ -0,0 +1,203 @@
+//! C-compatible API for embedding the engine in other languages.
+//!
+//! Requires the `ffi` feature; to build a shared library run:
+//!
+//! ```ignore
+//! cargo rustc --release --features ffi --crate-type cdylib
+//! ```
+//!
+//! The C header in `include/bracket.h` is generated with [cbindgen][]:
+//!
+//! ```ignore
+//! cbindgen --config cbindgen.toml --output include/bracket.h
+//! ```
+//!
+//! Functions that fail return `NULL` or a negative status code; call
+//! `bracket_last_error()` to get a message describing the last error
+//! on the calling thread.
+//!
+//! Strings returned by `bracket_render_json()` are owned by the caller
+//! and must be released with `bracket_string_free()`.
+//!
+//! [cbindgen]: https://github.com/eqrion/cbindgen
+use std::cell::RefCell;
+use std::ffi::{CStr, CString};
+use std::fmt;
+use std::os::raw::{c_char, c_int};
+use std::ptr;
+
+use serde_json::Value;
+
+use crate::Registry;
+
+/// Opaque registry handle.
+pub struct BracketRegistry {
+    registry: Registry<'static>,
+}
+
+thread_local! {
+    static LAST_ERROR: RefCell<Option<CString>> =
+        const { RefCell::new(None) };
+}
+
+fn set_error(message: impl fmt::Display) {
+    let message = message.to_string().replace('\0', "");
+    let message = CString::new(message).ok();
+    LAST_ERROR.with(|e| *e.borrow_mut() = message);
+}
+
+unsafe fn to_str<'a>(value: *const c_char, kind: &str) -> Option<&'a str> {
+    if value.is_null() {
+        set_error(format!("Argument '{}' is NULL", kind));
+        return None;
+    }
+    match CStr::from_ptr(value).to_str() {
+        Ok(s) => Some(s),
+        Err(e) => {
+            set_error(format!("Argument '{}' is not UTF-8: {}", kind, e));
+            None
+        }
+    }
+}
+
+/// Create a new registry.
+///
+/// The registry must be released with `bracket_registry_free()`.
+#[no_mangle]
+pub extern "C" fn bracket_registry_new() -> *mut BracketRegistry {
+    let registry = BracketRegistry {
+        registry: Registry::new(),
+    };
+    Box::into_raw(Box::new(registry))
+}
+
+/// Release a registry.
+///
+/// # Safety
+///
+/// The registry must have been created with `bracket_registry_new()`
+/// and must not be used after it has been released.
+#[no_mangle]
+pub unsafe extern "C" fn bracket_registry_free(registry: *mut BracketRegistry) {
+    if !registry.is_null() {
+        drop(Box::from_raw(registry));
+    }
+}
+
+/// Compile a template and add it to the registry.
+///
+/// Returns zero on success or `-1` when an argument is invalid or
+/// the template has a syntax error.
+///
+/// # Safety
+///
+/// The registry must be a valid handle and `name` and `source` must
+/// be valid NUL-terminated strings.
+#[no_mangle]
+pub unsafe extern "C" fn bracket_register_template(
+    registry: *mut BracketRegistry,
+    name: *const c_char,
+    source: *const c_char,
+) -> c_int {
+    let registry = match registry.as_mut() {
+        Some(r) => &mut r.registry,
+        None => {
+            set_error("Argument 'registry' is NULL");
+            return -1;
+        }
+    };
+    let name = match to_str(name, "name") {
+        Some(name) => name,
+        None => return -1,
+    };
+    let source = match to_str(source, "source") {
+        Some(source) => source,
+        None => return -1,
+    };
+    match registry.insert(name, source) {
+        Ok(_) => 0,
+        Err(e) => {
+            set_error(format!("{:?}", e));
+            -1
+        }
+    }
+}
+
+/// Render a named template with data encoded as a JSON string.
+///
+/// Returns the rendered string or `NULL` on error; the returned string
+/// must be released with `bracket_string_free()`.
+///
+/// # Safety
+///
+/// The registry must be a valid handle and `name` and `json` must
+/// be valid NUL-terminated strings.
+#[no_mangle]
+pub unsafe extern "C" fn bracket_render_json(
+    registry: *const BracketRegistry,
+    name: *const c_char,
+    json: *const c_char,
+) -> *mut c_char {
+    let registry = match registry.as_ref() {
+        Some(r) => &r.registry,
+        None => {
+            set_error("Argument 'registry' is NULL");
+            return ptr::null_mut();
+        }
+    };
+    let name = match to_str(name, "name") {
+        Some(name) => name,
+        None => return ptr::null_mut(),
+    };
+    let json = match to_str(json, "json") {
+        Some(json) => json,
+        None => return ptr::null_mut(),
+    };
+    let data: Value = match serde_json::from_str(json) {
+        Ok(data) => data,
+        Err(e) => {
+            set_error(format!("Invalid JSON data: {}", e));
+            return ptr::null_mut();
+        }
+    };
+    let result = match registry.render(name, &data) {
+        Ok(result) => result,
+        Err(e) => {
+            set_error(format!("{:?}", e));
+            return ptr::null_mut();
+        }
+    };
+    match CString::new(result) {
+        Ok(s) => s.into_raw(),
+        Err(e) => {
+            set_error(format!("Rendered output contains NUL: {}", e));
+            ptr::null_mut()
+        }
+    }
+}
+
+/// Release a string returned by `bracket_render_json()`.
+///
+/// # Safety
+///
+/// The string must have been returned by this library and must not
+/// be used after it has been released.
+#[no_mangle]
+pub unsafe extern "C" fn bracket_string_free(value: *mut c_char) {
+    if !value.is_null() {
+        drop(CString::from_raw(value));
+    }
+}
+
+/// Get the message for the last error on the calling thread.
+///
+/// Returns `NULL` when no error has occurred; the pointer is owned
+/// by the library and is valid until the next failing call on the
+/// same thread.
+#[no_mangle]
+pub extern "C" fn bracket_last_error() -> *const c_char {
+    LAST_ERROR.with(|e| match *e.borrow() {
+        Some(ref message) => message.as_ptr(),
+        None => ptr::null(),
+    })
+}
@@ -0,0 +1,160 @@
+//! Format JSON values written to the output.
+//!
+//! By default statements write strings verbatim, numbers, booleans and
+//! `null` using the JSON representation, arrays as `Array[N]` and
+//! objects as `Object`. Register formatters for a JSON type to change
+//! the default or for a tag so they can be selected in templates by
+//! the `format` helper:
+//!
+//! ```ignore
+//! use bracket::{format::NumberFormat, render::Type};
+//! let formatters = registry.formatters_mut();
+//! formatters.insert_type(Type::Number, Box::new(NumberFormat::new().precision(2)));
+//! formatters.insert_type(Type::Null, Box::new(|_: &Value| Some(String::new())));
+//! formatters.insert_tag("sci", Box::new(NumberFormat::new().exponent(true)));
+//! ```
+//!
+//! ```ignore
+//! {{price}} {{format distance "sci"}}
+//! ```
+use alloc::{
+    boxed::Box,
+    format,
+    string::{String, ToString},
+};
+use serde_json::{Number, Value};
+
+use crate::{collections::HashMap, render::Type};
+
+/// Policy for statements that are `null` or missing.
+///
+/// A formatter for the `null` type takes precedence over the
+/// policy for values that are `null`.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub enum NullPolicy {
+    /// Write nothing.
+    #[default]
+    Empty,
+    /// Write the literal `null`.
+    Null,
+    /// Write a placeholder string.
+    Placeholder(String),
+    /// Return an error.
+    Error,
+}
+
+/// Trait for types that format JSON values.
+pub trait DisplayValue: Send + Sync {
+    /// Format a value; return `None` to use the default formatting.
+    fn display(&self, value: &Value) -> Option<String>;
+}
+
+impl<F> DisplayValue for F
+where
+    F: Fn(&Value) -> Option<String> + Send + Sync,
+{
+    fn display(&self, value: &Value) -> Option<String> {
+        (self)(value)
+    }
+}
+
+/// Format numbers with a fixed precision or exponent notation.
+///
+/// Values that are not numbers use the default formatting.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct NumberFormat {
+    precision: Option<usize>,
+    exponent: bool,
+}
+
+impl NumberFormat {
+    /// Create a number format that matches the default formatting.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Set the number of digits after the decimal point.
+    pub fn precision(mut self, digits: usize) -> Self {
+        self.precision = Some(digits);
+        self
+    }
+
+    /// Use exponent notation such as `1.5e3`.
+    pub fn exponent(mut self, exponent: bool) -> Self {
+        self.exponent = exponent;
+        self
+    }
+
+    /// Format a number.
+    pub fn format(&self, number: &Number) -> String {
+        let float = number.as_f64().unwrap_or_default();
+        match (self.exponent, self.precision) {
+            (true, Some(digits)) => format!("{:.*e}", digits, float),
+            (true, None) => format!("{:e}", float),
+            (false, Some(digits)) => format!("{:.*}", digits, float),
+            (false, None) => number.to_string(),
+        }
+    }
+}
+
+impl DisplayValue for NumberFormat {
+    fn display(&self, value: &Value) -> Option<String> {
+        match value {
+            Value::Number(ref number) => Some(self.format(number)),
+            _ => None,
+        }
+    }
+}
+
+/// Collection of formatters for JSON types and tags.
+#[derive(Default)]
+pub struct Formatters {
+    types: HashMap<Type, Box<dyn DisplayValue>>,
+    tags: HashMap<String, Box<dyn DisplayValue>>,
+}
+
+impl Formatters {
+    /// Create an empty collection of formatters.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Set the default formatter for a JSON type.
+    pub fn insert_type(
+        &mut self,
+        kind: Type,
+        formatter: Box<dyn DisplayValue>,
+    ) {
+        self.types.insert(kind, formatter);
+    }
+
+    /// Add a formatter for a tag.
+    pub fn insert_tag(&mut self, tag: &str, formatter: Box<dyn DisplayValue>) {
+        self.tags.insert(tag.to_string(), formatter);
+    }
+
+    /// Remove the default formatter for a JSON type.
+    pub fn remove_type(&mut self, kind: Type) -> Option<Box<dyn DisplayValue>> {
+        self.types.remove(&kind)
+    }
+
+    /// Remove the formatter for a tag.
+    pub fn remove_tag(&mut self, tag: &str) -> Option<Box<dyn DisplayValue>> {
+        self.tags.remove(tag)
+    }
+
+    /// Get the default formatter for a JSON type.
+    pub fn get_type(&self, kind: Type) -> Option<&dyn DisplayValue> {
+        self.types.get(&kind).map(|f| f.as_ref())
+    }
+
+    /// Get the formatter for a tag.
+    pub fn get_tag(&self, tag: &str) -> Option<&dyn DisplayValue> {
+        self.tags.get(tag).map(|f| f.as_ref())
+    }
+
+    /// Determine if there are no formatters.
+    pub fn is_empty(&self) -> bool {
+        self.types.is_empty() && self.tags.is_empty()
+    }
+}
@@ -0,0 +1,50 @@
+//! Helper to get the URL for a static asset.
+use crate::{
+    error::HelperError,
+    helper::{Helper, HelperValue},
+    parser::ast::Node,
+    render::{Context, Render, Type},
+};
+use alloc::format;
+
+use serde_json::Value;
+
+/// Get the fingerprinted URL for a static asset.
+///
+/// Accepts a single string argument which is the logical path of the
+/// asset and returns the URL from the
+/// [manifest](crate::Registry#method.manifest) for the registry:
+///
+/// ```ignore
+/// <link rel="stylesheet" href="{{asset "css/app.css"}}">
+/// ```
+///
+/// It is an error if the registry does not have a manifest or the
+/// path is not in the manifest.
+pub struct Asset;
+
+impl Helper for Asset {
+    fn call<'render, 'call>(
+        &self,
+        rc: &mut Render<'render>,
+        ctx: &Context<'call>,
+        _template: Option<&'render Node<'render>>,
+    ) -> HelperValue {
+        ctx.arity(1..1)?;
+        let path = ctx.try_get(0, &[Type::String])?.as_str().unwrap();
+        let manifest = rc.registry().manifest().ok_or_else(|| {
+            HelperError::new(format!(
+                "Helper '{}' requires an asset manifest",
+                ctx.name()
+            ))
+        })?;
+        let url = manifest.url(path).ok_or_else(|| {
+            HelperError::new(format!(
+                "Helper '{}' could not find '{}' in the asset manifest",
+                ctx.name(),
+                path
+            ))
+        })?;
+        Ok(Some(Value::String(url)))
+    }
+}
@@ -0,0 +1,50 @@
+//! Helper that calls another helper by name.
+use crate::{
+    error::HelperError,
+    helper::{Helper, HelperValue},
+    parser::ast::Node,
+    render::{Context, Render, Type},
+};
+use alloc::{format, vec::Vec};
+
+/// Call a helper using a name computed when rendering.
+///
+/// Accepts the name of the helper followed by the arguments for the
+/// helper; hash parameters and the block template are passed to the
+/// helper unchanged:
+///
+/// ```ignore
+/// {{call widget.helper widget.value format="short"}}
+/// ```
+///
+/// Only helpers allowed using
+/// [allow_call()](crate::Registry#method.allow_call) may be called
+/// so templates cannot invoke every registered helper.
+pub struct Call;
+
+impl Helper for Call {
+    fn call<'render, 'call>(
+        &self,
+        rc: &mut Render<'render>,
+        ctx: &Context<'call>,
+        template: Option<&'render Node<'render>>,
+    ) -> HelperValue {
+        if ctx.arguments().is_empty() {
+            return Err(HelperError::new(format!(
+                "Helper '{}' expects the name of a helper",
+                ctx.name()
+            )));
+        }
+        let name = ctx.try_get(0, &[Type::String])?.as_str().unwrap();
+        if !rc.registry().call_allowed(name) {
+            return Err(HelperError::new(format!(
+                "Helper '{}' is not allowed to call '{}'",
+                ctx.name(),
+                name
+            )));
+        }
+        let arguments: Vec<_> = ctx.arguments()[1..].to_vec();
+        let parameters = ctx.parameters().clone();
+        rc.call_helper(name, ctx, arguments, parameters, template)
+    }
+}
@@ -0,0 +1,39 @@
+//! Block helper that wraps content in a CDATA section.
+use crate::{
+    helper::{Helper, HelperValue},
+    parser::ast::Node,
+    render::{Context, Render},
+};
+
+/// Render the inner block and wrap the result in an XML CDATA section.
+///
+/// Any `]]>` in the content is split across two CDATA sections so
+/// the content cannot terminate the section early:
+///
+/// ```ignore
+/// <description>{{#cdata}}{{{summary}}}{{/cdata}}</description>
+/// ```
+///
+/// The content of a CDATA section is not decoded by XML parsers so
+/// statements in the block should usually use a triple-stash to
+/// avoid writing escaped entities.
+pub struct Cdata;
+
+impl Helper for Cdata {
+    fn call<'render, 'call>(
+        &self,
+        rc: &mut Render<'render>,
+        ctx: &Context<'call>,
+        template: Option<&'render Node<'render>>,
+    ) -> HelperValue {
+        ctx.arity(0..0)?;
+
+        let template = ctx.assert_block(template)?;
+        let content = rc.buffer(template)?;
+        rc.write("<![CDATA[")?;
+        rc.write(&content.replace("]]>", "]]]]><![CDATA[>"))?;
+        rc.write("]]>")?;
+
+        Ok(None)
+    }
+}
@@ -0,0 +1,73 @@
+//! Block helper that splits an array into rows.
+use crate::{
+    error::HelperError,
+    helper::{Helper, HelperValue},
+    parser::ast::Node,
+    render::{Context, Render, Scope, Type},
+};
+use alloc::format;
+
+use serde_json::{Number, Value};
+
+const ROW: &str = "row";
+const IS_LAST_ROW: &str = "isLastRow";
+
+/// Render a block for each chunk of an array.
+///
+/// Accepts a single argument which is the array to split and
+/// requires the `size` hash parameter which is the maximum number of
+/// elements in each chunk; the last chunk may be smaller.
+///
+/// The block is rendered for each chunk with the array of elements
+/// as the scope so that templates can lay out a grid:
+///
+/// ```ignore
+/// {{#chunk products size=3}}
+///   <div class="row{{#if @isLastRow}} last{{/if}}">
+///     {{#each this}}<div class="col">{{name}}</div>{{/each}}
+///   </div>
+/// {{/chunk}}
+/// ```
+///
+/// The local variables are:
+///
+/// * `@row`: Zero-based index of the chunk.
+/// * `@isLastRow`: If this is the last chunk `true`.
+pub struct Chunk;
+
+impl Helper for Chunk {
+    fn call<'render, 'call>(
+        &self,
+        rc: &mut Render<'render>,
+        ctx: &Context<'call>,
+        template: Option<&'render Node<'render>>,
+    ) -> HelperValue {
+        ctx.arity(1..1)?;
+        let template = ctx.assert_block(template)?;
+        let items = ctx.try_get(0, &[Type::Array])?.as_array().unwrap();
+        let size = ctx
+            .try_param("size", &[Type::Number])?
+            .as_u64()
+            .filter(|size| *size > 0)
+            .ok_or_else(|| {
+                HelperError::new(format!(
+                    "Helper '{}' size must be a positive integer",
+                    ctx.name()
+                ))
+            })?;
+
+        let rows = items.chunks(size as usize);
+        let len = rows.len();
+        rc.push_scope(Scope::new());
+        for (row, chunk) in rows.enumerate() {
+            if let Some(ref mut scope) = rc.scope_mut() {
+                scope.set_local(ROW, Value::Number(Number::from(row)));
+                scope.set_local(IS_LAST_ROW, Value::Bool(row == len - 1));
+                scope.set_base_value(Value::Array(chunk.to_vec()));
+            }
+            rc.template(template)?;
+        }
+        rc.pop_scope();
+        Ok(None)
+    }
+}
@@ -10,6 +10,7 @@ use crate::{
     parser::ast::Node,
     render::{Context, Render, Type},
 };
+use alloc::string::ToString;
 
 use serde_json::Value;
 
@@ -48,6 +49,10 @@ impl Helper for Equal {
     ) -> HelperValue {
         cmp(ctx, |lhs: f64, rhs: f64| lhs == rhs)
     }
+
+    fn pure(&self) -> bool {
+        true
+    }
 }
 
 /// Perform a negated equality comparison.
@@ -62,6 +67,10 @@ impl Helper for NotEqual {
     ) -> HelperValue {
         cmp(ctx, |lhs: f64, rhs: f64| lhs != rhs)
     }
+
+    fn pure(&self) -> bool {
+        true
+    }
 }
 
 /// Perform a numerical greater than comparison.
@@ -76,6 +85,10 @@ impl Helper for GreaterThan {
     ) -> HelperValue {
         cmp(ctx, |lhs: f64, rhs: f64| lhs > rhs)
     }
+
+    fn pure(&self) -> bool {
+        true
+    }
 }
 
 /// Perform a numerical greater than or equal comparison.
@@ -90,6 +103,10 @@ impl Helper for GreaterThanEqual {
     ) -> HelperValue {
         cmp(ctx, |lhs: f64, rhs: f64| lhs >= rhs)
     }
+
+    fn pure(&self) -> bool {
+        true
+    }
 }
 
 /// Perform a numerical less than comparison.
@@ -104,6 +121,10 @@ impl Helper for LessThan {
     ) -> HelperValue {
         cmp(ctx, |lhs: f64, rhs: f64| lhs < rhs)
     }
+
+    fn pure(&self) -> bool {
+        true
+    }
 }
 
 /// Perform a numerical less than comparison.
@@ -118,4 +139,8 @@ impl Helper for LessThanEqual {
     ) -> HelperValue {
         cmp(ctx, |lhs: f64, rhs: f64| lhs <= rhs)
     }
+
+    fn pure(&self) -> bool {
+        true
+    }
 }
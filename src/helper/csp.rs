@@ -0,0 +1,48 @@
+//! Helper to write the Content Security Policy nonce.
+use crate::{
+    error::HelperError,
+    helper::{Helper, HelperValue},
+    parser::ast::Node,
+    render::{Context, Render, Type},
+};
+use alloc::format;
+
+/// Name of the render local for the nonce.
+pub const NONCE: &str = "nonce";
+
+/// Write the Content Security Policy nonce for the render.
+///
+/// Accepts no arguments and returns the `nonce` render local
+/// which must be a string; pass the nonce for each response using
+/// [render_with_locals()](crate::Registry#method.render_with_locals):
+///
+/// ```ignore
+/// <script nonce="{{csp_nonce}}">...</script>
+/// ```
+///
+/// When the nonce was not provided nothing is written so the script
+/// is blocked by the policy; in strict mode it is an error.
+pub struct CspNonce;
+
+impl Helper for CspNonce {
+    fn call<'render, 'call>(
+        &self,
+        rc: &mut Render<'render>,
+        ctx: &Context<'call>,
+        _template: Option<&'render Node<'render>>,
+    ) -> HelperValue {
+        ctx.arity(0..0)?;
+        match rc.render_local(NONCE) {
+            Some(value) => {
+                ctx.assert(value, &[Type::String])?;
+                Ok(Some(value.clone()))
+            }
+            None if rc.registry().strict() => Err(HelperError::new(format!(
+                "Helper '{}' requires the '{}' render local",
+                ctx.name(),
+                NONCE
+            ))),
+            None => Ok(None),
+        }
+    }
+}
@@ -0,0 +1,75 @@
+//! Helper that writes a row of delimited values.
+use crate::{
+    error::HelperError,
+    escape,
+    helper::{Helper, HelperValue},
+    parser::ast::Node,
+    render::{Context, Render},
+};
+use alloc::{format, string::String, vec::Vec};
+
+use serde_json::Value;
+
+/// Write a row of comma-separated values.
+///
+/// Each argument is a field; when a single array argument is given
+/// the elements of the array are the fields and `null` values are
+/// empty fields. Fields are escaped using [csv()](crate::escape::csv)
+/// which quotes fields as required and guards against formula injection.
+///
+/// The optional hash parameter `delimiter` is a single character used
+/// to separate fields, for example `"\t"` for tab-separated values.
+///
+/// The result is a string which would also be escaped for HTML by
+/// default so use a triple-stash or assign a different escape function:
+///
+/// ```ignore
+/// {{{csv name email role}}}
+/// {{{csv row delimiter=";"}}}
+/// ```
+pub struct Csv;
+
+impl Helper for Csv {
+    fn call<'render, 'call>(
+        &self,
+        rc: &mut Render<'render>,
+        ctx: &Context<'call>,
+        _template: Option<&'render Node<'render>>,
+    ) -> HelperValue {
+        ctx.arity(1..usize::MAX)?;
+
+        let delimiter = match ctx.param("delimiter") {
+            Some(value) => {
+                let value = value.as_str().unwrap_or("");
+                let mut chars = value.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) => c,
+                    _ => {
+                        return Err(HelperError::new(format!(
+                            "Helper '{}' delimiter must be a single character",
+                            ctx.name()
+                        )))
+                    }
+                }
+            }
+            None => ',',
+        };
+
+        let values: Vec<&Value> = match ctx.arguments().as_slice() {
+            [Value::Array(ref list)] => list.iter().collect(),
+            arguments => arguments.iter().collect(),
+        };
+        let registry = rc.registry();
+        let fields: Vec<String> = values
+            .into_iter()
+            .map(|value| match value {
+                Value::String(ref s) => s.clone(),
+                Value::Null => String::new(),
+                _ => registry.format(value),
+            })
+            .collect();
+        let row =
+            escape::delimited_row(fields.iter().map(|s| s.as_str()), delimiter);
+        Ok(Some(Value::String(row)))
+    }
+}
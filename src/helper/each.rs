@@ -5,6 +5,7 @@ use crate::{
     parser::ast::Node,
     render::{Context, Render, Scope},
 };
+use alloc::borrow::ToOwned;
 
 use serde_json::{Number, Value};
 
@@ -29,6 +30,12 @@ const INDEX: &str = "index";
 /// For objects the `@key` variable contains the name of the field; for
 /// arrays the `@index` variable contains the current zero-based index.
 ///
+/// Block parameters name the value and the key or index of each
+/// iteration:
+///
+/// ```ignore
+/// {{#each users as |user index|}}{{index}}: {{user.name}}{{/each}}
+/// ```
 pub struct Each;
 
 impl Helper for Each {
@@ -64,6 +71,12 @@ impl Helper for Each {
                             );
                             scope.set_local(KEY, Value::String(key.to_owned()));
                             scope.set_base_value(value.clone());
+                            set_block_params(
+                                scope,
+                                ctx,
+                                value,
+                                Value::String(key.to_owned()),
+                            );
                         }
                         rc.template(template)?;
                     }
@@ -80,6 +93,12 @@ impl Helper for Each {
                                 Value::Number(Number::from(index)),
                             );
                             scope.set_base_value(value.clone());
+                            set_block_params(
+                                scope,
+                                ctx,
+                                value,
+                                Value::Number(Number::from(index)),
+                            );
                         }
                         rc.template(template)?;
                     }
@@ -96,4 +115,24 @@ impl Helper for Each {
 
         Ok(None)
     }
+
+    fn pure(&self) -> bool {
+        true
+    }
+}
+
+/// Assign the value and key or index to the block parameters.
+fn set_block_params(
+    scope: &mut Scope,
+    ctx: &Context<'_>,
+    value: &Value,
+    key: Value,
+) {
+    let names = ctx.block_params();
+    if let Some(name) = names.first() {
+        scope.set_variable(name, value.clone());
+    }
+    if let Some(name) = names.get(1) {
+        scope.set_variable(name, key);
+    }
 }
@@ -0,0 +1,131 @@
+//! Helpers that encode and hash values.
+//!
+//! Each helper accepts a single argument; strings are encoded as
+//! UTF-8 bytes and other values are encoded using their JSON
+//! serialization so that an object can be signed or hashed as a
+//! webhook payload:
+//!
+//! ```ignore
+//! <script src="{{src}}" integrity="sha256-{{sha256 script encoding="base64"}}"></script>
+//! ```
+use crate::{
+    error::HelperError,
+    helper::{Helper, HelperValue},
+    json,
+    parser::ast::Node,
+    render::{Context, Render, Type},
+};
+use alloc::{format, string::String};
+
+use base64::{
+    engine::general_purpose::{STANDARD, URL_SAFE_NO_PAD},
+    Engine,
+};
+use serde_json::Value;
+use sha2::{Digest, Sha256 as Hasher};
+
+fn hex(bytes: &[u8]) -> String {
+    const DIGITS: &[u8; 16] = b"0123456789abcdef";
+    let mut out = String::with_capacity(bytes.len() * 2);
+    for b in bytes {
+        out.push(DIGITS[(b >> 4) as usize] as char);
+        out.push(DIGITS[(b & 0x0f) as usize] as char);
+    }
+    out
+}
+
+/// Encode a value using standard base64 with padding.
+///
+/// ```ignore
+/// Authorization: Basic {{base64 credentials}}
+/// ```
+pub struct Base64;
+
+impl Helper for Base64 {
+    fn call<'render, 'call>(
+        &self,
+        _rc: &mut Render<'render>,
+        ctx: &Context<'call>,
+        _template: Option<&'render Node<'render>>,
+    ) -> HelperValue {
+        ctx.arity(1..1)?;
+        let value = json::unquote(ctx.get(0).unwrap());
+        Ok(Some(Value::String(STANDARD.encode(value))))
+    }
+}
+
+/// Encode a value using the URL safe base64 alphabet without padding.
+///
+/// ```ignore
+/// https://example.com/verify?token={{base64url token}}
+/// ```
+pub struct Base64Url;
+
+impl Helper for Base64Url {
+    fn call<'render, 'call>(
+        &self,
+        _rc: &mut Render<'render>,
+        ctx: &Context<'call>,
+        _template: Option<&'render Node<'render>>,
+    ) -> HelperValue {
+        ctx.arity(1..1)?;
+        let value = json::unquote(ctx.get(0).unwrap());
+        Ok(Some(Value::String(URL_SAFE_NO_PAD.encode(value))))
+    }
+}
+
+/// Encode a value as lowercase hexadecimal.
+pub struct Hex;
+
+impl Helper for Hex {
+    fn call<'render, 'call>(
+        &self,
+        _rc: &mut Render<'render>,
+        ctx: &Context<'call>,
+        _template: Option<&'render Node<'render>>,
+    ) -> HelperValue {
+        ctx.arity(1..1)?;
+        let value = json::unquote(ctx.get(0).unwrap());
+        Ok(Some(Value::String(hex(value.as_bytes()))))
+    }
+}
+
+/// Compute the SHA-256 digest of a value.
+///
+/// The digest is written as lowercase hexadecimal unless the optional
+/// hash parameter `encoding` is `base64` which is the format used by
+/// subresource integrity attributes.
+pub struct Sha256;
+
+impl Helper for Sha256 {
+    fn call<'render, 'call>(
+        &self,
+        _rc: &mut Render<'render>,
+        ctx: &Context<'call>,
+        _template: Option<&'render Node<'render>>,
+    ) -> HelperValue {
+        ctx.arity(1..1)?;
+
+        let encoding = match ctx.param("encoding") {
+            Some(_) => ctx
+                .try_param("encoding", &[Type::String])?
+                .as_str()
+                .unwrap(),
+            None => "hex",
+        };
+        let value = json::unquote(ctx.get(0).unwrap());
+        let digest = Hasher::digest(value.as_bytes());
+        let digest = match encoding {
+            "hex" => hex(&digest),
+            "base64" => STANDARD.encode(digest),
+            _ => {
+                return Err(HelperError::new(format!(
+                    "Helper '{}' got unknown encoding '{}', expected 'hex' or 'base64'",
+                    ctx.name(),
+                    encoding
+                )))
+            }
+        };
+        Ok(Some(Value::String(digest)))
+    }
+}
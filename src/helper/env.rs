@@ -0,0 +1,51 @@
+//! Helper to access environment variables.
+use crate::{
+    error::HelperError,
+    helper::{Helper, HelperValue},
+    parser::ast::Node,
+    render::{Context, Render, Type},
+};
+
+use serde_json::Value;
+
+/// Get the value of an environment variable.
+///
+/// Accepts a single string argument which is the name of the
+/// environment variable; the variable must have been allowed using
+/// [allow_env()](crate::Registry#method.allow_env) otherwise an
+/// error is returned.
+///
+/// If the variable is not set `null` is returned.
+///
+/// This helper is disabled when the registry is sandboxed.
+pub struct Env;
+
+impl Helper for Env {
+    fn call<'render, 'call>(
+        &self,
+        rc: &mut Render<'render>,
+        ctx: &Context<'call>,
+        _template: Option<&'render Node<'render>>,
+    ) -> HelperValue {
+        ctx.arity(1..1)?;
+
+        let name = ctx.try_get(0, &[Type::String])?.as_str().unwrap();
+        if !rc.registry().env_allowed(name) {
+            return Err(HelperError::new(format!(
+                "Helper '{}' does not allow environment variable '{}'",
+                ctx.name(),
+                name
+            )));
+        }
+
+        Ok(Some(
+            std::env::var(name)
+                .map(Value::String)
+                .unwrap_or(Value::Null),
+        ))
+    }
+
+    fn ambient(&self) -> bool {
+        true
+    }
+}
@@ -0,0 +1,91 @@
+//! Helpers that override escaping for a statement.
+use crate::{
+    error::{HelperError, RenderError},
+    helper::{Helper, HelperValue},
+    parser::ast::{Node, Slice},
+    render::{Context, Render},
+};
+use alloc::{
+    boxed::Box,
+    string::{String, ToString},
+};
+
+use serde_json::Value;
+
+fn display(rc: &Render<'_>, value: &Value) -> String {
+    match value {
+        Value::String(ref s) => s.clone(),
+        _ => rc.registry().format(value),
+    }
+}
+
+/// Write a value without escaping.
+///
+/// Accepts a single argument which is written to the output
+/// regardless of the number of braces for the statement; it is
+/// usually combined with a pipe:
+///
+/// ```ignore
+/// {{article.body | safe}}
+/// ```
+///
+/// This helper writes to the output so it should not be used
+/// in a sub-expression; when the registry is sandboxed the profile
+/// must allow unescaped output.
+pub struct Safe;
+
+impl Helper for Safe {
+    fn call<'render, 'call>(
+        &self,
+        rc: &mut Render<'render>,
+        ctx: &Context<'call>,
+        template: Option<&'render Node<'render>>,
+    ) -> HelperValue {
+        ctx.arity(1..1)?;
+        ctx.assert_statement(template)?;
+
+        if let Some(profile) = rc.registry().sandbox() {
+            if !profile.raw {
+                return Err(HelperError::Render(Box::new(
+                    RenderError::RawNotAllowed(
+                        ctx.call().as_str().to_string(),
+                        None,
+                    ),
+                )));
+            }
+        }
+
+        let value = display(rc, ctx.get(0).unwrap());
+        rc.write(&value)?;
+        Ok(None)
+    }
+}
+
+/// Write a value using the escape function for the registry.
+///
+/// Accepts a single argument which is escaped even when the
+/// statement uses a triple-stash:
+///
+/// ```ignore
+/// {{{title | escape}}}
+/// ```
+///
+/// This helper writes to the output so it should not be used
+/// in a sub-expression.
+pub struct Escape;
+
+impl Helper for Escape {
+    fn call<'render, 'call>(
+        &self,
+        rc: &mut Render<'render>,
+        ctx: &Context<'call>,
+        template: Option<&'render Node<'render>>,
+    ) -> HelperValue {
+        ctx.arity(1..1)?;
+        ctx.assert_statement(template)?;
+
+        let value = display(rc, ctx.get(0).unwrap());
+        rc.write_escaped(&value)?;
+        Ok(None)
+    }
+}
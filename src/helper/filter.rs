@@ -0,0 +1,127 @@
+//! Helper that selects the elements of an array.
+use crate::{
+    error::HelperError,
+    helper::{Helper, HelperValue},
+    json,
+    parser::ast::Node,
+    render::{Context, Render, Type},
+};
+use alloc::{format, vec::Vec};
+use core::cmp::Ordering;
+
+use serde_json::Value;
+
+/// Comparison operators.
+const OPERATORS: &[&str] =
+    &["eq", "ne", "gt", "gte", "lt", "lte", "contains", "in"];
+
+/// Order two numbers or two strings.
+fn order(lhs: &Value, rhs: &Value) -> Option<Ordering> {
+    match (lhs, rhs) {
+        (Value::Number(lhs), Value::Number(rhs)) => {
+            lhs.as_f64()?.partial_cmp(&rhs.as_f64()?)
+        }
+        (Value::String(lhs), Value::String(rhs)) => Some(lhs.cmp(rhs)),
+        _ => None,
+    }
+}
+
+/// Determine if a value matches the operand.
+fn matches(op: &str, value: &Value, operand: &Value) -> bool {
+    let equal = |lhs: &Value, rhs: &Value| match (lhs, rhs) {
+        (Value::Number(_), Value::Number(_)) => {
+            order(lhs, rhs) == Some(Ordering::Equal)
+        }
+        _ => lhs == rhs,
+    };
+    match op {
+        "eq" => equal(value, operand),
+        "ne" => !equal(value, operand),
+        "gt" => order(value, operand) == Some(Ordering::Greater),
+        "gte" => matches!(
+            order(value, operand),
+            Some(Ordering::Greater) | Some(Ordering::Equal)
+        ),
+        "lt" => order(value, operand) == Some(Ordering::Less),
+        "lte" => matches!(
+            order(value, operand),
+            Some(Ordering::Less) | Some(Ordering::Equal)
+        ),
+        "contains" => match (value, operand) {
+            (Value::String(s), Value::String(o)) => s.contains(o.as_str()),
+            (Value::Array(list), _) => list.iter().any(|v| equal(v, operand)),
+            _ => false,
+        },
+        "in" => match operand {
+            Value::Array(list) => list.iter().any(|v| equal(value, v)),
+            _ => false,
+        },
+        _ => false,
+    }
+}
+
+/// Select the elements of an array.
+///
+/// Accepts the array and the path to a field of each element such as
+/// `status` or `author.name`; with a third argument elements are
+/// selected when the field compares to the argument otherwise when the
+/// field is truthy. Returns a new array so it is usually called as a
+/// sub-expression:
+///
+/// ```ignore
+/// {{#each (where orders "status" "paid")}}...{{/each}}
+/// {{#each (where orders "total" 100 op="gte")}}...{{/each}}
+/// ```
+///
+/// The `op` hash parameter is the comparison operator:
+///
+/// * `eq`: Equal to the argument (default).
+/// * `ne`: Not equal to the argument.
+/// * `gt`, `gte`, `lt`, `lte`: Ordered comparison of numbers or strings.
+/// * `contains`: String field contains the argument or array field contains an element equal to the argument.
+/// * `in`: Field is equal to an element of the argument array.
+///
+/// Numbers are compared as `f64` so `1` is equal to `1.0`; elements
+/// without the field only match the `ne` operator.
+pub struct Where;
+
+impl Helper for Where {
+    fn call<'render, 'call>(
+        &self,
+        rc: &mut Render<'render>,
+        ctx: &Context<'call>,
+        _template: Option<&'render Node<'render>>,
+    ) -> HelperValue {
+        ctx.arity(2..3)?;
+        let items = ctx.try_get(0, &[Type::Array])?.as_array().unwrap();
+        let path = ctx.try_get(1, &[Type::String])?.as_str().unwrap();
+        let op = match ctx.param("op") {
+            Some(_) => ctx.try_param("op", &[Type::String])?.as_str().unwrap(),
+            None => "eq",
+        };
+        if !OPERATORS.contains(&op) {
+            return Err(HelperError::new(format!(
+                "Helper '{}' got unknown operator '{}'",
+                ctx.name(),
+                op
+            )));
+        }
+
+        let operand = ctx.get(2);
+        let keys = rc.registry().numeric_keys();
+        let selected: Vec<Value> = items
+            .iter()
+            .filter(|item| {
+                let value = json::find_parts(path.split('.'), item, keys);
+                match (value, operand) {
+                    (Some(value), Some(operand)) => matches(op, value, operand),
+                    (None, Some(_)) => op == "ne",
+                    (Some(value), None) => ctx.is_truthy(value),
+                    (None, None) => false,
+                }
+            })
+            .cloned()
+            .collect();
+        Ok(Some(Value::Array(selected)))
+    }
+}
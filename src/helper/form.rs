@@ -0,0 +1,334 @@
+//! Helpers that write HTML forms bound to the template data.
+//!
+//! Fields are bound to the value in the current context with the
+//! same name as the field and validation errors are read from the
+//! conventional `errors` object which maps field names to a message
+//! or a list of messages:
+//!
+//! ```ignore
+//! {{#form action="/users" method="put" model=user errors=errors}}
+//!   {{input name="email" type="email"}}
+//!   {{select name="role" options=roles}}
+//! {{/form}}
+//! ```
+//!
+//! A field with errors is written with `aria-invalid="true"` and is
+//! followed by a `<span class="error">` element for each message.
+//!
+//! The `input` and `select` helpers write to the output so they
+//! should not be used in a sub-expression.
+use crate::{
+    error::HelperError,
+    escape,
+    helper::{
+        html::{attribute_name, push_attribute},
+        Helper, HelperValue,
+    },
+    parser::ast::Node,
+    render::{Context, Render, Scope, Type},
+};
+use alloc::{
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+
+use serde_json::Value;
+
+/// Name of the variable for validation errors.
+pub const ERRORS: &str = "errors";
+
+/// Name of the hidden field for methods other than GET and POST.
+pub const METHOD: &str = "_method";
+
+/// Format a value for comparison with an option.
+fn display(rc: &Render<'_>, value: &Value) -> String {
+    match value {
+        Value::String(ref s) => s.clone(),
+        _ => rc.registry().format(value),
+    }
+}
+
+/// Determine if a bound value selects an option value.
+fn selected(rc: &Render<'_>, bound: Option<&Value>, option: &str) -> bool {
+    match bound {
+        Some(Value::Array(ref list)) => {
+            list.iter().any(|v| display(rc, v) == option)
+        }
+        Some(Value::Null) | None => false,
+        Some(value) => display(rc, value) == option,
+    }
+}
+
+/// Get the field name and the value bound to the field.
+fn field<'a>(
+    rc: &'a Render<'_>,
+    ctx: &'a Context<'_>,
+) -> Result<(&'a str, Option<&'a Value>), HelperError> {
+    let name = ctx.try_param("name", &[Type::String])?.as_str().unwrap();
+    let bound = match ctx.param("value") {
+        Some(value) => Some(value),
+        None => rc.evaluate(name).ok().flatten(),
+    };
+    Ok((name, bound))
+}
+
+/// Get the validation error messages for a field.
+fn messages<'a>(rc: &'a Render<'_>, name: &str) -> Vec<&'a str> {
+    let errors = rc.evaluate(ERRORS).ok().flatten();
+    match errors.and_then(|e| e.get(name)) {
+        Some(Value::String(ref message)) => vec![message.as_str()],
+        Some(Value::Array(ref list)) => {
+            list.iter().filter_map(|v| v.as_str()).collect()
+        }
+        _ => Vec::new(),
+    }
+}
+
+/// Append the hash parameters as attributes except for
+/// the reserved parameters.
+fn push_attributes(
+    rc: &Render<'_>,
+    ctx: &Context<'_>,
+    out: &mut String,
+    reserved: &[&str],
+) -> Result<(), HelperError> {
+    for (name, value) in ctx.parameters() {
+        if !reserved.contains(&name.as_str()) {
+            attribute_name(ctx, name)?;
+            push_attribute(rc, out, name, value);
+        }
+    }
+    Ok(())
+}
+
+/// Append the validation error messages after a field.
+fn push_messages(out: &mut String, messages: &[&str]) {
+    for message in messages {
+        out.push_str("<span class=\"error\">");
+        out.push_str(&escape::html(message));
+        out.push_str("</span>");
+    }
+}
+
+/// Write a form.
+///
+/// Hash parameters are written as attributes except for `model`
+/// which sets the scope for the block so fields are bound to the
+/// model and `errors` which sets the validation errors for the
+/// fields in the block.
+///
+/// Methods other than `get` and `post` are written as `post` with a
+/// hidden `_method` field so the server can override the method.
+pub struct Form;
+
+impl Helper for Form {
+    fn call<'render, 'call>(
+        &self,
+        rc: &mut Render<'render>,
+        ctx: &Context<'call>,
+        template: Option<&'render Node<'render>>,
+    ) -> HelperValue {
+        ctx.arity(0..0)?;
+        let template = ctx.assert_block(template)?;
+
+        let method = match ctx.param("method") {
+            Some(_) => Some(
+                ctx.try_param("method", &[Type::String])?
+                    .as_str()
+                    .unwrap()
+                    .to_lowercase(),
+            ),
+            None => None,
+        };
+        let overridden = method
+            .as_ref()
+            .filter(|m| m.as_str() != "get" && m.as_str() != "post");
+
+        let mut out = String::from("<form");
+        for (name, value) in ctx.parameters() {
+            attribute_name(ctx, name)?;
+            match name.as_str() {
+                "model" | ERRORS => {}
+                "method" if overridden.is_some() => {
+                    push_attribute(rc, &mut out, name, &Value::from("post"))
+                }
+                _ => push_attribute(rc, &mut out, name, value),
+            }
+        }
+        out.push('>');
+        if let Some(method) = overridden {
+            out.push_str("<input type=\"hidden\"");
+            push_attribute(rc, &mut out, "name", &Value::from(METHOD));
+            push_attribute(
+                rc,
+                &mut out,
+                "value",
+                &Value::from(method.as_str()),
+            );
+            out.push('>');
+        }
+        rc.write(&out)?;
+
+        let model = ctx.param("model");
+        let errors = ctx.param(ERRORS);
+        if model.is_some() || errors.is_some() {
+            let mut scope = Scope::new();
+            if let Some(model) = model {
+                scope.set_base_value(model.clone());
+            }
+            if let Some(errors) = errors {
+                scope.set_variable(ERRORS, errors.clone());
+            }
+            rc.push_scope(scope);
+            rc.template(template)?;
+            rc.pop_scope();
+        } else {
+            rc.template(template)?;
+        }
+
+        rc.write("</form>")?;
+        Ok(None)
+    }
+}
+
+/// Write an input field.
+///
+/// Requires the `name` hash parameter; the `type` defaults to `text`
+/// and other hash parameters are written as attributes. The value is
+/// the `value` hash parameter or the value in the current context
+/// with the same name as the field; the value of a `password` field
+/// is never written.
+///
+/// A `checkbox` is checked when the bound value is truthy or, when a
+/// `value` is given, equal to or contains the value; a `radio` is
+/// checked when the bound value is equal to the `value`.
+pub struct Input;
+
+impl Helper for Input {
+    fn call<'render, 'call>(
+        &self,
+        rc: &mut Render<'render>,
+        ctx: &Context<'call>,
+        template: Option<&'render Node<'render>>,
+    ) -> HelperValue {
+        ctx.arity(0..0)?;
+        ctx.assert_statement(template)?;
+        let kind = match ctx.param("type") {
+            Some(_) => {
+                ctx.try_param("type", &[Type::String])?.as_str().unwrap()
+            }
+            None => "text",
+        };
+        let name = ctx.try_param("name", &[Type::String])?.as_str().unwrap();
+
+        let mut out = String::from("<input");
+        push_attribute(rc, &mut out, "type", &Value::from(kind));
+        push_attribute(rc, &mut out, "name", &Value::from(name));
+        match kind {
+            "checkbox" | "radio" => {
+                let bound = rc.evaluate(name).ok().flatten();
+                let checked = match ctx.param("value") {
+                    Some(value) => {
+                        push_attribute(rc, &mut out, "value", value);
+                        selected(rc, bound, &display(rc, value))
+                    }
+                    None => bound.map(|v| ctx.is_truthy(v)).unwrap_or(false),
+                };
+                push_attribute(rc, &mut out, "checked", &Value::Bool(checked));
+            }
+            "password" => {}
+            _ => {
+                let (_, bound) = field(rc, ctx)?;
+                if let Some(bound) = bound {
+                    let value = Value::from(display(rc, bound));
+                    push_attribute(rc, &mut out, "value", &value);
+                }
+            }
+        }
+
+        let messages = messages(rc, name);
+        if !messages.is_empty() {
+            push_attribute(rc, &mut out, "aria-invalid", &Value::from("true"));
+        }
+        push_attributes(rc, ctx, &mut out, &["type", "name", "value"])?;
+        out.push('>');
+        push_messages(&mut out, &messages);
+        rc.write(&out)?;
+        Ok(None)
+    }
+}
+
+/// Write a select field.
+///
+/// Requires the `name` and `options` hash parameters; other hash
+/// parameters are written as attributes. The options are an array of
+/// strings, an array of objects with `value` and `label` fields or an
+/// object that maps values to labels.
+///
+/// An option is selected when it is equal to the bound value or,
+/// when the bound value is an array, contained in the array.
+pub struct Select;
+
+impl Helper for Select {
+    fn call<'render, 'call>(
+        &self,
+        rc: &mut Render<'render>,
+        ctx: &Context<'call>,
+        template: Option<&'render Node<'render>>,
+    ) -> HelperValue {
+        ctx.arity(0..0)?;
+        ctx.assert_statement(template)?;
+        let (name, bound) = field(rc, ctx)?;
+        let options = match ctx.param("options") {
+            Some(options @ Value::Object(_)) => options,
+            _ => ctx.try_param("options", &[Type::Array])?,
+        };
+
+        let messages = messages(rc, name);
+        let mut out = String::from("<select");
+        push_attribute(rc, &mut out, "name", &Value::from(name));
+        if !messages.is_empty() {
+            push_attribute(rc, &mut out, "aria-invalid", &Value::from("true"));
+        }
+        push_attributes(rc, ctx, &mut out, &["name", "value", "options"])?;
+        out.push('>');
+
+        let mut push_option = |value: String, label: String| {
+            out.push_str("<option");
+            push_attribute(rc, &mut out, "value", &Value::from(value.as_str()));
+            let checked = Value::Bool(selected(rc, bound, &value));
+            push_attribute(rc, &mut out, "selected", &checked);
+            out.push('>');
+            out.push_str(&escape::html(&label));
+            out.push_str("</option>");
+        };
+        match options {
+            Value::Array(ref list) => {
+                for option in list {
+                    let (value, label) = match option {
+                        Value::Object(ref map) => {
+                            let value =
+                                map.get("value").unwrap_or(&Value::Null);
+                            let label = map.get("label").unwrap_or(value);
+                            (display(rc, value), display(rc, label))
+                        }
+                        _ => (display(rc, option), display(rc, option)),
+                    };
+                    push_option(value, label);
+                }
+            }
+            Value::Object(ref map) => {
+                for (value, label) in map {
+                    push_option(value.to_string(), display(rc, label));
+                }
+            }
+            _ => {}
+        }
+
+        out.push_str("</select>");
+        push_messages(&mut out, &messages);
+        rc.write(&out)?;
+        Ok(None)
+    }
+}
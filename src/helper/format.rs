@@ -0,0 +1,46 @@
+//! Helper that formats a value using a tagged formatter.
+use crate::{
+    error::HelperError,
+    helper::{Helper, HelperValue},
+    parser::ast::Node,
+    render::{Context, Render, Type},
+};
+use alloc::format;
+
+use serde_json::Value;
+
+/// Format a value using a formatter registered for a tag.
+///
+/// Requires two arguments; the value to format and the name of
+/// the tag:
+///
+/// ```ignore
+/// {{format distance "sci"}}
+/// ```
+///
+/// When the formatter declines the value the default formatting for
+/// the registry is used; it is an error if no formatter is registered
+/// for the tag.
+pub struct Format;
+
+impl Helper for Format {
+    fn call<'render, 'call>(
+        &self,
+        rc: &mut Render<'render>,
+        ctx: &Context<'call>,
+        _template: Option<&'render Node<'render>>,
+    ) -> HelperValue {
+        ctx.arity(2..2)?;
+
+        let value = ctx.get(0).unwrap();
+        let tag = ctx.try_get(1, &[Type::String])?.as_str().unwrap();
+        let registry = rc.registry();
+        let formatter = registry.tag_formatter(tag).ok_or_else(|| {
+            HelperError::new(format!("Formatter for tag '{}' not found", tag))
+        })?;
+        let result = formatter
+            .display(value)
+            .unwrap_or_else(|| registry.format(value));
+        Ok(Some(Value::String(result)))
+    }
+}
@@ -0,0 +1,38 @@
+//! Block helper that marks a fragment of a template.
+use crate::{
+    helper::{Helper, HelperValue},
+    parser::ast::Node,
+    render::{Context, Render, Type},
+};
+
+/// Render a block that may also be rendered on its own.
+///
+/// Accepts a single string literal argument which is the id of the
+/// fragment; the block is always rendered as part of the template
+/// and can be rendered without the rest of the template using
+/// [render_fragment()](crate::Registry#method.render_fragment) so a
+/// page and the partial updates for the page share a template:
+///
+/// ```ignore
+/// <table>
+///   {{#each rows}}
+///     {{#fragment "row"}}<tr><td>{{name}}</td></tr>{{/fragment}}
+///   {{/each}}
+/// </table>
+/// ```
+pub struct Fragment;
+
+impl Helper for Fragment {
+    fn call<'render, 'call>(
+        &self,
+        rc: &mut Render<'render>,
+        ctx: &Context<'call>,
+        template: Option<&'render Node<'render>>,
+    ) -> HelperValue {
+        ctx.arity(1..1)?;
+        let template = ctx.assert_block(template)?;
+        ctx.try_get(0, &[Type::String])?;
+        rc.template(template)?;
+        Ok(None)
+    }
+}
@@ -0,0 +1,82 @@
+//! Block helper that groups an array of objects by a key.
+use crate::{
+    helper::{Helper, HelperValue},
+    json,
+    parser::ast::Node,
+    render::{Context, Render, Scope, Type},
+};
+use alloc::{vec, vec::Vec};
+
+use serde_json::{Number, Value};
+
+const KEY: &str = "key";
+const INDEX: &str = "index";
+const FIRST: &str = "first";
+const LAST: &str = "last";
+
+/// Render a block for each group of an array.
+///
+/// Accepts a single argument which is the array to group and
+/// requires the `by` hash parameter which is the path to the key for
+/// each element, for example `category` or `author.name`. Groups are
+/// rendered in the order the keys first appear and elements without
+/// the key are grouped under `null`.
+///
+/// The block is rendered for each group with the array of grouped
+/// elements as the scope:
+///
+/// ```ignore
+/// {{#group products by="category"}}
+///   <h2>{{@key}}</h2>
+///   {{#each this}}<p>{{name}}</p>{{/each}}
+/// {{/group}}
+/// ```
+///
+/// The local variables are:
+///
+/// * `@key`: Value of the key for the group.
+/// * `@index`: Zero-based index of the group.
+/// * `@first`: If this is the first group `true`.
+/// * `@last`: If this is the last group `true`.
+pub struct Group;
+
+impl Helper for Group {
+    fn call<'render, 'call>(
+        &self,
+        rc: &mut Render<'render>,
+        ctx: &Context<'call>,
+        template: Option<&'render Node<'render>>,
+    ) -> HelperValue {
+        ctx.arity(1..1)?;
+        let template = ctx.assert_block(template)?;
+        let items = ctx.try_get(0, &[Type::Array])?.as_array().unwrap();
+        let by = ctx.try_param("by", &[Type::String])?.as_str().unwrap();
+
+        let keys = rc.registry().numeric_keys();
+        let mut groups: Vec<(Value, Vec<Value>)> = Vec::new();
+        for item in items {
+            let key = json::find_parts(by.split('.'), item, keys)
+                .cloned()
+                .unwrap_or(Value::Null);
+            match groups.iter_mut().find(|(k, _)| k == &key) {
+                Some((_, list)) => list.push(item.clone()),
+                None => groups.push((key, vec![item.clone()])),
+            }
+        }
+
+        let len = groups.len();
+        rc.push_scope(Scope::new());
+        for (index, (key, list)) in groups.into_iter().enumerate() {
+            if let Some(ref mut scope) = rc.scope_mut() {
+                scope.set_local(KEY, key);
+                scope.set_local(INDEX, Value::Number(Number::from(index)));
+                scope.set_local(FIRST, Value::Bool(index == 0));
+                scope.set_local(LAST, Value::Bool(index == len - 1));
+                scope.set_base_value(Value::Array(list));
+            }
+            rc.template(template)?;
+        }
+        rc.pop_scope();
+        Ok(None)
+    }
+}
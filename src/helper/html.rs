@@ -0,0 +1,245 @@
+//! Helpers that write HTML elements and attributes.
+//!
+//! Attribute values are always HTML escaped and attributes with a
+//! value of `null`, `false` or an empty string are omitted; a value
+//! of `true` writes a boolean attribute such as `disabled`.
+//!
+//! Attribute names are not escaped so a name that contains
+//! whitespace, quotes, `<`, `>`, `/`, `=` or control characters is
+//! an error.
+//!
+//! The `link_to`, `img` and `attr` helpers write to the output so
+//! they should not be used in a sub-expression.
+use crate::{
+    error::HelperError,
+    escape,
+    helper::{Helper, HelperValue},
+    parser::ast::Node,
+    render::{Context, Render, Type},
+};
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use serde_json::Value;
+
+/// Format a value for an attribute or `None` when the
+/// attribute should be omitted.
+fn attribute_value(rc: &Render<'_>, value: &Value) -> Option<String> {
+    match value {
+        Value::Null | Value::Bool(false) => None,
+        Value::Bool(true) => Some(String::new()),
+        Value::String(ref s) if s.is_empty() => None,
+        Value::String(ref s) => Some(s.clone()),
+        _ => Some(rc.registry().format(value)),
+    }
+}
+
+/// Ensure an attribute name cannot break out of the attribute.
+pub(crate) fn attribute_name(
+    ctx: &Context<'_>,
+    name: &str,
+) -> Result<(), HelperError> {
+    let invalid = |c: char| {
+        c.is_whitespace()
+            || c.is_control()
+            || matches!(c, '"' | '\'' | '<' | '>' | '/' | '=')
+    };
+    if name.is_empty() || name.contains(invalid) {
+        return Err(HelperError::new(format!(
+            "Helper '{}' got invalid attribute name '{}'",
+            ctx.name(),
+            name
+        )));
+    }
+    Ok(())
+}
+
+/// Append an attribute with a leading space.
+pub(crate) fn push_attribute(
+    rc: &Render<'_>,
+    out: &mut String,
+    name: &str,
+    value: &Value,
+) {
+    if let Some(value) = attribute_value(rc, value) {
+        out.push(' ');
+        out.push_str(name);
+        if !value.is_empty() {
+            out.push_str("=\"");
+            out.push_str(&escape::html(&value));
+            out.push('"');
+        }
+    }
+}
+
+/// Append the hash parameters as attributes.
+fn push_attributes(
+    rc: &Render<'_>,
+    ctx: &Context<'_>,
+    out: &mut String,
+) -> Result<(), HelperError> {
+    for (name, value) in ctx.parameters() {
+        attribute_name(ctx, name)?;
+        push_attribute(rc, out, name, value);
+    }
+    Ok(())
+}
+
+/// Write a link.
+///
+/// Accepts the label and the URL for the link; hash parameters
+/// are written as attributes:
+///
+/// ```ignore
+/// {{link_to "Home" "/" class="nav"}}
+/// ```
+///
+/// Writes `<a href="/" class="nav">Home</a>`.
+pub struct LinkTo;
+
+impl Helper for LinkTo {
+    fn call<'render, 'call>(
+        &self,
+        rc: &mut Render<'render>,
+        ctx: &Context<'call>,
+        template: Option<&'render Node<'render>>,
+    ) -> HelperValue {
+        ctx.arity(2..2)?;
+        ctx.assert_statement(template)?;
+        let label = ctx.get(0).unwrap();
+        let url = ctx.try_get(1, &[Type::String])?;
+
+        let mut out = String::from("<a");
+        push_attribute(rc, &mut out, "href", url);
+        push_attributes(rc, ctx, &mut out)?;
+        out.push('>');
+        let label = match label {
+            Value::String(ref s) => s.clone(),
+            _ => rc.registry().format(label),
+        };
+        out.push_str(&escape::html(&label));
+        out.push_str("</a>");
+        rc.write(&out)?;
+        Ok(None)
+    }
+}
+
+/// Write an image.
+///
+/// Accepts the URL for the image; hash parameters are written
+/// as attributes:
+///
+/// ```ignore
+/// {{img "/logo.png" alt="Logo" width=120}}
+/// ```
+///
+/// Writes `<img src="/logo.png" alt="Logo" width="120">`.
+pub struct Img;
+
+impl Helper for Img {
+    fn call<'render, 'call>(
+        &self,
+        rc: &mut Render<'render>,
+        ctx: &Context<'call>,
+        template: Option<&'render Node<'render>>,
+    ) -> HelperValue {
+        ctx.arity(1..1)?;
+        ctx.assert_statement(template)?;
+        let src = ctx.try_get(0, &[Type::String])?;
+
+        let mut out = String::from("<img");
+        push_attribute(rc, &mut out, "src", src);
+        push_attributes(rc, ctx, &mut out)?;
+        out.push('>');
+        rc.write(&out)?;
+        Ok(None)
+    }
+}
+
+/// Write attributes.
+///
+/// Accepts an attribute name and value or hash parameters for
+/// multiple attributes; each attribute is written with a leading
+/// space so it can be placed directly after the element name:
+///
+/// ```ignore
+/// <input type="checkbox"{{attr "disabled" isDisabled}}{{attr title=hint}}>
+/// ```
+pub struct Attr;
+
+impl Helper for Attr {
+    fn call<'render, 'call>(
+        &self,
+        rc: &mut Render<'render>,
+        ctx: &Context<'call>,
+        template: Option<&'render Node<'render>>,
+    ) -> HelperValue {
+        ctx.assert_statement(template)?;
+        let mut out = String::new();
+        match ctx.arguments().len() {
+            0 if !ctx.parameters().is_empty() => {}
+            2 => {
+                let name = ctx.try_get(0, &[Type::String])?.as_str().unwrap();
+                attribute_name(ctx, name)?;
+                push_attribute(rc, &mut out, name, ctx.get(1).unwrap());
+            }
+            _ => {
+                return Err(HelperError::new(format!(
+                    "Helper '{}' expects a name and value or hash parameters",
+                    ctx.name()
+                )))
+            }
+        }
+        push_attributes(rc, ctx, &mut out)?;
+        rc.write(&out)?;
+        Ok(None)
+    }
+}
+
+/// Build a list of class names.
+///
+/// Arguments are class names, arrays of class names or falsy values
+/// which are ignored; the key of each hash parameter is added when
+/// the value is truthy. Duplicate names are removed:
+///
+/// ```ignore
+/// <button class="{{class_list "btn" size active=isActive}}">
+/// ```
+pub struct ClassList;
+
+impl Helper for ClassList {
+    fn call<'render, 'call>(
+        &self,
+        _rc: &mut Render<'render>,
+        ctx: &Context<'call>,
+        _template: Option<&'render Node<'render>>,
+    ) -> HelperValue {
+        let mut names: Vec<String> = Vec::new();
+        let mut add = |name: &str| {
+            for name in name.split_whitespace() {
+                if !names.iter().any(|n| n == name) {
+                    names.push(name.to_string());
+                }
+            }
+        };
+        for value in ctx.arguments() {
+            match value {
+                Value::String(ref s) => add(s),
+                Value::Array(ref list) => {
+                    list.iter().filter_map(|v| v.as_str()).for_each(&mut add)
+                }
+                _ if !ctx.is_truthy(value) => {}
+                _ => ctx.assert(value, &[Type::String, Type::Array])?,
+            }
+        }
+        for (name, value) in ctx.parameters() {
+            if ctx.is_truthy(value) {
+                add(name);
+            }
+        }
+        Ok(Some(Value::String(names.join(" "))))
+    }
+}
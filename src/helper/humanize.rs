@@ -0,0 +1,151 @@
+//! Helpers that format numbers for people to read.
+use crate::{
+    error::HelperError,
+    helper::{Helper, HelperValue},
+    parser::ast::Node,
+    render::{Context, Render, Type},
+};
+use alloc::{format, string::String, vec::Vec};
+
+use serde_json::Value;
+
+const BINARY: [&str; 7] = ["B", "KiB", "MiB", "GiB", "TiB", "PiB", "EiB"];
+const DECIMAL: [&str; 7] = ["B", "kB", "MB", "GB", "TB", "PB", "EB"];
+const DURATION: [(&str, u64); 4] =
+    [("d", 86_400), ("h", 3_600), ("m", 60), ("s", 1)];
+
+/// Maximum number of decimal places for a file size.
+const MAX_PRECISION: u64 = 20;
+
+/// Get an optional integer hash parameter.
+fn integer(
+    ctx: &Context<'_>,
+    name: &str,
+    default: u64,
+) -> Result<u64, HelperError> {
+    match ctx.param(name) {
+        Some(_) => {
+            ctx.try_param(name, &[Type::Number])?
+                .as_u64()
+                .ok_or_else(|| {
+                    HelperError::new(format!(
+                        "Helper '{}' expects a positive integer for '{}'",
+                        ctx.name(),
+                        name
+                    ))
+                })
+        }
+        None => Ok(default),
+    }
+}
+
+/// Format a number of bytes as a file size.
+///
+/// Accepts a single argument which is the number of bytes and uses
+/// binary units by default so `1468006` is `1.4 MiB`; when the `si`
+/// hash parameter is truthy decimal units are used and the same
+/// number is `1.5 MB`. The `precision` hash parameter sets the number
+/// of decimal places up to `20` and defaults to `1`:
+///
+/// ```ignore
+/// {{filesize size}} {{filesize size si=true precision=2}}
+/// ```
+pub struct Filesize;
+
+impl Helper for Filesize {
+    fn call<'render, 'call>(
+        &self,
+        _rc: &mut Render<'render>,
+        ctx: &Context<'call>,
+        _template: Option<&'render Node<'render>>,
+    ) -> HelperValue {
+        ctx.arity(1..1)?;
+        let bytes = ctx.try_get(0, &[Type::Number])?.as_f64().unwrap();
+        if bytes < 0.0 {
+            return Err(HelperError::new(format!(
+                "Helper '{}' expects a positive number of bytes",
+                ctx.name()
+            )));
+        }
+        let si = ctx.param("si").map(|v| ctx.is_truthy(v)).unwrap_or(false);
+        let precision = integer(ctx, "precision", 1)?;
+        if precision > MAX_PRECISION {
+            return Err(HelperError::new(format!(
+                "Helper '{}' expects a precision of at most {}",
+                ctx.name(),
+                MAX_PRECISION
+            )));
+        }
+
+        let (base, units) = if si {
+            (1000.0, &DECIMAL)
+        } else {
+            (1024.0, &BINARY)
+        };
+        let mut size = bytes;
+        let mut unit = 0;
+        while size >= base && unit < units.len() - 1 {
+            size /= base;
+            unit += 1;
+        }
+        let result = if unit == 0 {
+            format!("{} {}", bytes.trunc(), units[0])
+        } else {
+            format!("{:.*} {}", precision as usize, size, units[unit])
+        };
+        Ok(Some(Value::String(result)))
+    }
+}
+
+/// Format a number of seconds as a duration.
+///
+/// Accepts a single argument which is the number of seconds and
+/// writes the days, hours, minutes and seconds starting with the
+/// largest unit so `7980` is `2h 13m`. The `units` hash parameter is
+/// the number of units to write and defaults to `2`; fractions of a
+/// second are truncated and units that are zero are omitted:
+///
+/// ```ignore
+/// Finished in {{duration elapsed units=3}}
+/// ```
+pub struct Duration;
+
+impl Helper for Duration {
+    fn call<'render, 'call>(
+        &self,
+        _rc: &mut Render<'render>,
+        ctx: &Context<'call>,
+        _template: Option<&'render Node<'render>>,
+    ) -> HelperValue {
+        ctx.arity(1..1)?;
+        let seconds = ctx.try_get(0, &[Type::Number])?.as_f64().unwrap();
+        let count = integer(ctx, "units", 2)?.max(1) as usize;
+
+        let mut remainder = seconds.abs().trunc() as u64;
+        let mut parts = Vec::new();
+        for (name, size) in DURATION.iter() {
+            let amount = remainder / size;
+            remainder %= size;
+            if amount > 0 || !parts.is_empty() {
+                parts.push((amount, name));
+            }
+        }
+        let parts: Vec<String> = parts
+            .into_iter()
+            .take(count)
+            .filter(|(amount, _)| *amount > 0)
+            .map(|(amount, name)| format!("{}{}", amount, name))
+            .collect();
+
+        let mut result = String::new();
+        if seconds <= -1.0 {
+            result.push('-');
+        }
+        if parts.is_empty() {
+            result.push_str("0s");
+        } else {
+            result.push_str(&parts.join(" "));
+        }
+        Ok(Some(Value::String(result)))
+    }
+}
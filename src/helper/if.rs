@@ -38,4 +38,8 @@ impl Helper for If {
             Ok(Some(Value::Bool(ctx.is_truthy(ctx.get(0).unwrap()))))
         }
     }
+
+    fn pure(&self) -> bool {
+        true
+    }
 }
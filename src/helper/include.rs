@@ -0,0 +1,46 @@
+//! Helper that renders another template with explicit data.
+use crate::{
+    helper::{Helper, HelperValue},
+    parser::ast::Node,
+    render::{Context, Render, Type},
+};
+use alloc::boxed::Box;
+
+use serde_json::{Map, Value};
+
+/// Render a registered template using the `data` hash parameter
+/// as the root data.
+///
+/// Requires a single string argument which is the name of the
+/// template to render:
+///
+/// ```ignore
+/// {{include "card" data=user.profile}}
+/// ```
+///
+/// Unlike a partial the included template cannot access the scopes
+/// of the calling template; only the `data` value is available and
+/// it is also the value of `@root`. When the `data` parameter is not
+/// given the template is rendered with an empty object.
+pub struct Include;
+
+impl Helper for Include {
+    fn call<'render, 'call>(
+        &self,
+        rc: &mut Render<'render>,
+        ctx: &Context<'call>,
+        template: Option<&'render Node<'render>>,
+    ) -> HelperValue {
+        ctx.arity(1..1)?;
+        ctx.assert_statement(template)?;
+
+        let name = ctx.try_get(0, &[Type::String])?.as_str().unwrap();
+        let data = ctx
+            .param("data")
+            .cloned()
+            .unwrap_or_else(|| Value::Object(Map::new()));
+        rc.include(name, data).map_err(Box::new)?;
+
+        Ok(None)
+    }
+}
@@ -1,4 +1,6 @@
 //! Helper that returns a JSON string.
+use alloc::{string::ToString, vec::Vec};
+
 use crate::{
     error::HelperError,
     helper::{Helper, HelperValue},
@@ -6,15 +8,38 @@ use crate::{
     render::{Context, Render},
 };
 
-use serde_json::{to_string, to_string_pretty, Value};
+use serde_json::{to_string, to_string_pretty, Map, Value};
 
 /// Convert to a JSON string.
 ///
 /// Accepts a single argument which is converted to a JSON string and returned.
 ///
 /// The optional hash parameter `pretty` when *truthy* will pretty print the value.
+///
+/// The optional hash parameter `pointer` is a JSON Pointer (RFC 6901) such
+/// as `/user/name` used to select part of the value; when the pointer
+/// does not match the value is `null`.
+///
+/// The optional hash parameter `only` is a comma-separated list of keys
+/// (or an array of keys) to keep when the selected value is an object;
+/// other values are not filtered.
 pub struct Json;
 
+fn filter(value: &Value, keys: &[&str]) -> Value {
+    match value {
+        Value::Object(ref map) => {
+            let mut filtered = Map::new();
+            for key in keys {
+                if let Some(value) = map.get(*key) {
+                    filtered.insert(key.to_string(), value.clone());
+                }
+            }
+            Value::Object(filtered)
+        }
+        _ => value.clone(),
+    }
+}
+
 impl Helper for Json {
     fn call<'render, 'call>(
         &self,
@@ -24,7 +49,29 @@ impl Helper for Json {
     ) -> HelperValue {
         ctx.arity(1..1)?;
 
-        let target = ctx.get(0).unwrap();
+        let mut target = ctx.get(0).unwrap();
+        if let Some(Value::String(ref pointer)) = ctx.param("pointer") {
+            target = target.pointer(pointer).unwrap_or(&Value::Null);
+        }
+
+        let filtered = match ctx.param("only") {
+            Some(Value::String(ref keys)) => {
+                let keys: Vec<&str> = keys
+                    .split(',')
+                    .map(|k| k.trim())
+                    .filter(|k| !k.is_empty())
+                    .collect();
+                Some(filter(target, &keys))
+            }
+            Some(Value::Array(ref keys)) => {
+                let keys: Vec<&str> =
+                    keys.iter().filter_map(|k| k.as_str()).collect();
+                Some(filter(target, &keys))
+            }
+            _ => None,
+        };
+        let target = filtered.as_ref().unwrap_or(target);
+
         let pretty =
             ctx.is_truthy(ctx.param("pretty").unwrap_or(&Value::Bool(false)));
         let value = if pretty {
@@ -35,4 +82,8 @@ impl Helper for Json {
 
         Ok(Some(value))
     }
+
+    fn pure(&self) -> bool {
+        true
+    }
 }
@@ -5,6 +5,7 @@ use crate::{
     parser::ast::Node,
     render::{Context, Render},
 };
+use alloc::{string::String, vec::Vec};
 
 use log::*;
 
@@ -24,6 +24,10 @@ impl Helper for And {
                 && ctx.is_truthy(ctx.get(1).unwrap()),
         )))
     }
+
+    fn pure(&self) -> bool {
+        true
+    }
 }
 
 /// Perform a logical OR on two arguments.
@@ -44,6 +48,10 @@ impl Helper for Or {
                 || ctx.is_truthy(ctx.get(1).unwrap()),
         )))
     }
+
+    fn pure(&self) -> bool {
+        true
+    }
 }
 
 /// Perform a logical NOT on an argument.
@@ -60,4 +68,8 @@ impl Helper for Not {
         ctx.arity(1..1)?;
         Ok(Some(Value::Bool(!ctx.is_truthy(ctx.get(0).unwrap()))))
     }
+
+    fn pure(&self) -> bool {
+        true
+    }
 }
@@ -5,6 +5,7 @@ use crate::{
     parser::ast::Node,
     render::{Context, Render, Type},
 };
+use alloc::string::ToString;
 
 /// Lookup a field of an array of object.
 ///
@@ -36,4 +37,8 @@ impl Helper for Lookup {
             ))
         }
     }
+
+    fn pure(&self) -> bool {
+        true
+    }
 }
@@ -17,6 +17,10 @@
 //!
 //! When a helper is called as a block the optional template node will be `Some`.
 //! Raw helpers can access the inner text using [text()](crate::render::context::Context#method.text).
+//! The open tag of a raw block is parsed like any other call so raw helpers
+//! receive arguments and hash parameters, for example
+//! `{{{{highlight lang="rust"}}}}...{{{{/highlight}}}}`, whilst the inner
+//! text is never parsed.
 //!
 //! To determine how a helper was invoked requires checking for an inner template
 //! or raw text; if neither is available it is a statement:
@@ -128,19 +132,23 @@
 //! [param_fallback()]: crate::render::Context#method.param_fallback
 //! [missing()]: crate::render::Context#method.missing
 //! [missing_param()]: crate::render::Context#method.missing_param
+use alloc::{
+    boxed::Box,
+    string::{String, ToString},
+};
 
 use dyn_clone::DynClone;
 use serde_json::Value;
-use std::collections::HashMap;
 
 use crate::{
+    collections::HashMap,
     error::HelperError,
     parser::ast::Node,
     render::{Context, Render},
 };
 
 /// Result type returned when invoking helpers.
-pub type HelperResult<T> = std::result::Result<T, HelperError>;
+pub type HelperResult<T> = core::result::Result<T, HelperError>;
 
 /// Result type that helper implementations should return.
 pub type HelperValue = HelperResult<Option<Value>>;
@@ -168,6 +176,32 @@ pub trait Helper: Send + Sync {
         ctx: &Context<'call>,
         template: Option<&'render Node<'render>>,
     ) -> HelperValue;
+
+    /// Whether this helper uses ambient authority such as the
+    /// process environment, file system or system clock.
+    ///
+    /// Helpers that return `true` cannot be called when the
+    /// registry is sandboxed.
+    fn ambient(&self) -> bool {
+        false
+    }
+
+    /// Whether the output of this helper depends only on its
+    /// arguments, hash parameters and inner template.
+    ///
+    /// Partials that call a helper which is not pure are never
+    /// stored in the [partial cache](crate::cache::PartialCache).
+    fn pure(&self) -> bool {
+        false
+    }
+
+    /// Short description of this helper.
+    ///
+    /// Exposed to templates by the `helpers` helper so that helper
+    /// reference pages can be rendered from the registry.
+    fn description(&self) -> Option<&str> {
+        None
+    }
 }
 
 /// Trait for local helpers which must implement `Clone`.
@@ -195,12 +229,46 @@ dyn_clone::clone_trait_object!(LocalHelper);
 
 pub mod prelude;
 
+#[cfg(feature = "asset-helper")]
+pub mod asset;
+#[cfg(feature = "call-helper")]
+pub mod call;
+#[cfg(feature = "cdata-helper")]
+pub mod cdata;
+#[cfg(feature = "chunk-helper")]
+pub mod chunk;
 #[cfg(feature = "comparison-helper")]
 pub mod comparison;
+#[cfg(feature = "csp-helper")]
+pub mod csp;
+#[cfg(feature = "csv-helper")]
+pub mod csv;
 #[cfg(feature = "each-helper")]
 pub mod each;
+#[cfg(feature = "encoding-helper")]
+pub mod encoding;
+#[cfg(feature = "env-helper")]
+pub mod env;
+#[cfg(feature = "escape-helper")]
+pub mod escape;
 #[cfg(feature = "conditional-helper")]
 pub mod r#if;
+#[cfg(feature = "filter-helper")]
+pub mod filter;
+#[cfg(feature = "form-helper")]
+pub mod form;
+#[cfg(feature = "format-helper")]
+pub mod format;
+#[cfg(feature = "fragment-helper")]
+pub mod fragment;
+#[cfg(feature = "group-helper")]
+pub mod group;
+#[cfg(feature = "html-helper")]
+pub mod html;
+#[cfg(feature = "humanize-helper")]
+pub mod humanize;
+#[cfg(feature = "include-helper")]
+pub mod include;
 #[cfg(feature = "json-helper")]
 pub mod json;
 #[cfg(feature = "log-helper")]
@@ -209,6 +277,36 @@ pub mod log;
 pub mod logical;
 #[cfg(feature = "lookup-helper")]
 pub mod lookup;
+#[cfg(feature = "now-helper")]
+pub mod now;
+#[cfg(feature = "once-helper")]
+pub mod once;
+#[cfg(feature = "paginate-helper")]
+pub mod paginate;
+#[cfg(feature = "plural-helper")]
+pub mod plural;
+#[cfg(feature = "random-helper")]
+pub mod random;
+#[cfg(feature = "range-helper")]
+pub mod range;
+#[cfg(feature = "reflect-helper")]
+pub mod reflect;
+#[cfg(feature = "repeat-helper")]
+pub mod repeat;
+#[cfg(feature = "set-helper")]
+pub mod set;
+#[cfg(feature = "shell-helper")]
+pub mod shell;
+#[cfg(feature = "sql-helper")]
+pub mod sql;
+#[cfg(feature = "table-helper")]
+pub mod table;
+#[cfg(feature = "timeago-helper")]
+pub mod timeago;
+#[cfg(feature = "tree-helper")]
+pub mod tree;
+#[cfg(feature = "truncate-helper")]
+pub mod truncate;
 #[cfg(feature = "conditional-helper")]
 pub mod unless;
 #[cfg(feature = "with-helper")]
@@ -218,6 +316,8 @@ pub mod with;
 #[derive(Default)]
 pub struct HelperRegistry<'reg> {
     helpers: HashMap<&'reg str, Box<dyn Helper + 'reg>>,
+    aliases: HashMap<String, String>,
+    deprecated: HashMap<String, String>,
 }
 
 impl<'reg> HelperRegistry<'reg> {
@@ -229,9 +329,7 @@ impl<'reg> HelperRegistry<'reg> {
     /// If you need a helper collection without the builtin helpers
     /// use `Default::default()`.
     pub fn new() -> Self {
-        let mut reg = Self {
-            helpers: Default::default(),
-        };
+        let mut reg: Self = Default::default();
         reg.builtins();
         reg
     }
@@ -259,6 +357,8 @@ impl<'reg> HelperRegistry<'reg> {
         self.insert("log", Box::new(log::Log {}));
         #[cfg(feature = "lookup-helper")]
         self.insert("lookup", Box::new(lookup::Lookup {}));
+        #[cfg(feature = "include-helper")]
+        self.insert("include", Box::new(include::Include {}));
 
         #[cfg(feature = "logical-helper")]
         self.insert("and", Box::new(logical::And {}));
@@ -271,9 +371,96 @@ impl<'reg> HelperRegistry<'reg> {
         self.insert("with", Box::new(with::With {}));
         #[cfg(feature = "each-helper")]
         self.insert("each", Box::new(each::Each {}));
+        #[cfg(feature = "repeat-helper")]
+        self.insert("repeat", Box::new(repeat::Repeat {}));
+        #[cfg(feature = "set-helper")]
+        self.insert("set", Box::new(set::Set {}));
+        #[cfg(feature = "paginate-helper")]
+        self.insert("paginate", Box::new(paginate::Paginate {}));
 
         #[cfg(feature = "json-helper")]
         self.insert("json", Box::new(json::Json {}));
+        #[cfg(feature = "format-helper")]
+        self.insert("format", Box::new(format::Format {}));
+        #[cfg(feature = "csv-helper")]
+        self.insert("csv", Box::new(csv::Csv {}));
+        #[cfg(feature = "shell-helper")]
+        self.insert("shell_quote", Box::new(shell::ShellQuote {}));
+        #[cfg(feature = "cdata-helper")]
+        self.insert("cdata", Box::new(cdata::Cdata {}));
+        #[cfg(feature = "escape-helper")]
+        self.insert("safe", Box::new(escape::Safe {}));
+        #[cfg(feature = "escape-helper")]
+        self.insert("escape", Box::new(escape::Escape {}));
+        #[cfg(feature = "random-helper")]
+        self.insert("uuid", Box::new(random::Uuid {}));
+        #[cfg(feature = "random-helper")]
+        self.insert("random", Box::new(random::Random {}));
+        #[cfg(feature = "now-helper")]
+        self.insert("now", Box::new(now::Now {}));
+        #[cfg(feature = "asset-helper")]
+        self.insert("asset", Box::new(asset::Asset {}));
+        #[cfg(feature = "csp-helper")]
+        self.insert("csp_nonce", Box::new(csp::CspNonce {}));
+        #[cfg(feature = "html-helper")]
+        self.insert("link_to", Box::new(html::LinkTo {}));
+        #[cfg(feature = "html-helper")]
+        self.insert("img", Box::new(html::Img {}));
+        #[cfg(feature = "html-helper")]
+        self.insert("attr", Box::new(html::Attr {}));
+        #[cfg(feature = "html-helper")]
+        self.insert("class_list", Box::new(html::ClassList {}));
+        #[cfg(feature = "form-helper")]
+        self.insert("form", Box::new(form::Form {}));
+        #[cfg(feature = "form-helper")]
+        self.insert("input", Box::new(form::Input {}));
+        #[cfg(feature = "form-helper")]
+        self.insert("select", Box::new(form::Select {}));
+        #[cfg(feature = "table-helper")]
+        self.insert("table", Box::new(table::Table {}));
+        #[cfg(feature = "tree-helper")]
+        self.insert("tree", Box::new(tree::Tree {}));
+        #[cfg(feature = "group-helper")]
+        self.insert("group", Box::new(group::Group {}));
+        #[cfg(feature = "filter-helper")]
+        self.insert("where", Box::new(filter::Where {}));
+        #[cfg(feature = "chunk-helper")]
+        self.insert("chunk", Box::new(chunk::Chunk {}));
+        #[cfg(feature = "range-helper")]
+        self.insert("range", Box::new(range::Range {}));
+        #[cfg(feature = "plural-helper")]
+        self.insert("plural", Box::new(plural::Plural {}));
+        #[cfg(feature = "timeago-helper")]
+        self.insert("timeago", Box::new(timeago::Timeago::new()));
+        #[cfg(feature = "humanize-helper")]
+        self.insert("filesize", Box::new(humanize::Filesize {}));
+        #[cfg(feature = "humanize-helper")]
+        self.insert("duration", Box::new(humanize::Duration {}));
+        #[cfg(feature = "truncate-helper")]
+        self.insert("truncate_html", Box::new(truncate::TruncateHtml {}));
+        #[cfg(feature = "once-helper")]
+        self.insert("once", Box::new(once::Once {}));
+        #[cfg(feature = "call-helper")]
+        self.insert("call", Box::new(call::Call {}));
+        #[cfg(feature = "fragment-helper")]
+        self.insert("fragment", Box::new(fragment::Fragment {}));
+        #[cfg(feature = "sql-helper")]
+        self.insert("sql_str", Box::new(sql::SqlStr {}));
+        #[cfg(feature = "sql-helper")]
+        self.insert("sql_ident", Box::new(sql::SqlIdent {}));
+        #[cfg(feature = "encoding-helper")]
+        self.insert("base64", Box::new(encoding::Base64 {}));
+        #[cfg(feature = "encoding-helper")]
+        self.insert("base64url", Box::new(encoding::Base64Url {}));
+        #[cfg(feature = "encoding-helper")]
+        self.insert("hex", Box::new(encoding::Hex {}));
+        #[cfg(feature = "encoding-helper")]
+        self.insert("sha256", Box::new(encoding::Sha256 {}));
+
+        #[cfg(feature = "env-helper")]
+        self.insert("env", Box::new(env::Env {}));
+        #[cfg(feature = "reflect-helper")]
+        self.insert("helpers", Box::new(reflect::Helpers {}));
     }
 
     /// Insert a helper into this collection.
@@ -287,9 +474,48 @@ impl<'reg> HelperRegistry<'reg> {
     }
 
     /// Get a helper from this collection.
+    ///
+    /// If the name is an alias the helper for the aliased
+    /// name is returned.
     pub fn get(&self, name: &str) -> Option<&Box<dyn Helper + 'reg>> {
+        let name = self.aliases.get(name).map(|s| s.as_str()).unwrap_or(name);
         self.helpers.get(name)
     }
+
+    /// Iterate the names of the helpers in this collection
+    /// excluding aliases.
+    pub fn names(&self) -> impl Iterator<Item = &str> + '_ {
+        self.helpers.keys().copied()
+    }
+
+    /// Iterate the aliases in this collection as pairs of
+    /// the alias and the aliased name.
+    pub fn aliases(&self) -> impl Iterator<Item = (&str, &str)> + '_ {
+        self.aliases.iter().map(|(k, v)| (k.as_str(), v.as_str()))
+    }
+
+    /// Add an alias so that calls to `alias` invoke the helper
+    /// registered with `name`.
+    ///
+    /// Aliases take precedence over a helper with the same name.
+    pub fn alias(&mut self, alias: &str, name: &str) {
+        self.aliases.insert(alias.to_string(), name.to_string());
+    }
+
+    /// Mark a helper name as deprecated.
+    ///
+    /// Templates that call the helper are reported by the
+    /// registry `lint()` function and when the `log` feature
+    /// is enabled a warning is logged when the helper is called.
+    pub fn deprecate(&mut self, name: &str, message: &str) {
+        self.deprecated
+            .insert(name.to_string(), message.to_string());
+    }
+
+    /// Get the deprecation message for a helper name.
+    pub fn deprecation(&self, name: &str) -> Option<&str> {
+        self.deprecated.get(name).map(|s| s.as_str())
+    }
 }
 
 /// Collection of helpers that are not for general purpose use.
@@ -305,3 +531,12 @@ pub struct HandlerRegistry<'reg> {
     /// Helper invoked when a block helper is missing.
     pub block_helper_missing: Option<Box<dyn Helper + 'reg>>,
 }
+
+impl HandlerRegistry<'_> {
+    /// Determine if no handlers have been assigned.
+    pub fn is_empty(&self) -> bool {
+        self.link.is_none()
+            && self.helper_missing.is_none()
+            && self.block_helper_missing.is_none()
+    }
+}
@@ -0,0 +1,145 @@
+//! Helper to get the current time.
+use crate::{
+    clock,
+    error::HelperError,
+    helper::{Helper, HelperValue},
+    parser::ast::Node,
+    render::{Context, Render, Type},
+};
+use alloc::{format, string::String};
+use core::fmt::Write;
+
+use serde_json::Value;
+
+const SECONDS_PER_DAY: i64 = 86_400;
+
+/// Get the current time.
+///
+/// Accepts no arguments and returns the time from the
+/// [clock](crate::Registry#method.clock) for the registry in UTC
+/// formatted as RFC 3339, for example `2024-03-01T09:30:00Z`.
+///
+/// The optional hash parameter `format` is a pattern that may contain
+/// `%Y` (year), `%m` (month), `%d` (day), `%H` (hour), `%M` (minute),
+/// `%S` (second), `%s` (seconds since the Unix epoch) and `%%`. The
+/// integer hash parameters `add_days` and `add_seconds` offset the
+/// time before it is formatted:
+///
+/// ```ignore
+/// Expires {{now format="%Y-%m-%d" add_days=7}}
+/// ```
+///
+/// When the registry is sandboxed a clock must be assigned because
+/// the system clock is not available.
+pub struct Now;
+
+impl Helper for Now {
+    fn call<'render, 'call>(
+        &self,
+        rc: &mut Render<'render>,
+        ctx: &Context<'call>,
+        _template: Option<&'render Node<'render>>,
+    ) -> HelperValue {
+        ctx.arity(0..0)?;
+
+        let now = clock::now(rc.registry()).ok_or_else(|| {
+            HelperError::new(format!(
+                "Helper '{}' requires a clock",
+                ctx.name()
+            ))
+        })?;
+
+        let days = offset(ctx, "add_days")?;
+        let seconds = offset(ctx, "add_seconds")?;
+        let timestamp = days
+            .checked_mul(SECONDS_PER_DAY)
+            .and_then(|days| now.checked_add(days))
+            .and_then(|now| now.checked_add(seconds))
+            .ok_or_else(|| {
+                HelperError::new(format!(
+                    "Helper '{}' offset is out of range",
+                    ctx.name()
+                ))
+            })?;
+
+        let pattern = match ctx.param("format") {
+            Some(_) => {
+                ctx.try_param("format", &[Type::String])?.as_str().unwrap()
+            }
+            None => "%Y-%m-%dT%H:%M:%SZ",
+        };
+        Ok(Some(Value::String(format_time(ctx, timestamp, pattern)?)))
+    }
+}
+
+fn offset(ctx: &Context<'_>, name: &str) -> Result<i64, HelperError> {
+    match ctx.param(name) {
+        Some(_) => {
+            ctx.try_param(name, &[Type::Number])?
+                .as_i64()
+                .ok_or_else(|| {
+                    HelperError::new(format!(
+                        "Helper '{}' expects an integer for '{}'",
+                        ctx.name(),
+                        name
+                    ))
+                })
+        }
+        None => Ok(0),
+    }
+}
+
+/// Convert days since the Unix epoch to a year, month and day.
+fn civil(days: i64) -> (i64, i64, i64) {
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z.rem_euclid(146_097);
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+    (year, month, day)
+}
+
+fn format_time(
+    ctx: &Context<'_>,
+    timestamp: i64,
+    pattern: &str,
+) -> Result<String, HelperError> {
+    let (year, month, day) = civil(timestamp.div_euclid(SECONDS_PER_DAY));
+    let seconds = timestamp.rem_euclid(SECONDS_PER_DAY);
+    let mut out = String::with_capacity(pattern.len() + 16);
+    let mut chars = pattern.chars();
+    while let Some(c) = chars.next() {
+        if c != '%' {
+            out.push(c);
+            continue;
+        }
+        let _ = match chars.next() {
+            Some('Y') => write!(out, "{:04}", year),
+            Some('m') => write!(out, "{:02}", month),
+            Some('d') => write!(out, "{:02}", day),
+            Some('H') => write!(out, "{:02}", seconds / 3600),
+            Some('M') => write!(out, "{:02}", seconds % 3600 / 60),
+            Some('S') => write!(out, "{:02}", seconds % 60),
+            Some('s') => write!(out, "{}", timestamp),
+            Some('%') => write!(out, "%"),
+            Some(c) => {
+                return Err(HelperError::new(format!(
+                    "Helper '{}' got unknown format specifier '%{}'",
+                    ctx.name(),
+                    c
+                )))
+            }
+            None => {
+                return Err(HelperError::new(format!(
+                    "Helper '{}' got a format that ends with '%'",
+                    ctx.name()
+                )))
+            }
+        };
+    }
+    Ok(out)
+}
@@ -0,0 +1,41 @@
+//! Block helper that renders content at most once per render.
+use crate::{
+    helper::{Helper, HelperValue},
+    parser::ast::Node,
+    render::{Context, Render, Type},
+};
+
+/// Render a block at most once per render.
+///
+/// Accepts a single argument which is the key for the block; the
+/// first block rendered with a key is written and later blocks with
+/// the same key are skipped so components that are used many times
+/// can declare the scripts and styles they need:
+///
+/// ```ignore
+/// {{#once "datepicker"}}
+///   <script src="/js/datepicker.js"></script>
+/// {{/once}}
+/// ```
+///
+/// When a block is skipped the `else` block is rendered if present.
+pub struct Once;
+
+impl Helper for Once {
+    fn call<'render, 'call>(
+        &self,
+        rc: &mut Render<'render>,
+        ctx: &Context<'call>,
+        template: Option<&'render Node<'render>>,
+    ) -> HelperValue {
+        ctx.arity(1..1)?;
+        let template = ctx.assert_block(template)?;
+        let key = ctx.try_get(0, &[Type::String])?.as_str().unwrap();
+        if rc.insert_once(key) {
+            rc.template(template)?;
+        } else if let Some(node) = rc.inverse(template)? {
+            rc.template(node)?;
+        }
+        Ok(None)
+    }
+}
@@ -0,0 +1,203 @@
+//! Block helper that computes pagination values.
+use crate::{
+    error::HelperError,
+    helper::{Helper, HelperResult, HelperValue},
+    parser::ast::Node,
+    render::{Context, Render, Scope, Type},
+};
+use alloc::{format, string::ToString, vec::Vec};
+
+use serde_json::{Number, Value};
+
+const WINDOW: u64 = 5;
+
+/// Maximum number of page numbers in the window.
+pub const MAX_WINDOW: u64 = 1_000;
+
+/// Computed values for a page of a list.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Pagination {
+    /// Total number of items.
+    pub total: u64,
+    /// Number of items per page.
+    pub size: u64,
+    /// Current page number, one-based.
+    pub page: u64,
+    /// Total number of pages, always at least one.
+    pub total_pages: u64,
+    /// Zero-based offset of the first item on the current page.
+    pub offset: u64,
+    /// Window of page numbers around the current page.
+    pub pages: Vec<u64>,
+}
+
+impl Pagination {
+    /// Compute the pagination for a total number of items, page size
+    /// and current page.
+    ///
+    /// The current page is clamped so that it is always a valid page
+    /// and `window` is the maximum number of page numbers to include
+    /// in `pages` which is never more than [MAX_WINDOW](MAX_WINDOW).
+    ///
+    /// Returns `None` when the page size is zero.
+    pub fn new(total: u64, size: u64, page: u64, window: u64) -> Option<Self> {
+        if size == 0 {
+            return None;
+        }
+
+        let total_pages = core::cmp::max(1, total.div_ceil(size));
+        let page = page.clamp(1, total_pages);
+        let offset = (page - 1) * size;
+
+        let window = window.clamp(1, total_pages.min(MAX_WINDOW));
+        let start = page.saturating_sub(window / 2).max(1);
+        let end = core::cmp::min(total_pages, start.saturating_add(window - 1));
+        let start = end.saturating_sub(window - 1).max(1);
+        let pages = (start..=end).collect();
+
+        Some(Self {
+            total,
+            size,
+            page,
+            total_pages,
+            offset,
+            pages,
+        })
+    }
+
+    /// Whether there is a page before the current page.
+    pub fn has_prev(&self) -> bool {
+        self.page > 1
+    }
+
+    /// Whether there is a page after the current page.
+    pub fn has_next(&self) -> bool {
+        self.page < self.total_pages
+    }
+
+    /// Assign the computed values as local variables of a scope.
+    pub fn set_locals(&self, scope: &mut Scope) {
+        let number = |n: u64| Value::Number(Number::from(n));
+        let page_or_null = |cond: bool, n: u64| {
+            if cond {
+                number(n)
+            } else {
+                Value::Null
+            }
+        };
+
+        scope.set_local("total", number(self.total));
+        scope.set_local("size", number(self.size));
+        scope.set_local("page", number(self.page));
+        scope.set_local("totalPages", number(self.total_pages));
+        scope.set_local("offset", number(self.offset));
+        scope.set_local("hasPrev", Value::Bool(self.has_prev()));
+        scope.set_local("hasNext", Value::Bool(self.has_next()));
+        scope.set_local(
+            "prev",
+            page_or_null(self.has_prev(), self.page.saturating_sub(1)),
+        );
+        scope.set_local(
+            "next",
+            page_or_null(self.has_next(), self.page.saturating_add(1)),
+        );
+        scope.set_local("firstPage", number(1));
+        scope.set_local("lastPage", number(self.total_pages));
+        scope.set_local(
+            "pages",
+            Value::Array(self.pages.iter().map(|n| number(*n)).collect()),
+        );
+    }
+}
+
+/// Compute pagination values for the inner block.
+///
+/// Accepts the total number of items and the page size followed
+/// by an optional current page number which defaults to one:
+///
+/// ```ignore
+/// {{#paginate total 10 page}}
+///   {{#if @hasPrev}}<a href="?page={{@prev}}">Previous</a>{{/if}}
+///   {{#each @pages}}{{this}} {{/each}}
+///   {{#if @hasNext}}<a href="?page={{@next}}">Next</a>{{/if}}
+/// {{/paginate}}
+/// ```
+///
+/// The inner block is rendered with the local variables:
+///
+/// * `@total`: Total number of items.
+/// * `@size`: Number of items per page.
+/// * `@page`: Current page number clamped to the valid range.
+/// * `@totalPages`: Total number of pages (at least one).
+/// * `@offset`: Zero-based offset of the first item on the page.
+/// * `@hasPrev`: Whether a previous page exists.
+/// * `@hasNext`: Whether a next page exists.
+/// * `@prev`: Previous page number or `null`.
+/// * `@next`: Next page number or `null`.
+/// * `@firstPage`: The first page number.
+/// * `@lastPage`: The last page number.
+/// * `@pages`: Window of page numbers around the current page.
+///
+/// The `window` hash parameter sets the maximum number of page
+/// numbers in `@pages`, the default is five; it is an error if the
+/// window exceeds [MAX_WINDOW](MAX_WINDOW).
+///
+/// The `this` value for the inner template is not changed.
+pub struct Paginate;
+
+impl Helper for Paginate {
+    fn call<'render, 'call>(
+        &self,
+        rc: &mut Render<'render>,
+        ctx: &Context<'call>,
+        template: Option<&'render Node<'render>>,
+    ) -> HelperValue {
+        ctx.arity(2..3)?;
+
+        let template = ctx.assert_block(template)?;
+        let total = unsigned(ctx, ctx.try_get(0, &[Type::Number])?)?;
+        let size = unsigned(ctx, ctx.try_get(1, &[Type::Number])?)?;
+        let page = if let Some(value) = ctx.get(2) {
+            unsigned(ctx, ctx.try_value(value, &[Type::Number])?)?
+        } else {
+            1
+        };
+        let window = if let Some(value) = ctx.param("window") {
+            unsigned(ctx, ctx.try_value(value, &[Type::Number])?)?
+        } else {
+            WINDOW
+        };
+        if window > MAX_WINDOW {
+            return Err(HelperError::new(format!(
+                "Helper '{}' window exceeds {} pages",
+                ctx.name(),
+                MAX_WINDOW
+            )));
+        }
+
+        let pagination = Pagination::new(total, size, page, window)
+            .ok_or_else(|| {
+                HelperError::InvalidNumericalOperand(ctx.name().to_string())
+            })?;
+
+        let this = rc.evaluate("this")?.cloned();
+        let mut scope = Scope::new();
+        pagination.set_locals(&mut scope);
+        if let Some(this) = this {
+            scope.set_base_value(this);
+        }
+
+        rc.push_scope(scope);
+        let result = rc.template(template);
+        rc.pop_scope();
+        result?;
+
+        Ok(None)
+    }
+}
+
+fn unsigned(ctx: &Context<'_>, value: &Value) -> HelperResult<u64> {
+    value.as_u64().ok_or_else(|| {
+        HelperError::InvalidNumericalOperand(ctx.name().to_string())
+    })
+}
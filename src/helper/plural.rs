@@ -0,0 +1,160 @@
+//! Helper that chooses the plural form of a word.
+use crate::{
+    error::HelperError,
+    helper::{Helper, HelperValue},
+    parser::ast::Node,
+    render::{Context, Render, Type},
+};
+use alloc::{format, string::String};
+
+use serde_json::Value;
+
+/// Choose the singular or plural form of a word for a count.
+///
+/// Accepts the count, the singular form and optionally the plural
+/// form which defaults to the singular form followed by `s`; the
+/// singular form is used when the count is `1`:
+///
+/// ```ignore
+/// {{count}} {{plural count "item" "items"}}
+/// ```
+///
+/// When the `i18n` feature is enabled the `locale` hash parameter
+/// selects the [CLDR plural rules](https://cldr.unicode.org/index/cldr-spec/plural-rules)
+/// for a language and the forms are given using the `zero`, `one`,
+/// `two`, `few`, `many` and `other` hash parameters; a category
+/// without a form uses the `other` form:
+///
+/// ```ignore
+/// {{count}} {{plural count locale="ru" one="файл" few="файла" many="файлов" other="файла"}}
+/// ```
+///
+/// Only integer counts are supported by the locale rules; languages
+/// without built-in rules use the English rules.
+pub struct Plural;
+
+impl Helper for Plural {
+    fn call<'render, 'call>(
+        &self,
+        _rc: &mut Render<'render>,
+        ctx: &Context<'call>,
+        _template: Option<&'render Node<'render>>,
+    ) -> HelperValue {
+        let count = ctx.try_get(0, &[Type::Number])?.as_f64().unwrap_or(0.0);
+
+        if ctx.param("locale").is_some() {
+            ctx.arity(1..1)?;
+            #[cfg(feature = "i18n")]
+            return locale(ctx, count);
+            #[cfg(not(feature = "i18n"))]
+            return Err(HelperError::new(format!(
+                "Helper '{}' requires the i18n feature for locale rules",
+                ctx.name()
+            )));
+        }
+
+        ctx.arity(2..3)?;
+        let singular = ctx.try_get(1, &[Type::String])?.as_str().unwrap();
+        let word = if count == 1.0 {
+            String::from(singular)
+        } else {
+            match ctx.get(2) {
+                Some(_) => String::from(
+                    ctx.try_get(2, &[Type::String])?.as_str().unwrap(),
+                ),
+                None => format!("{}s", singular),
+            }
+        };
+        Ok(Some(Value::String(word)))
+    }
+}
+
+/// Choose the form for a count using the rules for a locale.
+#[cfg(feature = "i18n")]
+fn locale(ctx: &Context<'_>, count: f64) -> HelperValue {
+    let locale = ctx.try_param("locale", &[Type::String])?.as_str().unwrap();
+    if count.fract() != 0.0 || count.abs() > u64::MAX as f64 {
+        return Err(HelperError::new(format!(
+            "Helper '{}' locale rules require an integer count",
+            ctx.name()
+        )));
+    }
+    let category = category(locale, count.abs() as u64);
+    let form = ctx
+        .param(category)
+        .or_else(|| ctx.param("other"))
+        .ok_or_else(|| {
+            HelperError::new(format!(
+                "Helper '{}' requires the '{}' or 'other' form",
+                ctx.name(),
+                category
+            ))
+        })?;
+    ctx.assert(form, &[Type::String])?;
+    Ok(Some(form.clone()))
+}
+
+/// Get the CLDR plural category of an integer for a locale.
+///
+/// The language is the part of the locale before a hyphen or
+/// underscore, for example `pt` for `pt-BR`.
+#[cfg(feature = "i18n")]
+pub fn category(locale: &str, n: u64) -> &'static str {
+    let language = locale.split(['-', '_']).next().unwrap_or("");
+    let (n10, n100) = (n % 10, n % 100);
+    match language {
+        "ja" | "zh" | "ko" | "vi" | "th" | "id" | "ms" | "tr" => "other",
+        "fr" | "hi" | "bn" => {
+            if n <= 1 {
+                "one"
+            } else {
+                "other"
+            }
+        }
+        "pt" if locale != "pt-PT" && locale != "pt_PT" => {
+            if n <= 1 {
+                "one"
+            } else {
+                "other"
+            }
+        }
+        "ru" | "uk" | "be" => {
+            if n10 == 1 && n100 != 11 {
+                "one"
+            } else if (2..=4).contains(&n10) && !(12..=14).contains(&n100) {
+                "few"
+            } else {
+                "many"
+            }
+        }
+        "pl" => {
+            if n == 1 {
+                "one"
+            } else if (2..=4).contains(&n10) && !(12..=14).contains(&n100) {
+                "few"
+            } else {
+                "many"
+            }
+        }
+        "cs" | "sk" => match n {
+            1 => "one",
+            2..=4 => "few",
+            _ => "other",
+        },
+        "ar" => match n {
+            0 => "zero",
+            1 => "one",
+            2 => "two",
+            _ if (3..=10).contains(&n100) => "few",
+            _ if (11..=99).contains(&n100) => "many",
+            _ => "other",
+        },
+        _ => {
+            if n == 1 {
+                "one"
+            } else {
+                "other"
+            }
+        }
+    }
+}
@@ -0,0 +1,126 @@
+//! Helpers that generate random values.
+//!
+//! Values are drawn from the [generator](crate::Registry#method.rng)
+//! assigned to the registry; see the [random](crate::random) module
+//! to make renders deterministic.
+use crate::{
+    error::HelperError,
+    helper::{Helper, HelperValue},
+    parser::ast::Node,
+    render::{Context, Render, Type},
+};
+use alloc::format;
+
+use serde_json::{Number, Value};
+
+fn next_u64(rc: &Render<'_>, ctx: &Context<'_>) -> Result<u64, HelperError> {
+    let next = match rc.registry().rng() {
+        Some(rng) => Some(rng.next_u64()),
+        #[cfg(feature = "std")]
+        None => Some(crate::random::entropy()),
+        #[cfg(not(feature = "std"))]
+        None => None,
+    };
+    next.ok_or_else(|| {
+        HelperError::new(format!(
+            "Helper '{}' requires a random number generator",
+            ctx.name()
+        ))
+    })
+}
+
+/// Generate a version 4 UUID.
+///
+/// Accepts no arguments and returns a lowercase hyphenated UUID
+/// such as `4b5ec3a2-6f1d-4c39-9a0e-8e2f5c7d1b64`.
+pub struct Uuid;
+
+impl Helper for Uuid {
+    fn call<'render, 'call>(
+        &self,
+        rc: &mut Render<'render>,
+        ctx: &Context<'call>,
+        _template: Option<&'render Node<'render>>,
+    ) -> HelperValue {
+        ctx.arity(0..0)?;
+
+        let high = next_u64(rc, ctx)?;
+        let low = next_u64(rc, ctx)?;
+        let high = (high & !0xf000) | 0x4000;
+        let low = (low & !(0xc0 << 56)) | (0x80 << 56);
+        let uuid = format!(
+            "{:08x}-{:04x}-{:04x}-{:04x}-{:012x}",
+            high >> 32,
+            (high >> 16) & 0xffff,
+            high & 0xffff,
+            low >> 48,
+            low & 0xffff_ffff_ffff
+        );
+        Ok(Some(Value::String(uuid)))
+    }
+}
+
+/// Generate a random number or choose a random element.
+///
+/// Without arguments returns a float in the range `0..1`; given two
+/// integers returns an integer between them inclusive and given an
+/// array returns a random element:
+///
+/// ```ignore
+/// {{random}} {{random 1 6}} {{random colors}}
+/// ```
+///
+/// An empty array returns `null`.
+pub struct Random;
+
+impl Helper for Random {
+    fn call<'render, 'call>(
+        &self,
+        rc: &mut Render<'render>,
+        ctx: &Context<'call>,
+        _template: Option<&'render Node<'render>>,
+    ) -> HelperValue {
+        ctx.arity(0..2)?;
+
+        match ctx.arguments().len() {
+            0 => {
+                let next = next_u64(rc, ctx)?;
+                let float = (next >> 11) as f64 / (1u64 << 53) as f64;
+                Ok(Number::from_f64(float).map(Value::Number))
+            }
+            1 => {
+                let list = ctx.try_get(0, &[Type::Array])?.as_array().unwrap();
+                if list.is_empty() {
+                    return Ok(Some(Value::Null));
+                }
+                let index = next_u64(rc, ctx)? % list.len() as u64;
+                Ok(Some(list[index as usize].clone()))
+            }
+            _ => {
+                let min = integer(ctx, 0)?;
+                let max = integer(ctx, 1)?;
+                if min > max {
+                    return Err(HelperError::new(format!(
+                        "Helper '{}' got a minimum greater than the maximum",
+                        ctx.name()
+                    )));
+                }
+                let span = (max as i128 - min as i128) as u128 + 1;
+                let offset = next_u64(rc, ctx)? as u128 % span;
+                let value = (min as i128 + offset as i128) as i64;
+                Ok(Some(Value::Number(Number::from(value))))
+            }
+        }
+    }
+}
+
+fn integer(ctx: &Context<'_>, index: usize) -> Result<i64, HelperError> {
+    ctx.try_get(index, &[Type::Number])?
+        .as_i64()
+        .ok_or_else(|| {
+            HelperError::new(format!(
+                "Helper '{}' expects integer arguments for a range",
+                ctx.name()
+            ))
+        })
+}
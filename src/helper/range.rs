@@ -0,0 +1,82 @@
+//! Helper that generates a sequence of numbers.
+use crate::{
+    error::HelperError,
+    helper::{Helper, HelperValue},
+    parser::ast::Node,
+    render::{Context, Render, Type},
+};
+use alloc::{format, vec::Vec};
+
+use serde_json::{Number, Value};
+
+/// Maximum number of elements in a range.
+pub const MAX_LENGTH: usize = 10_000;
+
+/// Generate an array of integers.
+///
+/// Accepts the start and end of the range which are both inclusive
+/// and returns an array so it is usually called as a sub-expression:
+///
+/// ```ignore
+/// {{#each (range 1 pages)}}<a href="?page={{this}}">{{this}}</a>{{/each}}
+/// ```
+///
+/// The optional `step` hash parameter is the increment between
+/// numbers (default `1`); when the end is less than the start the
+/// numbers count down. A range is limited to 10,000 numbers.
+pub struct Range;
+
+impl Helper for Range {
+    fn call<'render, 'call>(
+        &self,
+        _rc: &mut Render<'render>,
+        ctx: &Context<'call>,
+        _template: Option<&'render Node<'render>>,
+    ) -> HelperValue {
+        ctx.arity(2..2)?;
+        let integer = |value: &Value| {
+            value.as_i64().ok_or_else(|| {
+                HelperError::new(format!(
+                    "Helper '{}' expects integer arguments",
+                    ctx.name()
+                ))
+            })
+        };
+        let start = integer(ctx.try_get(0, &[Type::Number])?)?;
+        let end = integer(ctx.try_get(1, &[Type::Number])?)?;
+        let step = match ctx.param("step") {
+            Some(_) => integer(ctx.try_param("step", &[Type::Number])?)?,
+            None => 1,
+        };
+        if step == 0 {
+            return Err(HelperError::new(format!(
+                "Helper '{}' step must not be zero",
+                ctx.name()
+            )));
+        }
+
+        let (step, descending) = (step.unsigned_abs(), end < start);
+        let span = (end as i128 - start as i128).unsigned_abs();
+        let len = span / step as u128 + 1;
+        if len > MAX_LENGTH as u128 {
+            return Err(HelperError::new(format!(
+                "Helper '{}' range exceeds {} numbers",
+                ctx.name(),
+                MAX_LENGTH
+            )));
+        }
+
+        let numbers: Vec<Value> = (0..len as i128)
+            .map(|i| {
+                let offset = i * step as i128;
+                let n = if descending {
+                    start as i128 - offset
+                } else {
+                    start as i128 + offset
+                };
+                Value::Number(Number::from(n as i64))
+            })
+            .collect();
+        Ok(Some(Value::Array(numbers)))
+    }
+}
@@ -0,0 +1,83 @@
+//! Helper to list the registered helpers.
+use crate::{
+    helper::{Helper, HelperValue},
+    parser::ast::Node,
+    render::{Context, Render},
+};
+use alloc::vec::Vec;
+
+use serde_json::{Map, Value};
+
+/// List the helpers that templates may call.
+///
+/// Accepts no arguments and returns an array of objects sorted by
+/// name, one for each helper and alias in the registry and any
+/// parent registry:
+///
+/// * `name`: Name used to call the helper.
+/// * `alias`: Name of the aliased helper or `null`.
+/// * `deprecated`: Deprecation message or `null`.
+/// * `description`: Description of the helper or `null`.
+/// * `ambient`: Whether the helper uses ambient authority.
+///
+/// Use it to render a reference page for the registered helpers:
+///
+/// ```ignore
+/// {{#each (helpers)}}
+///   <dt>{{name}}</dt><dd>{{description}}</dd>
+/// {{/each}}
+/// ```
+///
+/// This helper is disabled when the registry is sandboxed.
+pub struct Helpers;
+
+impl Helper for Helpers {
+    fn call<'render, 'call>(
+        &self,
+        rc: &mut Render<'render>,
+        ctx: &Context<'call>,
+        _template: Option<&'render Node<'render>>,
+    ) -> HelperValue {
+        ctx.arity(0..0)?;
+
+        let registry = rc.registry();
+        let list: Vec<Value> = registry
+            .helper_names()
+            .into_iter()
+            .map(|name| {
+                let helper = registry.helper(name);
+                let optional = |value: Option<&str>| {
+                    value.map(|s| Value::String(s.into())).unwrap_or_default()
+                };
+                let mut item = Map::new();
+                item.insert("name".into(), Value::String(name.into()));
+                item.insert(
+                    "alias".into(),
+                    optional(registry.helper_alias(name)),
+                );
+                item.insert(
+                    "deprecated".into(),
+                    optional(registry.helper_deprecation(name)),
+                );
+                item.insert(
+                    "description".into(),
+                    optional(helper.and_then(|h| h.description())),
+                );
+                item.insert(
+                    "ambient".into(),
+                    Value::Bool(helper.map(|h| h.ambient()).unwrap_or(false)),
+                );
+                Value::Object(item)
+            })
+            .collect();
+        Ok(Some(Value::Array(list)))
+    }
+
+    fn ambient(&self) -> bool {
+        true
+    }
+
+    fn description(&self) -> Option<&str> {
+        Some("List the registered helpers.")
+    }
+}
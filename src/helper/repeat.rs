@@ -0,0 +1,126 @@
+//! Block helper that renders a template a fixed number of times.
+use crate::{
+    error::HelperError,
+    helper::{Helper, HelperResult, HelperValue},
+    parser::ast::Node,
+    render::{Context, Render, Scope, Type},
+};
+use alloc::{format, string::ToString};
+
+use serde_json::{Number, Value};
+
+const FIRST: &str = "first";
+const LAST: &str = "last";
+const INDEX: &str = "index";
+
+/// Maximum number of iterations.
+pub const MAX_COUNT: i64 = 10_000;
+
+/// Render the inner template a number of times.
+///
+/// Accepts a single argument of the number of iterations which must
+/// be a positive integer no greater than [MAX_COUNT].
+///
+/// Each iteration sets a new scope with the local variables:
+///
+/// * `@first`: If this is the first iteration `true`.
+/// * `@last`: If this is the last iteration `true`.
+/// * `@index`: The value for the current iteration.
+///
+/// The `@index` variable starts at zero and is incremented by one
+/// for each iteration; use the `start` and `step` hash parameters
+/// to change this behaviour:
+///
+/// ```ignore
+/// {{#repeat 3 start=10 step=-5}}{{@index}} {{/repeat}}
+/// ```
+///
+/// The `this` value for the inner template is not changed.
+pub struct Repeat;
+
+impl Helper for Repeat {
+    fn call<'render, 'call>(
+        &self,
+        rc: &mut Render<'render>,
+        ctx: &Context<'call>,
+        template: Option<&'render Node<'render>>,
+    ) -> HelperValue {
+        ctx.arity(1..1)?;
+
+        let template = ctx.assert_block(template)?;
+        let count = integer(ctx, ctx.try_get(0, &[Type::Number])?)?;
+        if count < 0 {
+            return Err(HelperError::InvalidNumericalOperand(
+                ctx.name().to_string(),
+            ));
+        }
+        if count > MAX_COUNT {
+            return Err(HelperError::new(format!(
+                "Helper '{}' count exceeds {} iterations",
+                ctx.name(),
+                MAX_COUNT
+            )));
+        }
+
+        let start = if let Some(value) = ctx.param("start") {
+            integer(ctx, ctx.try_value(value, &[Type::Number])?)?
+        } else {
+            0
+        };
+        let step = if let Some(value) = ctx.param("step") {
+            integer(ctx, ctx.try_value(value, &[Type::Number])?)?
+        } else {
+            1
+        };
+
+        // Check the last index so that an overflow is an error
+        // before anything is rendered
+        if count > 0 {
+            position(ctx, start, step, count - 1)?;
+        }
+
+        let this = rc.evaluate("this")?.cloned();
+
+        rc.push_scope(Scope::new());
+        for index in 0..count {
+            if let Some(ref mut scope) = rc.scope_mut() {
+                scope.set_local(FIRST, Value::Bool(index == 0));
+                scope.set_local(LAST, Value::Bool(index == count - 1));
+                let value = position(ctx, start, step, index)?;
+                scope.set_local(INDEX, Value::Number(Number::from(value)));
+                if let Some(ref this) = this {
+                    scope.set_base_value(this.clone());
+                }
+            }
+            rc.template(template)?;
+        }
+        rc.pop_scope();
+
+        Ok(None)
+    }
+}
+
+fn integer(ctx: &Context<'_>, value: &Value) -> HelperResult<i64> {
+    value.as_i64().ok_or_else(|| {
+        HelperError::InvalidNumericalOperand(ctx.name().to_string())
+    })
+}
+
+/// Compute the value of `@index` for an iteration.
+fn position(
+    ctx: &Context<'_>,
+    start: i64,
+    step: i64,
+    index: i64,
+) -> HelperResult<i64> {
+    index
+        .checked_mul(step)
+        .and_then(|n| n.checked_add(start))
+        .ok_or_else(|| {
+            HelperError::new(format!(
+                "Helper '{}' index overflows for step {}",
+                ctx.name(),
+                step
+            ))
+        })
+}
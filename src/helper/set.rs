@@ -0,0 +1,71 @@
+//! Helper to assign variables.
+use alloc::vec::Vec;
+
+use crate::{
+    helper::{Helper, HelperValue},
+    parser::ast::Node,
+    render::{Context, Render, Scope},
+};
+
+/// Assign variables from the hash parameters.
+///
+/// When called as a statement the variables are assigned to the
+/// current scope so they are visible to the rest of the block and
+/// any nested blocks:
+///
+/// ```ignore
+/// {{set total=(add price tax)}}{{total}}
+/// ```
+///
+/// When called as a block the variables are only visible to the
+/// inner template:
+///
+/// ```ignore
+/// {{#set name="world"}}Hello {{name}}{{/set}}
+/// ```
+pub struct Set;
+
+impl Helper for Set {
+    fn call<'render, 'call>(
+        &self,
+        rc: &mut Render<'render>,
+        ctx: &Context<'call>,
+        template: Option<&'render Node<'render>>,
+    ) -> HelperValue {
+        ctx.arity(0..0)?;
+
+        // At the top level create a scope for the root data so
+        // that parent references still resolve to the root
+        let top_level = rc.scope_mut().is_none();
+        if top_level {
+            let mut scope = Scope::new();
+            scope.set_base_value(rc.data().clone());
+            rc.push_scope(scope);
+        }
+
+        let mut previous = Vec::new();
+        if let Some(scope) = rc.scope_mut() {
+            for (name, value) in ctx.parameters() {
+                previous.push((name, scope.set_variable(name, value.clone())));
+            }
+        }
+
+        if let Some(template) = template {
+            let result = rc.template(template);
+            if top_level {
+                rc.pop_scope();
+            } else if let Some(scope) = rc.scope_mut() {
+                for (name, value) in previous {
+                    if let Some(value) = value {
+                        scope.set_variable(name, value);
+                    } else {
+                        scope.remove_variable(name);
+                    }
+                }
+            }
+            result?;
+        }
+
+        Ok(None)
+    }
+}
@@ -0,0 +1,158 @@
+//! Helpers that quote SQL literals and identifiers.
+//!
+//! These helpers are a last resort for templated migrations and
+//! report queries; prefer bound parameters whenever the database
+//! driver supports them.
+use crate::{
+    error::HelperError,
+    helper::{Helper, HelperValue},
+    parser::ast::Node,
+    render::{Context, Render, Type},
+};
+use alloc::{format, string::String};
+
+use serde_json::Value;
+
+/// SQL dialects that determine the quoting rules.
+#[derive(Copy, Clone, Debug, Eq, PartialEq)]
+enum Dialect {
+    Postgres,
+    Mysql,
+    Sqlite,
+}
+
+fn dialect(ctx: &Context<'_>) -> Result<Dialect, HelperError> {
+    let name = match ctx.param("dialect") {
+        Some(_) => ctx.try_param("dialect", &[Type::String])?.as_str().unwrap(),
+        None => "postgres",
+    };
+    match name {
+        "postgres" => Ok(Dialect::Postgres),
+        "mysql" => Ok(Dialect::Mysql),
+        "sqlite" => Ok(Dialect::Sqlite),
+        _ => Err(HelperError::new(format!(
+            "Helper '{}' got unknown dialect '{}', expected 'postgres', 'mysql' or 'sqlite'",
+            ctx.name(),
+            name
+        ))),
+    }
+}
+
+fn assert_no_nul(ctx: &Context<'_>, value: &str) -> Result<(), HelperError> {
+    if value.contains('\0') {
+        return Err(HelperError::new(format!(
+            "Helper '{}' cannot quote a value that contains a NUL byte",
+            ctx.name()
+        )));
+    }
+    Ok(())
+}
+
+/// Quote a value as an SQL literal.
+///
+/// Accepts a single argument; strings are quoted, numbers are written
+/// unchanged, booleans are written as `TRUE` or `FALSE` and `null` is
+/// written as `NULL`. The optional hash parameter `dialect` may be
+/// `postgres` (the default), `mysql` or `sqlite`:
+///
+/// ```ignore
+/// INSERT INTO notes (body) VALUES ({{{sql_str body dialect="mysql"}}});
+/// ```
+///
+/// Strings are quoted with single quotes which are doubled; for MySQL
+/// backslashes and control characters are also escaped as backslashes
+/// are escape characters by default. Strings that contain a NUL byte
+/// return an error for PostgreSQL and SQLite; objects and arrays
+/// always return an error.
+pub struct SqlStr;
+
+impl Helper for SqlStr {
+    fn call<'render, 'call>(
+        &self,
+        _rc: &mut Render<'render>,
+        ctx: &Context<'call>,
+        _template: Option<&'render Node<'render>>,
+    ) -> HelperValue {
+        ctx.arity(1..1)?;
+
+        let dialect = dialect(ctx)?;
+        let value = ctx.get(0).unwrap();
+        let literal = match value {
+            Value::Null => String::from("NULL"),
+            Value::Bool(true) => String::from("TRUE"),
+            Value::Bool(false) => String::from("FALSE"),
+            Value::Number(ref n) => n.to_string(),
+            Value::String(ref s) => {
+                let mut quoted = String::with_capacity(s.len() + 2);
+                quoted.push('\'');
+                for c in s.chars() {
+                    match (dialect, c) {
+                        (Dialect::Mysql, '\\') => quoted.push_str("\\\\"),
+                        (Dialect::Mysql, '\0') => quoted.push_str("\\0"),
+                        (Dialect::Mysql, '\n') => quoted.push_str("\\n"),
+                        (Dialect::Mysql, '\r') => quoted.push_str("\\r"),
+                        (Dialect::Mysql, '\u{1a}') => quoted.push_str("\\Z"),
+                        (_, '\0') => assert_no_nul(ctx, s)?,
+                        (_, '\'') => quoted.push_str("''"),
+                        _ => quoted.push(c),
+                    }
+                }
+                quoted.push('\'');
+                quoted
+            }
+            _ => {
+                return Err(HelperError::new(format!(
+                    "Helper '{}' cannot quote an object or array",
+                    ctx.name()
+                )))
+            }
+        };
+        Ok(Some(Value::String(literal)))
+    }
+}
+
+/// Quote a string as an SQL identifier.
+///
+/// Accepts a single string argument which is quoted with double
+/// quotes for PostgreSQL and SQLite or backticks for MySQL; embedded
+/// quote characters are doubled. The optional hash parameter `dialect`
+/// is the same as for [sql_str](SqlStr):
+///
+/// ```ignore
+/// SELECT {{{sql_ident column}}} FROM {{{sql_ident table}}};
+/// ```
+///
+/// Identifiers that are empty or contain a NUL byte return an error.
+pub struct SqlIdent;
+
+impl Helper for SqlIdent {
+    fn call<'render, 'call>(
+        &self,
+        _rc: &mut Render<'render>,
+        ctx: &Context<'call>,
+        _template: Option<&'render Node<'render>>,
+    ) -> HelperValue {
+        ctx.arity(1..1)?;
+
+        let dialect = dialect(ctx)?;
+        let name = ctx.try_get(0, &[Type::String])?.as_str().unwrap();
+        if name.is_empty() {
+            return Err(HelperError::new(format!(
+                "Helper '{}' cannot quote an empty identifier",
+                ctx.name()
+            )));
+        }
+        assert_no_nul(ctx, name)?;
+        let quote = if dialect == Dialect::Mysql { '`' } else { '"' };
+        let mut quoted = String::with_capacity(name.len() + 2);
+        quoted.push(quote);
+        for c in name.chars() {
+            if c == quote {
+                quoted.push(c);
+            }
+            quoted.push(c);
+        }
+        quoted.push(quote);
+        Ok(Some(Value::String(quoted)))
+    }
+}
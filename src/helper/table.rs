@@ -0,0 +1,186 @@
+//! Helper that writes an HTML table.
+use crate::{
+    error::HelperError,
+    helper::{html::push_attribute, Helper, HelperValue},
+    json,
+    parser::ast::Node,
+    render::{Context, Render, Scope, Type},
+};
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use serde_json::{Number, Value};
+
+const INDEX: &str = "index";
+
+/// Column of a table.
+struct Column {
+    key: String,
+    label: String,
+    format: Option<String>,
+}
+
+/// Parse the column specification.
+fn columns(
+    ctx: &Context<'_>,
+    rows: &[Value],
+) -> Result<Vec<Column>, HelperError> {
+    let column =
+        |key: &str, label: Option<&Value>, format: Option<&Value>| Column {
+            key: key.to_string(),
+            label: label.and_then(|l| l.as_str()).unwrap_or(key).to_string(),
+            format: format.and_then(|f| f.as_str()).map(|f| f.to_string()),
+        };
+    let invalid = || {
+        HelperError::new(format!(
+            "Helper '{}' column must be a string or an object with a 'key'",
+            ctx.name()
+        ))
+    };
+    match ctx.param("columns") {
+        Some(Value::Array(ref list)) => list
+            .iter()
+            .map(|value| match value {
+                Value::String(ref key) => Ok(column(key, None, None)),
+                Value::Object(ref map) => {
+                    let key = map
+                        .get("key")
+                        .and_then(|k| k.as_str())
+                        .ok_or_else(invalid)?;
+                    Ok(column(key, map.get("label"), map.get("format")))
+                }
+                _ => Err(invalid()),
+            })
+            .collect(),
+        Some(Value::Object(ref map)) => Ok(map
+            .iter()
+            .map(|(key, label)| column(key, Some(label), None))
+            .collect()),
+        Some(_) => {
+            ctx.try_param("columns", &[Type::Array])?;
+            Ok(Vec::new())
+        }
+        None => Ok(rows
+            .first()
+            .and_then(|row| row.as_object())
+            .map(|row| row.keys().map(|key| column(key, None, None)).collect())
+            .unwrap_or_default()),
+    }
+}
+
+/// Write a table for an array of objects.
+///
+/// Accepts a single argument which is the array of rows; the
+/// `columns` hash parameter is an array of keys, an array of objects
+/// with `key`, `label` and `format` fields or an object that maps keys
+/// to labels. When no columns are given the keys of the first row are
+/// used. Keys may be paths such as `author.name` and the `format` is
+/// the tag of a [formatter](crate::format) for the column. Other hash
+/// parameters are written as attributes of the table:
+///
+/// ```ignore
+/// {{table orders columns=columns class="report"}}
+/// ```
+///
+/// When called as a block the header is written from the columns
+/// and the block is rendered for each row with the row as the scope
+/// to write the cells, so columns can use sub-expressions:
+///
+/// ```ignore
+/// {{#table orders columns=columns}}
+///   <td>{{id}}</td><td>{{format total "currency"}}</td>
+/// {{/table}}
+/// ```
+///
+/// Inside the block the `@index` variable is the zero-based index of
+/// the row. Labels and cells are escaped.
+pub struct Table;
+
+impl Helper for Table {
+    fn call<'render, 'call>(
+        &self,
+        rc: &mut Render<'render>,
+        ctx: &Context<'call>,
+        template: Option<&'render Node<'render>>,
+    ) -> HelperValue {
+        ctx.arity(1..1)?;
+        let rows = ctx.try_get(0, &[Type::Array])?.as_array().unwrap();
+        let columns = columns(ctx, rows)?;
+
+        let mut open = String::from("<table");
+        for (name, value) in ctx.parameters() {
+            if name != "columns" {
+                push_attribute(rc, &mut open, name, value);
+            }
+        }
+        open.push_str("><thead><tr>");
+        rc.write(&open)?;
+        for column in columns.iter() {
+            rc.write("<th>")?;
+            rc.write_escaped(&column.label)?;
+            rc.write("</th>")?;
+        }
+        rc.write("</tr></thead><tbody>")?;
+
+        if let Some(template) = template {
+            rc.push_scope(Scope::new());
+            for (index, row) in rows.iter().enumerate() {
+                if let Some(ref mut scope) = rc.scope_mut() {
+                    scope.set_local(INDEX, Value::Number(Number::from(index)));
+                    scope.set_base_value(row.clone());
+                }
+                rc.write("<tr>")?;
+                rc.template(template)?;
+                rc.write("</tr>")?;
+            }
+            rc.pop_scope();
+        } else {
+            for row in rows {
+                rc.write("<tr>")?;
+                for column in columns.iter() {
+                    let cell = cell(rc, row, column)?;
+                    rc.write("<td>")?;
+                    rc.write_escaped(&cell)?;
+                    rc.write("</td>")?;
+                }
+                rc.write("</tr>")?;
+            }
+        }
+
+        rc.write("</tbody></table>")?;
+        Ok(None)
+    }
+}
+
+/// Format the value of a cell.
+fn cell(
+    rc: &Render<'_>,
+    row: &Value,
+    column: &Column,
+) -> Result<String, HelperError> {
+    let value = match json::find_parts(
+        column.key.split('.'),
+        row,
+        rc.registry().numeric_keys(),
+    ) {
+        Some(value) => value,
+        None => return Ok(String::new()),
+    };
+    let registry = rc.registry();
+    if let Some(ref tag) = column.format {
+        let formatter = registry.tag_formatter(tag).ok_or_else(|| {
+            HelperError::new(format!("Formatter for tag '{}' not found", tag))
+        })?;
+        if let Some(result) = formatter.display(value) {
+            return Ok(result);
+        }
+    }
+    Ok(match value {
+        Value::Null => String::new(),
+        Value::String(ref s) => s.clone(),
+        _ => registry.format(value),
+    })
+}
@@ -0,0 +1,338 @@
+//! Helper that describes a time relative to the current time.
+use crate::{
+    clock,
+    error::HelperError,
+    helper::{Helper, HelperValue},
+    parser::ast::Node,
+    render::{Context, Render, Type},
+};
+use alloc::{boxed::Box, format, string::String};
+
+use serde_json::Value;
+
+const MINUTE: u64 = 60;
+const HOUR: u64 = 3_600;
+const DAY: u64 = 86_400;
+const MONTH: u64 = DAY * 30;
+const YEAR: u64 = DAY * 365;
+
+/// Unit of a relative time.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum Unit {
+    /// Less than the `now` threshold from the current time.
+    Now,
+    /// Minutes.
+    Minute,
+    /// Hours.
+    Hour,
+    /// Days.
+    Day,
+    /// Months of thirty days.
+    Month,
+    /// Years of 365 days.
+    Year,
+}
+
+/// Trait for types that phrase a relative time.
+///
+/// The count is always positive and `future` is `true` when the
+/// time is after the current time.
+pub trait Locale: Send + Sync {
+    /// Phrase a relative time.
+    fn phrase(&self, unit: Unit, count: u64, future: bool) -> String;
+}
+
+impl<F> Locale for F
+where
+    F: Fn(Unit, u64, bool) -> String + Send + Sync,
+{
+    fn phrase(&self, unit: Unit, count: u64, future: bool) -> String {
+        (self)(unit, count, future)
+    }
+}
+
+/// Locale that phrases relative times in English.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct English;
+
+impl Locale for English {
+    fn phrase(&self, unit: Unit, count: u64, future: bool) -> String {
+        let name = match unit {
+            Unit::Now => return String::from("just now"),
+            Unit::Minute => "minute",
+            Unit::Hour => "hour",
+            Unit::Day => "day",
+            Unit::Month => "month",
+            Unit::Year => "year",
+        };
+        let plural = if count == 1 { "" } else { "s" };
+        if future {
+            format!("in {} {}{}", count, name, plural)
+        } else {
+            format!("{} {}{} ago", count, name, plural)
+        }
+    }
+}
+
+/// Thresholds that select the unit of a relative time.
+///
+/// Each threshold is the number of the smaller unit below which the
+/// smaller unit is used, for example with the default `hours` of `22`
+/// a difference of 21 hours is `21 hours ago` and a difference of
+/// 22 hours is `1 day ago`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct Thresholds {
+    /// Seconds below which the time is now.
+    pub now: u64,
+    /// Minutes below which minutes are used.
+    pub minutes: u64,
+    /// Hours below which hours are used.
+    pub hours: u64,
+    /// Days below which days are used.
+    pub days: u64,
+    /// Months below which months are used.
+    pub months: u64,
+}
+
+impl Default for Thresholds {
+    fn default() -> Self {
+        Self {
+            now: 45,
+            minutes: 45,
+            hours: 22,
+            days: 26,
+            months: 11,
+        }
+    }
+}
+
+/// Describe a time relative to the current time.
+///
+/// Accepts a single argument which is the number of seconds since
+/// the Unix epoch or an RFC 3339 string such as `2024-03-01T09:30:00Z`;
+/// the current time is read from the
+/// [clock](crate::Registry#method.clock) for the registry:
+///
+/// ```ignore
+/// Updated {{timeago updatedAt}}
+/// ```
+///
+/// Renders `3 hours ago`, `in 2 days` or `just now`. The counts are
+/// rounded and the unit is chosen using the [Thresholds](Thresholds)
+/// which may be changed for a call with the `now`, `minutes`, `hours`,
+/// `days` and `months` hash parameters.
+///
+/// A sandboxed registry without a clock returns an error rather
+/// than reading the system time.
+///
+/// To phrase times in another language replace the helper with
+/// one that uses a different [Locale](Locale):
+///
+/// ```ignore
+/// registry.helpers_mut().insert(
+///     "timeago",
+///     Box::new(Timeago::with_locale(Box::new(German))),
+/// );
+/// ```
+pub struct Timeago {
+    locale: Box<dyn Locale>,
+    thresholds: Thresholds,
+}
+
+impl Timeago {
+    /// Create a helper that phrases times in English.
+    pub fn new() -> Self {
+        Self::with_locale(Box::new(English))
+    }
+
+    /// Create a helper that phrases times using a locale.
+    pub fn with_locale(locale: Box<dyn Locale>) -> Self {
+        Self {
+            locale,
+            thresholds: Default::default(),
+        }
+    }
+
+    /// Set the default thresholds.
+    pub fn set_thresholds(&mut self, thresholds: Thresholds) {
+        self.thresholds = thresholds;
+    }
+
+    /// Get the default thresholds.
+    pub fn thresholds(&self) -> &Thresholds {
+        &self.thresholds
+    }
+}
+
+impl Default for Timeago {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Helper for Timeago {
+    fn call<'render, 'call>(
+        &self,
+        rc: &mut Render<'render>,
+        ctx: &Context<'call>,
+        _template: Option<&'render Node<'render>>,
+    ) -> HelperValue {
+        ctx.arity(1..1)?;
+        let target = ctx.get(0).unwrap();
+        let timestamp = match target {
+            Value::Number(ref n) => n.as_i64(),
+            Value::String(ref s) => parse(s),
+            _ => {
+                ctx.assert(target, &[Type::Number])?;
+                None
+            }
+        }
+        .ok_or_else(|| {
+            HelperError::new(format!(
+                "Helper '{}' expects an integer timestamp or an RFC 3339 string",
+                ctx.name()
+            ))
+        })?;
+        let now = clock::now(rc.registry()).ok_or_else(|| {
+            HelperError::new(format!(
+                "Helper '{}' requires a clock",
+                ctx.name()
+            ))
+        })?;
+
+        let mut thresholds = self.thresholds;
+        for (name, field) in [
+            ("now", &mut thresholds.now),
+            ("minutes", &mut thresholds.minutes),
+            ("hours", &mut thresholds.hours),
+            ("days", &mut thresholds.days),
+            ("months", &mut thresholds.months),
+        ] {
+            if ctx.param(name).is_some() {
+                *field = ctx
+                    .try_param(name, &[Type::Number])?
+                    .as_u64()
+                    .ok_or_else(|| {
+                        HelperError::new(format!(
+                            "Helper '{}' expects a positive integer for '{}'",
+                            ctx.name(),
+                            name
+                        ))
+                    })?;
+            }
+        }
+
+        let future = timestamp > now;
+        let delta = timestamp.abs_diff(now);
+        let (unit, count) = relative(&thresholds, delta);
+        Ok(Some(Value::String(self.locale.phrase(unit, count, future))))
+    }
+}
+
+/// Divide rounding halves up.
+fn round(delta: u64, unit: u64) -> u64 {
+    delta / unit + u64::from(delta % unit >= unit - unit / 2)
+}
+
+/// Choose the unit and count for a number of seconds.
+fn relative(thresholds: &Thresholds, delta: u64) -> (Unit, u64) {
+    if delta < thresholds.now {
+        return (Unit::Now, 0);
+    }
+    let minutes = round(delta, MINUTE).max(1);
+    if minutes < thresholds.minutes {
+        return (Unit::Minute, minutes);
+    }
+    let hours = round(delta, HOUR).max(1);
+    if hours < thresholds.hours {
+        return (Unit::Hour, hours);
+    }
+    let days = round(delta, DAY).max(1);
+    if days < thresholds.days {
+        return (Unit::Day, days);
+    }
+    let months = round(delta, MONTH).max(1);
+    if months < thresholds.months {
+        return (Unit::Month, months);
+    }
+    (Unit::Year, round(delta, YEAR).max(1))
+}
+
+/// Convert a year, month and day to days since the Unix epoch.
+fn days(year: i64, month: i64, day: i64) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let yoe = year.rem_euclid(400);
+    let mp = if month > 2 { month - 3 } else { month + 9 };
+    let doy = (153 * mp + 2) / 5 + day - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+/// Parse an RFC 3339 string to seconds since the Unix epoch.
+fn parse(value: &str) -> Option<i64> {
+    let bytes = value.as_bytes();
+    let number = |start: usize, end: usize| -> Option<i64> {
+        let digits = value.get(start..end)?;
+        if digits.bytes().all(|b| b.is_ascii_digit()) {
+            digits.parse().ok()
+        } else {
+            None
+        }
+    };
+    let separator = |index: usize, expected: &[u8]| {
+        bytes
+            .get(index)
+            .map(|b| expected.contains(b))
+            .unwrap_or(false)
+    };
+    if !separator(4, b"-")
+        || !separator(7, b"-")
+        || !separator(10, b"Tt ")
+        || !separator(13, b":")
+        || !separator(16, b":")
+    {
+        return None;
+    }
+    let (year, month, day) = (number(0, 4)?, number(5, 7)?, number(8, 10)?);
+    let (hour, minute, second) =
+        (number(11, 13)?, number(14, 16)?, number(17, 19)?);
+    if !(1..=12).contains(&month)
+        || !(1..=31).contains(&day)
+        || hour > 23
+        || minute > 59
+        || second > 60
+    {
+        return None;
+    }
+
+    // Skip fractional seconds.
+    let mut index = 19;
+    if separator(index, b".") {
+        index += 1;
+        while bytes
+            .get(index)
+            .map(|b| b.is_ascii_digit())
+            .unwrap_or(false)
+        {
+            index += 1;
+        }
+    }
+    let offset = match value.get(index..)? {
+        "Z" | "z" => 0,
+        zone if zone.len() == 6 && separator(index + 3, b":") => {
+            let sign = match zone.as_bytes()[0] {
+                b'+' => 1,
+                b'-' => -1,
+                _ => return None,
+            };
+            let hours = number(index + 1, index + 3)?;
+            let minutes = number(index + 4, index + 6)?;
+            sign * (hours * 3_600 + minutes * 60)
+        }
+        _ => return None,
+    };
+
+    let seconds = hour * 3_600 + minute * 60 + second;
+    Some(days(year, month, day) * DAY as i64 + seconds - offset)
+}
@@ -0,0 +1,152 @@
+//! Block helper that renders nested data recursively.
+use crate::{
+    error::HelperError,
+    helper::{Helper, HelperResult, HelperValue},
+    parser::ast::Node,
+    render::{Context, Render, Scope, Type},
+};
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use serde_json::{Map, Number, Value};
+
+/// Maximum depth of nested nodes.
+pub const MAX_DEPTH: usize = 128;
+
+const CHILDREN: &str = "children";
+const DEPTH: &str = "depth";
+const PATH: &str = "path";
+const INDEX: &str = "index";
+const FIRST: &str = "first";
+const LAST: &str = "last";
+const LEAF: &str = "leaf";
+
+/// Render a block for each node of a tree.
+///
+/// Accepts a single argument which is an array of nodes or a single
+/// root node; the children of a node are read from the `children`
+/// field which can be changed with the `children` hash parameter.
+///
+/// The block is rendered for each node with the node as the scope
+/// and the children are rendered first so that their output is
+/// available to the block as `@children` which should be written
+/// using a triple-stash; the children field is not included in the
+/// scope for the node and it is an error if nodes are nested deeper
+/// than [MAX_DEPTH](MAX_DEPTH):
+///
+/// ```ignore
+/// <ul>
+/// {{#tree menu children="items"}}
+///   <li>{{title}}{{#unless @leaf}}<ul>{{{@children}}}</ul>{{/unless}}</li>
+/// {{/tree}}
+/// </ul>
+/// ```
+///
+/// The local variables are:
+///
+/// * `@depth`: Zero-based depth of the node.
+/// * `@path`: Indices of the node and its ancestors joined by a period, for example `0.2.1`.
+/// * `@index`: Zero-based index of the node in the parent.
+/// * `@first`: If this is the first child of the parent `true`.
+/// * `@last`: If this is the last child of the parent `true`.
+/// * `@leaf`: If the node has no children `true`.
+/// * `@children`: Rendered output for the children.
+pub struct Tree;
+
+impl Helper for Tree {
+    fn call<'render, 'call>(
+        &self,
+        rc: &mut Render<'render>,
+        ctx: &Context<'call>,
+        template: Option<&'render Node<'render>>,
+    ) -> HelperValue {
+        ctx.arity(1..1)?;
+        let template = ctx.assert_block(template)?;
+        let key = match ctx.param(CHILDREN) {
+            Some(_) => {
+                ctx.try_param(CHILDREN, &[Type::String])?.as_str().unwrap()
+            }
+            None => CHILDREN,
+        };
+        let target = ctx.get(0).unwrap();
+        let nodes = match target {
+            Value::Array(ref list) => list.as_slice(),
+            Value::Object(_) => core::slice::from_ref(target),
+            _ => {
+                ctx.assert(target, &[Type::Array])?;
+                &[]
+            }
+        };
+
+        let mut path = Vec::new();
+        let result = level(rc, ctx, template, key, nodes, &mut path)?;
+        rc.write(&result)?;
+        Ok(None)
+    }
+}
+
+/// Render the nodes at a level of the tree.
+fn level<'render>(
+    rc: &mut Render<'render>,
+    ctx: &Context<'_>,
+    template: &'render Node<'render>,
+    key: &str,
+    nodes: &[Value],
+    path: &mut Vec<usize>,
+) -> HelperResult<String> {
+    let mut out = String::new();
+    for (index, node) in nodes.iter().enumerate() {
+        if path.len() >= MAX_DEPTH {
+            return Err(HelperError::new(format!(
+                "Helper '{}' nodes are nested deeper than {}",
+                ctx.name(),
+                MAX_DEPTH
+            )));
+        }
+        path.push(index);
+        let children = node
+            .get(key)
+            .and_then(|c| c.as_array())
+            .map(|c| c.as_slice())
+            .unwrap_or(&[]);
+        let inner = level(rc, ctx, template, key, children, path)?;
+
+        // NOTE: the children were rendered above so the subtree
+        // NOTE: is not copied into the scope for each node.
+        let value = match node {
+            Value::Object(ref map) => Value::Object(
+                map.iter()
+                    .filter(|(k, _)| k.as_str() != key)
+                    .map(|(k, v)| (k.clone(), v.clone()))
+                    .collect::<Map<String, Value>>(),
+            ),
+            _ => node.clone(),
+        };
+
+        let mut scope = Scope::new();
+        scope.set_base_value(value);
+        scope.set_local(DEPTH, Value::Number(Number::from(path.len() - 1)));
+        scope.set_local(PATH, Value::String(join(path)));
+        scope.set_local(INDEX, Value::Number(Number::from(index)));
+        scope.set_local(FIRST, Value::Bool(index == 0));
+        scope.set_local(LAST, Value::Bool(index == nodes.len() - 1));
+        scope.set_local(LEAF, Value::Bool(children.is_empty()));
+        scope.set_local(CHILDREN, Value::String(inner));
+        rc.push_scope(scope);
+        let result = rc.buffer(template);
+        rc.pop_scope();
+        out.push_str(&result?);
+        path.pop();
+    }
+    Ok(out)
+}
+
+fn join(path: &[usize]) -> String {
+    path.iter()
+        .map(|i| i.to_string())
+        .collect::<Vec<_>>()
+        .join(".")
+}
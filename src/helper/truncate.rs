@@ -0,0 +1,272 @@
+//! Helper that truncates HTML without breaking the markup.
+use crate::{
+    error::{HelperError, RenderError},
+    helper::{Helper, HelperValue},
+    parser::ast::{Node, Slice},
+    render::{Context, Render, Type},
+    trim,
+};
+use alloc::{
+    boxed::Box,
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+
+use unicode_segmentation::UnicodeSegmentation;
+
+/// Elements that never have a closing tag.
+const VOID: [&str; 14] = [
+    "area", "base", "br", "col", "embed", "hr", "img", "input", "link", "meta",
+    "param", "source", "track", "wbr",
+];
+
+/// Truncate HTML to a number of characters or words.
+///
+/// Accepts the HTML and the limit or, when called as a block, the
+/// limit and the block is rendered to get the HTML so that rich text
+/// from partials and other helpers can be truncated:
+///
+/// ```ignore
+/// {{truncate_html post.body 140}}
+/// {{#truncate_html 30 words=true}}{{> summary}}{{/truncate_html}}
+/// ```
+///
+/// Characters are counted as grapheme clusters and entities such as
+/// `&amp;` count as a single character; markup is not counted. When
+/// the `words` hash parameter is truthy the limit is a number of words.
+///
+/// When the text is truncated trailing whitespace is removed, the
+/// `ellipsis` hash parameter (default `…`) is appended and the
+/// elements that are still open are closed so the tags stay balanced.
+///
+/// The HTML is written without escaping so it must be trusted in the
+/// same way as a triple-stash expression; when the registry is
+/// sandboxed the statement form requires the sandbox to allow raw
+/// output.
+pub struct TruncateHtml;
+
+impl Helper for TruncateHtml {
+    fn call<'render, 'call>(
+        &self,
+        rc: &mut Render<'render>,
+        ctx: &Context<'call>,
+        template: Option<&'render Node<'render>>,
+    ) -> HelperValue {
+        let (html, limit) = match template {
+            Some(template) => {
+                ctx.arity(1..1)?;
+                (rc.buffer(template)?, 0)
+            }
+            None => {
+                ctx.arity(2..2)?;
+                if let Some(profile) = rc.registry().sandbox() {
+                    if !profile.raw {
+                        return Err(HelperError::Render(Box::new(
+                            RenderError::RawNotAllowed(
+                                ctx.call().as_str().to_string(),
+                                None,
+                            ),
+                        )));
+                    }
+                }
+                let html = ctx.try_get(0, &[Type::String])?.as_str().unwrap();
+                (html.to_string(), 1)
+            }
+        };
+        let limit =
+            ctx.try_get(limit, &[Type::Number])?
+                .as_u64()
+                .ok_or_else(|| {
+                    HelperError::new(format!(
+                        "Helper '{}' expects a positive integer limit",
+                        ctx.name()
+                    ))
+                })? as usize;
+        let words = ctx
+            .param("words")
+            .map(|v| ctx.is_truthy(v))
+            .unwrap_or(false);
+        let ellipsis = match ctx.param("ellipsis") {
+            Some(_) => ctx
+                .try_param("ellipsis", &[Type::String])?
+                .as_str()
+                .unwrap(),
+            None => "…",
+        };
+        rc.write(&truncate(&html, limit, words, ellipsis))?;
+        Ok(None)
+    }
+}
+
+/// Get the length of a tag at the start of the input
+/// respecting quoted attribute values.
+fn tag_len(s: &str) -> Option<usize> {
+    let mut quote = None;
+    for (index, c) in s.char_indices().skip(1) {
+        match (quote, c) {
+            (Some(q), c) if q == c => quote = None,
+            (Some(_), _) => {}
+            (None, '"') | (None, '\'') => quote = Some(c),
+            (None, '>') => return Some(index + 1),
+            (None, '<') => return None,
+            _ => {}
+        }
+    }
+    None
+}
+
+/// Get the lower case name of a tag.
+fn tag_name(tag: &str) -> String {
+    tag.trim_start_matches('<')
+        .trim_start_matches('/')
+        .chars()
+        .take_while(|c| c.is_ascii_alphanumeric() || *c == '-')
+        .collect::<String>()
+        .to_ascii_lowercase()
+}
+
+/// Get the length of an entity at the start of the input.
+fn entity_len(s: &str) -> Option<usize> {
+    let end = s.bytes().take(32).position(|b| b == b';')?;
+    let name = &s[1..end];
+    let valid = !name.is_empty()
+        && name
+            .trim_start_matches('#')
+            .bytes()
+            .all(|b| b.is_ascii_alphanumeric());
+    if valid {
+        Some(end + 1)
+    } else {
+        None
+    }
+}
+
+/// Token at the start of the input.
+enum Token {
+    /// Comment, doctype or tag with the lower case name of the tag.
+    Markup(usize, Option<String>),
+    /// Grapheme cluster or entity.
+    Text(usize),
+}
+
+/// Get the next token from the input.
+fn next_token(rest: &str) -> Token {
+    if rest.starts_with("<!--") {
+        let end = rest.find("-->").map(|i| i + 3).unwrap_or(rest.len());
+        return Token::Markup(end, None);
+    }
+    if rest.starts_with('<') {
+        if let Some(end) = tag_len(rest) {
+            let name = tag_name(&rest[..end]);
+            if !name.is_empty() {
+                return Token::Markup(end, Some(name));
+            } else if rest.starts_with("<!") {
+                return Token::Markup(end, None);
+            }
+        }
+    }
+    let entity = if rest.starts_with('&') {
+        entity_len(rest)
+    } else {
+        None
+    };
+    Token::Text(
+        entity
+            .or_else(|| rest.graphemes(true).next().map(|g| g.len()))
+            .unwrap_or(rest.len()),
+    )
+}
+
+/// Get the elements that are open at the end of the input.
+fn open_elements(html: &str) -> Vec<String> {
+    let mut open: Vec<String> = Vec::new();
+    let mut rest = html;
+    while !rest.is_empty() {
+        let len = match next_token(rest) {
+            Token::Markup(len, Some(name)) => {
+                let tag = &rest[..len];
+                if tag.starts_with("</") {
+                    if let Some(pos) = open.iter().rposition(|n| n == &name) {
+                        open.truncate(pos);
+                    }
+                } else if !tag.ends_with("/>") && !VOID.contains(&name.as_str())
+                {
+                    open.push(name);
+                }
+                len
+            }
+            Token::Markup(len, None) | Token::Text(len) => len,
+        };
+        rest = &rest[len..];
+    }
+    open
+}
+
+/// Truncate HTML keeping the tags balanced.
+///
+/// Markup after the last text that is kept is removed so that
+/// elements which would be empty are not written.
+fn truncate(html: &str, limit: usize, words: bool, ellipsis: &str) -> String {
+    let mut count = 0;
+    let mut in_word = false;
+    let mut end = None;
+    let mut rest = html;
+
+    while !rest.is_empty() {
+        let len = match next_token(rest) {
+            Token::Markup(len, _) => {
+                rest = &rest[len..];
+                continue;
+            }
+            Token::Text(len) => len,
+        };
+        let whitespace = rest[..len].chars().all(char::is_whitespace);
+        let counted = if words {
+            let start = !whitespace && !in_word;
+            in_word = !whitespace;
+            start
+        } else {
+            true
+        };
+        if counted {
+            if count == limit {
+                end = Some(html.len() - rest.len());
+                break;
+            }
+            count += 1;
+        }
+        rest = &rest[len..];
+    }
+
+    let end = match end {
+        Some(end) => end,
+        None => return html.to_string(),
+    };
+    let mut out = String::from(&html[..end]);
+
+    // Remove markup after the last text.
+    let mut kept = 0;
+    let mut rest = out.as_str();
+    while !rest.is_empty() {
+        let len = match next_token(rest) {
+            Token::Markup(len, _) => len,
+            Token::Text(len) => {
+                kept = out.len() - rest.len() + len;
+                len
+            }
+        };
+        rest = &rest[len..];
+    }
+    out.truncate(kept);
+    out.truncate(trim::trim_end(&out).len());
+
+    let open = open_elements(&out);
+    out.push_str(ellipsis);
+    for name in open.iter().rev() {
+        out.push_str("</");
+        out.push_str(name);
+        out.push('>');
+    }
+    out
+}
@@ -30,4 +30,8 @@ impl Helper for Unless {
 
         Ok(None)
     }
+
+    fn pure(&self) -> bool {
+        true
+    }
 }
@@ -8,6 +8,14 @@ use crate::{
 use serde_json::Value;
 
 /// Set the scope for a block to the target argument.
+///
+/// The target is also assigned to the first block parameter so it
+/// can be named, which is useful when the target is the result of a
+/// sub-expression:
+///
+/// ```ignore
+/// {{#with (lookup settings "theme") as |theme|}}{{theme.color}}{{/with}}
+/// ```
 pub struct With;
 
 impl Helper for With {
@@ -26,6 +34,9 @@ impl Helper for With {
                     rc.push_scope(Scope::new());
                     if let Some(ref mut scope) = rc.scope_mut() {
                         scope.set_base_value(ctx.get(0).cloned().unwrap());
+                        if let Some(name) = ctx.block_params().first() {
+                            scope.set_variable(name, arg.clone());
+                        }
                     }
                     rc.template(template)?;
                     rc.pop_scope();
@@ -35,4 +46,8 @@ impl Helper for With {
 
         Ok(None)
     }
+
+    fn pure(&self) -> bool {
+        true
+    }
 }
@@ -0,0 +1,166 @@
+//! Classify template source for syntax highlighting.
+//!
+//! [classify()](classify) maps the lexer tokens onto a small set of
+//! semantic classes so that highlighters do not need to understand the
+//! lexer modes; adjacent tokens of the same class (other than tag
+//! delimiters) are merged and the spans cover the entire source except
+//! for a leading byte order mark:
+//!
+//! ```ignore
+//! use bracket::highlight::classify;
+//! let mut html = String::new();
+//! for (span, class) in classify(source) {
+//!     html.push_str(&format!(
+//!         r#"<span class="{}">{}</span>"#,
+//!         class.as_str(),
+//!         bracket::escape::html(&source[span])));
+//! }
+//! ```
+use alloc::vec::Vec;
+use logos::Span;
+
+use crate::lexer::{lex, Block, Link, Parameters, Token};
+
+/// Semantic class for a span of template source.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum TokenClass {
+    /// Literal template text.
+    Text,
+    /// Delimiter that opens or closes a statement, block or link.
+    TagDelimiter,
+    /// Whitespace control character (`~`) in a delimiter.
+    WhitespaceControl,
+    /// Identifier in a path or call target.
+    Identifier,
+    /// Keyword such as `else`, `this`, `true`, `false` or `null`.
+    Keyword,
+    /// Key for a hash parameter including the `=` sign.
+    HashKey,
+    /// String literal including the quotes.
+    String,
+    /// Number literal.
+    Number,
+    /// Punctuation such as path delimiters, parentheses and pipes.
+    Punctuation,
+    /// Comment including the delimiters.
+    Comment,
+    /// Whitespace inside a statement or block tag.
+    Whitespace,
+    /// Token that could not be lexed.
+    Error,
+}
+
+impl TokenClass {
+    /// Name for the class suitable for use as a CSS class name.
+    pub fn as_str(&self) -> &'static str {
+        match *self {
+            Self::Text => "text",
+            Self::TagDelimiter => "tag-delimiter",
+            Self::WhitespaceControl => "whitespace-control",
+            Self::Identifier => "identifier",
+            Self::Keyword => "keyword",
+            Self::HashKey => "hash-key",
+            Self::String => "string",
+            Self::Number => "number",
+            Self::Punctuation => "punctuation",
+            Self::Comment => "comment",
+            Self::Whitespace => "whitespace",
+            Self::Error => "error",
+        }
+    }
+}
+
+/// Get the class for a token.
+pub fn class(token: &Token) -> TokenClass {
+    if token.is_error() {
+        return TokenClass::Error;
+    }
+    if token.is_comment() {
+        return TokenClass::Comment;
+    }
+    if token.is_string() {
+        return TokenClass::String;
+    }
+    match *token {
+        Token::Block(ref lex, _) => match lex {
+            Block::Text | Block::Newline => TokenClass::Text,
+            _ => TokenClass::TagDelimiter,
+        },
+        Token::RawStatement(_, _) if token.is_close() => {
+            TokenClass::TagDelimiter
+        }
+        Token::RawStatement(_, _) => TokenClass::Text,
+        Token::Parameters(ref lex, _) => match lex {
+            Parameters::Identifier | Parameters::LocalIdentifier => {
+                TokenClass::Identifier
+            }
+            Parameters::ElseKeyword
+            | Parameters::ExplicitThisKeyword
+            | Parameters::True
+            | Parameters::False
+            | Parameters::Null => TokenClass::Keyword,
+            Parameters::HashKey => TokenClass::HashKey,
+            Parameters::Number => TokenClass::Number,
+            Parameters::WhiteSpace | Parameters::Newline => {
+                TokenClass::Whitespace
+            }
+            Parameters::End => TokenClass::TagDelimiter,
+            Parameters::StartArray => TokenClass::Identifier,
+            _ => TokenClass::Punctuation,
+        },
+        Token::Array(_, _) => TokenClass::Identifier,
+        Token::Link(ref lex, _) => match lex {
+            Link::End => TokenClass::TagDelimiter,
+            Link::Pipe => TokenClass::Punctuation,
+            _ => TokenClass::Text,
+        },
+        _ => TokenClass::Text,
+    }
+}
+
+fn push(spans: &mut Vec<(Span, TokenClass)>, span: Span, class: TokenClass) {
+    if span.is_empty() {
+        return;
+    }
+    if let Some((ref mut last, last_class)) = spans.last_mut() {
+        // NOTE: delimiters are not merged so that adjacent
+        // NOTE: tags such as `}}{{` remain distinct spans
+        if *last_class == class
+            && class != TokenClass::TagDelimiter
+            && last.end == span.start
+        {
+            last.end = span.end;
+            return;
+        }
+    }
+    spans.push((span, class));
+}
+
+/// Classify the source of a template.
+///
+/// Returns spans in source order with adjacent spans of the same
+/// class merged except for tag delimiters; whitespace control
+/// characters in tag delimiters are split into their own spans.
+pub fn classify(source: &str) -> Vec<(Span, TokenClass)> {
+    let mut spans = Vec::new();
+    for token in lex(source) {
+        let span = token.span().clone();
+        let class = class(&token);
+        if class == TokenClass::TagDelimiter {
+            let text = &source[span.clone()];
+            if let Some(offset) = text.find('~') {
+                let tilde = span.start + offset;
+                push(&mut spans, span.start..tilde, class);
+                push(
+                    &mut spans,
+                    tilde..tilde + 1,
+                    TokenClass::WhitespaceControl,
+                );
+                push(&mut spans, tilde + 1..span.end, class);
+                continue;
+            }
+        }
+        push(&mut spans, span, class);
+    }
+    spans
+}
@@ -1,6 +1,13 @@
 //! Helper functions for working with JSON values.
+use alloc::{
+    borrow::ToOwned,
+    format,
+    string::{String, ToString},
+};
 use serde_json::Value;
 
+use crate::render::NumericKeys;
+
 const OBJECT: &str = "Object";
 const ARRAY: &str = "Array";
 
@@ -13,6 +20,7 @@ pub(crate) fn stringify(value: &Value) -> String {
     }
 }
 
+#[cfg(any(feature = "log-helper", feature = "encoding-helper"))]
 pub(crate) fn unquote(value: &Value) -> String {
     match value {
         Value::String(ref s) => s.to_owned(),
@@ -24,6 +32,7 @@ pub(crate) fn unquote(value: &Value) -> String {
 pub(crate) fn find_parts<'a, 'b, I>(
     mut it: I,
     doc: &'b Value,
+    keys: NumericKeys,
 ) -> Option<&'b Value>
 where
     I: Iterator<Item = &'a str>,
@@ -34,7 +43,7 @@ where
             let mut next_part = it.next();
             while let Some(part) = next_part {
                 if let Some(target) = current {
-                    current = find_field(target, part);
+                    current = find_field(target, part, keys);
                 } else {
                     break;
                 }
@@ -50,9 +59,14 @@ where
 }
 
 // Look up a field in an array or object.
+//
+// Arrays are indexed using the canonical decimal form of an integer
+// so `01` and `+1` are not indices; objects are always looked up by
+// key so a numeric field such as `0` finds the key `"0"`.
 pub(crate) fn find_field<'b, S: AsRef<str>>(
     target: &'b Value,
     field: S,
+    keys: NumericKeys,
 ) -> Option<&'b Value> {
     match target {
         Value::Object(ref map) => {
@@ -60,8 +74,8 @@ pub(crate) fn find_field<'b, S: AsRef<str>>(
                 return Some(val);
             }
         }
-        Value::Array(ref list) => {
-            if let Ok(index) = field.as_ref().parse::<usize>() {
+        Value::Array(ref list) if keys == NumericKeys::Index => {
+            if let Some(index) = index(field.as_ref()) {
                 return list.get(index);
             }
         }
@@ -70,6 +84,18 @@ pub(crate) fn find_field<'b, S: AsRef<str>>(
     None
 }
 
+// Parse an array index in canonical decimal form.
+pub(crate) fn index(field: &str) -> Option<usize> {
+    let canonical = !field.is_empty()
+        && field.bytes().all(|b| b.is_ascii_digit())
+        && (field == "0" || !field.starts_with('0'));
+    if canonical {
+        field.parse().ok()
+    } else {
+        None
+    }
+}
+
 pub(crate) fn is_truthy(val: &Value) -> bool {
     match val {
         Value::Object(_) => true,
@@ -1,6 +1,29 @@
 //! Iterator for grammar tokens.
+//!
+//! The lexer is a public API intended for tools such as syntax
+//! highlighters and editor plugins that need to tokenize templates
+//! without building an AST:
+//!
+//! ```ignore
+//! use bracket::lexer::lex;
+//! for spanned in lex("{{#if user}}{{name}}{{/if}}").spanned() {
+//!     if spanned.token().is_identifier() {
+//!         println!("{:?} {}", spanned.span(), spanned.as_str());
+//!     }
+//! }
+//! ```
+//!
+//! The lexer switches between [modes](Mode) as it encounters the
+//! delimiters for statements, comments and string literals; tools
+//! that tokenize a fragment of a template (for example a single line
+//! in an editor) can use [set_mode()](Lexer#method.set_mode) to resume
+//! in the mode that the previous fragment finished in.
+use alloc::{borrow::ToOwned, vec::Vec};
 use logos::{Lexer as Lex, Logos, Span};
 
+/// Byte order mark skipped at the start of a template.
+const BOM: char = '\u{feff}';
+
 /// Identity type for the lexer modes.
 #[derive(Clone, Default)]
 pub struct Extras;
@@ -34,6 +57,10 @@ pub enum Block {
     #[regex(r"\{\{\~?[\t ]*#[\t ]*")]
     StartBlockScope,
 
+    /// Start an inverted section.
+    #[regex(r"\{\{\~?[\t ]*\^[\t ]*")]
+    StartInvertedScope,
+
     /// Start a link.
     #[regex(r"\\?\[\[")]
     StartLink,
@@ -51,7 +78,7 @@ pub enum Block {
     Text,
 
     /// Newline token.
-    #[token("\n")]
+    #[regex("\r?\n")]
     Newline,
 
     /// Error token.
@@ -76,7 +103,7 @@ pub enum RawComment {
     End,
 
     /// Newline token.
-    #[token("\n")]
+    #[regex("\r?\n")]
     Newline,
 
     /// Error token.
@@ -100,7 +127,7 @@ pub enum RawStatement {
     End,
 
     /// Newline token.
-    #[token("\n")]
+    #[regex("\r?\n")]
     Newline,
 
     /// Error token.
@@ -125,7 +152,7 @@ pub enum Comment {
     End,
 
     /// Newline token.
-    #[token("\n")]
+    #[regex("\r?\n")]
     Newline,
 
     /// Error token.
@@ -216,6 +243,10 @@ pub enum Parameters {
     #[token("null")]
     Null,
 
+    /// Token for the pipe between filters.
+    #[token("|")]
+    Pipe,
+
     /// Token for whitespace delimiters.
     #[regex(r"[ \t]+")]
     WhiteSpace,
@@ -225,7 +256,7 @@ pub enum Parameters {
     End,
 
     /// Newline token.
-    #[token("\n")]
+    #[regex("\r?\n")]
     Newline,
 
     /// Error token.
@@ -415,6 +446,135 @@ impl Token {
         }
     }
 
+    /// The lexer mode that emitted this token.
+    pub fn mode(&self) -> Mode {
+        match *self {
+            Token::Block(_, _) => Mode::Block,
+            Token::RawComment(_, _) => Mode::RawComment,
+            Token::RawStatement(_, _) => Mode::RawStatement,
+            Token::Comment(_, _) => Mode::Comment,
+            Token::Parameters(_, _) => Mode::Parameters,
+            Token::DoubleQuoteString(_, _) => Mode::DoubleQuoteString,
+            Token::SingleQuoteString(_, _) => Mode::SingleQuoteString,
+            Token::Array(_, _) => Mode::Array,
+            Token::Link(_, _) => Mode::Link,
+        }
+    }
+
+    /// Determine if a token opens a statement, block, comment or link.
+    pub fn is_open(&self) -> bool {
+        match *self {
+            Token::Block(ref lex, _) => {
+                !matches!(lex, Block::Text | Block::Newline | Block::Error)
+            }
+            _ => false,
+        }
+    }
+
+    /// Determine if a token closes a statement, block, comment or link.
+    pub fn is_close(&self) -> bool {
+        match *self {
+            Token::RawComment(ref lex, _) => lex == &RawComment::End,
+            Token::RawStatement(ref lex, _) => lex == &RawStatement::End,
+            Token::Comment(ref lex, _) => lex == &Comment::End,
+            Token::Parameters(ref lex, _) => lex == &Parameters::End,
+            Token::Link(ref lex, _) => lex == &Link::End,
+            _ => false,
+        }
+    }
+
+    /// Determine if a token is part of a comment including
+    /// the comment delimiters.
+    pub fn is_comment(&self) -> bool {
+        match *self {
+            Token::Block(ref lex, _) => {
+                lex == &Block::StartComment || lex == &Block::StartRawComment
+            }
+            Token::RawComment(_, _) | Token::Comment(_, _) => true,
+            _ => false,
+        }
+    }
+
+    /// Determine if a token is part of a string literal including
+    /// the quote delimiters.
+    pub fn is_string(&self) -> bool {
+        match *self {
+            Token::Parameters(ref lex, _) => {
+                lex == &Parameters::DoubleQuoteString
+                    || lex == &Parameters::SingleQuoteString
+            }
+            Token::DoubleQuoteString(_, _) | Token::SingleQuoteString(_, _) => {
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Determine if a token is an identifier or local identifier.
+    pub fn is_identifier(&self) -> bool {
+        match *self {
+            Token::Parameters(ref lex, _) => {
+                lex == &Parameters::Identifier
+                    || lex == &Parameters::LocalIdentifier
+            }
+            _ => false,
+        }
+    }
+
+    /// Determine if a token is a keyword (`else` or `this`).
+    pub fn is_keyword(&self) -> bool {
+        match *self {
+            Token::Parameters(ref lex, _) => {
+                lex == &Parameters::ElseKeyword
+                    || lex == &Parameters::ExplicitThisKeyword
+            }
+            _ => false,
+        }
+    }
+
+    /// Determine if a token is a number, boolean or null literal.
+    pub fn is_literal(&self) -> bool {
+        matches!(
+            self,
+            Token::Parameters(
+                Parameters::Number
+                    | Parameters::True
+                    | Parameters::False
+                    | Parameters::Null,
+                _
+            )
+        )
+    }
+
+    /// Determine if a token is whitespace between call parameters.
+    pub fn is_whitespace(&self) -> bool {
+        match *self {
+            Token::Parameters(ref lex, _) => {
+                lex == &Parameters::WhiteSpace || lex == &Parameters::Newline
+            }
+            _ => false,
+        }
+    }
+
+    /// Determine if a token is an error token.
+    pub fn is_error(&self) -> bool {
+        match *self {
+            Token::Block(ref lex, _) => lex == &Block::Error,
+            Token::RawComment(ref lex, _) => lex == &RawComment::Error,
+            Token::RawStatement(ref lex, _) => lex == &RawStatement::Error,
+            Token::Comment(ref lex, _) => lex == &Comment::Error,
+            Token::Parameters(ref lex, _) => lex == &Parameters::Error,
+            Token::DoubleQuoteString(ref lex, _) => {
+                lex == &DoubleQuoteString::Error
+            }
+            Token::SingleQuoteString(ref lex, _) => {
+                lex == &SingleQuoteString::Error
+            }
+            Token::Array(ref lex, _) => lex == &Array::Error,
+            Token::Link(ref lex, _) => lex == &Link::Error,
+        }
+    }
+
     /// Determine if a token is the newline token.
     pub fn is_newline(&self) -> bool {
         match *self {
@@ -436,6 +596,92 @@ impl Token {
     }
 }
 
+/// Modes the lexer switches between.
+///
+/// Each mode corresponds to one of the token types.
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
+pub enum Mode {
+    /// Top-level document and block content.
+    Block,
+    /// Inside a raw comment.
+    RawComment,
+    /// Inside a raw statement.
+    RawStatement,
+    /// Inside a comment.
+    Comment,
+    /// Inside a statement or block open tag.
+    Parameters,
+    /// Inside a double-quoted string literal.
+    DoubleQuoteString,
+    /// Inside a single-quoted string literal.
+    SingleQuoteString,
+    /// Inside a raw square bracket literal.
+    Array,
+    /// Inside a link.
+    Link,
+}
+
+/// Token paired with the source text for the token span.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Spanned<'source> {
+    token: Token,
+    text: &'source str,
+}
+
+impl<'source> Spanned<'source> {
+    /// The token.
+    pub fn token(&self) -> &Token {
+        &self.token
+    }
+
+    /// The span for the token.
+    pub fn span(&self) -> &Span {
+        self.token.span()
+    }
+
+    /// The source text for the token.
+    pub fn as_str(&self) -> &'source str {
+        self.text
+    }
+
+    /// Consume this value and return the token.
+    pub fn into_token(self) -> Token {
+        self.token
+    }
+}
+
+/// Iterator for a stream of tokens paired with their source text.
+pub struct SpannedLexer<'source> {
+    lexer: Lexer<'source>,
+}
+
+impl<'source> Iterator for SpannedLexer<'source> {
+    type Item = Spanned<'source>;
+    fn next(&mut self) -> Option<Self::Item> {
+        let source = self.lexer.source();
+        self.lexer.next().map(|token| {
+            let text = &source[token.span().clone()];
+            Spanned { token, text }
+        })
+    }
+}
+
+macro_rules! morph {
+    ($lexer:expr, $mode:expr) => {
+        match $mode {
+            Mode::Block => Modes::Block($lexer.morph()),
+            Mode::RawComment => Modes::RawComment($lexer.morph()),
+            Mode::RawStatement => Modes::RawStatement($lexer.morph()),
+            Mode::Comment => Modes::Comment($lexer.morph()),
+            Mode::Parameters => Modes::Parameters($lexer.morph()),
+            Mode::DoubleQuoteString => Modes::DoubleQuoteString($lexer.morph()),
+            Mode::SingleQuoteString => Modes::SingleQuoteString($lexer.morph()),
+            Mode::Array => Modes::Array($lexer.morph()),
+            Mode::Link => Modes::Link($lexer.morph()),
+        }
+    };
+}
+
 enum Modes<'source> {
     Block(Lex<'source, Block>),
     RawComment(Lex<'source, RawComment>),
@@ -448,28 +694,97 @@ enum Modes<'source> {
     Link(Lex<'source, Link>),
 }
 
-impl<'source> Modes<'source> {
-    fn new(s: &'source str) -> Self {
-        Self::Block(Block::lexer(s))
-    }
-}
-
 /// Iterator for a stream of grammar tokens.
 pub struct Lexer<'source> {
     mode: Modes<'source>,
 }
 
 impl<'source> Lexer<'source> {
+    /// Create a lexer for the source template.
+    ///
+    /// A leading UTF-8 byte order mark is skipped and does not
+    /// appear in the token stream.
+    pub fn new(source: &'source str) -> Self {
+        let mut lexer = Block::lexer(source);
+        if source.starts_with(BOM) {
+            lexer.bump(BOM.len_utf8());
+        }
+        Self {
+            mode: Modes::Block(lexer),
+        }
+    }
+
+    /// Create a lexer for the source template that starts
+    /// at a byte offset into the source.
+    pub(crate) fn at(source: &'source str, offset: usize) -> Self {
+        let mut lexer = Block::lexer(source);
+        lexer.bump(offset);
+        Self {
+            mode: Modes::Block(lexer),
+        }
+    }
+
+    /// The source template.
+    pub fn source(&self) -> &'source str {
+        match &self.mode {
+            Modes::Block(lexer) => lexer.source(),
+            Modes::RawComment(lexer) => lexer.source(),
+            Modes::RawStatement(lexer) => lexer.source(),
+            Modes::Comment(lexer) => lexer.source(),
+            Modes::Parameters(lexer) => lexer.source(),
+            Modes::DoubleQuoteString(lexer) => lexer.source(),
+            Modes::SingleQuoteString(lexer) => lexer.source(),
+            Modes::Array(lexer) => lexer.source(),
+            Modes::Link(lexer) => lexer.source(),
+        }
+    }
+
+    /// The current lexer mode.
+    pub fn mode(&self) -> Mode {
+        match &self.mode {
+            Modes::Block(_) => Mode::Block,
+            Modes::RawComment(_) => Mode::RawComment,
+            Modes::RawStatement(_) => Mode::RawStatement,
+            Modes::Comment(_) => Mode::Comment,
+            Modes::Parameters(_) => Mode::Parameters,
+            Modes::DoubleQuoteString(_) => Mode::DoubleQuoteString,
+            Modes::SingleQuoteString(_) => Mode::SingleQuoteString,
+            Modes::Array(_) => Mode::Array,
+            Modes::Link(_) => Mode::Link,
+        }
+    }
+
+    /// Switch the lexer mode.
+    ///
+    /// Lexing continues from the current position using the tokens
+    /// for the new mode.
+    pub fn set_mode(&mut self, mode: Mode) {
+        self.mode = match &self.mode {
+            Modes::Block(lexer) => morph!(lexer.to_owned(), mode),
+            Modes::RawComment(lexer) => morph!(lexer.to_owned(), mode),
+            Modes::RawStatement(lexer) => morph!(lexer.to_owned(), mode),
+            Modes::Comment(lexer) => morph!(lexer.to_owned(), mode),
+            Modes::Parameters(lexer) => morph!(lexer.to_owned(), mode),
+            Modes::DoubleQuoteString(lexer) => morph!(lexer.to_owned(), mode),
+            Modes::SingleQuoteString(lexer) => morph!(lexer.to_owned(), mode),
+            Modes::Array(lexer) => morph!(lexer.to_owned(), mode),
+            Modes::Link(lexer) => morph!(lexer.to_owned(), mode),
+        };
+    }
+
+    /// Convert this lexer into an iterator that pairs each
+    /// token with the source text for the token.
+    pub fn spanned(self) -> SpannedLexer<'source> {
+        SpannedLexer { lexer: self }
+    }
+
     /// Utility for switching the lexer to parameters mode.
     ///
     /// Must be called immediately after creating the lexer otherwise
     /// it is not guaranteed to change the lexer mode.
     pub(crate) fn set_parameters_mode(&mut self) {
-        match &mut self.mode {
-            Modes::Block(lexer) => {
-                self.mode = Modes::Parameters(lexer.to_owned().morph())
-            }
-            _ => {}
+        if let Mode::Block = self.mode() {
+            self.set_mode(Mode::Parameters);
         }
     }
 
@@ -511,7 +826,9 @@ impl<'source> Iterator for Lexer<'source> {
                         self.mode = Modes::Comment(lexer.to_owned().morph());
                     } else if Block::StartStatement == token {
                         self.mode = Modes::Parameters(lexer.to_owned().morph());
-                    } else if Block::StartBlockScope == token {
+                    } else if Block::StartBlockScope == token
+                        || Block::StartInvertedScope == token
+                    {
                         self.mode = Modes::Parameters(lexer.to_owned().morph());
                     } else if Block::EndBlockScope == token {
                         self.mode = Modes::Parameters(lexer.to_owned().morph());
@@ -670,10 +987,8 @@ fn normalize(tokens: Vec<Token>) -> Vec<Token> {
 /// Get a token iterator for the given source template.
 ///
 /// The returned iterator will emit tokens of type `Token`.
-pub fn lex(s: &str) -> Lexer {
-    Lexer {
-        mode: Modes::new(s),
-    }
+pub fn lex(s: &str) -> Lexer<'_> {
+    Lexer::new(s)
 }
 
 /// Collect the input source into a vector of tokens.
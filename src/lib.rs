@@ -1,4 +1,5 @@
 #![deny(missing_docs)]
+#![cfg_attr(not(feature = "std"), no_std)]
 
 //! Bracket is a fast and correct implementation of the [Handlebars][]
 //! general purpose template engine.
@@ -76,6 +77,10 @@
 //! registry.load(PathBuf::from("documents/page.md"))?;
 //! ```
 //!
+//! Templates can also be constructed in code using a
+//! [DocumentBuilder](builder::DocumentBuilder) which never requires
+//! escaping text that contains template syntax.
+//!
 //! ## Render
 //!
 //! If a template has already been registered it can be rendered by name:
@@ -95,6 +100,15 @@
 //! println!("{}", result);
 //! ```
 //!
+//! To render part of a template, for example to update a page in place,
+//! mark a block with the `fragment` helper and render it by id using
+//! [render_fragment()](Registry#method.render_fragment):
+//!
+//! ```ignore
+//! registry.insert("table", r#"{{#each rows}}{{#fragment "row"}}<tr>{{name}}</tr>{{/fragment}}{{/each}}"#)?;
+//! let row = registry.render_fragment("table", "row", json!({"name": "Alice"}))?;
+//! ```
+//!
 //! ## Lint
 //!
 //! Sometimes it is useful to check whether a template is well-formed. The
@@ -104,6 +118,15 @@
 //! let errors = registry.lint("file-name.md", "{{.bad.path}}")?;
 //! ```
 //!
+//! The list also includes warnings for likely mistakes such as
+//! whitespace control (`~`) that has no effect or that joins a word
+//! to the output of a statement, blocks that can never render
+//! such as `{{#if false}}` and calls to deprecated helpers; use
+//! `is_warning()` on a syntax error to tell warnings from errors.
+//!
+//! To review a change to a template use [diff()](diff()) which compares
+//! the structure of two templates ignoring insignificant whitespace.
+//!
 //! ## Escape
 //!
 //! By default templates are escaped for HTML output; you can call `set_escape()`
@@ -113,8 +136,74 @@
 //!
 //! ```ignore
 //! use bracket::escape;
-//! registry.set_escape(escape::noop);
+//! registry.set_escape_write(Box::new(escape::noop_write));
+//! ```
+//!
+//! Escape functions write directly to the output destination, functions
+//! that return a `String` can be assigned using `set_escape()`:
+//!
+//! ```ignore
+//! registry.set_escape(Box::new(|s: &str| s.to_uppercase()));
+//! ```
+//!
+//! Escape functions for other formats are also available; for example
+//! to escape each value as a field in a data export:
+//!
+//! ```ignore
+//! registry.set_escape_write(Box::new(escape::csv_write));
+//! ```
+//!
+//! Use [xml_write()](escape::xml_write) for strict XML output such as
+//! RSS feeds and sitemaps.
+//!
+//! To render into bytes rather than a string use `render_bytes()` or
+//! render to a [BytesOutput](output::BytesOutput) which can also encode
+//! the output as Latin-1; characters that cannot be encoded are returned
+//! as a render error:
+//!
+//! ```ignore
+//! use bracket::output::BytesOutput;
+//! let mut writer = BytesOutput::latin1();
+//! registry.render_to_write("email.txt", &data, &mut writer)?;
+//! let bytes: Vec<u8> = writer.into();
+//! ```
+//!
+//! Newlines in template text and values are written unchanged; to
+//! normalize the newlines in rendered output assign a
+//! [Newline](output::Newline) mode:
+//!
+//! ```ignore
+//! use bracket::output::Newline;
+//! registry.set_newline(Newline::CrLf);
+//! ```
+//!
+//! Generated files often need to end with exactly one newline; assign a
+//! [TrailingNewline](output::TrailingNewline) policy to the registry or
+//! override it for a template:
+//!
+//! ```ignore
+//! use bracket::output::TrailingNewline;
+//! registry.set_trailing_newline(TrailingNewline::Single);
+//! if let Some(template) = registry.templates_mut().get_mut("data.json") {
+//!     template.set_trailing_newline(Some(TrailingNewline::Strip));
+//! }
+//! ```
+//!
+//! To minify HTML output collapsing whitespace and removing
+//! comments enable [minification](minify) for the registry:
+//!
+//! ```ignore
+//! registry.set_minify(true);
+//! ```
+//!
+//! To see which template produced each fragment of a rendered page
+//! assign a [trace](trace::Trace) that wraps the output of partials
+//! and blocks in markers:
+//!
+//! ```ignore
+//! registry.set_trace(Some(Trace::html()));
 //! ```
+//!
 //! ## Strict Mode
 //!
 //! By default the handlebars behaviour for variable interpolation is a noop
@@ -125,27 +214,119 @@
 //! registry.set_strict(true);
 //! ```
 //!
+//! Statements that are `null` or missing write nothing by default; use a
+//! [NullPolicy](format::NullPolicy) to write `null`, a placeholder or to
+//! return an error instead:
+//!
+//! ```ignore
+//! registry.set_null_policy(NullPolicy::Placeholder(String::from("-")));
+//! ```
+//!
+//! ## Sandbox
+//!
+//! Helpers that use ambient authority such as the environment are disabled
+//! when a registry is sandboxed; enable the sandbox when rendering untrusted
+//! templates:
+//!
+//! ```ignore
+//! registry.set_sandboxed(true);
+//! ```
+//!
+//! To also restrict which helpers may be called, limit partial depth and output
+//! size or disallow unescaped interpolation assign a [sandbox profile](sandbox::Profile):
+//!
+//! ```ignore
+//! registry.set_sandbox(Profile::new());
+//! ```
+//!
+//! The `env` helper can only read environment variables that have been allowed:
+//!
+//! ```ignore
+//! registry.allow_env("APP_VERSION");
+//! ```
+//!
+//! ## Partial Parameters
+//!
+//! Hash parameters passed to a partial are merged over the current
+//! context so partials can be written as components:
+//!
+//! ```ignore
+//! {{> button label="OK" kind="primary"}}
+//! ```
+//!
+//! Set the `isolated` flag so that variables the partial does not
+//! receive as parameters are not resolved from the calling template:
+//!
+//! ```ignore
+//! {{> button label="OK" isolated=true}}
+//! ```
+//!
+//! Declare the parameters a partial expects with a
+//! [contract](contract::Contract) so that call sites with unknown,
+//! missing or mistyped parameters are reported by `lint()` and are
+//! errors in strict mode:
+//!
+//! ```ignore
+//! registry.set_contract("button", Contract::new().required("label", &[Type::String]));
+//! ```
+//!
+//! ## Partial Cache
+//!
+//! Pages that render the same partial many times for repeated data can
+//! reuse previously rendered output by assigning a [cache](cache::PartialCache)
+//! for the partials that only depend upon their context; the partials
+//! must be called with `isolated=true` unless they only read `this`:
+//!
+//! ```ignore
+//! let mut cache = PartialCache::new(256);
+//! cache.allow_partials(&["card"]);
+//! registry.set_partial_cache(cache);
+//! ```
+//!
+//! ## Variants
+//!
+//! To run experiments register [variants](variant) of a template under
+//! a logical name and assign a selector to choose a variant for each render:
+//!
+//! ```ignore
+//! registry.register_variant("email/welcome", "control", control, 90)?;
+//! registry.register_variant("email/welcome", "v2", v2, 10)?;
+//! ```
+//!
+//! ## No Std
+//!
+//! The lexer, parser and renderer only require an allocator; disable the
+//! default features and enable `alloc` to build without the standard library:
+//!
+//! ```toml
+//! bracket = { version = "0.10", default-features = false, features = ["alloc", "each-helper"] }
+//! ```
+//!
+//! Without `std` the `fs` feature and the `env` helper are not available and
+//! [Output](output::Output) no longer requires `io::Write`.
+//!
 //! ## Helpers
 //!
-//! Helper functions make handlebars a versatile template engine; all
-//! helpers are enabled by default but can be disabled via feature flags if
-//! you need to.
+//! Helper functions make handlebars a versatile template engine; the
+//! core helpers are enabled by default but can be disabled via feature
+//! flags if you need to.
 //!
-//! By default all the built-in helpers are enabled:
+//! By default these built-in helpers are enabled:
 //!
 //! * [log](helper::log::Log) Print log messages.
 //! * [lookup](helper::lookup::Lookup) Lookup a field of an object or array.
 //! * [#if](helper::if::If) Conditional block helper.
 //! * [#unless](helper::unless::Unless) Negated conditional block helper.
 //! * [#each](helper::each::Each) Iterate arrays and objects.
+//! * [#repeat](helper::repeat::Repeat) Render a block a number of times.
 //! * [#with](helper::with::With) Set the block context scope.
-//!
-//! Some useful extra helpers are also enabled by default:
-//!
+//! * [set](helper::set::Set) Assign variables to the current scope.
+//! * [#paginate](helper::paginate::Paginate) Compute pagination values for a list.
 //! * [json](helper::json::Json) Convert values to JSON strings.
 //! * [and](helper::logical::And) Logical boolean AND operation.
 //! * [or](helper::logical::Or) Logical boolean OR operation.
 //! * [not](helper::logical::Not) Logical boolean NOT operation.
+//! * [env](helper::env::Env) Get an allowed environment variable.
 //!
 //! Numerical comparison helpers:
 //!
@@ -156,6 +337,57 @@
 //! * [lte](helper::comparison::LessThanEqual) Test for less than or equal to.
 //! * [gte](helper::comparison::GreaterThanEqual) Test for greater than or equal to.
 //!
+//! A helper takes precedence over a data field with the same name so
+//! some useful extra helpers are opt-in; enable them all with the
+//! `extra-helpers` feature or each one with its own feature flag:
+//!
+//! * [include](helper::include::Include) Render a template with explicit data.
+//! * [format](helper::format::Format) Format values using a tagged formatter.
+//! * [csv](helper::csv::Csv) Write a row of comma-separated values.
+//! * [shell_quote](helper::shell::ShellQuote) Quote a value for a command line.
+//! * [#cdata](helper::cdata::Cdata) Wrap a block in an XML CDATA section.
+//! * [safe](helper::escape::Safe) Write a value without escaping.
+//! * [escape](helper::escape::Escape) Write a value with escaping.
+//! * [uuid](helper::random::Uuid) Generate a version 4 UUID.
+//! * [random](helper::random::Random) Generate a random number or choose an element.
+//! * [now](helper::now::Now) Get the current time.
+//! * [asset](helper::asset::Asset) Get the fingerprinted URL for a static asset.
+//! * [csp_nonce](helper::csp::CspNonce) Write the Content Security Policy nonce.
+//! * [link_to](helper::html::LinkTo) Write a link.
+//! * [img](helper::html::Img) Write an image.
+//! * [attr](helper::html::Attr) Write attributes omitting empty values.
+//! * [class_list](helper::html::ClassList) Build a list of class names.
+//! * [#form](helper::form::Form) Write a form bound to a model.
+//! * [input](helper::form::Input) Write an input field with its value and errors.
+//! * [select](helper::form::Select) Write a select field with its options and errors.
+//! * [table](helper::table::Table) Write a table for an array of objects.
+//! * [#tree](helper::tree::Tree) Render a block for each node of nested data.
+//! * [#group](helper::group::Group) Render a block for each group of an array.
+//! * [where](helper::filter::Where) Select the elements of an array.
+//! * [#chunk](helper::chunk::Chunk) Render a block for each chunk of an array.
+//! * [range](helper::range::Range) Generate an array of integers.
+//! * [plural](helper::plural::Plural) Choose the plural form of a word.
+//! * [timeago](helper::timeago::Timeago) Describe a time relative to now.
+//! * [filesize](helper::humanize::Filesize) Format a number of bytes.
+//! * [duration](helper::humanize::Duration) Format a number of seconds.
+//! * [truncate_html](helper::truncate::TruncateHtml) Truncate HTML keeping tags balanced.
+//! * [once](helper::once::Once) Render a block at most once per render.
+//! * [call](helper::call::Call) Call an allowed helper by name.
+//! * [fragment](helper::fragment::Fragment) Mark a block that can be rendered on its own.
+//! * [helpers](helper::reflect::Helpers) List the registered helpers.
+//!
+//! SQL quoting helpers are only enabled with the `sql-helper` feature:
+//!
+//! * [sql_str](helper::sql::SqlStr) Quote a value as an SQL literal.
+//! * [sql_ident](helper::sql::SqlIdent) Quote an SQL identifier.
+//!
+//! Encoding helpers are only enabled with the `encoding-helper` feature:
+//!
+//! * [base64](helper::encoding::Base64) Encode a value as base64.
+//! * [base64url](helper::encoding::Base64Url) Encode a value as URL safe base64.
+//! * [hex](helper::encoding::Hex) Encode a value as hexadecimal.
+//! * [sha256](helper::encoding::Sha256) Compute the SHA-256 digest of a value.
+//!
 //! To add a helper to the registry use `helpers_mut()`:
 //!
 //! ```ignore
@@ -167,6 +399,14 @@
 //! {{custom "Hello world!" param=true}}
 //! ```
 //!
+//! To rename a helper gradually add an alias for the old name and mark
+//! it as deprecated so that calls are reported by `lint()`:
+//!
+//! ```ignore
+//! registry.alias_helper("fmt", "format");
+//! registry.deprecate_helper("fmt", "use 'format' instead");
+//! ```
+//!
 //! See the [Helper Module](helper) to learn more about creating your own
 //! helpers.
 //!
@@ -200,6 +440,63 @@
 //! To ignore a wiki-style link from processing prefix it with a backslash `\[[Plain Text]]` and
 //! it will be rendered as text without the backslash.
 //!
+//! ## Pipes
+//!
+//! The `pipes` feature which is enabled by default allows filters to be
+//! chained using a vertical pipe; the value on the left is passed as the
+//! first argument to the helper on the right so this statement:
+//!
+//! ```ignore
+//! {{ name | upper | truncate 20 }}
+//! ```
+//!
+//! Is equivalent to:
+//!
+//! ```ignore
+//! {{ truncate (upper name) 20 }}
+//! ```
+//!
+//! Pipes are allowed in statements and sub-expressions but not in
+//! partials, conditionals or when opening a block.
+//!
+//! ## Inverted Sections
+//!
+//! For compatibility with mustache templates an inverted section is
+//! parsed as a call to the `unless` helper so this block:
+//!
+//! ```ignore
+//! {{^user}}Guest{{/user}}
+//! ```
+//!
+//! Is equivalent to:
+//!
+//! ```ignore
+//! {{#unless user}}Guest{{/unless}}
+//! ```
+//!
+//! A lone dot is also accepted as shorthand for `this` which is
+//! convenient when iterating an array of scalars:
+//!
+//! ```ignore
+//! {{#each tags}}<li>{{.}}</li>{{/each}}
+//! ```
+//!
+//! ## Block Parameters
+//!
+//! Values passed to a block can be named using block parameters at the
+//! end of the open tag which is useful when the value is the result of
+//! a sub-expression:
+//!
+//! ```ignore
+//! {{#with (lookup settings "theme") as |theme|}}{{theme.color}}{{/with}}
+//! {{#each users as |user index|}}{{index}}: {{user.name}}{{/each}}
+//! ```
+//!
+//! The `with` and `each` helpers assign block parameters; other block
+//! helpers can read the names using
+//! [block_params()](render::Context#method.block_params) and assign
+//! them as variables of the scope for the block.
+//!
 //! ## Handlers
 //!
 //! Support for `helperMissing` and `blockHelperMissing` handlers can be enabled using the registry
@@ -218,29 +515,68 @@
 //! [Handlebars]: https://handlebarsjs.com
 //! [Handlebars Hooks]: https://handlebarsjs.com/guide/hooks.html
 //!
+#[cfg(not(any(feature = "std", feature = "alloc")))]
+compile_error!("either the `std` or `alloc` feature must be enabled");
+
+extern crate alloc;
+
+pub mod asset;
+pub mod builder;
+#[cfg(feature = "std")]
+pub mod cache;
+pub mod clock;
+pub(crate) mod collections;
+pub mod contract;
+pub mod dependency;
+pub mod diff;
+pub mod email;
 pub mod error;
 pub mod escape;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+pub mod format;
 pub mod helper;
+pub mod highlight;
 pub(crate) mod json;
 pub mod lexer;
+pub(crate) mod lint;
+#[cfg(feature = "metrics")]
+pub mod metrics;
+pub mod minify;
 pub mod output;
 pub mod parser;
+#[cfg(feature = "preview")]
+pub mod preview;
+#[cfg(feature = "std")]
+pub mod profile;
+pub mod query;
+pub mod random;
 pub mod registry;
 pub mod render;
+pub mod report;
+pub mod sandbox;
 pub mod template;
+#[cfg(feature = "testing")]
+pub mod testing;
+pub mod trace;
 pub mod trim;
+pub mod variables;
+pub mod variant;
+#[cfg(any(feature = "axum", feature = "actix-web", feature = "rocket"))]
+pub mod web;
 
 /// Result type returned by the registry.
-pub type Result<T> = std::result::Result<T, error::Error>;
+pub type Result<T> = core::result::Result<T, error::Error>;
 
 /// Result type returned when rendering templates.
-pub type RenderResult<T> = std::result::Result<T, error::RenderError>;
+pub type RenderResult<T> = core::result::Result<T, error::RenderError>;
 
 /// Result type returned when compiling templates.
-pub type SyntaxResult<T> = std::result::Result<T, error::SyntaxError>;
+pub type SyntaxResult<T> = core::result::Result<T, error::SyntaxError>;
 
+pub use diff::diff;
 pub use error::Error;
-pub use registry::Registry;
+pub use registry::{Registry, RegistryBuilder};
 pub use template::Template;
 
-pub use escape::EscapeFn;
+pub use escape::{EscapeFn, EscapeWriteFn};
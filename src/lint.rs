@@ -0,0 +1,181 @@
+//! Lint rules that inspect template source for likely mistakes.
+//!
+//! Lint rules return warnings as syntax errors so they can be
+//! reported alongside parse errors by
+//! [lint()](crate::Registry#method.lint).
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+
+use serde_json::Value;
+
+use crate::{
+    error::{ErrorInfo, SourcePos, SyntaxError},
+    json,
+    lexer::{lex, Block, Parameters, Token},
+    parser::ast::{self, Lines, ParameterValue, Slice},
+    Error,
+};
+
+/// Check whitespace control characters.
+///
+/// Warns when a `~` has no effect because the tag is at a boundary
+/// of the template, is next to another tag or there is no whitespace
+/// to trim; and when trimming removes the space on a single line
+/// between text and the output of a statement so that they are joined.
+pub(crate) fn whitespace(name: &str, source: &str) -> Vec<Error> {
+    let mut warnings = Vec::new();
+    let mut statement = false;
+    for token in lex(source) {
+        let span = token.span().clone();
+        let text = &source[span.clone()];
+        let offset = match text.find('~') {
+            Some(index) => span.start + index,
+            None => {
+                if let Token::Block(ref lex, _) = token {
+                    statement = lex == &Block::StartStatement;
+                }
+                continue;
+            }
+        };
+        let warning = match token {
+            Token::Block(ref lex, _) if token.is_open() => {
+                statement = lex == &Block::StartStatement;
+                let before = source[..span.start].trim_start_matches(BOM);
+                check(before.chars().rev(), statement, "start", "before")
+            }
+            Token::Parameters(Parameters::End, _) => {
+                let after = &source[span.end..];
+                check(after.chars(), statement, "end", "after")
+            }
+            _ => None,
+        };
+        if let Some(warning) = warning {
+            let line = source[..offset].matches('\n').count();
+            let pos = SourcePos(line, offset);
+            warnings.push(Error::from(match warning {
+                Warning::NoEffect(reason) => SyntaxError::TrimNoEffect(
+                    reason,
+                    ErrorInfo::new(source, name, pos, vec![]).into(),
+                ),
+                Warning::JoinsText => SyntaxError::TrimJoinsText(
+                    ErrorInfo::new(
+                        source,
+                        name,
+                        pos,
+                        vec![String::from("remove the '~' to keep the space")],
+                    )
+                    .into(),
+                ),
+            }));
+        }
+    }
+    warnings
+}
+
+const BOM: char = '\u{feff}';
+
+enum Warning {
+    NoEffect(String),
+    JoinsText,
+}
+
+/// Check the characters next to a whitespace control character
+/// iterating away from the tag.
+fn check<I>(
+    mut chars: I,
+    statement: bool,
+    boundary: &str,
+    side: &str,
+) -> Option<Warning>
+where
+    I: Iterator<Item = char> + Clone,
+{
+    let next = match chars.clone().next() {
+        Some(c) => c,
+        None => {
+            return Some(Warning::NoEffect(format!(
+                "the tag is at the {} of the template",
+                boundary
+            )))
+        }
+    };
+    if next == '}' || next == '{' {
+        return Some(Warning::NoEffect(format!(
+            "there is another tag {} the tag",
+            side
+        )));
+    }
+    if !next.is_whitespace() {
+        return Some(Warning::NoEffect(format!(
+            "there is no whitespace {} the tag",
+            side
+        )));
+    }
+    if statement {
+        // NOTE: whitespace on the same line followed by text
+        // NOTE: is usually a word separator
+        let text = chars.find(|c| !c.is_whitespace() || *c == '\n');
+        if let Some(c) = text {
+            if c != '\n' && c != '}' && c != '{' {
+                return Some(Warning::JoinsText);
+            }
+        }
+    }
+    None
+}
+
+/// Check for block templates that can never render.
+///
+/// Uses the semantics of the built-in helpers to detect conditions
+/// that are literals such as `{{#if false}}` or `{{#each []}}`.
+pub(crate) fn dead_code(
+    name: &str,
+    source: &str,
+    block: &ast::Block<'_>,
+) -> Option<Error> {
+    let call = block.call();
+    let helper = call.target().as_str();
+    let (value, span) = match call.arguments().first() {
+        Some(ParameterValue::Json {
+            ref value,
+            ref span,
+            ..
+        }) if call.arguments().len() == 1 => (value, span),
+        _ => return None,
+    };
+    let has_else = !block.conditions().is_empty();
+    let reason = match helper {
+        "if" if !json::is_truthy(value) => "the condition is always false",
+        "if" if has_else => {
+            "the else branch is never rendered as the condition is always true"
+        }
+        "unless" if json::is_truthy(value) => "the condition is always true",
+        "unless" if has_else => {
+            "the else branch is never rendered as the condition is always false"
+        }
+        "each" if !json::is_truthy(value) || is_empty(value) => {
+            "the value to iterate is always empty"
+        }
+        "with" if value.is_null() => "the value is always null",
+        _ => return None,
+    };
+    let pos = SourcePos(call.lines().start, span.start);
+    let info = ErrorInfo::new(source, name, pos, vec![]);
+    Some(Error::from(SyntaxError::UnreachableBlock(
+        helper.to_string(),
+        reason.to_string(),
+        info.into(),
+    )))
+}
+
+fn is_empty(value: &Value) -> bool {
+    match value {
+        Value::Array(ref list) => list.is_empty(),
+        Value::Object(ref map) => map.is_empty(),
+        _ => false,
+    }
+}
@@ -0,0 +1,99 @@
+//! Record render metrics using the `metrics` facade.
+//!
+//! When the `metrics` feature is enabled renders of named templates
+//! and helper errors are recorded using the recorder installed for
+//! the process, for example a Prometheus exporter:
+//!
+//! * `bracket_renders_started_total`: Counter of renders started.
+//! * `bracket_renders_completed_total`: Counter of renders that succeeded.
+//! * `bracket_renders_failed_total`: Counter of renders that failed.
+//! * `bracket_render_duration_seconds`: Histogram of render durations.
+//! * `bracket_render_output_bytes`: Histogram of output sizes.
+//! * `bracket_helper_errors_total`: Counter of errors returned by helpers.
+//!
+//! Render metrics have a `template` label with the name of the template
+//! and helper errors have a `helper` label with the name of the helper.
+//! Partials are part of the render for the template that includes
+//! them so they are not recorded separately.
+use std::{io::Write, time::Instant};
+
+use metrics::{counter, histogram};
+
+use crate::{
+    output::{io::Result, Output},
+    RenderResult,
+};
+
+/// Name of the counter for renders started.
+pub const RENDERS_STARTED: &str = "bracket_renders_started_total";
+/// Name of the counter for renders that succeeded.
+pub const RENDERS_COMPLETED: &str = "bracket_renders_completed_total";
+/// Name of the counter for renders that failed.
+pub const RENDERS_FAILED: &str = "bracket_renders_failed_total";
+/// Name of the histogram for render durations in seconds.
+pub const RENDER_DURATION: &str = "bracket_render_duration_seconds";
+/// Name of the histogram for the number of bytes written by a render.
+pub const RENDER_OUTPUT_BYTES: &str = "bracket_render_output_bytes";
+/// Name of the counter for errors returned by helpers.
+pub const HELPER_ERRORS: &str = "bracket_helper_errors_total";
+
+/// Record the metrics for a render of a named template.
+pub(crate) fn render<R, F>(
+    name: &str,
+    writer: &mut dyn Output,
+    render: F,
+) -> RenderResult<R>
+where
+    F: FnOnce(&mut dyn Output) -> RenderResult<R>,
+{
+    let template = name.to_string();
+    counter!(RENDERS_STARTED, "template" => template.clone()).increment(1);
+    let started = Instant::now();
+    let mut output = CountOutput { writer, written: 0 };
+    let result = render(&mut output);
+    histogram!(RENDER_DURATION, "template" => template.clone())
+        .record(started.elapsed().as_secs_f64());
+    if result.is_ok() {
+        histogram!(RENDER_OUTPUT_BYTES, "template" => template.clone())
+            .record(output.written as f64);
+        counter!(RENDERS_COMPLETED, "template" => template).increment(1);
+    } else {
+        counter!(RENDERS_FAILED, "template" => template).increment(1);
+    }
+    result
+}
+
+/// Record an error returned by a helper.
+pub(crate) fn helper_error(name: &str) {
+    counter!(HELPER_ERRORS, "helper" => name.to_string()).increment(1);
+}
+
+/// Output adapter that counts the bytes written.
+struct CountOutput<'a> {
+    writer: &'a mut dyn Output,
+    written: usize,
+}
+
+impl Output for CountOutput<'_> {
+    fn write_str(&mut self, s: &str) -> Result<usize> {
+        let written = self.writer.write_str(s)?;
+        self.written += written;
+        Ok(written)
+    }
+
+    fn write_bytes(&mut self, b: &[u8]) -> Result<usize> {
+        let written = self.writer.write_bytes(b)?;
+        self.written += written;
+        Ok(written)
+    }
+}
+
+impl Write for CountOutput<'_> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        self.write_bytes(buf)
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        self.writer.flush()
+    }
+}
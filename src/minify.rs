@@ -0,0 +1,244 @@
+//! Minify rendered HTML.
+//!
+//! Enable minification for a registry to minify the output of
+//! every render:
+//!
+//! ```ignore
+//! registry.set_minify(true);
+//! ```
+//!
+//! Or wrap an output destination, call `finish()` once rendering
+//! completes to write any buffered content:
+//!
+//! ```ignore
+//! use bracket::{minify::HtmlMinify, output::StringOutput};
+//! let mut writer = StringOutput::new();
+//! let mut output = HtmlMinify::new(&mut writer);
+//! template.render(&registry, "page", &data, &mut output, Vec::new())?;
+//! output.finish()?;
+//! ```
+//!
+//! Runs of whitespace are collapsed to a single space, including
+//! whitespace between tags which may separate inline elements, and
+//! leading and trailing whitespace is removed. Comments are removed
+//! except for conditional comments such as `<!--[if IE]>...<![endif]-->`.
+//!
+//! The content of `pre`, `textarea`, `script` and `style` elements
+//! and the content of tags including attribute values are written
+//! unchanged.
+use alloc::string::String;
+
+#[cfg(feature = "std")]
+use std::io::Write;
+
+use crate::output::{io::Result, Output};
+
+/// Elements where whitespace is significant.
+const RAW: [&str; 4] = ["pre", "textarea", "script", "style"];
+
+const COMMENT: &str = "<!--";
+
+enum State {
+    /// Text content.
+    Text,
+    /// Got a `<` that may begin a tag or comment.
+    Open,
+    /// Inside a tag with an optional attribute quote.
+    Tag(Option<char>),
+    /// Inside a comment.
+    Comment,
+    /// Inside an element where whitespace is significant.
+    Raw(&'static str),
+    /// Got a `<` inside an element where whitespace is significant
+    /// that may begin the close tag.
+    RawClose(&'static str),
+}
+
+/// Output adapter that minifies HTML.
+pub struct HtmlMinify<'a> {
+    writer: &'a mut dyn Output,
+    state: State,
+    buffer: String,
+    space: bool,
+    empty: bool,
+}
+
+impl<'a> HtmlMinify<'a> {
+    /// Create an adapter that writes minified HTML to `writer`.
+    pub fn new(writer: &'a mut dyn Output) -> Self {
+        Self {
+            writer,
+            state: State::Text,
+            buffer: String::new(),
+            space: false,
+            empty: true,
+        }
+    }
+
+    /// Write incomplete tags and comments held in the buffer.
+    ///
+    /// Trailing whitespace is discarded.
+    pub fn finish(mut self) -> Result<()> {
+        if !self.buffer.is_empty() {
+            let mut out = String::new();
+            self.flush_space(&mut out);
+            out.push_str(&self.buffer);
+            self.writer.write_str(&out)?;
+        }
+        Ok(())
+    }
+
+    /// Write pending whitespace before content as a single space.
+    fn flush_space(&mut self, out: &mut String) {
+        if core::mem::take(&mut self.space) && !self.empty {
+            out.push(' ');
+        }
+        self.empty = false;
+    }
+
+    /// Finish a tag held in the buffer.
+    fn end_tag(&mut self, out: &mut String) {
+        let tag = core::mem::take(&mut self.buffer);
+        self.flush_space(out);
+        out.push_str(&tag);
+        self.state = State::Text;
+        if !tag.ends_with("/>") {
+            let name: String = tag[1..]
+                .chars()
+                .take_while(|c| c.is_ascii_alphanumeric())
+                .collect();
+            if let Some(raw) =
+                RAW.iter().find(|r| r.eq_ignore_ascii_case(&name))
+            {
+                self.state = State::Raw(raw);
+            }
+        }
+    }
+
+    /// Finish a comment held in the buffer.
+    fn end_comment(&mut self, out: &mut String) {
+        let comment = core::mem::take(&mut self.buffer);
+        let body = &comment[COMMENT.len()..];
+        if body.starts_with("[if") || body.starts_with("<![endif]") {
+            self.flush_space(out);
+            out.push_str(&comment);
+        }
+        self.state = State::Text;
+    }
+
+    fn push(&mut self, c: char, out: &mut String) {
+        match self.state {
+            State::Text => {
+                if c.is_whitespace() {
+                    self.space = true;
+                } else if c == '<' {
+                    self.buffer.push(c);
+                    self.state = State::Open;
+                } else {
+                    self.flush_space(out);
+                    out.push(c);
+                }
+            }
+            State::Open => {
+                self.buffer.push(c);
+                if COMMENT.starts_with(self.buffer.as_str()) {
+                    if self.buffer == COMMENT {
+                        self.state = State::Comment;
+                    }
+                } else if self.buffer.len() > 2
+                    || c.is_ascii_alphabetic()
+                    || c == '/'
+                    || c == '!'
+                    || c == '?'
+                {
+                    self.state = State::Tag(None);
+                    if c == '>' {
+                        self.end_tag(out);
+                    }
+                } else {
+                    // NOTE: not markup so write the `<` as text
+                    self.buffer.clear();
+                    self.flush_space(out);
+                    out.push('<');
+                    self.state = State::Text;
+                    self.push(c, out);
+                }
+            }
+            State::Tag(quote) => {
+                self.buffer.push(c);
+                match quote {
+                    Some(q) if c == q => self.state = State::Tag(None),
+                    Some(_) => {}
+                    None if c == '"' || c == '\'' => {
+                        self.state = State::Tag(Some(c))
+                    }
+                    None if c == '>' => self.end_tag(out),
+                    None => {}
+                }
+            }
+            State::Comment => {
+                self.buffer.push(c);
+                if self.buffer.len() > COMMENT.len() + 1
+                    && self.buffer.ends_with("-->")
+                {
+                    self.end_comment(out);
+                }
+            }
+            State::Raw(name) => {
+                if c == '<' {
+                    self.buffer.push(c);
+                    self.state = State::RawClose(name);
+                } else {
+                    out.push(c);
+                }
+            }
+            State::RawClose(name) => {
+                self.buffer.push(c);
+                let len = self.buffer.len();
+                let matched = if len == 2 {
+                    c == '/'
+                } else if len < name.len() + 3 {
+                    c.eq_ignore_ascii_case(&(name.as_bytes()[len - 3] as char))
+                } else {
+                    c == '>' || c == '/' || c.is_whitespace()
+                };
+                if !matched {
+                    self.buffer.pop();
+                    out.push_str(&self.buffer);
+                    self.buffer.clear();
+                    self.state = State::Raw(name);
+                    self.push(c, out);
+                } else if len == name.len() + 3 {
+                    self.state = State::Tag(None);
+                    if c == '>' {
+                        self.end_tag(out);
+                    }
+                }
+            }
+        }
+    }
+}
+
+impl Output for HtmlMinify<'_> {
+    fn write_str(&mut self, s: &str) -> Result<usize> {
+        let mut out = String::with_capacity(s.len());
+        for c in s.chars() {
+            self.push(c, &mut out);
+        }
+        if !out.is_empty() {
+            self.writer.write_str(&out)?;
+        }
+        Ok(s.len())
+    }
+}
+
+#[cfg(feature = "std")]
+impl Write for HtmlMinify<'_> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        self.write_bytes(buf)
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        self.writer.flush()
+    }
+}
@@ -1,23 +1,131 @@
 //! Trait and type for rendering to destinations.
-use std::io::{Result, Write};
+//!
+//! Without the `std` feature the `io` module provides a minimal
+//! error type in place of `std::io` and outputs do not implement `Write`.
+use alloc::{borrow::Cow, format, string::String, vec::Vec};
+
+#[cfg(feature = "std")]
+pub use std::io;
+
+#[cfg(feature = "std")]
+use std::io::Write;
+
+use io::{Error, ErrorKind, Result};
+
+#[cfg(not(feature = "std"))]
+pub mod io {
+    //! Minimal IO error types used when `std` is not available.
+    use alloc::boxed::Box;
+    use core::fmt;
+
+    /// Result type for output operations.
+    pub type Result<T> = core::result::Result<T, Error>;
+
+    /// Kinds of output error.
+    #[derive(Clone, Copy, Debug, Eq, PartialEq)]
+    pub enum ErrorKind {
+        /// Data was not valid for the operation.
+        InvalidData,
+        /// Any other error.
+        Other,
+    }
+
+    /// Error returned by output destinations.
+    #[derive(Debug)]
+    pub struct Error {
+        kind: ErrorKind,
+        error: Box<dyn core::error::Error + Send + Sync>,
+    }
+
+    impl Error {
+        /// Create a new error of the given kind.
+        pub fn new<E>(kind: ErrorKind, error: E) -> Self
+        where
+            E: Into<Box<dyn core::error::Error + Send + Sync>>,
+        {
+            Self {
+                kind,
+                error: error.into(),
+            }
+        }
+
+        /// The kind of this error.
+        pub fn kind(&self) -> ErrorKind {
+            self.kind
+        }
+    }
+
+    impl fmt::Display for Error {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            fmt::Display::fmt(&self.error, f)
+        }
+    }
+
+    impl core::error::Error for Error {}
+}
 
 /// Trait for types that we can render to.
+#[cfg(feature = "std")]
 pub trait Output: Write {
     /// Convenience function as we are typically writing string slices.
     fn write_str(&mut self, s: &str) -> Result<usize>;
+
+    /// Write a byte slice to the output.
+    ///
+    /// The default implementation validates the bytes as UTF-8 and
+    /// delegates to `write_str()`; implementations that do not need
+    /// string data should override this to skip the validation.
+    fn write_bytes(&mut self, b: &[u8]) -> Result<usize> {
+        from_utf8(b).and_then(|s| self.write_str(s))
+    }
+}
+
+/// Trait for types that we can render to.
+#[cfg(not(feature = "std"))]
+pub trait Output {
+    /// Convenience function as we are typically writing string slices.
+    fn write_str(&mut self, s: &str) -> Result<usize>;
+
+    /// Write a byte slice to the output.
+    ///
+    /// The default implementation validates the bytes as UTF-8 and
+    /// delegates to `write_str()`; implementations that do not need
+    /// string data should override this to skip the validation.
+    fn write_bytes(&mut self, b: &[u8]) -> Result<usize> {
+        from_utf8(b).and_then(|s| self.write_str(s))
+    }
+}
+
+fn from_utf8(b: &[u8]) -> Result<&str> {
+    core::str::from_utf8(b).map_err(|e| Error::new(ErrorKind::InvalidData, e))
 }
 
 /// Output type that wraps an `io::Write` writer.
+#[cfg(feature = "std")]
 pub struct Writer<W: Write> {
     writer: W,
 }
 
+#[cfg(feature = "std")]
+impl<W: Write> Writer<W> {
+    /// Create a new output wrapping the given writer.
+    pub fn new(writer: W) -> Self {
+        Self { writer }
+    }
+}
+
+#[cfg(feature = "std")]
 impl<W: Write> Output for Writer<W> {
     fn write_str(&mut self, s: &str) -> Result<usize> {
         self.writer.write(s.as_bytes())
     }
+
+    fn write_bytes(&mut self, b: &[u8]) -> Result<usize> {
+        self.writer.write(b)
+    }
 }
 
+#[cfg(feature = "std")]
 impl<W: Write> Write for Writer<W> {
     fn write(&mut self, buf: &[u8]) -> Result<usize> {
         self.writer.write(buf)
@@ -43,6 +151,29 @@ impl StringOutput {
             value: String::new(),
         }
     }
+
+    /// Create a new string output buffer with at least the
+    /// specified capacity.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            value: String::with_capacity(capacity),
+        }
+    }
+
+    /// Reserve capacity for at least `additional` more bytes.
+    pub fn reserve(&mut self, additional: usize) {
+        self.value.reserve(additional)
+    }
+
+    /// Remove the buffered content keeping the allocated capacity.
+    pub fn clear(&mut self) {
+        self.value.clear()
+    }
+
+    /// Get the buffered content.
+    pub fn as_str(&self) -> &str {
+        &self.value
+    }
 }
 
 impl Into<String> for StringOutput {
@@ -53,13 +184,15 @@ impl Into<String> for StringOutput {
 
 impl Output for StringOutput {
     fn write_str(&mut self, s: &str) -> Result<usize> {
-        self.write(s.as_bytes())
+        self.value.push_str(s);
+        Ok(s.len())
     }
 }
 
+#[cfg(feature = "std")]
 impl Write for StringOutput {
     fn write(&mut self, buf: &[u8]) -> Result<usize> {
-        let s = match std::str::from_utf8(buf) {
+        let s = match core::str::from_utf8(buf) {
             Ok(v) => v,
             Err(e) => panic!("Invalid UTF-8 sequence: {}", e),
         };
@@ -71,3 +204,296 @@ impl Write for StringOutput {
         Ok(())
     }
 }
+
+/// Character encoding for text written to a bytes output.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Encoding {
+    /// Write text as UTF-8.
+    #[default]
+    Utf8,
+    /// Write text as ISO-8859-1; characters above `U+00FF`
+    /// cannot be encoded and are an error.
+    Latin1,
+}
+
+/// Newline normalization for rendered text.
+///
+/// Normalization applies to text from templates and values written
+/// by the renderer; bytes written directly to an output by helpers
+/// or escape functions are not modified.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Newline {
+    /// Write newlines unchanged.
+    #[default]
+    PassThrough,
+    /// Convert `\r\n` and `\r` to `\n`.
+    Lf,
+    /// Convert `\n` and `\r` to `\r\n`.
+    CrLf,
+}
+
+impl Newline {
+    /// Normalize the newlines in a string.
+    ///
+    /// When `after_cr` is set the previous text ended with a carriage
+    /// return so a leading line feed completes that newline and is
+    /// skipped; on return the flag is updated for the next call.
+    pub fn normalize<'a>(
+        &self,
+        s: &'a str,
+        after_cr: &mut bool,
+    ) -> Cow<'a, str> {
+        let target = match *self {
+            Self::PassThrough => return Cow::Borrowed(s),
+            Self::Lf => "\n",
+            Self::CrLf => "\r\n",
+        };
+        let s = if *after_cr {
+            s.strip_prefix('\n').unwrap_or(s)
+        } else {
+            s
+        };
+        *after_cr = s.ends_with('\r');
+        let normalized = match *self {
+            Self::Lf => !s.contains('\r'),
+            _ => !s.contains('\r') && !s.contains('\n'),
+        };
+        if normalized {
+            return Cow::Borrowed(s);
+        }
+        let mut value = String::with_capacity(s.len() + s.len() / 8);
+        let mut chars = s.chars().peekable();
+        while let Some(c) = chars.next() {
+            match c {
+                '\r' => {
+                    if chars.peek() == Some(&'\n') {
+                        chars.next();
+                    }
+                    value.push_str(target);
+                }
+                '\n' => value.push_str(target),
+                _ => value.push(c),
+            }
+        }
+        Cow::Owned(value)
+    }
+}
+
+/// Policy for newlines at the end of rendered output.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum TrailingNewline {
+    /// Write trailing newlines unchanged.
+    #[default]
+    Preserve,
+    /// Ensure non-empty output ends with exactly one newline.
+    Single,
+    /// Remove all trailing newlines.
+    Strip,
+}
+
+/// Output type that buffers into a byte vector.
+///
+/// Unlike [StringOutput](StringOutput) bytes are accepted without
+/// validation so escape functions and helpers may emit data that is
+/// not UTF-8; text is encoded using the output encoding.
+///
+/// Call `into()` to access the result after rendering.
+#[derive(Default)]
+pub struct BytesOutput {
+    value: Vec<u8>,
+    encoding: Encoding,
+}
+
+impl BytesOutput {
+    /// Create a new bytes output buffer that encodes text as UTF-8.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Create a new bytes output buffer with at least the
+    /// specified capacity.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self {
+            value: Vec::with_capacity(capacity),
+            encoding: Default::default(),
+        }
+    }
+
+    /// Create a new bytes output buffer that encodes text as ISO-8859-1.
+    pub fn latin1() -> Self {
+        Self {
+            value: Vec::new(),
+            encoding: Encoding::Latin1,
+        }
+    }
+
+    /// Set the encoding for text written to this output.
+    pub fn set_encoding(&mut self, encoding: Encoding) {
+        self.encoding = encoding;
+    }
+
+    /// The encoding for text written to this output.
+    pub fn encoding(&self) -> Encoding {
+        self.encoding
+    }
+
+    /// Reserve capacity for at least `additional` more bytes.
+    pub fn reserve(&mut self, additional: usize) {
+        self.value.reserve(additional)
+    }
+
+    /// Remove the buffered content keeping the allocated capacity.
+    pub fn clear(&mut self) {
+        self.value.clear()
+    }
+
+    /// Get the buffered content.
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.value
+    }
+}
+
+impl From<BytesOutput> for Vec<u8> {
+    fn from(output: BytesOutput) -> Self {
+        output.value
+    }
+}
+
+impl Output for BytesOutput {
+    fn write_str(&mut self, s: &str) -> Result<usize> {
+        let start = self.value.len();
+        match self.encoding {
+            Encoding::Utf8 => self.value.extend_from_slice(s.as_bytes()),
+            Encoding::Latin1 => {
+                // NOTE: validate first so a failed write
+                // NOTE: does not leave partial output
+                if let Some(c) = s.chars().find(|c| *c as u32 > 0xff) {
+                    return Err(Error::new(
+                        ErrorKind::InvalidData,
+                        format!(
+                            "Character '{}' cannot be encoded as Latin-1",
+                            c
+                        ),
+                    ));
+                }
+                self.value.extend(s.chars().map(|c| c as u8));
+            }
+        }
+        Ok(self.value.len() - start)
+    }
+
+    fn write_bytes(&mut self, b: &[u8]) -> Result<usize> {
+        self.value.extend_from_slice(b);
+        Ok(b.len())
+    }
+}
+
+#[cfg(feature = "std")]
+impl Write for BytesOutput {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        self.write_bytes(buf)
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        Ok(())
+    }
+}
+
+/// Output adapter that holds back trailing newlines so that a
+/// trailing newline policy can be applied when rendering finishes.
+pub(crate) struct TrailingOutput<'a> {
+    writer: &'a mut dyn Output,
+    policy: TrailingNewline,
+    newline: &'static str,
+    pending: String,
+    empty: bool,
+}
+
+impl<'a> TrailingOutput<'a> {
+    /// Create an adapter; the `newline` mode determines the newline
+    /// to add when the output does not already end with one.
+    pub(crate) fn new(
+        writer: &'a mut dyn Output,
+        policy: TrailingNewline,
+        newline: Newline,
+    ) -> Self {
+        let newline = match newline {
+            Newline::CrLf => "\r\n",
+            _ => "\n",
+        };
+        Self {
+            writer,
+            policy,
+            newline,
+            pending: String::new(),
+            empty: true,
+        }
+    }
+
+    /// Apply the policy to the held back newlines.
+    pub(crate) fn finish(self) -> Result<()> {
+        let newline = if self.pending.starts_with("\r\n") {
+            "\r\n"
+        } else if self.pending.starts_with('\n') {
+            "\n"
+        } else {
+            self.newline
+        };
+        match self.policy {
+            TrailingNewline::Preserve => {
+                self.writer.write_str(&self.pending)?;
+            }
+            TrailingNewline::Single if !self.empty => {
+                self.writer.write_str(newline)?;
+            }
+            _ => {}
+        }
+        Ok(())
+    }
+
+    /// Write the held back newlines before more output.
+    fn write_pending(&mut self) -> Result<()> {
+        if !self.pending.is_empty() {
+            self.writer.write_str(&self.pending)?;
+            self.pending.clear();
+        }
+        self.empty = false;
+        Ok(())
+    }
+}
+
+impl Output for TrailingOutput<'_> {
+    fn write_str(&mut self, s: &str) -> Result<usize> {
+        let text = s.trim_end_matches(['\n', '\r']);
+        if !text.is_empty() {
+            self.write_pending()?;
+            self.writer.write_str(text)?;
+        }
+        self.pending.push_str(&s[text.len()..]);
+        Ok(s.len())
+    }
+
+    fn write_bytes(&mut self, b: &[u8]) -> Result<usize> {
+        let len = b
+            .iter()
+            .rposition(|c| *c != b'\n' && *c != b'\r')
+            .map_or(0, |i| i + 1);
+        if len > 0 {
+            self.write_pending()?;
+            self.writer.write_bytes(&b[..len])?;
+        }
+        self.pending.extend(b[len..].iter().map(|c| char::from(*c)));
+        Ok(b.len())
+    }
+}
+
+#[cfg(feature = "std")]
+impl Write for TrailingOutput<'_> {
+    fn write(&mut self, buf: &[u8]) -> Result<usize> {
+        self.write_bytes(buf)
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        self.writer.flush()
+    }
+}
@@ -1,14 +1,19 @@
 //! Abstract syntax tree node types.
-use std::collections::HashMap;
-use std::fmt;
-use std::ops::Range;
+use alloc::{borrow::Cow, boxed::Box, string::String, vec, vec::Vec};
+use core::fmt;
+use core::hash::{Hash, Hasher};
+use core::ops::Range;
 
 use serde_json::Value;
 
-use crate::{parser::iter::BranchIter, trim::TrimHint};
+use crate::{
+    parser::iter::{BranchIter, BreadthFirst, Children, DepthFirst},
+    trim::TrimHint,
+};
 
 const WHITESPACE: &str = "~";
 const ROOT: &str = "@root";
+const UNLESS: &str = "unless";
 //pub const LEVEL: &str = "@level";
 
 /// Trait for nodes that reference a slice of the
@@ -75,7 +80,10 @@ pub trait Element<'source> {
 ///
 /// Every node provides access to a [TrimHint](crate::trim::TrimHint) used
 /// by the renderer to determine how whitespace should be handled.
-#[derive(Eq, PartialEq)]
+///
+/// Equality and hashing are structural and compare the content
+/// of nodes ignoring spans and line numbers so identical templates
+/// compiled from different sources are equal.
 pub enum Node<'source> {
     /// Document nodes encapsulate a collection of children.
     Document(Document<'source>),
@@ -106,6 +114,67 @@ impl Default for Node<'_> {
     }
 }
 
+impl PartialEq for Node<'_> {
+    fn eq(&self, other: &Self) -> bool {
+        match (self, other) {
+            (Self::Document(a), Self::Document(b)) => a.nodes() == b.nodes(),
+            (Self::Block(a), Self::Block(b)) => {
+                block_eq(a, b) && a.close() == b.close()
+            }
+            (Self::Text(a), Self::Text(b)) => a.value() == b.value(),
+            (Self::Statement(_), Self::Statement(_))
+            | (Self::RawStatement(_), Self::RawStatement(_))
+            | (Self::RawComment(_), Self::RawComment(_))
+            | (Self::Comment(_), Self::Comment(_))
+            | (Self::Link(_), Self::Link(_)) => self.as_str() == other.as_str(),
+            _ => false,
+        }
+    }
+}
+
+impl Eq for Node<'_> {}
+
+impl Hash for Node<'_> {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        core::mem::discriminant(self).hash(state);
+        match *self {
+            Self::Document(ref n) => n.nodes().hash(state),
+            Self::Block(ref n) => {
+                block_hash(n, state);
+                n.close().hash(state);
+            }
+            Self::Text(ref n) => n.value().hash(state),
+            _ => self.as_str().hash(state),
+        }
+    }
+}
+
+// NOTE: the close span for conditional blocks is an implementation
+// NOTE: detail of whitespace handling so it is not compared.
+fn block_eq(a: &Block<'_>, b: &Block<'_>) -> bool {
+    a.call().as_str() == b.call().as_str()
+        && a.nodes() == b.nodes()
+        && a.conditions().len() == b.conditions().len()
+        && a.conditions()
+            .iter()
+            .zip(b.conditions().iter())
+            .all(|pair| match pair {
+                (Node::Block(a), Node::Block(b)) => block_eq(a, b),
+                (a, b) => a == b,
+            })
+}
+
+fn block_hash<H: Hasher>(block: &Block<'_>, state: &mut H) {
+    block.call().as_str().hash(state);
+    block.nodes().hash(state);
+    for c in block.conditions() {
+        match c {
+            Node::Block(ref n) => block_hash(n, state),
+            _ => c.hash(state),
+        }
+    }
+}
+
 impl<'source> Node<'source> {
     /// Get the trim hint for this node.
     pub fn trim(&self) -> TrimHint {
@@ -145,6 +214,61 @@ impl<'source> Node<'source> {
     pub fn into_iter<'a>(&'a self) -> BranchIter<'a> {
         BranchIter::new(self)
     }
+
+    /// Iterate this node and all descendants breadth-first.
+    pub fn iter<'a>(&'a self) -> BreadthFirst<'a> {
+        BreadthFirst::new(self)
+    }
+
+    /// Iterate this node and all descendants depth-first.
+    pub fn iter_depth_first<'a>(&'a self) -> DepthFirst<'a> {
+        DepthFirst::new(self)
+    }
+
+    /// Iterate all descendants of this node depth-first.
+    ///
+    /// Unlike `into_iter()` this includes nested nodes and
+    /// the conditional branches of blocks.
+    pub fn descendants<'a>(&'a self) -> DepthFirst<'a> {
+        DepthFirst::descendants(self)
+    }
+
+    /// Iterate the direct children of this node including
+    /// the conditional branches of blocks.
+    pub fn children<'a>(&'a self) -> Children<'a> {
+        Children::new(self)
+    }
+
+    /// Call a function for every text node in this node and all
+    /// descendants including the conditional branches of blocks.
+    ///
+    /// Use this to transform the text written when rendering,
+    /// see [set_value()](Text#method.set_value).
+    pub fn for_each_text<F>(&mut self, f: &mut F)
+    where
+        F: FnMut(&mut Text<'source>),
+    {
+        match *self {
+            Self::Text(ref mut n) => f(n),
+            Self::Document(ref mut n) => {
+                n.nodes_mut().iter_mut().for_each(|n| n.for_each_text(f))
+            }
+            Self::Block(ref mut n) => {
+                n.nodes.iter_mut().for_each(|n| n.for_each_text(f));
+                n.conditionals.iter_mut().for_each(|n| n.for_each_text(f));
+            }
+            _ => {}
+        }
+    }
+}
+
+impl<'source> IntoIterator for &'source Node<'source> {
+    type Item = &'source Node<'source>;
+    type IntoIter = BranchIter<'source>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        BranchIter::new(self)
+    }
 }
 
 impl<'source> Slice<'source> for Node<'source> {
@@ -206,11 +330,16 @@ impl fmt::Debug for Node<'_> {
 }
 
 /// Text nodes refer to a consecutive range of bytes.
+///
+/// The value written when rendering borrows the source range
+/// until it is replaced using [set_value()](#method.set_value);
+/// the span and `as_str()` always refer to the original source.
 #[derive(Eq, PartialEq)]
 pub struct Text<'source> {
     source: &'source str,
     span: Range<usize>,
     line: Range<usize>,
+    value: Cow<'source, str>,
 }
 
 impl<'source> Text<'source> {
@@ -220,7 +349,36 @@ impl<'source> Text<'source> {
         span: Range<usize>,
         line: Range<usize>,
     ) -> Self {
-        Self { source, span, line }
+        let value = Cow::Borrowed(&source[span.start..span.end]);
+        Self {
+            source,
+            span,
+            line,
+            value,
+        }
+    }
+
+    /// The byte range for this text.
+    pub fn span(&self) -> &Range<usize> {
+        &self.span
+    }
+
+    /// The text written when rendering.
+    pub fn value(&self) -> &str {
+        &self.value
+    }
+
+    /// Replace the text written when rendering.
+    pub fn set_value<V>(&mut self, value: V)
+    where
+        V: Into<Cow<'source, str>>,
+    {
+        self.value = value.into();
+    }
+
+    /// Determine if the value has been replaced.
+    pub fn is_owned(&self) -> bool {
+        matches!(self.value, Cow::Owned(_))
     }
 }
 
@@ -252,8 +410,12 @@ impl fmt::Display for Text<'_> {
 
 impl fmt::Debug for Text<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        f.debug_struct("Text")
-            .field("source", &self.as_str())
+        let mut debug = f.debug_struct("Text");
+        debug.field("source", &self.as_str());
+        if self.is_owned() {
+            debug.field("value", &self.value());
+        }
+        debug
             .field("span", &self.span)
             .field("line", &self.line)
             .finish()
@@ -285,6 +447,12 @@ impl<'source> TextBlock<'source> {
             close,
         }
     }
+
+    /// The byte range for this text block including the open
+    /// and close tags.
+    pub fn span(&self) -> Range<usize> {
+        self.open.start..self.close.end
+    }
 }
 
 impl<'source> Slice<'source> for TextBlock<'source> {
@@ -663,6 +831,17 @@ impl<'source> From<(&'source str, Value, Range<usize>, Range<usize>)>
     }
 }
 
+impl<'source> ParameterValue<'source> {
+    /// Get the byte span for the value.
+    pub fn span(&self) -> Range<usize> {
+        match *self {
+            Self::Path(ref path) => path.span().clone(),
+            Self::Json { ref span, .. } => span.clone(),
+            Self::SubExpr(ref call) => call.span(),
+        }
+    }
+}
+
 impl<'source> Slice<'source> for ParameterValue<'source> {
     fn as_str(&self) -> &'source str {
         match *self {
@@ -783,6 +962,106 @@ impl Default for CallTarget<'_> {
     }
 }
 
+/// Hash parameters for a call in the order they were declared.
+///
+/// Adding a parameter with an existing key replaces the value but
+/// keeps the position of the original declaration.
+#[derive(Default, Eq, PartialEq)]
+pub struct Parameters<'source> {
+    entries: Vec<(&'source str, ParameterValue<'source>)>,
+}
+
+impl<'source> Parameters<'source> {
+    /// Create an empty collection of parameters.
+    pub fn new() -> Self {
+        Self {
+            entries: Vec::new(),
+        }
+    }
+
+    /// Add a parameter returning the previous value for the key.
+    pub fn insert(
+        &mut self,
+        key: &'source str,
+        value: ParameterValue<'source>,
+    ) -> Option<ParameterValue<'source>> {
+        if let Some(entry) = self.entries.iter_mut().find(|(k, _)| *k == key) {
+            Some(core::mem::replace(&mut entry.1, value))
+        } else {
+            self.entries.push((key, value));
+            None
+        }
+    }
+
+    /// Get the value for a key.
+    pub fn get(&self, key: &str) -> Option<&ParameterValue<'source>> {
+        self.entries.iter().find(|(k, _)| *k == key).map(|(_, v)| v)
+    }
+
+    /// Get a mutable value for a key.
+    pub(crate) fn get_mut(
+        &mut self,
+        key: &str,
+    ) -> Option<&mut ParameterValue<'source>> {
+        self.entries
+            .iter_mut()
+            .find(|(k, _)| *k == key)
+            .map(|(_, v)| v)
+    }
+
+    /// Determine if a key exists.
+    pub fn contains_key(&self, key: &str) -> bool {
+        self.get(key).is_some()
+    }
+
+    /// Number of parameters.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Determine if there are no parameters.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Iterate the keys and values in declaration order.
+    pub fn iter(
+        &self,
+    ) -> impl Iterator<Item = (&&'source str, &ParameterValue<'source>)> {
+        self.entries.iter().map(|(k, v)| (k, v))
+    }
+
+    /// Iterate the keys in declaration order.
+    pub fn keys(&self) -> impl Iterator<Item = &&'source str> {
+        self.entries.iter().map(|(k, _)| k)
+    }
+
+    /// Iterate the values in declaration order.
+    pub fn values(&self) -> impl Iterator<Item = &ParameterValue<'source>> {
+        self.entries.iter().map(|(_, v)| v)
+    }
+}
+
+impl<'a, 'source> IntoIterator for &'a Parameters<'source> {
+    type Item = (&'a &'source str, &'a ParameterValue<'source>);
+    type IntoIter = core::iter::Map<
+        core::slice::Iter<'a, (&'source str, ParameterValue<'source>)>,
+        fn(
+            &'a (&'source str, ParameterValue<'source>),
+        ) -> (&'a &'source str, &'a ParameterValue<'source>),
+    >;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.entries.iter().map(|(k, v)| (k, v))
+    }
+}
+
+impl fmt::Debug for Parameters<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_map().entries(self.iter()).finish()
+    }
+}
+
 /// Call is a variable interpolation, helper invocation or partial
 /// render.
 ///
@@ -800,7 +1079,8 @@ pub struct Call<'source> {
     close: Option<Range<usize>>,
     target: CallTarget<'source>,
     arguments: Vec<ParameterValue<'source>>,
-    parameters: HashMap<&'source str, ParameterValue<'source>>,
+    parameters: Parameters<'source>,
+    block_params: Vec<&'source str>,
     line: Range<usize>,
 }
 
@@ -822,7 +1102,8 @@ impl<'source> Call<'source> {
             close: None,
             target: CallTarget::Path(Path::new(source, 0..0, 0..0)),
             arguments: Vec::new(),
-            parameters: HashMap::new(),
+            parameters: Parameters::new(),
+            block_params: Vec::new(),
             line,
         }
     }
@@ -857,6 +1138,13 @@ impl<'source> Call<'source> {
         &self.arguments
     }
 
+    /// Get the mutable list of arguments.
+    pub(crate) fn arguments_mut(
+        &mut self,
+    ) -> &mut Vec<ParameterValue<'source>> {
+        &mut self.arguments
+    }
+
     /// Add a hash parameter to this call.
     pub fn add_parameter(
         &mut self,
@@ -866,13 +1154,27 @@ impl<'source> Call<'source> {
         self.parameters.insert(key, val);
     }
 
-    /// Get the map of hash parameters.
-    pub fn parameters(
-        &self,
-    ) -> &HashMap<&'source str, ParameterValue<'source>> {
+    /// Get the hash parameters in declaration order.
+    pub fn parameters(&self) -> &Parameters<'source> {
         &self.parameters
     }
 
+    /// Get the mutable hash parameters.
+    pub(crate) fn parameters_mut(&mut self) -> &mut Parameters<'source> {
+        &mut self.parameters
+    }
+
+    /// Get the names of the block parameters declared
+    /// using `as |name|` when opening a block.
+    pub fn block_params(&self) -> &[&'source str] {
+        &self.block_params
+    }
+
+    /// Add a block parameter name.
+    pub(crate) fn add_block_param(&mut self, name: &'source str) {
+        self.block_params.push(name);
+    }
+
     /// Determine if this call has the partial flag.
     pub fn is_partial(&self) -> bool {
         self.partial
@@ -899,6 +1201,53 @@ impl<'source> Call<'source> {
         !self.open().starts_with("{{{")
     }
 
+    /// Move the target, arguments and parameters of this call into a
+    /// sub-expression that becomes the first argument of this call.
+    ///
+    /// The parser uses this to desugar filter pipes so that
+    /// `{{name | upper}}` is equivalent to `{{upper (name)}}`;
+    /// `span` is the source of the sub-expression.
+    #[cfg(feature = "pipes")]
+    pub(crate) fn pipe(&mut self, span: Range<usize>) {
+        let inner = Call {
+            source: self.source,
+            partial: false,
+            conditional: false,
+            open: span.start..span.start,
+            close: Some(span.end..span.end),
+            target: core::mem::take(&mut self.target),
+            arguments: core::mem::take(&mut self.arguments),
+            parameters: core::mem::take(&mut self.parameters),
+            block_params: Vec::new(),
+            line: self.line.clone(),
+        };
+        self.arguments.push(ParameterValue::SubExpr(inner));
+    }
+
+    /// Move the target path of this call to the first argument and
+    /// set the target to the `unless` helper.
+    ///
+    /// The parser uses this to desugar inverted sections so that
+    /// `{{^name}}` is equivalent to `{{#unless name}}`; the new target
+    /// path does not reference the template source.
+    pub(crate) fn invert(&mut self) {
+        let target = core::mem::take(&mut self.target);
+        if let CallTarget::Path(path) = target {
+            let mut unless =
+                Path::new(UNLESS, 0..UNLESS.len(), path.lines().clone());
+            unless.add_component(Component::new(
+                UNLESS,
+                ComponentType::Identifier,
+                0..UNLESS.len(),
+                None,
+            ));
+            self.arguments.insert(0, ParameterValue::Path(path));
+            self.target = CallTarget::Path(unless);
+        } else {
+            self.target = target;
+        }
+    }
+
     fn trim_before(&self) -> bool {
         self.open().ends_with(WHITESPACE)
     }
@@ -976,6 +1325,7 @@ impl fmt::Debug for Call<'_> {
             .field("target", &self.target)
             .field("arguments", &self.arguments)
             .field("parameters", &self.parameters)
+            .field("block_params", &self.block_params)
             .finish()
     }
 }
@@ -984,6 +1334,10 @@ impl fmt::Debug for Call<'_> {
 /// of child nodes.
 ///
 /// They are used as the root node of a compiled template.
+///
+/// The `Display` implementation writes each child node so the
+/// output reproduces the template source, including any changes
+/// made to the child nodes.
 #[derive(Eq, PartialEq)]
 pub struct Document<'source>(pub &'source str, pub Vec<Node<'source>>);
 
@@ -1010,7 +1364,15 @@ impl<'source> Slice<'source> for Document<'source> {
 
 impl fmt::Display for Document<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{}", self.as_str())
+        // NOTE: the lexer skips a leading byte order mark so
+        // NOTE: it is not part of the first node
+        if self.0.starts_with('\u{feff}') {
+            f.write_str("\u{feff}")?;
+        }
+        for t in self.nodes() {
+            t.fmt(f)?;
+        }
+        Ok(())
     }
 }
 
@@ -1064,6 +1426,11 @@ impl<'source> Block<'source> {
         &self.call
     }
 
+    /// Get the mutable call for the block.
+    pub(crate) fn call_mut(&mut self) -> &mut Call<'source> {
+        &mut self.call
+    }
+
     /// Set the call for the block.
     pub fn set_call(&mut self, call: Call<'source>) {
         self.call = call;
@@ -1126,6 +1493,12 @@ impl<'source> Block<'source> {
         &self.nodes
     }
 
+    /// The mutable collection of nodes for this block
+    /// excluding any conditionals.
+    pub(crate) fn nodes_mut(&mut self) -> &mut Vec<Node<'source>> {
+        &mut self.nodes
+    }
+
     /// The trim hint for the close tag.
     pub fn trim_close(&self) -> TrimHint {
         TrimHint {
@@ -1259,10 +1632,22 @@ impl<'source> Element<'source> for Block<'source> {
 
 impl fmt::Display for Block<'_> {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.call.as_str())?;
         for t in self.nodes() {
             t.fmt(f)?;
         }
-        Ok(())
+
+        // NOTE: conditionals share the close tag of the parent block
+        // NOTE: so we only write the open tag and child nodes.
+        for c in self.conditions() {
+            if let Node::Block(ref condition) = c {
+                write!(f, "{}", condition.call.as_str())?;
+                for t in condition.nodes() {
+                    t.fmt(f)?;
+                }
+            }
+        }
+        write!(f, "{}", self.close())
     }
 }
 
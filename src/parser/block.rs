@@ -1,10 +1,13 @@
-use std::ops::Range;
+use alloc::{format, vec};
+use core::ops::Range;
 
 use crate::{
     error::{ErrorInfo, SyntaxError},
     lexer::{self, Lexer, Token},
     parser::{
-        ast::{Block, Element, Lines, Node, Slice, Text, TextBlock},
+        ast::{
+            Block, CallTarget, Element, Lines, Node, Slice, Text, TextBlock,
+        },
         call::{self, CallParseContext},
         ParseState,
     },
@@ -68,14 +71,15 @@ pub(crate) fn raw<'source>(
     let call =
         call::parse(source, lexer, state, span.clone(), CallParseContext::Raw)?;
 
-    if !call.is_closed() {
-        return Err(SyntaxError::RawBlockOpenNotTerminated(
-            ErrorInfo::from((source, state)).into(),
-        ));
-    }
-
     // NOTE: must have an accurate end span before reading the Text chunk!
-    let end_span = call.close_span().clone().unwrap();
+    let end_span = match call.close_span() {
+        Some(span) => span.clone(),
+        None => {
+            return Err(SyntaxError::RawBlockOpenNotTerminated(
+                ErrorInfo::from((source, state)).into(),
+            ))
+        }
+    };
 
     let open_name = call.target().as_str();
 
@@ -239,3 +243,28 @@ pub(crate) fn scope<'source>(
     block.set_call(call);
     Ok(block)
 }
+
+/// Parse an inverted section (`{{^name}}`) as an `unless` block.
+///
+/// Returns the block and the name of the section which must match
+/// the close tag.
+pub(crate) fn inverted<'source>(
+    source: &'source str,
+    lexer: &mut Lexer<'source>,
+    state: &mut ParseState,
+    span: Range<usize>,
+) -> SyntaxResult<(Option<&'source str>, Block<'source>)> {
+    let mut block = Block::new(source, span.clone(), false, state.line_range());
+    let mut call =
+        call::parse(source, lexer, state, span, CallParseContext::Block)?;
+    let name = match call.target() {
+        CallTarget::Path(ref path) if path.is_simple() => Some(path.as_str()),
+        _ => None,
+    }
+    .filter(|_| !call.is_partial());
+    if name.is_some() {
+        call.invert();
+    }
+    block.set_call(call);
+    Ok((name, block))
+}
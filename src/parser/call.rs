@@ -1,12 +1,13 @@
+use alloc::{boxed::Box, string::String};
+use core::ops::Range;
 use serde_json::{Number, Value};
-use std::ops::Range;
 
 use crate::{
     error::{ErrorInfo, SyntaxError},
     lexer::{Lexer, Parameters, Token},
     parser::{
         ast::{Call, CallTarget, Element, Lines, ParameterValue},
-        path, string, ParseState,
+        hints, path, string, ParseState,
     },
     SyntaxResult,
 };
@@ -33,9 +34,12 @@ pub(crate) enum CallParseContext {
 ///
 /// Sub expressions do not parse partial information and must
 /// use a path for the call target.
-#[derive(Eq, PartialEq)]
+///
+/// Filter pipes are not allowed when opening a block.
+#[derive(Clone, Copy, Eq, PartialEq)]
 enum CallContext {
     Call,
+    Block,
     SubExpr,
 }
 
@@ -53,7 +57,15 @@ fn json_literal<'source>(
         Parameters::True => Value::Bool(true),
         Parameters::False => Value::Bool(false),
         Parameters::Number => {
-            let num: Number = source[span].parse().unwrap();
+            let num: Number = match source[span.clone()].parse() {
+                Ok(num) => num,
+                Err(_) => {
+                    *state.byte_mut() = span.start;
+                    return Err(SyntaxError::NumberLiteral(
+                        ErrorInfo::from((source, state)).into(),
+                    ));
+                }
+            };
             Value::Number(num)
         }
         // NOTE: For string literal values we need to add one
@@ -157,7 +169,6 @@ fn value<'source>(
             ))
         }
         _ => {
-            println!("Value for unknown token {:?}", &lex);
             return Err(SyntaxError::TokenParameter(
                 ErrorInfo::from((source, state)).into(),
             ));
@@ -165,6 +176,66 @@ fn value<'source>(
     }
 }
 
+/// Determine if an identifier is the `as` keyword
+/// that starts block parameters.
+fn is_block_params(source: &str, span: &Range<usize>) -> bool {
+    let rest = &source[span.end..];
+    source.get(span.clone()) == Some("as")
+        && rest.starts_with(char::is_whitespace)
+        && rest.trim_start().starts_with('|')
+}
+
+/// Parse the block parameters after the `as` keyword
+/// which must be the end of the open tag.
+fn block_params<'source>(
+    source: &'source str,
+    lexer: &mut Lexer<'source>,
+    state: &mut ParseState,
+    call: &mut Call<'source>,
+) -> SyntaxResult<Option<Token>> {
+    let mut opened = false;
+    let mut closed = false;
+    for token in lexer.by_ref() {
+        match token {
+            Token::Parameters(lex, span) => {
+                *state.byte_mut() = span.start;
+                match &lex {
+                    Parameters::WhiteSpace | Parameters::Newline => {
+                        if lex == Parameters::Newline {
+                            *state.line_mut() += 1;
+                        }
+                    }
+                    Parameters::Pipe if !opened => opened = true,
+                    Parameters::Pipe
+                        if !closed && !call.block_params().is_empty() =>
+                    {
+                        closed = true
+                    }
+                    Parameters::Identifier if opened && !closed => {
+                        path::identifier(source, state, span.clone())?;
+                        call.add_block_param(&source[span]);
+                    }
+                    Parameters::End if closed => {
+                        call.exit(span);
+                        return Ok(None);
+                    }
+                    _ => {
+                        return Err(SyntaxError::BlockParams(
+                            ErrorInfo::from((source, state)).into(),
+                        ));
+                    }
+                }
+            }
+            _ => {
+                return Err(SyntaxError::BlockParams(
+                    ErrorInfo::from((source, state)).into(),
+                ));
+            }
+        }
+    }
+    Ok(None)
+}
+
 fn key_value<'source>(
     source: &'source str,
     lexer: &mut Lexer<'source>,
@@ -174,6 +245,7 @@ fn key_value<'source>(
     context: CallContext,
 ) -> SyntaxResult<Option<Token>> {
     let (_lex, span) = current;
+    path::identifier(source, state, span.start..span.end - 1)?;
     let key = &source[span.start..span.end - 1];
     let mut next: Option<Token> = None;
 
@@ -214,6 +286,16 @@ fn key_value<'source>(
                         context,
                     );
                 }
+                Parameters::Identifier
+                    if context == CallContext::Block
+                        && is_block_params(source, &span) =>
+                {
+                    return block_params(source, lexer, state, call);
+                }
+                #[cfg(feature = "pipes")]
+                Parameters::Pipe => {
+                    return pipe(source, lexer, state, call, span, context);
+                }
                 Parameters::End => {
                     call.exit(span);
                     return Ok(None);
@@ -272,6 +354,13 @@ fn arguments<'source>(
                         ))
                     }
                     Parameters::ElseKeyword => {}
+                    // Block parameters
+                    Parameters::Identifier
+                        if context == CallContext::Block
+                            && is_block_params(source, &span) =>
+                    {
+                        return block_params(source, lexer, state, call);
+                    }
                     // Path components
                     Parameters::ExplicitThisKeyword
                     | Parameters::PathDelimiter
@@ -351,12 +440,24 @@ fn arguments<'source>(
                             ));
                         }
                     }
-                    Parameters::Error => {
+                    #[cfg(feature = "pipes")]
+                    Parameters::Pipe => {
+                        return pipe(source, lexer, state, call, span, context);
+                    }
+                    #[cfg(not(feature = "pipes"))]
+                    Parameters::Pipe => {
                         return Err(SyntaxError::TokenError(
                             String::from("parameters"),
                             ErrorInfo::from((source, state)).into(),
                         ))
                     }
+                    Parameters::Error => {
+                        let notes = hints::error_token(source, &span);
+                        return Err(SyntaxError::TokenError(
+                            String::from("parameters"),
+                            ErrorInfo::from((source, state, notes)).into(),
+                        ));
+                    }
                     Parameters::End => {
                         if context != CallContext::SubExpr {
                             call.exit(span);
@@ -376,6 +477,90 @@ fn arguments<'source>(
     Ok(None)
 }
 
+/// Parse a filter pipe.
+///
+/// The call parsed so far becomes a sub-expression which is the first
+/// argument for the filter named after the pipe, so `{{name | upper}}`
+/// is equivalent to `{{upper (name)}}`; any further arguments are
+/// passed to the filter.
+#[cfg(feature = "pipes")]
+fn pipe<'source>(
+    source: &'source str,
+    lexer: &mut Lexer<'source>,
+    state: &mut ParseState,
+    call: &mut Call<'source>,
+    span: Range<usize>,
+    context: CallContext,
+) -> SyntaxResult<Option<Token>> {
+    *state.byte_mut() = span.start;
+
+    if context == CallContext::Block
+        || call.is_partial()
+        || call.is_conditional()
+    {
+        return Err(SyntaxError::PipeNotAllowed(
+            ErrorInfo::from((source, state)).into(),
+        ));
+    }
+
+    if !call.has_target() {
+        return Err(SyntaxError::ExpectedIdentifier(
+            ErrorInfo::from((source, state)).into(),
+        ));
+    }
+
+    // Source of the sub-expression without surrounding whitespace
+    let open = call.open_span().end;
+    let inner = &source[open..span.start];
+    let start = open + (inner.len() - inner.trim_start().len());
+    let end = open + inner.trim_end().len();
+    call.pipe(start..end);
+
+    let mut next = lexer.next();
+    while let Some(token) = next {
+        match token {
+            Token::Parameters(lex, span) => match &lex {
+                Parameters::WhiteSpace | Parameters::Newline => {
+                    if lex == Parameters::Newline {
+                        *state.line_mut() += 1;
+                    }
+                }
+                Parameters::Identifier => {
+                    *state.byte_mut() = span.start;
+                    let (mut path, token) =
+                        path::parse(source, lexer, state, (lex, span))?;
+                    match path.take() {
+                        Some(path) if path.is_simple() => {
+                            call.set_target(CallTarget::Path(path));
+                        }
+                        _ => {
+                            return Err(SyntaxError::ExpectedIdentifier(
+                                ErrorInfo::from((source, state)).into(),
+                            ));
+                        }
+                    }
+                    return arguments(
+                        source, lexer, state, call, token, context,
+                    );
+                }
+                _ => {
+                    *state.byte_mut() = span.start;
+                    return Err(SyntaxError::ExpectedIdentifier(
+                        ErrorInfo::from((source, state)).into(),
+                    ));
+                }
+            },
+            _ => {
+                return Err(SyntaxError::TokenParameter(
+                    ErrorInfo::from((source, state)).into(),
+                ));
+            }
+        }
+        next = lexer.next();
+    }
+    Ok(None)
+}
+
 /// Parse the call target.
 fn target<'source>(
     source: &'source str,
@@ -498,14 +683,33 @@ pub(crate) fn sub_expr<'source>(
     state: &mut ParseState,
     open: Range<usize>,
 ) -> SyntaxResult<(Call<'source>, Option<Token>)> {
+    if *state.depth() >= state.max_depth() {
+        *state.byte_mut() = open.start;
+        return Err(SyntaxError::NestingDepth(
+            state.max_depth(),
+            ErrorInfo::from((source, state)).into(),
+        ));
+    }
+
     *state.byte_mut() = open.end;
 
     let mut call = Call::new(source, open, state.line_range());
     let next = lexer.next();
-    let next =
-        target(source, lexer, state, &mut call, next, CallContext::SubExpr)?;
-    let next =
-        arguments(source, lexer, state, &mut call, next, CallContext::SubExpr)?;
+    *state.depth_mut() += 1;
+    let result =
+        target(source, lexer, state, &mut call, next, CallContext::SubExpr)
+            .and_then(|next| {
+                arguments(
+                    source,
+                    lexer,
+                    state,
+                    &mut call,
+                    next,
+                    CallContext::SubExpr,
+                )
+            });
+    *state.depth_mut() -= 1;
+    let next = result?;
     if !call.is_closed() {
         return Err(SyntaxError::SubExpressionNotTerminated(
             ErrorInfo::from((source, state)).into(),
@@ -523,8 +727,13 @@ pub(crate) fn parse<'source>(
     state: &mut ParseState,
     open: Range<usize>,
     // TODO: use this to determine whether `else` keyword is legal
-    _parse_context: CallParseContext,
+    parse_context: CallParseContext,
 ) -> SyntaxResult<Call<'source>> {
+    let context = match parse_context {
+        CallParseContext::Block | CallParseContext::Raw => CallContext::Block,
+        _ => CallContext::Call,
+    };
+
     *state.byte_mut() = open.end;
 
     let mut call = Call::new(source, open, state.line_range());
@@ -537,13 +746,18 @@ pub(crate) fn parse<'source>(
         ));
     }
 
-    let next =
-        target(source, lexer, state, &mut call, next, CallContext::Call)?;
-    let _next =
-        arguments(source, lexer, state, &mut call, next, CallContext::Call)?;
+    let next = target(source, lexer, state, &mut call, next, context)?;
+    let _next = arguments(source, lexer, state, &mut call, next, context)?;
 
     // FIXME: should we return the next token here so it is consumed ???
 
+    // NOTE: raw blocks report their own more specific errors.
+    if !call.is_closed() && parse_context != CallParseContext::Raw {
+        return Err(SyntaxError::StatementNotTerminated(
+            ErrorInfo::from((source, state)).into(),
+        ));
+    }
+
     call.lines_end(state.line());
 
     Ok(call)
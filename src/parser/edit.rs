@@ -0,0 +1,209 @@
+//! Rewrite template source using the spans of AST nodes.
+//!
+//! A [Rewriter](Rewriter) records replacement text for byte spans of
+//! the original source and produces the patched source string so that
+//! codemods can be written without manually adjusting offsets:
+//!
+//! ```ignore
+//! let source = "{{#each items}}{{fmt this}}{{/each}}";
+//! let node = registry.parse("codemod", source)?;
+//! let mut rewriter = Rewriter::new(source);
+//! for node in node.descendants() {
+//!     match node {
+//!         Node::Statement(ref call) if call.as_str().contains("fmt") => {
+//!             rewriter.rename_call(call, "format")?;
+//!             rewriter.add_parameter(call, "lang", "\"en\"")?;
+//!         }
+//!         _ => {}
+//!     }
+//! }
+//! assert_eq!(
+//!     "{{#each items}}{{format this lang=\"en\"}}{{/each}}",
+//!     rewriter.finish());
+//! ```
+//!
+//! Spans always refer to the original source; edits may not overlap
+//! but any number of insertions may be made at the same offset and
+//! they are applied in the order they were recorded.
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::ops::Range;
+
+use crate::{
+    error::EditError,
+    parser::ast::{Block, Call, CallTarget, Element, Slice},
+};
+
+type EditResult = core::result::Result<(), EditError>;
+
+#[derive(Debug)]
+struct Edit {
+    span: Range<usize>,
+    text: String,
+}
+
+/// Record edits to a template source string.
+#[derive(Debug)]
+pub struct Rewriter<'source> {
+    source: &'source str,
+    edits: Vec<Edit>,
+}
+
+impl<'source> Rewriter<'source> {
+    /// Create a rewriter for the template source.
+    pub fn new(source: &'source str) -> Self {
+        Self {
+            source,
+            edits: Vec::new(),
+        }
+    }
+
+    /// The original template source.
+    pub fn source(&self) -> &'source str {
+        self.source
+    }
+
+    /// Number of recorded edits.
+    pub fn len(&self) -> usize {
+        self.edits.len()
+    }
+
+    /// Determine if no edits have been recorded.
+    pub fn is_empty(&self) -> bool {
+        self.edits.is_empty()
+    }
+
+    /// Replace the text for a span.
+    pub fn replace<S>(&mut self, span: Range<usize>, text: S) -> EditResult
+    where
+        S: Into<String>,
+    {
+        self.check(&span)?;
+        self.edits.push(Edit {
+            span,
+            text: text.into(),
+        });
+        Ok(())
+    }
+
+    /// Insert text at a byte offset.
+    pub fn insert<S>(&mut self, offset: usize, text: S) -> EditResult
+    where
+        S: Into<String>,
+    {
+        self.replace(offset..offset, text)
+    }
+
+    /// Remove the text for a span.
+    pub fn remove(&mut self, span: Range<usize>) -> EditResult {
+        self.replace(span, "")
+    }
+
+    /// Rename the target of a call.
+    ///
+    /// The call target must be a path; sub-expression targets used
+    /// for dynamic partials cannot be renamed.
+    pub fn rename_call(&mut self, call: &Call<'_>, name: &str) -> EditResult {
+        match call.target() {
+            CallTarget::Path(ref path) => {
+                self.replace(path.span().clone(), name)
+            }
+            CallTarget::SubExpr(ref call) => {
+                Err(EditError::Rename(call.as_str().to_string()))
+            }
+        }
+    }
+
+    /// Rename the target of a block in both the open
+    /// and close tags.
+    pub fn rename_block(
+        &mut self,
+        block: &Block<'_>,
+        name: &str,
+    ) -> EditResult {
+        let call = block.call();
+        let target = call.target().as_str();
+        let close = block.close_span().clone().and_then(|close| {
+            let tag = self.source.get(close.clone())?;
+            let start = tag.find('/')?;
+            let offset = tag[start..].find(target)?;
+            let start = close.start + start + offset;
+            Some(start..start + target.len())
+        });
+
+        self.rename_call(call, name)?;
+        if let Some(close) = close {
+            self.replace(close, name)?;
+        }
+        Ok(())
+    }
+
+    /// Set a hash parameter for a call.
+    ///
+    /// When the call already has the parameter the value is replaced
+    /// otherwise the parameter is appended to the call; the value is
+    /// template source such as `"text"`, `true` or `user.name`.
+    pub fn add_parameter(
+        &mut self,
+        call: &Call<'_>,
+        key: &str,
+        value: &str,
+    ) -> EditResult {
+        if let Some(current) = call.parameters().get(key) {
+            return self.replace(current.span(), value);
+        }
+
+        let end = call
+            .parameters()
+            .values()
+            .map(|v| v.span().end)
+            .chain(call.arguments().iter().map(|v| v.span().end))
+            .chain(core::iter::once(call.target().span().end))
+            .max()
+            .unwrap_or_else(|| call.open_span().end);
+        self.insert(end, format!(" {}={}", key, value))
+    }
+
+    /// Apply the edits and return the patched source.
+    pub fn finish(mut self) -> String {
+        // NOTE: sort is stable so insertions at the same
+        // NOTE: offset are applied in the order they were recorded
+        self.edits.sort_by_key(|e| (e.span.start, e.span.end));
+        let mut output = String::with_capacity(self.source.len());
+        let mut position = 0;
+        for edit in self.edits {
+            output.push_str(&self.source[position..edit.span.start]);
+            output.push_str(&edit.text);
+            position = edit.span.end;
+        }
+        output.push_str(&self.source[position..]);
+        output
+    }
+
+    fn check(&self, span: &Range<usize>) -> EditResult {
+        if span.start > span.end || span.end > self.source.len() {
+            return Err(EditError::OutOfBounds(span.clone()));
+        }
+        if !self.source.is_char_boundary(span.start)
+            || !self.source.is_char_boundary(span.end)
+        {
+            return Err(EditError::CharBoundary(span.clone()));
+        }
+        for edit in self.edits.iter() {
+            let other = &edit.span;
+            let overlaps = if span.is_empty() || other.is_empty() {
+                span.start > other.start && span.start < other.end
+                    || other.start > span.start && other.start < span.end
+            } else {
+                span.start < other.end && other.start < span.end
+            };
+            if overlaps {
+                return Err(EditError::Overlap(span.clone(), other.clone()));
+            }
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,79 @@
+//! Heuristics that suggest fixes for common syntax errors.
+//!
+//! Each function inspects the source around the token that caused
+//! a syntax error and returns notes to attach to the error.
+use alloc::{format, string::String, vec, vec::Vec};
+use core::ops::Range;
+
+/// Notes for an error token in a statement or block tag.
+///
+/// Detects a tag closed with a single brace such as `{{#if x}`.
+pub(crate) fn error_token(source: &str, span: &Range<usize>) -> Vec<String> {
+    let token = source.get(span.clone()).unwrap_or("");
+    let next = source.get(span.end..).and_then(|s| s.chars().next());
+    if token == "}" && next != Some('}') {
+        let tag = tag_before(source, span.start);
+        return vec![format!(
+            "did you mean '{}}}}}'? tags must be closed with two braces",
+            tag
+        )];
+    }
+    vec![]
+}
+
+/// Notes for a string literal that is not terminated.
+///
+/// Detects a closing quote missing before the end of the tag such
+/// as `{{foo bar="baz}}`.
+pub(crate) fn unterminated_literal(
+    source: &str,
+    start: usize,
+    quote: char,
+) -> Vec<String> {
+    let rest = source.get(start..).unwrap_or("");
+    let line = rest.split('\n').next().unwrap_or("");
+    if let Some(end) = line.find("}}") {
+        let value = line[..end].trim_end_matches('~');
+        return vec![format!(
+            "did you mean {}{}{}? the string literal is not terminated",
+            quote, value, quote
+        )];
+    }
+    vec![]
+}
+
+/// Notes for a closing tag when no block is open.
+///
+/// Detects a statement with the same name as the closing tag that
+/// is missing the `#` block prefix such as `{{if x}}...{{/if}}`.
+pub(crate) fn block_not_open(
+    source: &str,
+    offset: usize,
+    name: &str,
+) -> Vec<String> {
+    let before = source.get(..offset).unwrap_or("");
+    let mut search = before;
+    while let Some(index) = search.rfind("{{") {
+        let inner = search[index + 2..].trim_start_matches('~').trim_start();
+        if inner.starts_with(name)
+            && inner[name.len()..].starts_with(char::is_whitespace)
+        {
+            return vec![format!(
+                "did you mean '{{{{#{}'? blocks are opened with '#'",
+                name
+            )];
+        }
+        search = &search[..index];
+    }
+    vec![]
+}
+
+/// Find the text of the tag containing an offset from the
+/// last opening delimiter.
+fn tag_before(source: &str, offset: usize) -> &str {
+    let before = source.get(..offset).unwrap_or("");
+    match before.rfind("{{") {
+        Some(index) => &before[index..],
+        None => before,
+    }
+}
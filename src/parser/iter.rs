@@ -1,4 +1,7 @@
 //! Iterators for the AST nodes.
+use alloc::collections::VecDeque;
+use alloc::{vec, vec::Vec};
+
 use crate::{
     parser::ast::Node,
     trim::{TrimHint, TrimState},
@@ -40,7 +43,7 @@ impl<'a> NodeEvent<'a> {
 /// nodes.
 pub struct BranchIter<'source> {
     node: &'source Node<'source>,
-    children: Option<std::slice::Iter<'source, Node<'source>>>,
+    children: Option<core::slice::Iter<'source, Node<'source>>>,
 }
 
 impl<'source> BranchIter<'source> {
@@ -102,7 +105,7 @@ impl<'source> Iterator for BranchIter<'source> {
 /// They may also be seeded with a [TrimHint](crate::trim::TrimHint) from a
 /// previous iteration.
 pub struct EventIter<'source> {
-    iter: std::iter::Peekable<BranchIter<'source>>,
+    iter: core::iter::Peekable<BranchIter<'source>>,
     prev_trim_after: Option<bool>,
     hint: Option<TrimHint>,
 }
@@ -172,3 +175,142 @@ impl<'source> Iterator for EventIter<'source> {
         node.map(|n| NodeEvent::new(n, state, first, peek.is_none()))
     }
 }
+
+/// Iterator for the direct children of a node.
+///
+/// For blocks the child nodes are followed by any conditional
+/// blocks (`else` branches); leaf nodes have no children.
+pub struct Children<'source> {
+    iter: core::iter::Chain<
+        core::slice::Iter<'source, Node<'source>>,
+        core::slice::Iter<'source, Node<'source>>,
+    >,
+}
+
+impl<'source> Children<'source> {
+    /// Create a new children iterator.
+    pub fn new(node: &'source Node) -> Self {
+        let (nodes, conditions): (&[Node], &[Node]) = match *node {
+            Node::Document(ref node) => (node.nodes(), &[]),
+            Node::Block(ref node) => (node.nodes(), node.conditions()),
+            Node::Text(_)
+            | Node::Link(_)
+            | Node::Statement(_)
+            | Node::RawStatement(_)
+            | Node::RawComment(_)
+            | Node::Comment(_) => (&[], &[]),
+        };
+        Self {
+            iter: nodes.iter().chain(conditions.iter()),
+        }
+    }
+}
+
+impl<'source> Iterator for Children<'source> {
+    type Item = &'source Node<'source>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.iter.next()
+    }
+}
+
+impl<'source> DoubleEndedIterator for Children<'source> {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.iter.next_back()
+    }
+}
+
+/// Iterator that visits nodes depth-first (pre-order).
+///
+/// Use `max_depth()` to limit how far the iterator descends; the
+/// node the iterator was created from has a depth of zero.
+pub struct DepthFirst<'source> {
+    stack: Vec<(usize, &'source Node<'source>)>,
+    max_depth: Option<usize>,
+}
+
+impl<'source> DepthFirst<'source> {
+    /// Create an iterator that yields the node and all descendants.
+    pub fn new(node: &'source Node) -> Self {
+        Self {
+            stack: vec![(0, node)],
+            max_depth: None,
+        }
+    }
+
+    /// Create an iterator that yields all descendants of the node.
+    pub fn descendants(node: &'source Node) -> Self {
+        Self {
+            stack: Children::new(node).rev().map(|n| (1, n)).collect(),
+            max_depth: None,
+        }
+    }
+
+    /// Do not descend past the given depth.
+    pub fn max_depth(mut self, depth: usize) -> Self {
+        self.max_depth = Some(depth);
+        self
+    }
+}
+
+impl<'source> Iterator for DepthFirst<'source> {
+    type Item = &'source Node<'source>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            let (depth, node) = self.stack.pop()?;
+            if let Some(max) = self.max_depth {
+                if depth > max {
+                    continue;
+                }
+                if depth == max {
+                    return Some(node);
+                }
+            }
+            self.stack
+                .extend(Children::new(node).rev().map(|n| (depth + 1, n)));
+            return Some(node);
+        }
+    }
+}
+
+/// Iterator that visits nodes breadth-first (level order).
+///
+/// Use `max_depth()` to limit how far the iterator descends; the
+/// node the iterator was created from has a depth of zero.
+pub struct BreadthFirst<'source> {
+    queue: VecDeque<(usize, &'source Node<'source>)>,
+    max_depth: Option<usize>,
+}
+
+impl<'source> BreadthFirst<'source> {
+    /// Create an iterator that yields the node and all descendants.
+    pub fn new(node: &'source Node) -> Self {
+        let mut queue = VecDeque::new();
+        queue.push_back((0, node));
+        Self {
+            queue,
+            max_depth: None,
+        }
+    }
+
+    /// Do not descend past the given depth.
+    pub fn max_depth(mut self, depth: usize) -> Self {
+        self.max_depth = Some(depth);
+        self
+    }
+}
+
+impl<'source> Iterator for BreadthFirst<'source> {
+    type Item = &'source Node<'source>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let (depth, node) = self.queue.pop_front()?;
+        let descend = self.max_depth.map(|max| depth < max).unwrap_or(true);
+        if descend {
+            self.queue
+                .extend(Children::new(node).map(|n| (depth + 1, n)));
+        }
+        Some(node)
+    }
+}
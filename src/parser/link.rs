@@ -1,4 +1,5 @@
-use std::ops::Range;
+use alloc::string::{String, ToString};
+use core::ops::Range;
 
 use crate::{
     error::{ErrorInfo, SyntaxError},
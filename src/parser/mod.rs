@@ -1,4 +1,11 @@
 //! Convert the lexer token stream to AST nodes.
+use alloc::{
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
+
 use crate::{
     error::{Error, ErrorInfo, SyntaxError},
     lexer::{self, lex, Lexer, Token},
@@ -8,14 +15,19 @@ use crate::{
     },
     SyntaxResult,
 };
-use std::ops::Range;
+use core::ops::Range;
 
 /// Default file name.
 pub(crate) const UNKNOWN: &str = "unknown";
 
+/// Default maximum nesting depth for blocks and sub-expressions.
+pub const MAX_DEPTH: usize = 64;
+
 pub mod ast;
 mod block;
 mod call;
+pub mod edit;
+mod hints;
 pub mod iter;
 mod link;
 pub(crate) mod path;
@@ -34,6 +46,11 @@ pub struct ParserOptions {
     pub line_offset: usize,
     /// Byte offset into the source file.
     pub byte_offset: usize,
+    /// Maximum nesting depth for blocks and sub-expressions.
+    ///
+    /// Deeper templates are a syntax error rather than a
+    /// stack overflow; defaults to [MAX_DEPTH](MAX_DEPTH).
+    pub max_depth: usize,
 }
 
 impl ParserOptions {
@@ -47,6 +64,7 @@ impl ParserOptions {
             file_name,
             line_offset,
             byte_offset,
+            max_depth: MAX_DEPTH,
         }
     }
 }
@@ -57,6 +75,7 @@ impl Default for ParserOptions {
             file_name: UNKNOWN.to_string(),
             line_offset: 0,
             byte_offset: 0,
+            max_depth: MAX_DEPTH,
         }
     }
 }
@@ -66,6 +85,8 @@ pub(crate) struct ParseState {
     file_name: String,
     line: usize,
     byte: usize,
+    depth: usize,
+    max_depth: usize,
 }
 
 impl ParseState {
@@ -75,6 +96,8 @@ impl ParseState {
             file_name: UNKNOWN.to_string(),
             line: 0,
             byte: 0,
+            depth: 0,
+            max_depth: MAX_DEPTH,
         }
     }
 
@@ -98,6 +121,18 @@ impl ParseState {
         &mut self.byte
     }
 
+    pub fn depth(&self) -> &usize {
+        &self.depth
+    }
+
+    pub fn depth_mut(&mut self) -> &mut usize {
+        &mut self.depth
+    }
+
+    pub fn max_depth(&self) -> usize {
+        self.max_depth
+    }
+
     /// Get an initial line range for this parse state.
     pub fn line_range(&self) -> Range<usize> {
         self.line.clone()..self.line.clone() + 1
@@ -110,6 +145,8 @@ impl From<&ParserOptions> for ParseState {
             file_name: opts.file_name.clone(),
             line: opts.line_offset.clone(),
             byte: opts.byte_offset.clone(),
+            depth: 0,
+            max_depth: opts.max_depth,
         }
     }
 }
@@ -173,6 +210,39 @@ impl<'source> Parser<'source> {
         Ok(Node::Document(doc))
     }
 
+    /// Parse the tag that starts at a byte offset into the source.
+    ///
+    /// The open tag for a block yields a block without any nodes that
+    /// has not been closed so the caller can add nodes and exit the
+    /// block; used to create the nodes for a built document.
+    pub(crate) fn tag(
+        source: &'source str,
+        options: ParserOptions,
+        offset: usize,
+    ) -> SyntaxResult<Option<Node<'source>>> {
+        let mut parser = Self {
+            source,
+            lexer: Lexer::at(source, offset),
+            state: ParseState::from(&options),
+            stack: vec![],
+            next_token: None,
+            errors: None,
+        };
+        match parser.token() {
+            Some(Token::Block(lexer::Block::StartBlockScope, span)) => {
+                let block = block::scope(
+                    source,
+                    &mut parser.lexer,
+                    &mut parser.state,
+                    span,
+                )?;
+                Ok(Some(Node::Block(block)))
+            }
+            Some(token) => parser.advance(token),
+            None => Ok(None),
+        }
+    }
+
     /// Yield the next token accounting for text normalization which
     /// saves the next token for further processing.
     fn token(&mut self) -> Option<Token> {
@@ -251,15 +321,27 @@ impl<'source> Parser<'source> {
                     )
                     .map(Some);
                 }
-                lexer::Block::StartBlockScope => {
-                    let block = block::scope(
-                        self.source,
-                        &mut self.lexer,
-                        &mut self.state,
-                        span,
-                    )?;
+                lexer::Block::StartBlockScope
+                | lexer::Block::StartInvertedScope => {
+                    let (name, block) =
+                        if lexer::Block::StartInvertedScope == lex {
+                            block::inverted(
+                                self.source,
+                                &mut self.lexer,
+                                &mut self.state,
+                                span,
+                            )?
+                        } else {
+                            let block = block::scope(
+                                self.source,
+                                &mut self.lexer,
+                                &mut self.state,
+                                span,
+                            )?;
+                            (block.name(), block)
+                        };
 
-                    let name = block.name().ok_or_else(|| {
+                    let name = name.ok_or_else(|| {
                         *self.state.byte_mut() =
                             block.call().target().span().start;
                         SyntaxError::BlockName(
@@ -283,6 +365,15 @@ impl<'source> Parser<'source> {
                         }
                     }
 
+                    if self.stack.len() >= self.state.max_depth() {
+                        *self.state.byte_mut() = block.call().span().start;
+                        return Err(SyntaxError::NestingDepth(
+                            self.state.max_depth(),
+                            ErrorInfo::from((self.source, &mut self.state))
+                                .into(),
+                        ));
+                    }
+
                     self.stack.push((name, block));
 
                     while let Some(t) = self.token() {
@@ -330,6 +421,16 @@ impl<'source> Parser<'source> {
                             Err(e) => return Err(e),
                         }
                     }
+
+                    // NOTE: tokens are exhausted before the close tag
+                    // NOTE: for the innermost open block.
+                    if let Some((_, block)) = self.stack.last() {
+                        *self.state.byte_mut() = block.open_span().start;
+                    }
+                    self.stack.clear();
+                    return Err(SyntaxError::BlockNotTerminated(
+                        ErrorInfo::from((self.source, &mut self.state)).into(),
+                    ));
                 }
                 lexer::Block::EndBlockScope => {
                     // Need a temp block to parse the call parameters so we
@@ -341,26 +442,36 @@ impl<'source> Parser<'source> {
                         span.clone(),
                     )?;
 
-                    if self.stack.is_empty() {
-                        let notes = if let Some(close) = temp.name() {
-                            vec![format!("perhaps open the block '{}'", close)]
-                        } else {
-                            vec![]
-                        };
-
-                        *self.state.byte_mut() = span.start;
+                    let (open_name, mut block) = match self.stack.pop() {
+                        Some(open) => open,
+                        None => {
+                            let notes = if let Some(close) = temp.name() {
+                                let mut notes = vec![format!(
+                                    "perhaps open the block '{}'",
+                                    close
+                                )];
+                                notes.extend(hints::block_not_open(
+                                    self.source,
+                                    span.start,
+                                    close,
+                                ));
+                                notes
+                            } else {
+                                vec![]
+                            };
 
-                        return Err(SyntaxError::BlockNotOpen(
-                            ErrorInfo::from((
-                                self.source,
-                                &mut self.state,
-                                notes,
-                            ))
-                            .into(),
-                        ));
-                    }
+                            *self.state.byte_mut() = span.start;
 
-                    let (open_name, mut block) = self.stack.pop().unwrap();
+                            return Err(SyntaxError::BlockNotOpen(
+                                ErrorInfo::from((
+                                    self.source,
+                                    &mut self.state,
+                                    notes,
+                                ))
+                                .into(),
+                            ));
+                        }
+                    };
 
                     if let Some(close_name) = temp.name() {
                         if open_name != close_name {
@@ -420,6 +531,14 @@ impl<'source> Parser<'source> {
                     )?;
                     return Ok(Some(Node::Link(link)));
                 }
+                // NOTE: raw blocks consume their own end tag so
+                // NOTE: one here has no matching open tag.
+                lexer::Block::EndRawBlock => {
+                    *self.state.byte_mut() = span.start;
+                    return Err(SyntaxError::BlockNotOpen(
+                        ErrorInfo::from((self.source, &mut self.state)).into(),
+                    ));
+                }
                 _ => {}
             },
             Token::Link(_, _) => {}
@@ -1,4 +1,5 @@
-use std::ops::Range;
+use alloc::{format, vec};
+use core::ops::Range;
 
 use crate::{
     error::{ErrorInfo, SyntaxError},
@@ -26,6 +27,42 @@ fn is_path_component(lex: &Parameters) -> bool {
     }
 }
 
+/// Verify the characters in an identifier.
+///
+/// ASCII characters are accepted as lexed so existing identifiers
+/// such as `my-helper` continue to work; other characters must be
+/// valid in an identifier according to UAX #31 so that invisible
+/// or symbolic characters are not confused for names.
+pub(crate) fn identifier(
+    source: &str,
+    state: &mut ParseState,
+    span: Range<usize>,
+) -> SyntaxResult<()> {
+    let value = &source[span.clone()];
+    for (index, c) in value.char_indices() {
+        if c.is_ascii() {
+            continue;
+        }
+        let valid = if index == 0 {
+            unicode_ident::is_xid_start(c)
+        } else {
+            unicode_ident::is_xid_continue(c)
+        };
+        if !valid {
+            *state.byte_mut() = span.start + index;
+            let notes = vec![format!(
+                "use a raw literal such as [{}] for names that are not identifiers",
+                value
+            )];
+            return Err(SyntaxError::IdentifierCharacter(
+                c,
+                ErrorInfo::from((source, state, notes)).into(),
+            ));
+        }
+    }
+    Ok(())
+}
+
 fn to_component<'source>(
     source: &'source str,
     state: &mut ParseState,
@@ -43,6 +80,14 @@ fn to_component<'source>(
         None
     };
 
+    match &lex {
+        Parameters::Identifier => identifier(source, state, span.clone())?,
+        Parameters::LocalIdentifier => {
+            identifier(source, state, span.start + 1..span.end)?
+        }
+        _ => {}
+    }
+
     let kind = match &lex {
         Parameters::ExplicitThisKeyword => ComponentType::ThisKeyword,
         Parameters::ExplicitThisDotSlash => ComponentType::ThisDotSlash,
@@ -89,6 +134,21 @@ fn parents<'source>(
     None
 }
 
+/// Error when a path ends with a delimiter.
+fn trailing_delimiter(
+    source: &str,
+    state: &mut ParseState,
+    delimiter: Option<Range<usize>>,
+) -> SyntaxResult<()> {
+    if let Some(span) = delimiter {
+        *state.byte_mut() = span.end;
+        return Err(SyntaxError::ExpectedIdentifier(
+            ErrorInfo::from((source, state)).into(),
+        ));
+    }
+    Ok(())
+}
+
 pub(crate) fn components<'source>(
     source: &'source str,
     state: &mut ParseState,
@@ -96,8 +156,11 @@ pub(crate) fn components<'source>(
     path: &mut Path<'source>,
     mut wants_delimiter: bool,
 ) -> SyntaxResult<Option<Token>> {
+    // Span of a delimiter that is not yet followed by a component
+    let mut delimiter: Option<Range<usize>> = None;
     while let Some(token) = lexer.next() {
         if token.is_newline() {
+            trailing_delimiter(source, state, delimiter)?;
             *state.line_mut() += 1;
             // Paths are terminated if we hit a newline!
             return Ok(lexer.next());
@@ -110,9 +173,40 @@ pub(crate) fn components<'source>(
                 let mut raw_id: Option<RawLiteral> = None;
 
                 if lex == Parameters::End {
+                    trailing_delimiter(source, state, delimiter)?;
                     return Ok(Some(Token::Parameters(lex, span)));
                 }
 
+                // Integer components such as `0` in `items.0` are lexed
+                // as numbers and `1.2` in `rows.1.2` as a single number
+                if lex == Parameters::Number && !wants_delimiter {
+                    let value = &source[span.clone()];
+                    if value.bytes().all(|b| b.is_ascii_digit() || b == b'.') {
+                        path.span_mut().end = span.end;
+                        let mut start = span.start;
+                        for part in value.split('.') {
+                            if part.is_empty() {
+                                *state.byte_mut() = start;
+                                return Err(SyntaxError::ExpectedIdentifier(
+                                    ErrorInfo::from((source, state)).into(),
+                                ));
+                            }
+                            let end = start + part.len();
+                            path.add_component(to_component(
+                                source,
+                                state,
+                                &Parameters::Identifier,
+                                start..end,
+                                None,
+                            )?);
+                            start = end + 1;
+                        }
+                        wants_delimiter = true;
+                        delimiter = None;
+                        continue;
+                    }
+                }
+
                 if is_path_component(&lex) {
                     path.span_mut().end = span.end;
 
@@ -184,6 +278,7 @@ pub(crate) fn components<'source>(
                         match &lex {
                             Parameters::PathDelimiter => {
                                 wants_delimiter = false;
+                                delimiter = Some(span);
                                 continue;
                             }
                             _ => {
@@ -213,14 +308,20 @@ pub(crate) fn components<'source>(
                         source, state, &lex, span, raw_id,
                     )?);
                     wants_delimiter = true;
+                    delimiter = None;
                 } else {
+                    trailing_delimiter(source, state, delimiter)?;
                     return Ok(Some(Token::Parameters(lex, span)));
                 }
             }
-            _ => return Ok(Some(token)),
+            _ => {
+                trailing_delimiter(source, state, delimiter)?;
+                return Ok(Some(token));
+            }
         }
     }
 
+    trailing_delimiter(source, state, delimiter)?;
     Ok(None)
 }
 
@@ -242,6 +343,28 @@ pub(crate) fn parse<'source>(
             if initial == "/" {
                 path.set_absolute(true);
                 next = lexer.next();
+            } else if initial == "." {
+                // A lone dot is shorthand for `this` as in mustache
+                next = lexer.next();
+                let lone = match next {
+                    Some(Token::Parameters(ref lex, _)) => {
+                        !is_path_component(lex)
+                    }
+                    _ => true,
+                };
+                if !lone {
+                    return Err(SyntaxError::UnexpectedPathDelimiter(
+                        ErrorInfo::from((source, state)).into(),
+                    ));
+                }
+                path.set_explicit(true);
+                path.add_component(Component::new(
+                    source,
+                    ComponentType::ThisKeyword,
+                    path.span().clone(),
+                    None,
+                ));
+                return Ok((Some(path), next));
             } else {
                 return Err(SyntaxError::UnexpectedPathDelimiter(
                     ErrorInfo::from((source, state)).into(),
@@ -1,12 +1,16 @@
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::ops::Range;
 use serde_json::Value;
-use std::ops::Range;
 
 use crate::{
     error::{ErrorInfo, SyntaxError},
     lexer::{
         Array, DoubleQuoteString, Lexer, Parameters, SingleQuoteString, Token,
     },
-    parser::ParseState,
+    parser::{hints, ParseState},
     SyntaxResult,
 };
 
@@ -73,14 +77,25 @@ pub(crate) fn parse<'source>(
         delimiter: false,
     };
 
+    let unterminated = |source: &str| match string_type {
+        RawLiteralType::Double => {
+            hints::unterminated_literal(source, str_start, '"')
+        }
+        RawLiteralType::Single => {
+            hints::unterminated_literal(source, str_start, '\'')
+        }
+        RawLiteralType::Array => Vec::new(),
+    };
+
     while let Some(token) = lexer.next() {
         match string_type {
             RawLiteralType::Double => match token {
                 Token::DoubleQuoteString(lex, span) => match &lex {
                     DoubleQuoteString::Newline => {
+                        let notes = unterminated(source);
                         return Err(SyntaxError::LiteralNewline(
-                            ErrorInfo::from((source, state)).into(),
-                        ))
+                            ErrorInfo::from((source, state, notes)).into(),
+                        ));
                     }
                     DoubleQuoteString::EscapedNewline => {
                         flags.newline = true;
@@ -105,9 +120,10 @@ pub(crate) fn parse<'source>(
             RawLiteralType::Single => match token {
                 Token::SingleQuoteString(lex, span) => match &lex {
                     SingleQuoteString::Newline => {
+                        let notes = unterminated(source);
                         return Err(SyntaxError::LiteralNewline(
-                            ErrorInfo::from((source, state)).into(),
-                        ))
+                            ErrorInfo::from((source, state, notes)).into(),
+                        ));
                     }
                     SingleQuoteString::EscapedNewline => {
                         flags.newline = true;
@@ -156,8 +172,9 @@ pub(crate) fn parse<'source>(
         }
     }
 
+    let notes = unterminated(source);
     return Err(SyntaxError::TokenRawLiteral(
-        ErrorInfo::from((source, state)).into(),
+        ErrorInfo::from((source, state, notes)).into(),
     ));
 }
 
@@ -0,0 +1,288 @@
+//! Development server for previewing templates.
+//!
+//! Requires the `preview` feature. Serves the templates in a directory
+//! rendered with the data in a JSON file alongside each template so
+//! that template authors can preview their changes in a browser:
+//!
+//! ```ignore
+//! use bracket::preview::Preview;
+//! Preview::new("templates").serve(([127, 0, 0, 1], 8080))?;
+//! ```
+//!
+//! Requesting `/card` renders `templates/card.hbs` with the data in
+//! `templates/card.json`; when the data file does not exist the data is
+//! a [skeleton](crate::Template#method.skeleton) generated from the
+//! variables the template references. The index page at `/` lists the
+//! templates and every template may be used as a partial.
+//!
+//! The directory is checked for changes on each request using the file
+//! modification times and the templates are reloaded when a file has
+//! changed; rendered pages include a script that polls the server and
+//! reloads the page after a change.
+//!
+//! Errors are shown in the page so do not expose the server publicly.
+use std::{
+    convert::Infallible,
+    fmt::Write,
+    net::SocketAddr,
+    path::{Path, PathBuf},
+    sync::{Arc, Mutex},
+    time::UNIX_EPOCH,
+};
+
+use http_body_util::Full;
+use hyper::{
+    body::{Bytes, Incoming},
+    header,
+    server::conn::http1,
+    service::service_fn,
+    Request, Response, StatusCode,
+};
+use hyper_util::rt::TokioIo;
+use serde_json::Value;
+use tokio::net::TcpListener;
+
+use crate::{error::RenderError, escape, Error, Registry, Result};
+
+/// Path that returns the modification stamp for live reload.
+pub const RELOAD: &str = "/_reload";
+
+/// Content type for rendered pages.
+const HTML: &str = "text/html; charset=utf-8";
+
+/// Content type for the modification stamp.
+const TEXT: &str = "text/plain; charset=utf-8";
+
+/// Function called to configure a registry after it is created.
+pub type SetupFn = Box<dyn Fn(&mut Registry<'static>) + Send + Sync>;
+
+/// Response for a request to the preview server.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Page {
+    /// HTTP status code.
+    pub status: u16,
+    /// Content type of the body.
+    pub content_type: &'static str,
+    /// Response body.
+    pub body: String,
+}
+
+impl Page {
+    fn new(status: u16, content_type: &'static str, body: String) -> Self {
+        Self {
+            status,
+            content_type,
+            body,
+        }
+    }
+}
+
+/// Templates loaded from the directory.
+struct State {
+    stamp: u128,
+    registry: core::result::Result<Registry<'static>, String>,
+}
+
+/// Preview server for a directory of templates.
+pub struct Preview {
+    dir: PathBuf,
+    extension: String,
+    setup: Option<SetupFn>,
+    state: Mutex<Option<State>>,
+}
+
+impl Preview {
+    /// Create a preview for the templates in a directory
+    /// with the `hbs` extension.
+    pub fn new<P: AsRef<Path>>(dir: P) -> Self {
+        Self {
+            dir: dir.as_ref().to_path_buf(),
+            extension: String::from("hbs"),
+            setup: None,
+            state: Mutex::new(None),
+        }
+    }
+
+    /// Set the file extension for templates.
+    pub fn set_extension<S: Into<String>>(&mut self, extension: S) {
+        self.extension = extension.into();
+    }
+
+    /// Set a function to configure the registry, for example to
+    /// register helpers, before the templates are loaded.
+    pub fn set_setup(&mut self, setup: SetupFn) {
+        self.setup = Some(setup);
+    }
+
+    /// Modification stamp for the directory.
+    ///
+    /// The stamp changes when a file in the directory is added,
+    /// removed or modified.
+    pub fn stamp(&self) -> u128 {
+        let mut stamp: u128 = 0;
+        if let Ok(entries) = std::fs::read_dir(&self.dir) {
+            for entry in entries.flatten() {
+                let modified = entry
+                    .metadata()
+                    .and_then(|m| m.modified())
+                    .ok()
+                    .and_then(|t| t.duration_since(UNIX_EPOCH).ok())
+                    .map(|d| d.as_nanos())
+                    .unwrap_or(0);
+                stamp = stamp.wrapping_add(modified + 1);
+            }
+        }
+        stamp
+    }
+
+    /// Handle a request for a path.
+    ///
+    /// Templates are reloaded when the directory has changed.
+    pub fn respond(&self, path: &str) -> Page {
+        let path = path.split('?').next().unwrap_or("");
+        let stamp = self.stamp();
+        if path == RELOAD {
+            return Page::new(200, TEXT, stamp.to_string());
+        }
+
+        let mut state = self.state.lock().unwrap();
+        if state.as_ref().map(|s| s.stamp) != Some(stamp) {
+            *state = Some(State {
+                stamp,
+                registry: self.load().map_err(|e| e.to_string()),
+            });
+        }
+        let registry = match state.as_ref().unwrap().registry {
+            Ok(ref registry) => registry,
+            Err(ref e) => return self.error(500, e, stamp),
+        };
+
+        let name = path.trim_start_matches('/');
+        if name.is_empty() {
+            return self.index(registry, stamp);
+        }
+        let template = match registry.get(name) {
+            Some(template) => template,
+            None => {
+                let message = format!("Template '{}' not found", name);
+                return self.error(404, &message, stamp);
+            }
+        };
+        let data = match self.data(name) {
+            Ok(Some(data)) => data,
+            Ok(None) => template.skeleton(),
+            Err(e) => return self.error(500, &e.to_string(), stamp),
+        };
+        match registry.render(name, &data) {
+            Ok(mut body) => {
+                inject(&mut body, stamp);
+                Page::new(200, HTML, body)
+            }
+            Err(e) => self.error(500, &e.to_string(), stamp),
+        }
+    }
+
+    /// Serve the preview until the server fails.
+    ///
+    /// Blocks the current thread.
+    pub fn serve<A: Into<SocketAddr>>(self, addr: A) -> Result<()> {
+        let addr = addr.into();
+        let runtime = tokio::runtime::Builder::new_current_thread()
+            .enable_all()
+            .build()?;
+        runtime.block_on(accept(Arc::new(self), addr))
+    }
+
+    fn handle(&self, req: Request<Incoming>) -> Response<Full<Bytes>> {
+        let page = self.respond(req.uri().path());
+        Response::builder()
+            .status(
+                StatusCode::from_u16(page.status)
+                    .unwrap_or(StatusCode::INTERNAL_SERVER_ERROR),
+            )
+            .header(header::CONTENT_TYPE, page.content_type)
+            .header(header::CACHE_CONTROL, "no-store")
+            .body(Full::new(Bytes::from(page.body)))
+            .unwrap()
+    }
+
+    /// Create a registry and load the templates.
+    fn load(&self) -> Result<Registry<'static>> {
+        let mut registry = Registry::new();
+        if let Some(ref setup) = self.setup {
+            setup(&mut registry);
+        }
+        registry.read_dir(&self.dir, &self.extension)?;
+        Ok(registry)
+    }
+
+    /// Read the data file for a template.
+    fn data(&self, name: &str) -> Result<Option<Value>> {
+        let path = self.dir.join(format!("{}.json", name));
+        if !path.is_file() {
+            return Ok(None);
+        }
+        let content = std::fs::read_to_string(path)?;
+        serde_json::from_str(&content)
+            .map(Some)
+            .map_err(|e| Error::from(RenderError::from(e)))
+    }
+
+    fn index(&self, registry: &Registry<'_>, stamp: u128) -> Page {
+        let mut names: Vec<&str> =
+            registry.templates().keys().map(|k| k.as_str()).collect();
+        names.sort_unstable();
+        let mut body = String::from("<!doctype html>\n<ul>\n");
+        for name in names {
+            let name = escape::html(name);
+            let _ = writeln!(body, "<li><a href=\"/{0}\">{0}</a></li>", name);
+        }
+        body.push_str("</ul>\n");
+        inject(&mut body, stamp);
+        Page::new(200, HTML, body)
+    }
+
+    fn error(&self, status: u16, message: &str, stamp: u128) -> Page {
+        let mut body =
+            format!("<!doctype html>\n<pre>{}</pre>\n", escape::html(message));
+        inject(&mut body, stamp);
+        Page::new(status, HTML, body)
+    }
+}
+
+/// Add the live reload script to a page.
+fn inject(body: &mut String, stamp: u128) {
+    let script = format!(
+        "<script>setInterval(function(){{fetch(\"{}\")\
+.then(function(r){{return r.text()}})\
+.then(function(t){{if(t!==\"{}\")location.reload()}})\
+.catch(function(){{}})}},1000)</script>",
+        RELOAD, stamp
+    );
+    match body.rfind("</body>") {
+        Some(index) => body.insert_str(index, &script),
+        None => body.push_str(&script),
+    }
+}
+
+/// Accept connections and serve each one on a task.
+async fn accept(preview: Arc<Preview>, addr: SocketAddr) -> Result<()> {
+    let listener = TcpListener::bind(addr).await?;
+    log::info!("Preview templates at http://{}", addr);
+    loop {
+        let (stream, _) = listener.accept().await?;
+        let preview = Arc::clone(&preview);
+        tokio::spawn(async move {
+            let service = service_fn(move |req| {
+                let preview = Arc::clone(&preview);
+                async move { Ok::<_, Infallible>(preview.handle(req)) }
+            });
+            if let Err(e) = http1::Builder::new()
+                .serve_connection(TokioIo::new(stream), service)
+                .await
+            {
+                log::warn!("Preview connection failed: {}", e);
+            }
+        });
+    }
+}
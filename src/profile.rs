@@ -0,0 +1,188 @@
+//! Profile the time spent rendering a template.
+//!
+//! Use [render_profiled()](crate::Registry#method.render_profiled)
+//! to render a named template and collect timings for each type of
+//! node, each helper and each partial along with a tree of frames
+//! that mirrors the nesting of the template:
+//!
+//! ```ignore
+//! let (output, profile) = registry.render_profiled("page", &data)?;
+//! for (name, timing) in profile.helpers.iter() {
+//!     println!("{} called {} times in {:?}", name, timing.count, timing.elapsed);
+//! }
+//! std::fs::write("page.folded", profile.folded())?;
+//! ```
+//!
+//! Timings are inclusive so the time for a block or a partial
+//! includes the time for the nodes and helpers it renders.
+use std::{
+    fmt::Write,
+    ops::Range,
+    time::{Duration, Instant},
+};
+
+use crate::collections::HashMap;
+
+/// Number of calls and the total time for a category.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub struct Timing {
+    /// Number of times the node, helper or partial was rendered.
+    pub count: usize,
+    /// Total time spent.
+    pub elapsed: Duration,
+}
+
+impl Timing {
+    fn add(&mut self, elapsed: Duration) {
+        self.count += 1;
+        self.elapsed += elapsed;
+    }
+}
+
+/// Time spent rendering a node and the nodes it contains.
+#[derive(Clone, Debug)]
+pub struct Frame {
+    /// Type of node, for example `statement` or `block`.
+    pub kind: &'static str,
+    /// Source for statements and the open tag for blocks,
+    /// otherwise the type of node.
+    pub label: String,
+    /// Name of the template that contains the node.
+    pub name: String,
+    /// Byte range of the node in the template.
+    pub span: Range<usize>,
+    /// Zero-based line range of the node in the template.
+    pub lines: Range<usize>,
+    /// Time spent rendering the node.
+    pub elapsed: Duration,
+    /// Frames for the nodes rendered by this node.
+    pub children: Vec<Frame>,
+}
+
+impl Frame {
+    /// Time spent in this frame excluding the children.
+    pub fn self_time(&self) -> Duration {
+        let children: Duration = self.children.iter().map(|f| f.elapsed).sum();
+        self.elapsed.saturating_sub(children)
+    }
+
+    fn fold(&self, prefix: &str, out: &mut String) {
+        let path = format!(
+            "{}{}:{} {}",
+            prefix,
+            self.name,
+            self.lines.start + 1,
+            self.label.replace([';', '\n'], " ")
+        );
+        let _ = writeln!(out, "{} {}", path, self.self_time().as_micros());
+        let prefix = format!("{};", path);
+        for child in self.children.iter() {
+            child.fold(&prefix, out);
+        }
+    }
+}
+
+/// Profile collected for a render.
+#[derive(Clone, Debug, Default)]
+pub struct RenderProfile {
+    /// Total time for the render.
+    pub elapsed: Duration,
+    /// Timings keyed by the type of node.
+    pub nodes: HashMap<&'static str, Timing>,
+    /// Timings keyed by helper name.
+    pub helpers: HashMap<String, Timing>,
+    /// Timings keyed by partial name.
+    pub partials: HashMap<String, Timing>,
+    /// Frames for the top-level nodes of the template.
+    pub frames: Vec<Frame>,
+}
+
+impl RenderProfile {
+    /// Format the frames as folded stacks with the self time
+    /// in microseconds, suitable for flame graph tools.
+    pub fn folded(&self) -> String {
+        let mut out = String::new();
+        for frame in self.frames.iter() {
+            frame.fold("", &mut out);
+        }
+        out
+    }
+}
+
+/// Collects timings during a render.
+pub(crate) struct Profiler {
+    started: Instant,
+    stack: Vec<(Frame, Instant)>,
+    profile: RenderProfile,
+}
+
+impl Profiler {
+    pub(crate) fn new() -> Self {
+        Self {
+            started: Instant::now(),
+            stack: Vec::new(),
+            profile: Default::default(),
+        }
+    }
+
+    /// Open a frame for a node.
+    pub(crate) fn enter(
+        &mut self,
+        kind: &'static str,
+        label: String,
+        name: String,
+        span: Range<usize>,
+        lines: Range<usize>,
+    ) {
+        let frame = Frame {
+            kind,
+            label,
+            name,
+            span,
+            lines,
+            elapsed: Duration::ZERO,
+            children: Vec::new(),
+        };
+        self.stack.push((frame, Instant::now()));
+    }
+
+    /// Close the most recent frame.
+    pub(crate) fn exit(&mut self) {
+        if let Some((mut frame, started)) = self.stack.pop() {
+            frame.elapsed = started.elapsed();
+            self.profile
+                .nodes
+                .entry(frame.kind)
+                .or_default()
+                .add(frame.elapsed);
+            match self.stack.last_mut() {
+                Some((parent, _)) => parent.children.push(frame),
+                None => self.profile.frames.push(frame),
+            }
+        }
+    }
+
+    pub(crate) fn helper(&mut self, name: &str, started: Instant) {
+        self.profile
+            .helpers
+            .entry(name.to_string())
+            .or_default()
+            .add(started.elapsed());
+    }
+
+    pub(crate) fn partial(&mut self, name: &str, started: Instant) {
+        self.profile
+            .partials
+            .entry(name.to_string())
+            .or_default()
+            .add(started.elapsed());
+    }
+
+    pub(crate) fn finish(mut self) -> RenderProfile {
+        while !self.stack.is_empty() {
+            self.exit();
+        }
+        self.profile.elapsed = self.started.elapsed();
+        self.profile
+    }
+}
@@ -0,0 +1,220 @@
+//! Query JSON values using paths.
+//!
+//! A [ValuePath](ValuePath) resolves values using the same rules as
+//! variables in templates so that data can be inspected outside of
+//! rendering: object fields are matched by name and array elements
+//! are matched when a segment is a valid index.
+//!
+//! Paths are parsed from strings using this syntax:
+//!
+//! * `user.name`: Segments are delimited by a period.
+//! * `items.0` or `items[0]`: Array indices are plain segments.
+//! * `user.[first name]` or `user[first name]`: Bracket segments may
+//!   contain any character; use `\]` and `\\` to include a closing
+//!   bracket or a backslash.
+//! * `a\.b`: Outside of brackets a backslash escapes the next character.
+//!
+//! ```ignore
+//! use bracket::query::{self, ValuePath};
+//! let path: ValuePath = "users[0].[first name]".parse()?;
+//! let name = path.get(&data);
+//! if let Some(active) = query::get_mut(&mut data, "users.0.active")? {
+//!     *active = Value::Bool(false);
+//! }
+//! ```
+use alloc::{string::String, vec::Vec};
+use core::{fmt, str::FromStr};
+use serde_json::Value;
+
+use crate::{error::PathError, json, render::NumericKeys};
+
+/// Parsed path to a value.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Hash)]
+pub struct ValuePath {
+    segments: Vec<String>,
+}
+
+impl ValuePath {
+    /// Create an empty path that resolves to the target value.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Parse a path.
+    pub fn parse(path: &str) -> Result<Self, PathError> {
+        let mut segments = Vec::new();
+        if path.is_empty() {
+            return Ok(Self { segments });
+        }
+
+        let mut current = String::new();
+        let mut after_bracket = false;
+        let mut chars = path.char_indices();
+        while let Some((offset, c)) = chars.next() {
+            if after_bracket {
+                after_bracket = false;
+                match c {
+                    '.' => continue,
+                    '[' => {}
+                    _ => return Err(PathError::ExpectedDelimiter(offset)),
+                }
+            }
+            match c {
+                '\\' => match chars.next() {
+                    Some((_, c)) => current.push(c),
+                    None => return Err(PathError::TrailingEscape(offset)),
+                },
+                '.' => {
+                    if current.is_empty() {
+                        return Err(PathError::EmptySegment(offset));
+                    }
+                    segments.push(core::mem::take(&mut current));
+                }
+                '[' => {
+                    if !current.is_empty() {
+                        segments.push(core::mem::take(&mut current));
+                    }
+                    let mut closed = false;
+                    while let Some((_, c)) = chars.next() {
+                        match c {
+                            '\\' => match chars.next() {
+                                Some((_, c)) => current.push(c),
+                                None => break,
+                            },
+                            ']' => {
+                                closed = true;
+                                break;
+                            }
+                            _ => current.push(c),
+                        }
+                    }
+                    if !closed {
+                        return Err(PathError::UnterminatedBracket(offset));
+                    }
+                    segments.push(core::mem::take(&mut current));
+                    after_bracket = true;
+                }
+                _ => current.push(c),
+            }
+        }
+
+        if !after_bracket {
+            if current.is_empty() {
+                return Err(PathError::EmptySegment(path.len()));
+            }
+            segments.push(current);
+        }
+        Ok(Self { segments })
+    }
+
+    /// Append a segment to this path.
+    pub fn push<S: Into<String>>(&mut self, segment: S) {
+        self.segments.push(segment.into());
+    }
+
+    /// Iterate the segments of this path.
+    pub fn segments(&self) -> impl Iterator<Item = &str> {
+        self.segments.iter().map(|s| s.as_str())
+    }
+
+    /// Number of segments in this path.
+    pub fn len(&self) -> usize {
+        self.segments.len()
+    }
+
+    /// Determine if this path has no segments.
+    pub fn is_empty(&self) -> bool {
+        self.segments.is_empty()
+    }
+
+    /// Get a reference to the value at this path.
+    pub fn get<'a>(&self, value: &'a Value) -> Option<&'a Value> {
+        find(value, self.segments())
+    }
+
+    /// Get a mutable reference to the value at this path.
+    pub fn get_mut<'a>(&self, value: &'a mut Value) -> Option<&'a mut Value> {
+        find_mut(value, self.segments())
+    }
+}
+
+impl FromStr for ValuePath {
+    type Err = PathError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+impl fmt::Display for ValuePath {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for (index, segment) in self.segments.iter().enumerate() {
+            let plain = !segment.is_empty()
+                && !segment.contains(['.', '[', ']', '\\'])
+                && !segment.contains(char::is_whitespace);
+            if plain {
+                if index > 0 {
+                    write!(f, ".")?;
+                }
+                write!(f, "{}", segment)?;
+            } else {
+                write!(f, "[")?;
+                for c in segment.chars() {
+                    if c == ']' || c == '\\' {
+                        write!(f, "\\")?;
+                    }
+                    write!(f, "{}", c)?;
+                }
+                write!(f, "]")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Find a value using path segments.
+///
+/// When there are no segments the target value is returned.
+pub fn find<I, S>(value: &Value, segments: I) -> Option<&Value>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    segments.into_iter().try_fold(value, |target, segment| {
+        json::find_field(target, segment, NumericKeys::Index)
+    })
+}
+
+/// Find a mutable value using path segments.
+///
+/// When there are no segments the target value is returned.
+pub fn find_mut<I, S>(value: &mut Value, segments: I) -> Option<&mut Value>
+where
+    I: IntoIterator<Item = S>,
+    S: AsRef<str>,
+{
+    segments
+        .into_iter()
+        .try_fold(value, |target, segment| match target {
+            Value::Object(ref mut map) => map.get_mut(segment.as_ref()),
+            Value::Array(ref mut list) => json::index(segment.as_ref())
+                .and_then(move |index| list.get_mut(index)),
+            _ => None,
+        })
+}
+
+/// Parse a path and get a reference to the value at the path.
+pub fn get<'a>(
+    value: &'a Value,
+    path: &str,
+) -> Result<Option<&'a Value>, PathError> {
+    Ok(ValuePath::parse(path)?.get(value))
+}
+
+/// Parse a path and get a mutable reference to the value at the path.
+pub fn get_mut<'a>(
+    value: &'a mut Value,
+    path: &str,
+) -> Result<Option<&'a mut Value>, PathError> {
+    Ok(ValuePath::parse(path)?.get_mut(value))
+}
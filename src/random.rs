@@ -0,0 +1,69 @@
+//! Random number generators for helpers.
+//!
+//! Helpers such as `uuid` and `random` draw values from the generator
+//! assigned to the registry; install a seeded generator so that
+//! renders are deterministic in tests and fixtures:
+//!
+//! ```ignore
+//! use bracket::random::SeededRng;
+//! registry.set_rng(Box::new(SeededRng::new(42)));
+//! ```
+//!
+//! When no generator is assigned and the `std` feature is enabled
+//! helpers use a generator seeded from the randomized hasher state
+//! of the standard library. None of these generators are suitable
+//! for secrets such as session tokens or passwords.
+use core::sync::atomic::{AtomicU64, Ordering};
+
+/// Trait for types that generate random numbers.
+pub trait Rng: Send + Sync {
+    /// Get the next random number.
+    fn next_u64(&self) -> u64;
+}
+
+/// Generator that yields the same sequence for the same seed.
+///
+/// Uses the SplitMix64 algorithm; the state is atomic so a
+/// generator may be shared between threads.
+#[derive(Debug)]
+pub struct SeededRng {
+    state: AtomicU64,
+}
+
+impl SeededRng {
+    /// Create a generator with a seed.
+    pub fn new(seed: u64) -> Self {
+        Self {
+            state: AtomicU64::new(seed),
+        }
+    }
+
+    /// Create a generator with a random seed.
+    #[cfg(feature = "std")]
+    pub fn from_entropy() -> Self {
+        Self::new(entropy())
+    }
+}
+
+impl Rng for SeededRng {
+    fn next_u64(&self) -> u64 {
+        const GAMMA: u64 = 0x9e37_79b9_7f4a_7c15;
+        let mut z = self
+            .state
+            .fetch_add(GAMMA, Ordering::Relaxed)
+            .wrapping_add(GAMMA);
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+}
+
+/// Get a random seed from the randomized hasher state.
+#[cfg(feature = "std")]
+pub fn entropy() -> u64 {
+    use std::{
+        collections::hash_map::RandomState,
+        hash::{BuildHasher, Hasher},
+    };
+    RandomState::new().build_hasher().finish()
+}
@@ -1,18 +1,46 @@
 //! Primary entry point for compiling and rendering templates.
+use alloc::{
+    borrow::ToOwned,
+    boxed::Box,
+    format,
+    string::{String, ToString},
+    vec,
+    vec::Vec,
+};
 use serde::Serialize;
+use serde_json::{Map, Value};
 
 #[cfg(feature = "fs")]
 use std::ffi::OsStr;
 #[cfg(feature = "fs")]
 use std::path::Path;
 
+#[cfg(feature = "std")]
+use crate::{cache::PartialCache, profile::RenderProfile};
+
 use crate::{
-    escape::{self, EscapeFn},
-    helper::{HandlerRegistry, HelperRegistry},
-    output::{Output, StringOutput},
-    parser::{Parser, ParserOptions},
-    render::CallSite,
-    template::{Template, Templates},
+    asset::Manifest,
+    clock::Clock,
+    collections::{HashMap, HashSet},
+    contract::Contract,
+    dependency::{self, Dependencies},
+    error::{ErrorInfo, RenderError, SourcePos, SyntaxError},
+    escape::{self, EscapeFn, EscapeWriteFn},
+    format::{DisplayValue, Formatters, NullPolicy},
+    helper::{HandlerRegistry, Helper, HelperRegistry},
+    json, lint,
+    output::{BytesOutput, Newline, Output, StringOutput, TrailingNewline},
+    parser::{
+        ast::{Call, CallTarget, Lines, Node, ParameterValue, Slice},
+        path, Parser, ParserOptions, UNKNOWN,
+    },
+    random::Rng,
+    render::{CallSite, ErrorFallback, NumericKeys, Render, Type},
+    report::RenderReport,
+    sandbox::Profile,
+    template::{Template, TemplateResolver, Templates},
+    trace::Trace,
+    variant::{Variant, VariantSelector, Variants},
     Error, Result,
 };
 
@@ -20,25 +48,146 @@ use crate::{
 ///
 /// A template name is always required for error messages.
 pub struct Registry<'reg> {
+    parent: Option<&'reg Registry<'reg>>,
     helpers: HelperRegistry<'reg>,
     handlers: HandlerRegistry<'reg>,
+    formatters: Formatters,
     templates: Templates,
+    layers: Vec<(String, Templates)>,
+    contracts: HashMap<String, Contract>,
     escape: EscapeFn,
+    escape_write: EscapeWriteFn,
+    escape_html: bool,
+    escape_inherited: bool,
     strict: bool,
+    newline: Newline,
+    trailing_newline: TrailingNewline,
+    minify: bool,
+    trace: Option<Trace>,
+    null_policy: NullPolicy,
+    error_fallback: ErrorFallback,
+    numeric_keys: NumericKeys,
+    last_result: bool,
+    rng: Option<Box<dyn Rng>>,
+    clock: Option<Box<dyn Clock>>,
+    manifest: Option<Box<dyn Manifest>>,
+    sandboxed: bool,
+    sandbox: Option<Profile>,
+    env: HashSet<String>,
+    callable: HashSet<String>,
+    variants: HashMap<String, Variants>,
+    variant_selector: Option<VariantSelector>,
+    resolver: Option<TemplateResolver>,
+    #[cfg(feature = "std")]
+    partial_cache: Option<PartialCache>,
 }
 
 impl<'reg> Registry<'reg> {
     /// Create an empty registry.
     pub fn new() -> Self {
         Self {
+            parent: None,
             helpers: HelperRegistry::new(),
             handlers: Default::default(),
+            formatters: Default::default(),
             templates: Default::default(),
+            layers: Vec::new(),
+            contracts: Default::default(),
             escape: Box::new(escape::html),
+            escape_write: Box::new(escape::html_write),
+            escape_html: true,
+            escape_inherited: false,
             strict: false,
+            newline: Default::default(),
+            trailing_newline: Default::default(),
+            minify: false,
+            trace: None,
+            null_policy: Default::default(),
+            error_fallback: Default::default(),
+            numeric_keys: Default::default(),
+            last_result: false,
+            rng: None,
+            clock: None,
+            manifest: None,
+            sandboxed: false,
+            sandbox: None,
+            env: Default::default(),
+            callable: Default::default(),
+            variants: Default::default(),
+            variant_selector: None,
+            resolver: None,
+            #[cfg(feature = "std")]
+            partial_cache: None,
+        }
+    }
+
+    /// Create a child registry that overlays this registry.
+    ///
+    /// Templates, contracts, helpers, formatters and variants that do
+    /// not exist in the child are resolved using this registry so a
+    /// child can override partials, helpers or the escape function for
+    /// a tenant without copying the parent:
+    ///
+    /// ```ignore
+    /// let mut tenant = registry.child();
+    /// tenant.insert("footer", "Acme Corp")?;
+    /// tenant.set_escape_write(Box::new(escape::noop_write));
+    /// let result = tenant.render("page", &data)?;
+    /// ```
+    ///
+    /// The strict mode, newline settings, minification, trace, null
+    /// policy, error fallback, numeric key resolution, `@last_result`
+    /// setting, sandbox, allowed environment variables and helpers
+    /// allowed for the `call` helper are copied when the child is
+    /// created.
+    ///
+    /// The escape function, random number generator, clock, asset
+    /// manifest, variant selector and resolver fall back to this
+    /// registry until they are assigned to the child; the event
+    /// handlers are inherited until handlers are assigned to the
+    /// child and the partial cache is never inherited.
+    pub fn child(&'reg self) -> Registry<'reg> {
+        Self {
+            parent: Some(self),
+            helpers: Default::default(),
+            handlers: Default::default(),
+            formatters: Default::default(),
+            templates: Default::default(),
+            layers: Vec::new(),
+            contracts: Default::default(),
+            escape: Box::new(escape::html),
+            escape_write: Box::new(escape::html_write),
+            escape_html: self.escape_html,
+            escape_inherited: true,
+            strict: self.strict,
+            newline: self.newline,
+            trailing_newline: self.trailing_newline,
+            minify: self.minify,
+            trace: self.trace.clone(),
+            null_policy: self.null_policy.clone(),
+            error_fallback: self.error_fallback.clone(),
+            numeric_keys: self.numeric_keys,
+            last_result: self.last_result,
+            rng: None,
+            clock: None,
+            manifest: None,
+            sandboxed: self.sandboxed,
+            sandbox: self.sandbox.clone(),
+            env: self.env.clone(),
+            callable: self.callable.clone(),
+            variants: Default::default(),
+            variant_selector: None,
+            resolver: None,
+            #[cfg(feature = "std")]
+            partial_cache: None,
         }
     }
 
+    /// Get the parent of a child registry.
+    pub fn parent(&self) -> Option<&'reg Registry<'reg>> {
+        self.parent
+    }
+
     /// Set the strict mode.
     pub fn set_strict(&mut self, strict: bool) {
         self.strict = strict
@@ -49,14 +198,261 @@ impl<'reg> Registry<'reg> {
         self.strict
     }
 
+    /// Set the newline normalization for rendered output.
+    pub fn set_newline(&mut self, newline: Newline) {
+        self.newline = newline
+    }
+
+    /// Get the newline normalization for rendered output.
+    pub fn newline(&self) -> Newline {
+        self.newline
+    }
+
+    /// Set the trailing newline policy for rendered output.
+    ///
+    /// Templates may override the policy using
+    /// [set_trailing_newline()](crate::Template#method.set_trailing_newline).
+    pub fn set_trailing_newline(&mut self, policy: TrailingNewline) {
+        self.trailing_newline = policy
+    }
+
+    /// Get the trailing newline policy for rendered output.
+    pub fn trailing_newline(&self) -> TrailingNewline {
+        self.trailing_newline
+    }
+
+    /// Set whether rendered output is minified as HTML.
+    ///
+    /// See the [minify](crate::minify) module for the rules applied.
+    pub fn set_minify(&mut self, minify: bool) {
+        self.minify = minify
+    }
+
+    /// Get whether rendered output is minified as HTML.
+    pub fn minify(&self) -> bool {
+        self.minify
+    }
+
+    /// Set the markers written around the output of partials and
+    /// blocks, use `None` to disable tracing.
+    ///
+    /// See the [trace](crate::trace) module for the marker patterns.
+    pub fn set_trace(&mut self, trace: Option<Trace>) {
+        self.trace = trace
+    }
+
+    /// Get the trace markers.
+    pub fn trace(&self) -> Option<&Trace> {
+        self.trace.as_ref()
+    }
+
+    /// Set the policy for statements that are `null` or missing.
+    ///
+    /// The default writes nothing; choose a policy that suits the
+    /// output format, for example `NullPolicy::Null` for SQL.
+    pub fn set_null_policy(&mut self, policy: NullPolicy) {
+        self.null_policy = policy
+    }
+
+    /// Get the policy for statements that are `null` or missing.
+    pub fn null_policy(&self) -> &NullPolicy {
+        &self.null_policy
+    }
+
+    /// Set the output written in place of nodes that fail to render
+    /// by [render_graceful()](#method.render_graceful).
+    pub fn set_error_fallback(&mut self, fallback: ErrorFallback) {
+        self.error_fallback = fallback
+    }
+
+    /// Get the output written in place of nodes that fail to render.
+    pub fn error_fallback(&self) -> &ErrorFallback {
+        &self.error_fallback
+    }
+
+    /// Set how path components that are integers are resolved.
+    ///
+    /// By default `items.0` is the first element of an array and
+    /// the key `"0"` of an object.
+    pub fn set_numeric_keys(&mut self, keys: NumericKeys) {
+        self.numeric_keys = keys
+    }
+
+    /// Get how path components that are integers are resolved.
+    pub fn numeric_keys(&self) -> NumericKeys {
+        self.numeric_keys
+    }
+
+    /// Set whether the value of the last helper statement is exposed
+    /// as the `@last_result` local.
+    ///
+    /// After a statement that calls a helper the value it returned is
+    /// assigned to `@last_result` in the current scope, or as a render
+    /// local outside of any scope, so later statements can use it
+    /// without nesting sub-expressions:
+    ///
+    /// ```ignore
+    /// {{#each posts}}<a href="/{{slugify title}}">/{{@last_result}}</a>{{/each}}
+    /// ```
+    ///
+    /// Disabled by default because the local shadows any
+    /// `@last_result` declared by a helper or an outer scope.
+    pub fn set_last_result(&mut self, last_result: bool) {
+        self.last_result = last_result
+    }
+
+    /// Get whether the value of the last helper statement is exposed.
+    pub fn last_result(&self) -> bool {
+        self.last_result
+    }
+
+    /// Set the random number generator used by helpers.
+    pub fn set_rng(&mut self, rng: Box<dyn Rng>) {
+        self.rng = Some(rng)
+    }
+
+    /// Get the random number generator falling back
+    /// to the parent registry.
+    pub fn rng(&self) -> Option<&dyn Rng> {
+        self.rng
+            .as_deref()
+            .or_else(|| self.parent.and_then(|p| p.rng()))
+    }
+
+    /// Set the clock used by helpers.
+    pub fn set_clock(&mut self, clock: Box<dyn Clock>) {
+        self.clock = Some(clock)
+    }
+
+    /// Get the clock falling back to the parent registry.
+    pub fn clock(&self) -> Option<&dyn Clock> {
+        self.clock
+            .as_deref()
+            .or_else(|| self.parent.and_then(|p| p.clock()))
+    }
+
+    /// Set the asset manifest used by the `asset` helper.
+    pub fn set_manifest(&mut self, manifest: Box<dyn Manifest>) {
+        self.manifest = Some(manifest)
+    }
+
+    /// Get the asset manifest falling back to the parent registry.
+    pub fn manifest(&self) -> Option<&dyn Manifest> {
+        self.manifest
+            .as_deref()
+            .or_else(|| self.parent.and_then(|p| p.manifest()))
+    }
+
+    /// Set the sandbox flag.
+    ///
+    /// When sandboxed, helpers that use ambient authority such as
+    /// the `env` helper return an error; enable this when rendering
+    /// untrusted templates.
+    pub fn set_sandboxed(&mut self, sandboxed: bool) {
+        self.sandboxed = sandboxed
+    }
+
+    /// Get the sandbox flag.
+    pub fn sandboxed(&self) -> bool {
+        self.sandboxed
+    }
+
+    /// Assign a sandbox profile.
+    ///
+    /// This also sets the sandbox flag so helpers that use ambient
+    /// authority are disabled.
+    pub fn set_sandbox(&mut self, profile: Profile) {
+        self.sandboxed = true;
+        self.sandbox = Some(profile);
+    }
+
+    /// Get the sandbox profile.
+    pub fn sandbox(&self) -> Option<&Profile> {
+        self.sandbox.as_ref()
+    }
+
+    /// Allow the `env` helper to access an environment variable.
+    pub fn allow_env<S>(&mut self, name: S)
+    where
+        S: AsRef<str>,
+    {
+        self.env.insert(name.as_ref().to_string());
+    }
+
+    /// Determine if an environment variable is allowed.
+    pub fn env_allowed(&self, name: &str) -> bool {
+        self.env.contains(name)
+    }
+
+    /// Allow the `call` helper to invoke a helper.
+    pub fn allow_call<S>(&mut self, name: S)
+    where
+        S: AsRef<str>,
+    {
+        self.callable.insert(name.as_ref().to_string());
+    }
+
+    /// Determine if the `call` helper may invoke a helper.
+    pub fn call_allowed(&self, name: &str) -> bool {
+        self.callable.contains(name)
+    }
+
+    /// Assign a cache for rendered partial output.
+    #[cfg(feature = "std")]
+    pub fn set_partial_cache(&mut self, cache: PartialCache) {
+        self.partial_cache = Some(cache);
+    }
+
+    /// Get the partial cache.
+    #[cfg(feature = "std")]
+    pub fn partial_cache(&self) -> Option<&PartialCache> {
+        self.partial_cache.as_ref()
+    }
+
     /// Set the escape function for rendering.
+    ///
+    /// The function is adapted to write to the output destination;
+    /// prefer `set_escape_write()` to avoid allocating a string
+    /// for each escaped value.
     pub fn set_escape(&mut self, escape: EscapeFn) {
+        let (escape, escape_write) = escape::share(escape);
         self.escape = escape;
+        self.escape_write = escape_write;
+        self.escape_html = false;
+        self.escape_inherited = false;
     }
 
-    /// The escape function to use for rendering.
+    /// Set an escape function that writes to the output destination.
+    pub fn set_escape_write(&mut self, escape: EscapeWriteFn) {
+        let (escape, escape_write) = escape::share_write(escape);
+        self.escape = escape;
+        self.escape_write = escape_write;
+        self.escape_html = false;
+        self.escape_inherited = false;
+    }
+
+    /// Determine if the default HTML escape function is in use.
+    ///
+    /// Assigning an escape function clears this flag so the output
+    /// is no longer assumed to be HTML.
+    pub fn escape_html(&self) -> bool {
+        self.escape_html
+    }
+
+    /// The escape function as a function that returns a string.
     pub fn escape(&self) -> &EscapeFn {
-        &self.escape
+        match self.parent {
+            Some(parent) if self.escape_inherited => parent.escape(),
+            _ => &self.escape,
+        }
+    }
+
+    /// The escape function to use for rendering.
+    pub fn escape_write(&self) -> &EscapeWriteFn {
+        match self.parent {
+            Some(parent) if self.escape_inherited => parent.escape_write(),
+            _ => &self.escape_write,
+        }
     }
 
     /// Helper registry.
@@ -69,9 +465,82 @@ impl<'reg> Registry<'reg> {
         &mut self.helpers
     }
 
+    /// Get a helper by name falling back to the parent registry.
+    ///
+    /// Aliases are resolved first so an alias in a child registry
+    /// may refer to a helper in the parent registry.
+    pub fn helper(&self, name: &str) -> Option<&(dyn Helper + 'reg)> {
+        let name = self.helper_alias(name).unwrap_or(name);
+        self.helpers
+            .get(name)
+            .map(|h| h.as_ref())
+            .or_else(|| self.parent.and_then(|p| p.helper(name)))
+    }
+
+    /// Get the names of the helpers and aliases that templates
+    /// may call including the helpers of a parent registry.
+    ///
+    /// Names are sorted and unique.
+    pub fn helper_names(&self) -> Vec<&str> {
+        let mut names: Vec<&str> = self
+            .helpers
+            .names()
+            .chain(self.helpers.aliases().map(|(alias, _)| alias))
+            .collect();
+        if let Some(parent) = self.parent {
+            names.extend(parent.helper_names());
+        }
+        names.sort_unstable();
+        names.dedup();
+        names
+    }
+
+    /// Get the name of the helper invoked for an alias
+    /// falling back to the parent registry.
+    pub fn helper_alias(&self, alias: &str) -> Option<&str> {
+        self.helpers
+            .aliases()
+            .find(|(name, _)| *name == alias)
+            .map(|(_, name)| name)
+            .or_else(|| self.parent.and_then(|p| p.helper_alias(alias)))
+    }
+
+    /// Get the deprecation message for a helper name falling
+    /// back to the parent registry.
+    pub fn helper_deprecation(&self, name: &str) -> Option<&str> {
+        self.helpers
+            .deprecation(name)
+            .or_else(|| self.parent.and_then(|p| p.helper_deprecation(name)))
+    }
+
+    /// Add an alias so that templates calling `alias` invoke
+    /// the helper registered with `name`.
+    pub fn alias_helper(&mut self, alias: &str, name: &str) {
+        self.helpers.alias(alias, name);
+    }
+
+    /// Mark a helper name as deprecated.
+    ///
+    /// Calls to the helper are reported by `lint()` using the
+    /// message which should explain how to migrate, for example:
+    ///
+    /// ```ignore
+    /// registry.alias_helper("fmt", "format");
+    /// registry.deprecate_helper("fmt", "use 'format' instead");
+    /// ```
+    pub fn deprecate_helper(&mut self, name: &str, message: &str) {
+        self.helpers.deprecate(name, message);
+    }
+
     /// Event handler registry.
+    ///
+    /// A child registry without any handlers uses the
+    /// handlers of the parent.
     pub fn handlers(&self) -> &HandlerRegistry<'reg> {
-        &self.handlers
+        match self.parent {
+            Some(parent) if self.handlers.is_empty() => parent.handlers(),
+            _ => &self.handlers,
+        }
     }
 
     /// Mutable reference to the event handler registry.
@@ -79,6 +548,39 @@ impl<'reg> Registry<'reg> {
         &mut self.handlers
     }
 
+    /// Value formatters.
+    pub fn formatters(&self) -> &Formatters {
+        &self.formatters
+    }
+
+    /// Mutable reference to the value formatters.
+    pub fn formatters_mut(&mut self) -> &mut Formatters {
+        &mut self.formatters
+    }
+
+    /// Get the formatter for a JSON type falling back
+    /// to the parent registry.
+    pub fn type_formatter(&self, kind: Type) -> Option<&dyn DisplayValue> {
+        self.formatters
+            .get_type(kind)
+            .or_else(|| self.parent.and_then(|p| p.type_formatter(kind)))
+    }
+
+    /// Get the formatter for a tag falling back to the parent registry.
+    pub fn tag_formatter(&self, tag: &str) -> Option<&dyn DisplayValue> {
+        self.formatters
+            .get_tag(tag)
+            .or_else(|| self.parent.and_then(|p| p.tag_formatter(tag)))
+    }
+
+    /// Convert a value to a string for output using the
+    /// formatter for the type of the value.
+    pub fn format(&self, value: &Value) -> String {
+        self.type_formatter(Type::from(value))
+            .and_then(|f| f.display(value))
+            .unwrap_or_else(|| json::stringify(value))
+    }
+
     /// Templates collection.
     pub fn templates(&self) -> &Templates {
         &self.templates
@@ -96,8 +598,172 @@ impl<'reg> Registry<'reg> {
     }
 
     /// Get a named template.
+    ///
+    /// Templates in the registry take precedence over templates in
+    /// the [layers](Registry#method.add_layer) and for a child registry
+    /// the parent templates are searched when the child does not have
+    /// a template with the name.
     pub fn get<S>(&self, name: S) -> Option<&Template> where S: AsRef<str> {
-        self.templates.get(name.as_ref())
+        self.effective_template(name.as_ref()).map(|(_, t)| t)
+    }
+
+    /// Get a named template and the name of the layer that provides it.
+    ///
+    /// The layer is `None` when the template was added to the
+    /// registry directly rather than to a layer.
+    pub fn effective_template(
+        &self,
+        name: &str,
+    ) -> Option<(Option<&str>, &Template)> {
+        self.templates
+            .get(name)
+            .map(|t| (None, t))
+            .or_else(|| {
+                self.layers.iter().find_map(|(layer, templates)| {
+                    templates.get(name).map(|t| (Some(layer.as_str()), t))
+                })
+            })
+            .or_else(|| self.parent.and_then(|p| p.effective_template(name)))
+    }
+
+    /// Add a layer of templates with a lower priority than the
+    /// existing layers.
+    ///
+    /// Layers allow a site to override the templates of a theme
+    /// which overrides the templates of a core set of templates:
+    ///
+    /// ```ignore
+    /// registry.add_layer("site");
+    /// registry.add_layer("theme");
+    /// registry.add_layer("core");
+    /// registry.insert_layer("core", "footer", "Powered by bracket")?;
+    /// registry.read_layer("theme", "themes/dark", "hbs")?;
+    /// ```
+    ///
+    /// Adding a layer that already exists does not change the priority.
+    pub fn add_layer<S>(&mut self, layer: S)
+    where
+        S: AsRef<str>,
+    {
+        let layer = layer.as_ref();
+        if self.layer(layer).is_none() {
+            self.layers.push((layer.to_string(), Default::default()));
+        }
+    }
+
+    /// Iterate the layer names in priority order.
+    pub fn layers(&self) -> impl Iterator<Item = &str> {
+        self.layers.iter().map(|(layer, _)| layer.as_str())
+    }
+
+    /// Get the templates for a layer.
+    pub fn layer(&self, layer: &str) -> Option<&Templates> {
+        self.layers
+            .iter()
+            .find(|(name, _)| name == layer)
+            .map(|(_, templates)| templates)
+    }
+
+    /// Get a mutable reference to the templates for a layer.
+    pub fn layer_mut(&mut self, layer: &str) -> Option<&mut Templates> {
+        self.layers
+            .iter_mut()
+            .find(|(name, _)| name == layer)
+            .map(|(_, templates)| templates)
+    }
+
+    /// Insert a named string template into a layer.
+    ///
+    /// The layer is added when it does not exist.
+    pub fn insert_layer<L, N, C>(
+        &mut self,
+        layer: L,
+        name: N,
+        content: C,
+    ) -> Result<()>
+    where
+        L: AsRef<str>,
+        N: AsRef<str>,
+        C: AsRef<str>,
+    {
+        let name = name.as_ref().to_owned();
+        let template = self.compile(
+            content.as_ref(),
+            ParserOptions::new(name.clone(), 0, 0),
+        )?;
+        self.add_layer(layer.as_ref());
+        self.layer_mut(layer.as_ref())
+            .unwrap()
+            .insert(name, template);
+        Ok(())
+    }
+
+    /// Get the first template that exists from a list of names.
+    ///
+    /// Use this to fall back from a specific template to a more
+    /// general template, for example:
+    ///
+    /// ```ignore
+    /// let template = registry.get_fallback(&[
+    ///     "emails/fr/welcome",
+    ///     "emails/en/welcome",
+    ///     "emails/welcome",
+    /// ]);
+    /// ```
+    pub fn get_fallback<I, S>(&self, names: I) -> Option<(S, &Template)>
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        names.into_iter().find_map(|name| {
+            let template = self.get(name.as_ref())?;
+            Some((name, template))
+        })
+    }
+
+    /// Set a function that resolves template names to candidate
+    /// names for each render.
+    ///
+    /// When a template is rendered by name or used as a partial the
+    /// candidates are searched in order before the name itself so
+    /// that localized or themed templates can be preferred:
+    ///
+    /// ```ignore
+    /// registry.set_resolver(Box::new(|name, data| {
+    ///     let mut names = Vec::new();
+    ///     if let Some(locale) = data.get("locale").and_then(|v| v.as_str()) {
+    ///         names.push(format!("{}/{}", locale, name));
+    ///     }
+    ///     names
+    /// }));
+    /// ```
+    pub fn set_resolver(&mut self, resolver: TemplateResolver) {
+        self.resolver = Some(resolver);
+    }
+
+    /// Determine if a resolver has been assigned.
+    pub fn has_resolver(&self) -> bool {
+        self.resolver.is_some()
+            || self.parent.map(|p| p.has_resolver()).unwrap_or(false)
+    }
+
+    /// Resolve a template by name for the render data.
+    ///
+    /// Candidates returned by the resolver are preferred, then
+    /// a template with the name and finally a variant selected
+    /// for the name.
+    pub fn resolve(&self, name: &str, data: &Value) -> Option<&Template> {
+        self.resolver()
+            .and_then(|resolver| self.get_fallback(resolver(name, data)))
+            .map(|(_, template)| template)
+            .or_else(|| self.get(name))
+            .or_else(|| self.select_variant(name, data).map(|v| v.template()))
+    }
+
+    fn resolver(&self) -> Option<&TemplateResolver> {
+        self.resolver
+            .as_ref()
+            .or_else(|| self.parent.and_then(|p| p.resolver()))
     }
 
     /// Remove a named template.
@@ -120,6 +786,96 @@ impl<'reg> Registry<'reg> {
         Ok(())
     }
 
+    /// Register a variant of a logical template name.
+    ///
+    /// When the logical name is rendered, or used as a partial,
+    /// a variant is chosen using the variant selector; the weight
+    /// is the relative frequency the variant should be selected.
+    ///
+    /// A template registered with the same name takes precedence
+    /// over the variants.
+    pub fn register_variant<N, V, C>(
+        &mut self,
+        name: N,
+        variant: V,
+        content: C,
+        weight: u32,
+    ) -> Result<()>
+    where
+        N: AsRef<str>,
+        V: AsRef<str>,
+        C: AsRef<str>,
+    {
+        let name = name.as_ref();
+        let variant = variant.as_ref();
+        let template = self.compile(
+            content.as_ref(),
+            ParserOptions::new(format!("{}#{}", name, variant), 0, 0),
+        )?;
+        self.variants.entry(name.to_string()).or_default().insert(
+            variant.to_string(),
+            weight,
+            template,
+        );
+        Ok(())
+    }
+
+    /// Get the variants for a logical template name.
+    pub fn variants(&self, name: &str) -> Option<&Variants> {
+        self.variants
+            .get(name)
+            .or_else(|| self.parent.and_then(|p| p.variants(name)))
+    }
+
+    /// Remove the variants for a logical template name.
+    pub fn remove_variants(&mut self, name: &str) -> Option<Variants> {
+        self.variants.remove(name)
+    }
+
+    /// Set the function used to select a variant for each render.
+    pub fn set_variant_selector(&mut self, selector: VariantSelector) {
+        self.variant_selector = Some(selector);
+    }
+
+    /// Select a variant of a logical template name for the render data.
+    pub fn select_variant(&self, name: &str, data: &Value) -> Option<&Variant> {
+        self.variants(name).and_then(|variants| {
+            variants.select(name, self.variant_selector(), data)
+        })
+    }
+
+    fn variant_selector(&self) -> Option<&VariantSelector> {
+        self.variant_selector
+            .as_ref()
+            .or_else(|| self.parent.and_then(|p| p.variant_selector()))
+    }
+
+    /// Assign a contract that declares the hash parameters
+    /// expected by a partial.
+    ///
+    /// Call sites are checked by [lint()](Registry#method.lint),
+    /// when a [builder](RegistryBuilder) validates templates and when
+    /// the partial is rendered in strict mode.
+    pub fn set_contract<S>(&mut self, name: S, contract: Contract)
+    where
+        S: AsRef<str>,
+    {
+        self.contracts.insert(name.as_ref().to_string(), contract);
+    }
+
+    /// Get the contract for a partial falling back to
+    /// the parent registry.
+    pub fn contract(&self, name: &str) -> Option<&Contract> {
+        self.contracts
+            .get(name)
+            .or_else(|| self.parent.and_then(|p| p.contract(name)))
+    }
+
+    /// Remove the contract for a partial.
+    pub fn remove_contract(&mut self, name: &str) -> Option<Contract> {
+        self.contracts.remove(name)
+    }
+
     /// Add a named template from a file.
     ///
     /// Requires the `fs` feature.
@@ -172,19 +928,56 @@ impl<'reg> Registry<'reg> {
         file: P,
         extension: &str,
     ) -> Result<()> {
-        let ext = OsStr::new(extension);
-        for entry in std::fs::read_dir(file.as_ref())? {
-            let entry = entry?;
-            let path = entry.path();
-            if path.is_file() {
-                if let Some(extension) = path.extension() {
-                    if extension == ext {
-                        let file_name = path
-                            .to_string_lossy()
-                            .into_owned()
-                            .to_string();
+        let templates = self.read_templates(file, extension)?;
+        self.templates.extend(templates);
+        Ok(())
+    }
 
-                        let name = path
+    /// Load all the files in a target directory that match the
+    /// given extension into a layer.
+    ///
+    /// The layer is added when it does not exist.
+    ///
+    /// Requires the `fs` feature.
+    #[cfg(feature = "fs")]
+    pub fn read_layer<L, P>(
+        &mut self,
+        layer: L,
+        file: P,
+        extension: &str,
+    ) -> Result<()>
+    where
+        L: AsRef<str>,
+        P: AsRef<Path>,
+    {
+        let templates = self.read_templates(file, extension)?;
+        self.add_layer(layer.as_ref());
+        self.layer_mut(layer.as_ref()).unwrap().extend(templates);
+        Ok(())
+    }
+
+    /// Compile the files in a directory that match the given
+    /// extension named using the file stem.
+    #[cfg(feature = "fs")]
+    fn read_templates<P: AsRef<Path>>(
+        &self,
+        file: P,
+        extension: &str,
+    ) -> Result<Vec<(String, Template)>> {
+        let mut templates = Vec::new();
+        let ext = OsStr::new(extension);
+        for entry in std::fs::read_dir(file.as_ref())? {
+            let entry = entry?;
+            let path = entry.path();
+            if path.is_file() {
+                if let Some(extension) = path.extension() {
+                    if extension == ext {
+                        let file_name = path
+                            .to_string_lossy()
+                            .into_owned()
+                            .to_string();
+
+                        let name = path
                             .file_stem()
                             .unwrap()
                             .to_string_lossy()
@@ -195,19 +988,19 @@ impl<'reg> Registry<'reg> {
                             content,
                             ParserOptions::new(file_name, 0, 0),
                         )?;
-                        self.templates.insert(name, template);
+                        templates.push((name, template));
                     }
                 }
             }
         }
-        Ok(())
+        Ok(templates)
     }
 
     #[cfg(feature = "fs")]
     fn read<P: AsRef<Path>>(
         &self,
         file: P,
-    ) -> std::io::Result<(String, String)> {
+    ) -> crate::output::io::Result<(String, String)> {
         let path = file.as_ref();
         let name = path.to_string_lossy().to_owned().to_string();
         let content = std::fs::read_to_string(path)?;
@@ -245,16 +1038,94 @@ impl<'reg> Registry<'reg> {
     where
         S: AsRef<str>,
     {
+        let source = template.as_ref();
         let mut errors: Vec<Error> = Vec::new();
-        let mut parser = Parser::new(
-            template.as_ref(),
-            ParserOptions::new(name.to_string(), 0, 0),
-        );
+        let mut warnings: Vec<Error> = Vec::new();
+        let mut parser =
+            Parser::new(source, ParserOptions::new(name.to_string(), 0, 0));
         parser.set_errors(&mut errors);
-        for _ in parser {}
+        for node in parser.flatten() {
+            for node in core::iter::once(&node).chain(node.descendants()) {
+                let call = match node {
+                    Node::Statement(ref call) => call,
+                    Node::Block(ref block) => {
+                        warnings.extend(lint::dead_code(name, source, block));
+                        block.call()
+                    }
+                    _ => continue,
+                };
+                self.deprecations(name, source, call, &mut warnings);
+                self.contract_violations(name, source, call, &mut warnings);
+            }
+        }
+        warnings.append(&mut lint::whitespace(name, source));
+        errors.append(&mut warnings);
         Ok(errors)
     }
 
+    /// Collect calls to deprecated helpers including calls
+    /// in sub-expressions.
+    fn deprecations(
+        &self,
+        name: &str,
+        source: &str,
+        call: &Call<'_>,
+        warnings: &mut Vec<Error>,
+    ) {
+        if let CallTarget::Path(ref path) = call.target() {
+            let message = self.helper_deprecation(path.as_str());
+            if let (Some(message), false) = (message, call.is_partial()) {
+                let pos = SourcePos(call.lines().start, path.span().start);
+                let info = ErrorInfo::new(source, name, pos, vec![]);
+                warnings.push(Error::from(SyntaxError::DeprecatedHelper(
+                    path.as_str().to_string(),
+                    message.to_string(),
+                    info.into(),
+                )));
+            }
+        }
+
+        let values = call.arguments().iter().chain(call.parameters().values());
+        for value in values {
+            if let ParameterValue::SubExpr(ref call) = value {
+                self.deprecations(name, source, call, warnings);
+            }
+        }
+    }
+
+    /// Collect the contract violations for a partial call.
+    fn contract_violations(
+        &self,
+        name: &str,
+        source: &str,
+        call: &Call<'_>,
+        errors: &mut Vec<Error>,
+    ) {
+        let (partial, path) =
+            match (dependency::partial_name(call), call.target()) {
+                (Some(partial), CallTarget::Path(ref path)) => (partial, path),
+                _ => return,
+            };
+        if let Some(contract) = self.contract(partial) {
+            let params = call.parameters().iter().map(|(k, v)| {
+                let value = match v {
+                    ParameterValue::Json { ref value, .. } => Some(value),
+                    _ => None,
+                };
+                (*k, value)
+            });
+            for violation in contract.check(params) {
+                let pos = SourcePos(call.lines().start, path.span().start);
+                let info = ErrorInfo::new(source, name, pos, vec![]);
+                errors.push(Error::from(SyntaxError::PartialContract(
+                    partial.to_string(),
+                    violation.to_string(),
+                    info.into(),
+                )));
+            }
+        }
+    }
+
     /// Render a template without registering it and return
     /// the result as a string.
     ///
@@ -264,11 +1135,11 @@ impl<'reg> Registry<'reg> {
         T: Serialize,
         S: AsRef<str>,
     {
-        let mut writer = StringOutput::new();
         let template = self.compile(
             source.as_ref(),
             ParserOptions::new(name.to_string(), 0, 0),
         )?;
+        let mut writer = StringOutput::with_capacity(template.size_hint());
         template.render(self, name, data, &mut writer, Default::default())?;
         Ok(writer.into())
     }
@@ -302,15 +1173,65 @@ impl<'reg> Registry<'reg> {
         T: Serialize,
         S: AsRef<str>,
     {
-        let mut writer = StringOutput::new();
         let template = self.compile(
             source.as_ref(),
             ParserOptions::new(name.to_string(), 0, 0),
         )?;
-        template.render(self, name, data, &mut writer, stack)?;
+        let mut writer = StringOutput::with_capacity(template.size_hint());
+        template.render_nested(self, name, data, &mut writer, stack)?;
         Ok(writer.into())
     }
 
+    /// Evaluate a path or sub-expression against data and
+    /// return the resulting value without rendering any text.
+    ///
+    /// Paths are resolved like variables in a template so a missing
+    /// value is `null` unless strict mode is enabled; sub-expressions
+    /// invoke helpers and return the helper value:
+    ///
+    /// ```ignore
+    /// let name = registry.evaluate("user.name", &data)?;
+    /// let adult = registry.evaluate("(gte user.age 18)", &data)?;
+    /// ```
+    ///
+    /// Any output written by helpers is discarded.
+    pub fn evaluate<T>(&self, expression: &str, data: &T) -> Result<Value>
+    where
+        T: Serialize,
+    {
+        let source = expression.trim();
+        let mut writer = StringOutput::new();
+        let mut rc = Render::new(
+            self,
+            UNKNOWN,
+            data,
+            Box::new(&mut writer),
+            Default::default(),
+        )?;
+        let value = if source.starts_with('(') {
+            let statement = format!("{{{{{}}}}}", source);
+            let node = Parser::new(&statement, Default::default()).parse()?;
+            let nodes = match node {
+                Node::Document(ref doc) => doc.nodes().as_slice(),
+                _ => &[],
+            };
+            match nodes {
+                [Node::Statement(ref call)] => rc.call(call)?,
+                _ => {
+                    return Err(Error::from(RenderError::EvaluatePath(
+                        source.to_string(),
+                        None,
+                    )))
+                }
+            }
+        } else if let Some(path) = path::from_str(source)? {
+            rc.resolve(&path)?
+        } else {
+            None
+        };
+        Ok(value.unwrap_or(Value::Null))
+    }
+
     /*
 
     /// Stream a dynamic template and buffer the result to a string.
@@ -350,7 +1271,7 @@ impl<'reg> Registry<'reg> {
         let mut buffer: Vec<Node<'_>> = Vec::new();
         let mut rc = Render::new(
             self.strict(),
-            self.escape(),
+            self.escape_write(),
             self.helpers(),
             self.templates(),
             source,
@@ -387,11 +1308,301 @@ impl<'reg> Registry<'reg> {
     where
         T: Serialize,
     {
-        let mut writer = StringOutput::new();
+        let capacity = self.get(name).map(|t| t.size_hint()).unwrap_or(0);
+        let mut writer = StringOutput::with_capacity(capacity);
+        self.render_to_write(name, data, &mut writer)?;
+        Ok(writer.into())
+    }
+
+    /// Render a named template and buffer the result to bytes.
+    ///
+    /// Escape functions and helpers may write bytes that are not valid
+    /// UTF-8; to encode the template text differently render to a
+    /// [BytesOutput](crate::output::BytesOutput) using `render_to_write()`.
+    pub fn render_bytes<T>(&self, name: &str, data: &T) -> Result<Vec<u8>>
+    where
+        T: Serialize,
+    {
+        let capacity = self.get(name).map(|t| t.size_hint()).unwrap_or(0);
+        let mut writer = BytesOutput::with_capacity(capacity);
         self.render_to_write(name, data, &mut writer)?;
         Ok(writer.into())
     }
 
+    /// Convert the data to a value and find the named template
+    /// using the resolver, the registered templates and the variants.
+    fn lookup_with_data<T>(
+        &self,
+        name: &str,
+        data: &T,
+    ) -> Result<(&Template, Value)>
+    where
+        T: Serialize,
+    {
+        let data = serde_json::to_value(data).map_err(RenderError::from)?;
+        let template = self
+            .resolve(name, &data)
+            .ok_or_else(|| Error::TemplateNotFound(name.to_string()))?;
+        Ok((template, data))
+    }
+
+    /// Render a named template with render locals.
+    ///
+    /// Render locals are per-render values that are not part of the
+    /// template data, for example the nonce for a Content Security
+    /// Policy; they are available to templates as `@{name}` in every
+    /// scope and to helpers using
+    /// [render_local()](crate::render::Render#method.render_local):
+    ///
+    /// ```ignore
+    /// let mut locals = Map::new();
+    /// locals.insert("nonce".to_string(), json!(nonce));
+    /// let result = registry.render_with_locals("page", &data, &locals)?;
+    /// ```
+    pub fn render_with_locals<T>(
+        &self,
+        name: &str,
+        data: &T,
+        locals: &Map<String, Value>,
+    ) -> Result<String>
+    where
+        T: Serialize,
+    {
+        let (template, data) = self.lookup_with_data(name, data)?;
+        let mut writer = StringOutput::with_capacity(template.size_hint());
+        template.render_with(
+            self,
+            name,
+            &data,
+            &mut writer,
+            &(),
+            |rc, node, _| {
+                for (key, value) in locals {
+                    rc.set_render_local(key, value.clone());
+                }
+                rc.render(node)
+            },
+        )?;
+        Ok(writer.into())
+    }
+
+    /// Render a named template without aborting when a statement
+    /// or block fails to render.
+    ///
+    /// The [error fallback](#method.set_error_fallback) is written in
+    /// place of each node that fails and the errors are returned with
+    /// the output so that a preview can show as much of the page as
+    /// possible:
+    ///
+    /// ```ignore
+    /// registry.set_error_fallback(ErrorFallback::Comment);
+    /// let (output, errors) = registry.render_graceful("page", &data)?;
+    /// for error in errors {
+    ///     eprintln!("{:?}", error);
+    /// }
+    /// ```
+    ///
+    /// Errors for a missing template, the output limit of a sandbox
+    /// and writing to the output destination abort the render.
+    pub fn render_graceful<T>(
+        &self,
+        name: &str,
+        data: &T,
+    ) -> Result<(String, Vec<RenderError>)>
+    where
+        T: Serialize,
+    {
+        let (template, data) = self.lookup_with_data(name, data)?;
+        let mut writer = StringOutput::with_capacity(template.size_hint());
+        let errors = template.render_with(
+            self,
+            name,
+            &data,
+            &mut writer,
+            &(),
+            |rc, node, _| {
+                rc.collect_errors();
+                rc.render(node)?;
+                Ok(rc.take_errors())
+            },
+        )?;
+        Ok((writer.into(), errors))
+    }
+
+    /// Render a fragment of a named template.
+    ///
+    /// The block for the fragment is found using
+    /// [fragment()](crate::Template#method.fragment) and only the
+    /// content of the block is rendered with the data so that a
+    /// single template can render a page and partial updates:
+    ///
+    /// ```ignore
+    /// let row = registry.render_fragment("table", "row", &data)?;
+    /// ```
+    pub fn render_fragment<T>(
+        &self,
+        name: &str,
+        id: &str,
+        data: &T,
+    ) -> Result<String>
+    where
+        T: Serialize,
+    {
+        let (template, data) = self.lookup_with_data(name, data)?;
+        let node = template.fragment(id).ok_or_else(|| {
+            Error::FragmentNotFound(name.to_string(), id.to_string())
+        })?;
+
+        let mut writer = StringOutput::new();
+        template.render_with(
+            self,
+            name,
+            &data,
+            &mut writer,
+            node,
+            |rc, _, fragment| rc.template(fragment).map_err(RenderError::from),
+        )?;
+        Ok(writer.into())
+    }
+
+    /// Render a named template and collect a report of missing
+    /// variables, missing partials, helper warnings and text removed
+    /// by whitespace control; see the [report](crate::report) module.
+    ///
+    /// Missing partials are rendered as the empty string so that the
+    /// problems can be logged without failing the request; other
+    /// errors abort the render as usual.
+    pub fn render_with_report<T>(
+        &self,
+        name: &str,
+        data: &T,
+    ) -> Result<(String, RenderReport)>
+    where
+        T: Serialize,
+    {
+        let (template, data) = self.lookup_with_data(name, data)?;
+        let mut writer = StringOutput::with_capacity(template.size_hint());
+        let report = template.render_with(
+            self,
+            name,
+            &data,
+            &mut writer,
+            &(),
+            |rc, node, _| {
+                rc.collect_report();
+                rc.render(node)?;
+                Ok(rc.take_report())
+            },
+        )?;
+        Ok((writer.into(), report))
+    }
+
+    /// Render a named template and collect a profile of the time
+    /// spent rendering nodes, helpers and partials.
+    ///
+    /// Profiling adds overhead to every node so use this function
+    /// to find the slow parts of a template rather than for every
+    /// render; see the [profile](crate::profile) module.
+    #[cfg(feature = "std")]
+    pub fn render_profiled<T>(
+        &self,
+        name: &str,
+        data: &T,
+    ) -> Result<(String, RenderProfile)>
+    where
+        T: Serialize,
+    {
+        let (template, data) = self.lookup_with_data(name, data)?;
+        let mut writer = StringOutput::with_capacity(template.size_hint());
+        let profile = template.render_with(
+            self,
+            name,
+            &data,
+            &mut writer,
+            &(),
+            |rc, node, _| {
+                rc.start_profile();
+                rc.render(node)?;
+                Ok(rc.finish_profile().unwrap_or_default())
+            },
+        )?;
+        Ok((writer.into(), profile))
+    }
+
+    /// Render a named template for each data item and buffer
+    /// the results to strings.
+    ///
+    /// The named template must exist in the templates collection
+    /// or have registered variants.
+    pub fn render_batch<T, I>(
+        &self,
+        name: &str,
+        items: I,
+    ) -> Result<Vec<String>>
+    where
+        T: Serialize,
+        I: IntoIterator<Item = T>,
+    {
+        let mut results = Vec::new();
+        self.render_batch_with(name, items, |_, output| {
+            results.push(output.to_string());
+            Ok(())
+        })?;
+        Ok(results)
+    }
+
+    /// Render a named template for each data item and pass the
+    /// index of the item and the rendered output to a callback.
+    ///
+    /// The template is resolved once and the output buffer is reused
+    /// for every item so the callback should write the output to its
+    /// destination, for example:
+    ///
+    /// ```ignore
+    /// registry.render_batch_with("invoice", customers, |index, output| {
+    ///     std::fs::write(format!("invoice-{}.html", index), output)?;
+    ///     Ok(())
+    /// })?;
+    /// ```
+    ///
+    /// Rendering stops when the callback returns an error.
+    pub fn render_batch_with<T, I, F>(
+        &self,
+        name: &str,
+        items: I,
+        mut callback: F,
+    ) -> Result<()>
+    where
+        T: Serialize,
+        I: IntoIterator<Item = T>,
+        F: FnMut(usize, &str) -> Result<()>,
+    {
+        let resolved = self.has_resolver();
+        let template = if resolved { None } else { self.get(name) };
+        if template.is_none() && !resolved && self.variants(name).is_none() {
+            return Err(Error::TemplateNotFound(name.to_string()));
+        }
+
+        let capacity = template.map(|t| t.size_hint()).unwrap_or(0);
+        let mut writer = StringOutput::with_capacity(capacity);
+        for (index, item) in items.into_iter().enumerate() {
+            writer.clear();
+            if let Some(template) = template {
+                template.render(
+                    self,
+                    name,
+                    &item,
+                    &mut writer,
+                    Default::default(),
+                )?;
+            } else {
+                self.render_to_write(name, &item, &mut writer)?;
+            }
+            callback(index, writer.as_str())?;
+        }
+        Ok(())
+    }
+
     /// Render a compiled template without registering it and
     /// buffer the result to a string.
     pub fn render_template<'a, T>(
@@ -403,11 +1614,67 @@ impl<'reg> Registry<'reg> {
     where
         T: Serialize,
     {
-        let mut writer = StringOutput::new();
+        let mut writer = StringOutput::with_capacity(template.size_hint());
         template.render(self, name, data, &mut writer, Default::default())?;
         Ok(writer.into())
     }
 
+    /// Compile and render a template with ad-hoc partials and
+    /// buffer the result to a string.
+    ///
+    /// The partials are compiled and available only for this render,
+    /// they take precedence over registered templates with the same name.
+    ///
+    /// ```ignore
+    /// let mut partials = HashMap::new();
+    /// partials.insert("footer", "Sent to {{email}}");
+    /// let options = ParserOptions::new("body.hbs".to_string(), 0, 0);
+    /// let body = registry.render_template_string(
+    ///     "Hello {{name}}, {{> footer}}", &data, options, partials)?;
+    /// ```
+    pub fn render_template_string<T, S, P, N, C>(
+        &self,
+        source: S,
+        data: &T,
+        options: ParserOptions,
+        partials: P,
+    ) -> Result<String>
+    where
+        T: Serialize,
+        S: AsRef<str>,
+        P: IntoIterator<Item = (N, C)>,
+        N: AsRef<str>,
+        C: AsRef<str>,
+    {
+        let name = options.file_name.clone();
+        let template = self.compile(source, options)?;
+        let partials = partials
+            .into_iter()
+            .map(|(name, content)| {
+                let name = name.as_ref().to_string();
+                let template = self
+                    .compile(content, ParserOptions::new(name.clone(), 0, 0))?;
+                Ok((name, template))
+            })
+            .collect::<Result<Vec<(String, Template)>>>()?;
+
+        let mut writer = StringOutput::with_capacity(template.size_hint());
+        template.render_with(
+            self,
+            &name,
+            data,
+            &mut writer,
+            &partials,
+            |rc, node, partials| {
+                for (name, partial) in partials.iter() {
+                    rc.insert_partial(name, partial.node());
+                }
+                rc.render(node)
+            },
+        )?;
+        Ok(writer.into())
+    }
+
     /// Render a named template to a writer.
     ///
     /// The named template must exist in the templates collection.
@@ -420,12 +1687,353 @@ impl<'reg> Registry<'reg> {
     where
         T: Serialize,
     {
-        let tpl = self
-            .templates
-            .get(name)
-            .ok_or_else(|| Error::TemplateNotFound(name.to_string()))?;
-        tpl.render(self, name, data, writer, Default::default())?;
+        let direct = if self.has_resolver() {
+            None
+        } else {
+            self.get(name)
+        };
+        if let Some(tpl) = direct {
+            tpl.render(self, name, data, writer, Default::default())?;
+        } else {
+            let data = serde_json::to_value(data).map_err(RenderError::from)?;
+            let tpl = self
+                .resolve(name, &data)
+                .ok_or_else(|| Error::TemplateNotFound(name.to_string()))?;
+            tpl.render(self, name, &data, writer, Default::default())?;
+        }
 
         Ok(())
     }
+
+    /// Build the dependency graph for a named template.
+    ///
+    /// The template and the partials it references are walked
+    /// statically; referenced partials that do not exist in the
+    /// templates collection are recorded as missing.
+    pub fn dependencies(&self, name: &str) -> Result<Dependencies> {
+        if self.get(name).is_none() {
+            return Err(Error::TemplateNotFound(name.to_string()));
+        }
+
+        let mut graph = Dependencies::new(name);
+        let mut pending = vec![name.to_string()];
+        while let Some(current) = pending.pop() {
+            if graph.visited(&current) {
+                continue;
+            }
+            if let Some(tpl) = self.get(&current) {
+                let partials = dependency::partials(tpl.node());
+                pending.extend(partials.iter().rev().cloned());
+                graph.insert(&current, partials);
+            } else {
+                graph.insert_missing(&current);
+            }
+        }
+        Ok(graph)
+    }
+}
+
+/// Source for a template added to a registry builder.
+enum Source {
+    Text(String, String),
+    #[cfg(feature = "fs")]
+    File(String, std::path::PathBuf),
+    #[cfg(feature = "fs")]
+    Dir(std::path::PathBuf, String),
+}
+
+/// Build a registry and validate the templates.
+///
+/// Templates, helpers and settings are accumulated and every
+/// template is compiled and checked when the registry is built so
+/// that all the problems are reported at once rather than when
+/// a template is rendered:
+///
+/// ```ignore
+/// let registry = Registry::builder()
+///     .template("page", "{{> header}}{{upper title}}")
+///     .template("header", "<h1>{{site}}</h1>")
+///     .helper("upper", Box::new(Upper {}))
+///     .strict(true)
+///     .build()?;
+/// ```
+///
+/// Validation reports syntax errors, partials that do not exist and
+/// calls to helpers that do not exist. A call is only treated as a
+/// helper call when it has arguments or hash parameters or is a
+/// sub-expression because a plain statement may be a variable.
+/// Helpers are not checked when a helper missing handler is assigned.
+pub struct RegistryBuilder<'reg> {
+    registry: Registry<'reg>,
+    sources: Vec<Source>,
+}
+
+impl<'reg> RegistryBuilder<'reg> {
+    /// Create a builder for an empty registry.
+    pub fn new() -> Self {
+        Self {
+            registry: Registry::new(),
+            sources: Vec::new(),
+        }
+    }
+
+    /// Add a named string template.
+    pub fn template<N, C>(mut self, name: N, content: C) -> Self
+    where
+        N: Into<String>,
+        C: Into<String>,
+    {
+        self.sources.push(Source::Text(name.into(), content.into()));
+        self
+    }
+
+    /// Add a named template from a file.
+    ///
+    /// Requires the `fs` feature.
+    #[cfg(feature = "fs")]
+    pub fn file<N, P>(mut self, name: N, file: P) -> Self
+    where
+        N: Into<String>,
+        P: AsRef<Path>,
+    {
+        self.sources
+            .push(Source::File(name.into(), file.as_ref().to_path_buf()));
+        self
+    }
+
+    /// Add the files in a directory that match the given extension
+    /// using the file stem as the template name.
+    ///
+    /// Requires the `fs` feature.
+    #[cfg(feature = "fs")]
+    pub fn dir<P>(mut self, dir: P, extension: &str) -> Self
+    where
+        P: AsRef<Path>,
+    {
+        self.sources.push(Source::Dir(
+            dir.as_ref().to_path_buf(),
+            extension.to_string(),
+        ));
+        self
+    }
+
+    /// Add a helper.
+    pub fn helper(
+        mut self,
+        name: &'reg str,
+        helper: Box<dyn Helper + 'reg>,
+    ) -> Self {
+        self.registry.helpers_mut().insert(name, helper);
+        self
+    }
+
+    /// Assign a contract for a partial; call sites that do not
+    /// satisfy the contract are reported when the registry is built.
+    pub fn contract<S>(mut self, name: S, contract: Contract) -> Self
+    where
+        S: AsRef<str>,
+    {
+        self.registry.set_contract(name, contract);
+        self
+    }
+
+    /// Set the escape function.
+    pub fn escape(mut self, escape: EscapeFn) -> Self {
+        self.registry.set_escape(escape);
+        self
+    }
+
+    /// Set the strict mode.
+    pub fn strict(mut self, strict: bool) -> Self {
+        self.registry.set_strict(strict);
+        self
+    }
+
+    /// Set the sandbox profile.
+    pub fn sandbox(mut self, profile: Profile) -> Self {
+        self.registry.set_sandbox(profile);
+        self
+    }
+
+    /// Modify the registry directly for settings that the
+    /// builder does not provide.
+    pub fn configure<F>(mut self, f: F) -> Self
+    where
+        F: FnOnce(&mut Registry<'reg>),
+    {
+        f(&mut self.registry);
+        self
+    }
+
+    /// Compile and validate the templates and return the registry.
+    ///
+    /// Returns every error when any template fails to load, compile
+    /// or validate.
+    pub fn build(self) -> core::result::Result<Registry<'reg>, Vec<Error>> {
+        let mut registry = self.registry;
+        let mut errors = Vec::new();
+        let sources = read_sources(self.sources, &mut errors);
+        for (name, file_name, content) in sources {
+            let options = ParserOptions::new(file_name, 0, 0);
+            match registry.compile(content, options) {
+                Ok(template) => {
+                    registry.templates.insert(name, template);
+                }
+                Err(e) => errors.push(e),
+            }
+        }
+
+        let mut names: Vec<&String> = registry.templates.keys().collect();
+        names.sort();
+        for name in names {
+            let template = registry.templates.get(name).unwrap();
+            registry.validate(template, &mut errors);
+        }
+
+        if errors.is_empty() {
+            Ok(registry)
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+impl Default for RegistryBuilder<'_> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Read the sources for a builder as a name, file name
+/// and content collecting any errors.
+#[cfg_attr(not(feature = "fs"), allow(unused_variables))]
+fn read_sources(
+    sources: Vec<Source>,
+    errors: &mut Vec<Error>,
+) -> Vec<(String, String, String)> {
+    let mut templates = Vec::new();
+    for source in sources {
+        match source {
+            Source::Text(name, content) => {
+                templates.push((name.clone(), name, content));
+            }
+            #[cfg(feature = "fs")]
+            Source::File(name, path) => match std::fs::read_to_string(&path) {
+                Ok(content) => templates.push((
+                    name,
+                    path.to_string_lossy().into_owned(),
+                    content,
+                )),
+                Err(e) => errors.push(Error::from(e)),
+            },
+            #[cfg(feature = "fs")]
+            Source::Dir(dir, extension) => {
+                let entries = match std::fs::read_dir(&dir) {
+                    Ok(entries) => entries,
+                    Err(e) => {
+                        errors.push(Error::from(e));
+                        continue;
+                    }
+                };
+                let mut paths: Vec<_> = entries
+                    .filter_map(|entry| entry.ok().map(|e| e.path()))
+                    .filter(|path| {
+                        path.is_file()
+                            && path.extension() == Some(OsStr::new(&extension))
+                    })
+                    .collect();
+                paths.sort();
+                for path in paths {
+                    let name = path
+                        .file_stem()
+                        .unwrap()
+                        .to_string_lossy()
+                        .into_owned();
+                    match std::fs::read_to_string(&path) {
+                        Ok(content) => templates.push((
+                            name,
+                            path.to_string_lossy().into_owned(),
+                            content,
+                        )),
+                        Err(e) => errors.push(Error::from(e)),
+                    }
+                }
+            }
+        }
+    }
+    templates
+}
+
+impl<'reg> Registry<'reg> {
+    /// Create a builder that validates the templates
+    /// when the registry is built.
+    pub fn builder() -> RegistryBuilder<'reg> {
+        RegistryBuilder::new()
+    }
+
+    /// Check that the partials and helpers referenced
+    /// by a template exist.
+    fn validate(&self, template: &Template, errors: &mut Vec<Error>) {
+        let source = template.source();
+        let name = template.file_name().unwrap_or(UNKNOWN);
+        let node = template.node();
+        for node in core::iter::once(node).chain(node.descendants()) {
+            let call = match node {
+                Node::Statement(ref call) => call,
+                Node::Block(ref block) => block.call(),
+                _ => continue,
+            };
+            self.validate_call(name, source, call, false, errors);
+        }
+    }
+
+    fn validate_call(
+        &self,
+        name: &str,
+        source: &str,
+        call: &Call<'_>,
+        sub_expr: bool,
+        errors: &mut Vec<Error>,
+    ) {
+        if let CallTarget::Path(ref path) = call.target() {
+            let target = path.as_str();
+            let pos = SourcePos(call.lines().start, path.span().start);
+            if call.is_partial() {
+                let missing = dependency::partial_name(call).is_some()
+                    && self.get(target).is_none()
+                    && self.variants(target).is_none();
+                if missing {
+                    let info = ErrorInfo::new(source, name, pos, vec![]);
+                    errors.push(Error::from(SyntaxError::MissingPartial(
+                        target.to_string(),
+                        info.into(),
+                    )));
+                } else {
+                    self.contract_violations(name, source, call, errors);
+                }
+            } else {
+                let helper = sub_expr
+                    || !call.arguments().is_empty()
+                    || !call.parameters().is_empty();
+                let missing = helper
+                    && path.is_simple()
+                    && self.helper(target).is_none()
+                    && self.handlers().helper_missing.is_none();
+                if missing {
+                    let info = ErrorInfo::new(source, name, pos, vec![]);
+                    errors.push(Error::from(SyntaxError::MissingHelper(
+                        target.to_string(),
+                        info.into(),
+                    )));
+                }
+            }
+        }
+
+        let values = call.arguments().iter().chain(call.parameters().values());
+        for value in values {
+            if let ParameterValue::SubExpr(ref call) = value {
+                self.validate_call(name, source, call, true, errors);
+            }
+        }
+    }
 }
@@ -1,9 +1,10 @@
 //! Utility functions for type assertions.
+use alloc::string::{String, ToString};
+use core::fmt;
 use serde_json::Value;
-use std::fmt;
 
 /// JSON types used for type assertions.
-#[derive(Copy, Clone, Eq, PartialEq)]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, Hash)]
 pub enum Type {
     /// The `null` JSON type.
     Null,
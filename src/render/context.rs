@@ -1,5 +1,9 @@
 //! Context information for the call to a helper.
-use std::ops::Range;
+use alloc::{
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::ops::Range;
 
 use serde_json::{Map, Value};
 
@@ -8,7 +12,10 @@ use crate::{
     helper::HelperResult,
     json,
     parser::ast::{Call, Node, Slice},
-    render::assert::{assert, Type},
+    render::{
+        assert::{assert, Type},
+        NumericKeys,
+    },
 };
 
 /// Represents a value to use when a variable lookup fails.
@@ -160,6 +167,14 @@ impl<'call> Context<'call> {
         self.call
     }
 
+    /// Get the names of the block parameters for the call.
+    ///
+    /// Block helpers assign values to these names using
+    /// [set_variable()](crate::render::Scope#method.set_variable).
+    pub fn block_params(&self) -> &[&'call str] {
+        self.call.block_params()
+    }
+
     /// Get the raw string value for an argument at an index.
     pub fn raw(&self, index: usize) -> Option<&str> {
         self.call.arguments().get(index).map(|v| v.as_str())
@@ -216,7 +231,9 @@ impl<'call> Context<'call> {
 
     /// Get the text for this context.
     ///
-    /// Only available when invoked as a raw block.
+    /// Only available when invoked as a raw block; arguments and
+    /// hash parameters from the raw block open tag are available
+    /// in the same way as for other calls.
     pub fn text(&self) -> &Option<&'call str> {
         &self.text
     }
@@ -233,7 +250,8 @@ impl<'call> Context<'call> {
     /// If the range start and end are equal than an exact number
     /// of arguments are expected and a more concise error message
     /// is used. Range ends are inclusive so 0..1 indicates zero or
-    /// one arguments are allowed.
+    /// one arguments are allowed; a range ending in `usize::MAX` has
+    /// no upper bound.
     pub fn arity(&self, range: Range<usize>) -> HelperResult<()> {
         if range.start == range.end {
             if self.arguments.len() != range.start {
@@ -242,6 +260,13 @@ impl<'call> Context<'call> {
                     range.start,
                 ));
             }
+        } else if range.end == usize::MAX {
+            if self.arguments.len() < range.start {
+                return Err(HelperError::ArityMinimum(
+                    self.name.clone(),
+                    range.start,
+                ));
+            }
         } else {
             if self.arguments.len() < range.start
                 || self.arguments.len() > range.end
@@ -305,7 +330,7 @@ impl<'call> Context<'call> {
         target: &'a Value,
         field: S,
     ) -> Option<&'a Value> {
-        json::find_field(target, field)
+        json::find_field(target, field, NumericKeys::Index)
     }
 
     /// Determine if a value is truthy.
@@ -1,33 +1,55 @@
 //! Render a template to output using the data.
-use std::cell::RefCell;
-use std::collections::HashMap;
-use std::fmt;
-use std::rc::Rc;
+use alloc::{
+    borrow::ToOwned,
+    boxed::Box,
+    format,
+    rc::Rc,
+    string::{String, ToString},
+    vec::Vec,
+};
+use core::cell::RefCell;
+use core::fmt;
+use core::ops::Range;
 
 use serde::Serialize;
 use serde_json::{Map, Value};
 
 use crate::{
-    error::{HelperError, RenderError},
+    collections::{HashMap, HashSet},
+    error::{render::Location, HelperError, RenderError},
+    escape::{self, EscapeWriteFn},
+    format::NullPolicy,
     helper::{Helper, HelperResult, LocalHelper},
     json,
-    output::{Output, StringOutput},
+    output::{BytesOutput, Output, StringOutput},
     parser::{
         ast::{
-            Block, Call, CallTarget, Lines, Link, Node, ParameterValue, Path,
-            Slice,
+            Block, Call, CallTarget, Element, Lines, Link, Node,
+            ParameterValue, Path, Slice,
         },
-        path,
+        path, ParserOptions,
     },
+    report::{RenderReport, ReportEntry, ReportKind},
     template::Template,
-    trim::{TrimHint, TrimState},
+    trim::{self, TrimHint, TrimState},
     Registry, RenderResult,
 };
 
+#[cfg(feature = "std")]
+use crate::profile::{Profiler, RenderProfile};
+#[cfg(feature = "std")]
+use std::time::Instant;
+
 const PARTIAL_BLOCK: &str = "@partial-block";
 const HELPER_MISSING: &str = "helperMissing";
 const BLOCK_HELPER_MISSING: &str = "blockHelperMissing";
 const HELPER_LINK: &str = "link";
+const PARTIAL_ISOLATED: &str = "isolated";
+const LAST_RESULT: &str = "last_result";
+
+/// Kinds of template boundaries annotated by a trace.
+const PARTIAL: &str = "partial";
+const BLOCK: &str = "block";
 
 type HelperValue = Option<Value>;
 
@@ -42,6 +64,9 @@ pub use scope::Scope;
 /// Maximum stack size for helper calls
 const STACK_MAX: usize = 32;
 
+/// Maximum nesting depth for rendering nodes
+const DEPTH_MAX: usize = 128;
+
 enum HelperTarget<'a> {
     Name(&'a str),
     Helper(&'a Box<dyn Helper + 'a>),
@@ -88,13 +113,48 @@ impl Into<String> for CallSite {
     }
 }
 
+/// Output written in place of a node that fails to render when
+/// errors are collected by
+/// [render_graceful()](crate::Registry#method.render_graceful).
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub enum ErrorFallback {
+    /// Write nothing.
+    #[default]
+    Empty,
+    /// Write an HTML comment with the error message.
+    Comment,
+    /// Write a string.
+    Text(String),
+}
+
+/// Resolution of path components that are integers such as `0` in
+/// `items.0` or `items.[0]`.
+///
+/// Components are resolved one at a time against the current value;
+/// a key of an object is always found by name so data from YAML or
+/// JavaScript with numeric string keys resolves the same way with
+/// either policy. Only the canonical decimal form is an array index
+/// so `items.01` and `items.[+1]` do not find an element.
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum NumericKeys {
+    /// Index arrays and look up the key of objects.
+    #[default]
+    Index,
+    /// Only look up the key of objects; arrays cannot be indexed
+    /// by a path and must use a helper such as `lookup`.
+    Key,
+}
+
 /// Render a template.
 pub struct Render<'render> {
     registry: &'render Registry<'render>,
+    escape: &'render EscapeWriteFn,
     local_helpers: Rc<RefCell<HashMap<String, Box<dyn LocalHelper + 'render>>>>,
     partials: HashMap<String, &'render Node<'render>>,
     name: &'render str,
     root: Value,
+    locals: Rc<Value>,
+    once: Rc<RefCell<HashSet<String>>>,
     writer: Box<&'render mut dyn Output>,
     scopes: Vec<Scope>,
     trim: TrimState,
@@ -102,6 +162,14 @@ pub struct Render<'render> {
     end_tag_hint: Option<TrimHint>,
     stack: Vec<CallSite>,
     current_partial_name: Vec<Option<&'render str>>,
+    written: usize,
+    captures: Vec<Option<String>>,
+    after_cr: bool,
+    depth: usize,
+    errors: Option<Rc<RefCell<Vec<RenderError>>>>,
+    report: Option<Rc<RefCell<RenderReport>>>,
+    #[cfg(feature = "std")]
+    profiler: Option<Profiler>,
 }
 
 impl<'render> Render<'render> {
@@ -124,10 +192,13 @@ impl<'render> Render<'render> {
 
         Ok(Self {
             registry,
+            escape: registry.escape_write(),
             local_helpers: Rc::new(RefCell::new(HashMap::new())),
             partials: HashMap::new(),
             name,
             root,
+            locals: Rc::new(Value::Object(Map::new())),
+            once: Rc::new(RefCell::new(HashSet::new())),
             writer,
             scopes,
             trim: Default::default(),
@@ -135,43 +206,56 @@ impl<'render> Render<'render> {
             end_tag_hint: None,
             stack,
             current_partial_name: Vec::new(),
+            written: 0,
+            captures: Vec::new(),
+            after_cr: false,
+            depth: 0,
+            errors: None,
+            report: None,
+            #[cfg(feature = "std")]
+            profiler: None,
         })
     }
 
     /// Get the name of the template being rendered.
     ///
-    /// This will equal the name given when the renderer is started 
-    /// and does not account for partials; to get the name of a template 
+    /// This will equal the name given when the renderer is started
+    /// and does not account for partials; to get the name of a template
     /// including the current partial use [current_name()](#method.current_name).
     pub fn template_name(&self) -> &str {
-        self.name 
+        self.name
     }
 
     /// Get the current name for the template being rendered.
     ///
-    /// When a partial is being rendered this will return the name 
-    /// for the current partial otherwise it falls back to the name 
+    /// When a partial is being rendered this will return the name
+    /// for the current partial otherwise it falls back to the name
     /// given when the renderer was started.
     ///
-    /// When partials have been loaded from the file system 
-    /// the name will be the file path and should be safe to 
+    /// When partials have been loaded from the file system
+    /// the name will be the file path and should be safe to
     /// pass to `PathBuf::from` to get a reference to the original file.
     ///
-    /// It is the caller's responsiblity to account for relative paths 
+    /// It is the caller's responsiblity to account for relative paths
     /// when converting template names to paths.
     pub fn current_name(&self) -> &str {
         if !self.current_partial_name.is_empty() {
-            self.current_partial_name.last().unwrap().unwrap_or(self.name)
-        } else { self.name }
+            self.current_partial_name
+                .last()
+                .unwrap()
+                .unwrap_or(self.name)
+        } else {
+            self.name
+        }
     }
 
     /// Render template string content and return the buffered result.
     ///
-    /// The current call site stack is cloned and used in a new render 
+    /// The current call site stack is cloned and used in a new render
     /// pass so that cyclic calls can be detected before the stack overflows.
     ///
-    /// Use this function when a helper wants to render a 
-    /// dynamic template but needs to prevent a stack overflow when a 
+    /// Use this function when a helper wants to render a
+    /// dynamic template but needs to prevent a stack overflow when a
     /// cyclic call is detected.
     pub fn once<T>(
         &self,
@@ -189,11 +273,148 @@ impl<'render> Render<'render> {
         Ok(result)
     }
 
+    /// Render a named template to the output destination using
+    /// a value as the root data.
+    ///
+    /// Unlike a partial the scopes for the current render are not
+    /// visible to the template so it can only access the given value;
+    /// the call site stack is shared so that cyclic calls are detected
+    /// and the sandbox partial depth applies.
+    pub fn include(&mut self, name: &str, data: Value) -> RenderResult<()> {
+        let template = self.get_template(name).ok_or_else(|| {
+            RenderError::PartialNotFound(name.to_string(), None)
+        })?;
+
+        self.push_partial_site(name)?;
+        self.current_partial_name.push(template.file_name());
+        let root = core::mem::replace(&mut self.root, data);
+        let scopes = core::mem::take(&mut self.scopes);
+        let result = template
+            .node()
+            .into_iter()
+            .event(self.hint)
+            .try_for_each(|event| self.render_node(event.node, event.trim));
+        self.scopes = scopes;
+        self.root = root;
+        self.current_partial_name.pop();
+        self.stack.pop();
+        result
+    }
+
+    /// Register a partial for this render.
+    ///
+    /// Partials registered with the renderer take precedence over
+    /// templates in the registry with the same name.
+    pub fn insert_partial<S>(&mut self, name: S, node: &'render Node<'render>)
+    where
+        S: AsRef<str>,
+    {
+        self.partials.insert(name.as_ref().to_string(), node);
+    }
+
     /// Get a reference to the registry.
     pub fn registry(&self) -> &Registry<'_> {
         self.registry
     }
 
+    /// Set a render local.
+    ///
+    /// Render locals are visible to every scope for the lifetime of
+    /// the render so templates can reference `@{name}` anywhere unless
+    /// a scope declares a local with the same name. The name does not
+    /// need an `@` prefix it is automatically prepended to the key.
+    pub fn set_render_local(&mut self, name: &str, value: Value) {
+        Rc::make_mut(&mut self.locals)
+            .as_object_mut()
+            .unwrap()
+            .insert(format!("@{}", name), value);
+    }
+
+    /// Get a render local.
+    ///
+    /// The name should not have the `@` prefix.
+    pub fn render_local(&self, name: &str) -> Option<&Value> {
+        self.locals.as_object().unwrap().get(&format!("@{}", name))
+    }
+
+    /// Record a key for this render.
+    ///
+    /// Returns `true` the first time a key is recorded and `false`
+    /// afterwards; the keys are shared with buffered renders so
+    /// helpers can emit content at most once per render.
+    pub fn insert_once(&self, key: &str) -> bool {
+        let mut keys = self.once.borrow_mut();
+        if keys.contains(key) {
+            false
+        } else {
+            keys.insert(key.to_string())
+        }
+    }
+
+    /// Collect render errors and write the fallback for the
+    /// registry in place of nodes that fail to render.
+    pub(crate) fn collect_errors(&mut self) {
+        self.errors = Some(Rc::new(RefCell::new(Vec::new())));
+    }
+
+    /// Take the collected render errors.
+    pub(crate) fn take_errors(&mut self) -> Vec<RenderError> {
+        self.errors
+            .take()
+            .map(|errors| errors.take())
+            .unwrap_or_default()
+    }
+
+    /// Collect a report of the problems that do not abort the render.
+    pub(crate) fn collect_report(&mut self) {
+        self.report = Some(Rc::new(RefCell::new(Default::default())));
+    }
+
+    /// Take the collected report.
+    pub(crate) fn take_report(&mut self) -> RenderReport {
+        self.report
+            .take()
+            .map(|report| report.take())
+            .unwrap_or_default()
+    }
+
+    /// Record an entry in the report when collecting a report.
+    fn record(
+        &self,
+        kind: ReportKind,
+        subject: &str,
+        lines: Option<Range<usize>>,
+    ) {
+        if let Some(report) = self.report.as_ref() {
+            report.borrow_mut().record(
+                kind,
+                ReportEntry {
+                    name: self.current_name().to_string(),
+                    subject: subject.to_string(),
+                    lines,
+                },
+            );
+        }
+    }
+
+    /// Start collecting timings for this render.
+    #[cfg(feature = "std")]
+    pub(crate) fn start_profile(&mut self) {
+        self.profiler = Some(Profiler::new());
+    }
+
+    /// Stop collecting timings and return the profile.
+    #[cfg(feature = "std")]
+    pub(crate) fn finish_profile(&mut self) -> Option<RenderProfile> {
+        self.profiler.take().map(Profiler::finish)
+    }
+
+    /// Get the current time when profiling.
+    #[cfg(feature = "std")]
+    fn profile_started(&self) -> Option<Instant> {
+        self.profiler.as_ref().map(|_| Instant::now())
+    }
+
     /// Render a node by iterating it's children.
     ///
     /// The supplied node should be a document or block node.
@@ -205,8 +426,12 @@ impl<'render> Render<'render> {
     }
 
     /// Get a named template.
+    ///
+    /// The name is resolved using the registry resolver and when no
+    /// template exists with the name a variant is selected if variants
+    /// have been registered for the name.
     pub fn get_template(&self, name: &str) -> Option<&'render Template> {
-        self.registry.get(name)
+        self.registry.resolve(name, &self.root)
     }
 
     /// Get a mutable reference to the output destination.
@@ -214,13 +439,40 @@ impl<'render> Render<'render> {
     /// You should prefer the `write()` and `write_escaped()` functions
     /// when writing strings but if you want to write bytes directly to
     /// the output destination you can use this reference.
+    ///
+    /// Writes to this reference bypass the output limit of a sandbox
+    /// so helpers that may run in a sandbox should use `write()`.
     pub fn out(&mut self) -> &mut Box<&'render mut dyn Output> {
         &mut self.writer
     }
 
+    /// Set the escape function for this render.
+    ///
+    /// By default the escape function assigned to the registry is used.
+    pub fn set_escape(&mut self, escape: &'render EscapeWriteFn) {
+        self.escape = escape;
+    }
+
     /// Escape a value using the current escape function.
+    ///
+    /// Escape functions that emit bytes which are not valid UTF-8
+    /// are converted lossily; use `escape_bytes()` to get the
+    /// exact output.
     pub fn escape(&self, val: &str) -> String {
-        (self.registry.escape())(val)
+        let bytes = self.escape_bytes(val);
+        match String::from_utf8(bytes) {
+            Ok(s) => s,
+            Err(e) => String::from_utf8_lossy(e.as_bytes()).into_owned(),
+        }
+    }
+
+    /// Escape a value using the current escape function
+    /// and return the bytes written by the escape function.
+    pub fn escape_bytes(&self, val: &str) -> Vec<u8> {
+        let mut writer = BytesOutput::with_capacity(val.len());
+        // NOTE: writing to a UTF-8 bytes buffer does not fail
+        let _ = (self.escape)(val, &mut writer);
+        writer.into()
     }
 
     /// Write a string to the output destination.
@@ -230,6 +482,30 @@ impl<'render> Render<'render> {
             .map_err(HelperError::from)
     }
 
+    /// Write bytes to the output destination.
+    ///
+    /// The bytes are passed through without escaping or whitespace
+    /// trimming; outputs that buffer into a string will reject
+    /// bytes that are not valid UTF-8.
+    pub fn write_bytes(&mut self, b: &[u8]) -> HelperResult<usize> {
+        if b.is_empty() {
+            return Ok(0);
+        }
+        self.assert_output(b.len()).map_err(Box::new)?;
+        let written = self
+            .writer
+            .write_bytes(b)
+            .map_err(RenderError::from)
+            .map_err(Box::new)?;
+        self.written += written;
+        match core::str::from_utf8(b) {
+            Ok(s) => self.capture(s),
+            // NOTE: output that is not valid UTF-8 cannot be cached
+            Err(_) => self.captures.iter_mut().for_each(|c| *c = None),
+        }
+        Ok(written)
+    }
+
     /// Write a string to the output destination and escape the content
     /// using the current escape function.
     pub fn write_escaped(&mut self, s: &str) -> HelperResult<usize> {
@@ -363,6 +639,12 @@ impl<'render> Render<'render> {
         // Inherit the stack and scope from this renderer
         rc.stack = self.stack.clone();
         rc.scopes = self.scopes.clone();
+        rc.locals = Rc::clone(&self.locals);
+        rc.once = Rc::clone(&self.once);
+        rc.escape = self.escape;
+        rc.depth = self.depth;
+        rc.errors = self.errors.clone();
+        rc.report = self.report.clone();
 
         // NOTE: call `template()` not `render()` so trim settings
         // NOTE: on the parent node are respected!
@@ -374,6 +656,53 @@ impl<'render> Render<'render> {
         Ok(writer.into())
     }
 
+    /// Compile a template fragment and write the rendered result
+    /// to the output destination.
+    ///
+    /// The call stack and scopes are inherited from this renderer.
+    ///
+    /// The `line_offset` is the line where the fragment begins in the
+    /// current template so that syntax errors report the correct line;
+    /// raw block helpers can use this to render their content:
+    ///
+    /// ```ignore
+    /// if let Some(text) = ctx.text() {
+    ///     rc.fragment(text, ctx.call().lines().start)?;
+    /// }
+    /// ```
+    pub fn fragment(
+        &mut self,
+        text: &str,
+        line_offset: usize,
+    ) -> Result<(), HelperError> {
+        let options =
+            ParserOptions::new(self.current_name().to_string(), line_offset, 0);
+        let template = Template::compile_fragment(text, options)?;
+
+        let mut writer = StringOutput::new();
+        let mut rc = Render::new(
+            self.registry,
+            self.name,
+            &self.root,
+            Box::new(&mut writer),
+            self.stack.clone(),
+        )
+        .map_err(Box::new)?;
+        rc.scopes = self.scopes.clone();
+        rc.locals = Rc::clone(&self.locals);
+        rc.once = Rc::clone(&self.once);
+        rc.escape = self.escape;
+        rc.depth = self.depth;
+        rc.errors = self.errors.clone();
+        rc.report = self.report.clone();
+        rc.render(template.node()).map_err(Box::new)?;
+        drop(rc);
+
+        let result: String = writer.into();
+        self.write(&result)?;
+        Ok(())
+    }
+
     /// Evaluate a path and return the resolved value.
     ///
     /// This allows helpers to find variables in the template data
@@ -418,36 +747,40 @@ impl<'render> Render<'render> {
         Ok(val)
     }
 
+    /// Get the current context which is the base value of the
+    /// nearest scope that has a base value or the root data.
+    fn context(&self) -> &Value {
+        self.scopes
+            .iter()
+            .rev()
+            .find_map(|scope| scope.base_value().as_ref())
+            .unwrap_or(&self.root)
+    }
+
     /// Infallible variable lookup by path.
     fn lookup<'a>(&'a self, path: &Path<'_>) -> Option<&'a Value> {
         //println!("Lookup path {:?}", path.as_str());
         //println!("Lookup path {:?}", path);
 
-
         // Absolute paths should never be resolved to variables
-        // the correct syntax is to use `@root` to resolve a 
+        // the correct syntax is to use `@root` to resolve a
         // variable from the root of the template data.
         if path.absolute() {
-            return None; 
+            return None;
         }
 
+        let keys = self.registry.numeric_keys();
+
         // Handle explicit `@root` reference
         if path.is_root() {
             json::find_parts(
                 path.components().iter().skip(1).map(|c| c.as_value()),
                 &self.root,
+                keys,
             )
         // Handle explicit this
         } else if path.is_explicit() {
-            let value = if let Some(scope) = self.scopes.last() {
-                if let Some(base) = scope.base_value() {
-                    base
-                } else {
-                    &self.root
-                }
-            } else {
-                &self.root
-            };
+            let value = self.context();
 
             // Handle explicit this only
             if path.components().len() == 1 {
@@ -457,19 +790,19 @@ impl<'render> Render<'render> {
                 json::find_parts(
                     path.components().iter().skip(1).map(|c| c.as_value()),
                     value,
+                    keys,
                 )
             }
         // Handle local @variable references which must
         // be resolved using the current scope
         } else if path.is_local() {
-            if let Some(scope) = self.scopes.last() {
-                json::find_parts(
-                    path.components().iter().map(|c| c.as_value()),
-                    scope.locals(),
-                )
-            } else {
-                None
-            }
+            let parts = || path.components().iter().map(|c| c.as_value());
+            self.scopes
+                .last()
+                .and_then(|scope| {
+                    json::find_parts(parts(), scope.locals(), keys)
+                })
+                .or_else(|| json::find_parts(parts(), &self.locals, keys))
         } else if path.parents() > 0 {
             let mut all: Vec<(&Value, Option<&Value>)> = self
                 .scopes
@@ -487,12 +820,14 @@ impl<'render> Render<'render> {
                     if let Some(res) = json::find_parts(
                         path.components().iter().map(|c| c.as_value()),
                         locals,
+                        keys,
                     ) {
                         return Some(res);
                     } else if let Some(value) = value {
                         if let Some(res) = json::find_parts(
                             path.components().iter().map(|c| c.as_value()),
                             value,
+                            keys,
                         ) {
                             return Some(res);
                         }
@@ -505,24 +840,31 @@ impl<'render> Render<'render> {
                 None
             }
         } else {
-            let mut values: Vec<(&Value, Option<&Value>)> = self
-                .scopes
-                .iter()
-                .map(|v| (v.locals(), v.base_value().as_ref()))
-                .rev()
-                .collect();
-            values.push((&self.root, None));
+            let mut values: Vec<(&Value, Option<&Value>)> = Vec::new();
+            let mut isolated = false;
+            for scope in self.scopes.iter().rev() {
+                values.push((scope.locals(), scope.base_value().as_ref()));
+                if scope.is_isolated() {
+                    isolated = true;
+                    break;
+                }
+            }
+            if !isolated {
+                values.push((&self.root, None));
+            }
 
             for (locals, value) in values {
                 if let Some(res) = json::find_parts(
                     path.components().iter().map(|c| c.as_value()),
                     locals,
+                    keys,
                 ) {
                     return Some(res);
                 } else if let Some(value) = value {
                     if let Some(res) = json::find_parts(
                         path.components().iter().map(|c| c.as_value()),
                         value,
+                        keys,
                     ) {
                         return Some(res);
                     }
@@ -626,6 +968,60 @@ impl<'render> Render<'render> {
         registry.borrow_mut().remove(name);
     }
 
+    /// Call a registered helper by name.
+    ///
+    /// The arguments and hash parameters replace those of the current
+    /// call so helpers can dispatch to another helper; the sandbox
+    /// restrictions of the registry apply to the called helper.
+    pub fn call_helper(
+        &mut self,
+        name: &str,
+        ctx: &Context<'_>,
+        arguments: Vec<Value>,
+        parameters: Map<String, Value>,
+        template: Option<&'render Node<'render>>,
+    ) -> Result<HelperValue, HelperError> {
+        let helper = self.registry.helper(name).ok_or_else(|| {
+            HelperError::new(format!("Helper '{}' does not exist", name))
+        })?;
+
+        let sandboxed = self.registry.sandboxed() && helper.ambient();
+        let denied = self
+            .registry
+            .sandbox()
+            .map(|profile| !profile.is_helper_allowed(name))
+            .unwrap_or(false);
+        if sandboxed || denied {
+            return Err(Box::new(RenderError::HelperSandboxed(
+                name.to_string(),
+                None,
+            ))
+            .into());
+        }
+
+        let site = CallSite::Helper(name.to_string());
+        let amount = self.stack.iter().filter(|&n| *n == site).count();
+        if amount >= STACK_MAX {
+            return Err(
+                Box::new(RenderError::HelperCycle(site.into(), None)).into()
+            );
+        }
+
+        let context = Context::new(
+            ctx.call(),
+            name.to_string(),
+            arguments,
+            parameters,
+            None,
+            None,
+            Vec::new(),
+        );
+        self.stack.push(site);
+        let result = helper.call(self, &context, template);
+        self.stack.pop();
+        result
+    }
+
     fn invoke<'a>(
         &mut self,
         name: &str,
@@ -643,7 +1039,7 @@ impl<'render> Render<'render> {
 
         let amount = self.stack.iter().filter(|&n| *n == site).count();
         if amount >= STACK_MAX {
-            return Err(RenderError::HelperCycle(site.into()));
+            return Err(RenderError::HelperCycle(site.into(), None));
         }
         self.stack.push(site);
 
@@ -662,36 +1058,162 @@ impl<'render> Render<'render> {
 
         let local_helpers = Rc::clone(&self.local_helpers);
 
+        #[cfg(feature = "std")]
+        let started = self.profile_started();
+
+        let sandboxed = self.registry.sandboxed();
+        let denied = self
+            .registry
+            .sandbox()
+            .map(|profile| !profile.is_helper_allowed(name))
+            .unwrap_or(false);
+        let allowed = |helper: &dyn Helper| -> RenderResult<()> {
+            if denied || sandboxed && helper.ambient() {
+                Err(RenderError::HelperSandboxed(name.to_string(), None))
+            } else {
+                Ok(())
+            }
+        };
+
         let value: Option<Value> = match target {
             HelperTarget::Name(name) => {
                 if let Some(helper) = local_helpers.borrow().get(name) {
-                    helper.call(self, &mut context, content)?
-                } else if let Some(helper) = self.registry.helpers().get(name) {
-                    helper.call(self, &mut context, content)?
+                    allowed(helper.as_ref())?;
+                    helper
+                        .call(self, &mut context, content)
+                        .map_err(|e| self.helper_error(e, call))?
+                } else if let Some(helper) = self.registry.helper(name) {
+                    allowed(helper)?;
+                    if let Some(message) =
+                        self.registry.helper_deprecation(name)
+                    {
+                        self.record(
+                            ReportKind::HelperWarning,
+                            &format!(
+                                "Helper '{}' is deprecated, {}",
+                                name, message
+                            ),
+                            Some(call.lines().clone()),
+                        );
+                    }
+                    #[cfg(feature = "log")]
+                    if let Some(message) =
+                        self.registry.helper_deprecation(name)
+                    {
+                        log::warn!(
+                            "Helper '{}' is deprecated, {} ({})",
+                            name,
+                            message,
+                            self.current_name()
+                        );
+                    }
+                    helper
+                        .call(self, &mut context, content)
+                        .map_err(|e| self.helper_error(e, call))?
                 } else {
                     None
                 }
             }
             // NOTE: evnet handlers will pass a reference to the helper.
             HelperTarget::Helper(helper) => {
-                helper.call(self, &mut context, content)?
+                allowed(helper.as_ref())?;
+                helper
+                    .call(self, &mut context, content)
+                    .map_err(|e| self.helper_error(e, call))?
             }
         };
 
         drop(local_helpers);
 
+        #[cfg(feature = "std")]
+        if let (Some(profiler), Some(started)) = (&mut self.profiler, started) {
+            profiler.helper(name, started);
+        }
+
         self.stack.pop();
 
         Ok(value)
     }
 
+    /// Assign the location of a helper call to an error returned
+    /// by the helper.
+    ///
+    /// Errors returned by nested helper calls already have a location
+    /// so they are returned as is to report the call that failed.
+    fn helper_error(&self, err: HelperError, call: &Call<'_>) -> RenderError {
+        match err {
+            HelperError::Render(e) if e.location().is_some() => *e,
+            _ => {
+                #[cfg(feature = "metrics")]
+                crate::metrics::helper_error(call.target().as_str());
+                let location = Location::new(
+                    self.current_name().to_string(),
+                    call.span(),
+                    call.lines().clone(),
+                    call.as_str().to_string(),
+                );
+                RenderError::Helper(err, Some(Box::new(location)))
+            }
+        }
+    }
+
+    /// Determine if a statement resolves a variable rather
+    /// than invoking a helper or rendering a partial.
+    fn is_variable(&mut self, call: &Call<'_>) -> bool {
+        if call.is_partial() {
+            return false;
+        }
+        match call.target() {
+            CallTarget::Path(ref path) => {
+                path.is_explicit()
+                    || !path.is_simple()
+                    || !(self.has_helper(path.as_str())
+                        || self.registry.handlers().helper_missing.is_some())
+            }
+            CallTarget::SubExpr(_) => false,
+        }
+    }
+
+    /// Convert the result of a statement to the output string.
+    fn display(
+        &mut self,
+        call: &Call<'_>,
+        value: HelperValue,
+    ) -> RenderResult<Option<String>> {
+        let formatted = self.registry.type_formatter(Type::Null).is_some();
+        match value {
+            Some(Value::Null) if !formatted => self.null_value(call),
+            Some(ref value) => Ok(Some(self.registry.format(value))),
+            None if self.is_variable(call) => self.null_value(call),
+            None => Ok(None),
+        }
+    }
+
+    /// Get the output for a statement that is null or
+    /// missing using the null policy for the registry.
+    fn null_value(&self, call: &Call<'_>) -> RenderResult<Option<String>> {
+        match self.registry.null_policy() {
+            NullPolicy::Empty => Ok(None),
+            NullPolicy::Null => Ok(Some(String::from("null"))),
+            NullPolicy::Placeholder(ref value) => Ok(Some(value.clone())),
+            NullPolicy::Error => Err(RenderError::NullValue(
+                call.target().as_str().to_string(),
+                self.name.to_string(),
+                None,
+            )),
+        }
+    }
+
     fn has_helper(&mut self, name: &str) -> bool {
         self.local_helpers.borrow().get(name).is_some()
-            || self.registry.helpers().get(name).is_some()
+            || self.registry.helper(name).is_some()
     }
 
     // Fallible version of path lookup.
-    fn resolve(&mut self, path: &Path<'_>) -> RenderResult<HelperValue> {
+    pub(crate) fn resolve(
+        &mut self,
+        path: &Path<'_>,
+    ) -> RenderResult<HelperValue> {
         if let Some(value) = self.lookup(path).cloned().take() {
             Ok(Some(value))
         } else {
@@ -699,9 +1221,15 @@ impl<'render> Render<'render> {
                 Err(RenderError::VariableNotFound(
                     path.as_str().to_string(),
                     self.name.to_string(),
+                    None,
                 ))
             } else {
                 // TODO: call a missing_variable handler?
+                self.record(
+                    ReportKind::MissingVariable,
+                    path.as_str(),
+                    Some(path.lines().clone()),
+                );
                 Ok(None)
             }
         }
@@ -716,7 +1244,15 @@ impl<'render> Render<'render> {
             CallTarget::Path(ref path) => {
                 // Explicit paths should resolve to a lookup
                 if path.is_explicit() {
-                    Ok(self.lookup(path).cloned())
+                    let value = self.lookup(path).cloned();
+                    if value.is_none() {
+                        self.record(
+                            ReportKind::MissingVariable,
+                            path.as_str(),
+                            Some(call.lines().clone()),
+                        );
+                    }
+                    Ok(value)
                 // Simple paths may be helpers
                 } else if path.is_simple() {
                     if self.has_helper(path.as_str()) {
@@ -748,8 +1284,14 @@ impl<'render> Render<'render> {
                                     return Err(RenderError::VariableNotFound(
                                         path.as_str().to_string(),
                                         self.name.to_string(),
+                                        None,
                                     ));
                                 }
+                                self.record(
+                                    ReportKind::MissingVariable,
+                                    path.as_str(),
+                                    Some(call.lines().clone()),
+                                );
                             }
                         }
                         Ok(value)
@@ -762,6 +1304,28 @@ impl<'render> Render<'render> {
         }
     }
 
+    /// Determine if a statement calls a helper.
+    fn calls_helper(&mut self, call: &Call<'_>) -> bool {
+        if call.is_partial() {
+            return false;
+        }
+        match call.target() {
+            CallTarget::Path(ref path) => {
+                path.is_simple() && self.has_helper(path.as_str())
+            }
+            CallTarget::SubExpr(_) => true,
+        }
+    }
+
+    /// Assign the value of a helper statement to `@last_result`.
+    fn set_last_result(&mut self, value: Value) {
+        if let Some(scope) = self.scopes.last_mut() {
+            scope.set_local(LAST_RESULT, value);
+        } else {
+            self.set_render_local(LAST_RESULT, value);
+        }
+    }
+
     fn statement(&mut self, call: &Call<'_>) -> RenderResult<HelperValue> {
         if call.is_partial() {
             self.render_partial(call, None)?;
@@ -784,12 +1348,13 @@ impl<'render> Render<'render> {
                 } else {
                     return Err(RenderError::PartialIdentifier(
                         path.as_str().to_string(),
+                        None,
                     ));
                 }
             }
             CallTarget::SubExpr(ref call) => {
                 let result = self.statement(call)?.unwrap_or(Value::Null);
-                return Ok(json::stringify(&result));
+                return Ok(self.registry.format(&result));
             }
         }
     }
@@ -799,24 +1364,37 @@ impl<'render> Render<'render> {
         call: &Call<'_>,
         partial_block: Option<&'render Node<'render>>,
     ) -> RenderResult<()> {
-        let name = self.get_partial_name(call)?;
+        #[cfg(feature = "std")]
+        let started = self.profile_started();
 
-        let site = CallSite::Partial(name.to_string());
-        if self.stack.contains(&site) {
-            return Err(RenderError::PartialCycle(site.into()));
-        }
-        self.stack.push(site);
+        let name = self.get_partial_name(call)?;
+        self.push_partial_site(&name)?;
 
         if let Some(node) = partial_block {
             self.partials.insert(PARTIAL_BLOCK.to_string(), node);
         }
 
         let node = if let Some(local_partial) = self.partials.get(&name) {
+            // NOTE: local partials inherit the current partial name
+            let current = self.current_partial_name.last().cloned().flatten();
+            self.current_partial_name.push(current);
             local_partial
         } else {
-            let template = self
-                .get_template(&name)
-                .ok_or_else(|| RenderError::PartialNotFound(name))?;
+            let template = match self.get_template(&name) {
+                Some(template) => template,
+                None if self.report.is_some() => {
+                    self.record(
+                        ReportKind::MissingPartial,
+                        &name,
+                        Some(call.lines().clone()),
+                    );
+                    self.stack.pop();
+                    return Ok(());
+                }
+                None => {
+                    return Err(RenderError::PartialNotFound(name, None));
+                }
+            };
 
             self.current_partial_name.push(template.file_name());
 
@@ -824,33 +1402,193 @@ impl<'render> Render<'render> {
         };
 
         let mut missing: Vec<MissingValue> = Vec::new();
-        let hash = self.hash(call, &mut missing)?;
-        let scope = if !call.arguments().is_empty() {
-            let arguments = self.arguments(call, &mut missing)?;
-            if let Some(context) = arguments.get(0) {
-                Scope::from((context.clone(), hash))
-            } else {
-                Scope::from(hash)
+        let mut hash = self.hash(call, &mut missing)?;
+        if self.registry.strict() {
+            if let Some(contract) = self.registry.contract(&name) {
+                let params = hash.iter().map(|(k, v)| (k.as_str(), Some(v)));
+                if let Some(violation) = contract.check(params).first() {
+                    return Err(RenderError::PartialContract(
+                        name,
+                        violation.to_string(),
+                        None,
+                    ));
+                }
             }
+        }
+        let isolated = match hash.remove(PARTIAL_ISOLATED) {
+            Some(Value::Bool(isolated)) => isolated,
+            Some(value) => {
+                return Err(RenderError::PartialIsolated(
+                    name,
+                    Type::from(&value).to_string(),
+                    None,
+                ))
+            }
+            None => false,
+        };
+        let context = if !call.arguments().is_empty() {
+            self.arguments(call, &mut missing)?.into_iter().next()
         } else {
-            Scope::from(hash)
+            None
+        };
+        let mut scope = match context {
+            Some(context) => Scope::from((context, hash)),
+            None if isolated => Scope::from(hash),
+            // NOTE: without hash parameters the scope has no base value
+            // NOTE: so the current context is used without a copy
+            None if hash.is_empty() => Scope::new(),
+            None => {
+                // NOTE: hash parameters are merged over the current context
+                let mut context =
+                    self.context().as_object().cloned().unwrap_or_default();
+                context.extend(hash);
+                Scope::from(context)
+            }
         };
+        scope.set_isolated(isolated);
+
+        let trace = match self.registry.trace() {
+            Some(trace) if trace.partials => {
+                Some((trace.begin(PARTIAL, &name), trace.end(PARTIAL, &name)))
+            }
+            _ => None,
+        };
+        if let Some((ref begin, _)) = trace {
+            self.write_marker(begin)?;
+        }
+
+        #[cfg(feature = "std")]
+        let cache_key =
+            if partial_block.is_none() && !self.partials.contains_key(&name) {
+                self.partial_cache_key(&name, &scope)
+            } else {
+                None
+            };
+
+        #[cfg(feature = "std")]
+        if let Some(ref key) = cache_key {
+            let cache = self.registry.partial_cache().unwrap();
+            if let Some(output) = cache.get(key) {
+                self.assert_output(output.len())?;
+                self.written += self
+                    .writer
+                    .write_str(&output)
+                    .map_err(RenderError::from)?;
+                self.capture(&output);
+                if let Some((_, ref end)) = trace {
+                    self.write_marker(end)?;
+                }
+                self.current_partial_name.pop();
+                self.stack.pop();
+                if let (Some(profiler), Some(started)) =
+                    (&mut self.profiler, started)
+                {
+                    profiler.partial(&name, started);
+                }
+                return Ok(());
+            }
+            self.captures.push(Some(String::new()));
+        }
 
         self.scopes.push(scope);
         // WARN: We must iterate the document child nodes
         // WARN: when rendering partials otherwise the
         // WARN: rendering process will halt after the first partial!
-        for event in node.into_iter().event(self.hint) {
-            self.render_node(event.node, event.trim)?;
-        }
+        let result = node
+            .into_iter()
+            .event(self.hint)
+            .try_for_each(|event| self.render_node(event.node, event.trim));
         self.scopes.pop();
 
+        #[cfg(feature = "std")]
+        if let Some(key) = cache_key {
+            if let (Some(Some(output)), Ok(_)) = (self.captures.pop(), &result)
+            {
+                let cache = self.registry.partial_cache().unwrap();
+                cache.insert(key, output);
+            }
+        }
+        result?;
+
+        if let Some((_, ref end)) = trace {
+            self.write_marker(end)?;
+        }
+
         self.current_partial_name.pop();
         self.stack.pop();
 
+        #[cfg(feature = "std")]
+        if let (Some(profiler), Some(started)) = (&mut self.profiler, started) {
+            profiler.partial(&name, started);
+        }
+
         Ok(())
     }
 
+    /// Push a partial call site onto the stack after checking
+    /// for cycles and the sandbox partial depth.
+    fn push_partial_site(&mut self, name: &str) -> RenderResult<()> {
+        let site = CallSite::Partial(name.to_string());
+        if self.stack.contains(&site) {
+            return Err(RenderError::PartialCycle(site.into(), None));
+        }
+
+        if let Some(max) =
+            self.registry.sandbox().and_then(|p| p.max_partial_depth)
+        {
+            let depth = self
+                .stack
+                .iter()
+                .filter(|s| matches!(s, CallSite::Partial(_)))
+                .count();
+            if depth >= max {
+                return Err(RenderError::PartialDepth(
+                    name.to_string(),
+                    max,
+                    None,
+                ));
+            }
+        }
+        self.stack.push(site);
+        Ok(())
+    }
+
+    /// Compute the cache key for a partial render when the
+    /// registry has a cache that allows the partial.
+    #[cfg(feature = "std")]
+    fn partial_cache_key(&self, name: &str, scope: &Scope) -> Option<String> {
+        let cache = self.registry.partial_cache()?;
+        // NOTE: variants and resolved names are selected for
+        // NOTE: each render so their output is never cached and
+        // NOTE: the key does not include the escape function so
+        // NOTE: renders with a different escape function are skipped;
+        // NOTE: the key only covers the context for the partial so
+        // NOTE: partials that read other values are also skipped
+        if !cache.is_cached(name)
+            || self.registry.get(name).is_none()
+            || self.registry.has_resolver()
+            || !core::ptr::eq(self.escape, self.registry.escape_write())
+            || !cache.is_cacheable(name, scope.is_isolated(), || {
+                !partial_reads_outer(
+                    self.registry,
+                    name,
+                    scope.is_isolated(),
+                    &mut Vec::new(),
+                )
+            })
+        {
+            return None;
+        }
+        let context = scope.base_value().as_ref().unwrap_or(self.context());
+        let context = serde_json::to_string(context).ok()?;
+        let locals = serde_json::to_string(scope.locals()).ok()?;
+        let hint = self.hint.unwrap_or_default();
+        Some(format!(
+            "{}\u{0}{}\u{0}{}\u{0}{}{}",
+            name, context, locals, hint.before as u8, hint.after as u8
+        ))
+    }
+
     fn block_helper_missing(
         &mut self,
         node: &'render Node<'render>,
@@ -903,6 +1641,7 @@ impl<'render> Render<'render> {
                         if self.registry.strict() {
                             return Err(RenderError::HelperNotFound(
                                 path.as_str().to_string(),
+                                None,
                             ));
                         }
                     }
@@ -932,11 +1671,11 @@ impl<'render> Render<'render> {
 
                         if raw {
                             // Raw block nodes should have a single Text child node
-                            text = if !block.nodes().is_empty() {
-                                Some(block.nodes().get(0).unwrap().as_str())
-                            // Empty raw block should be treated as the empty string
-                            } else {
-                                Some("")
+                            text = match block.nodes().get(0) {
+                                Some(Node::Text(ref n)) => Some(n.value()),
+                                Some(n) => Some(n.as_str()),
+                                // Empty raw block should be treated as the empty string
+                                None => Some(""),
                             };
 
                             // Store the hint so we can remove leading whitespace
@@ -948,14 +1687,16 @@ impl<'render> Render<'render> {
                                     // Trim leading inside a raw block
                                     if node.trim().after {
                                         if let Some(ref content) = text {
-                                            text = Some(content.trim_start());
+                                            text =
+                                                Some(trim::trim_start(content));
                                         }
                                     }
 
                                     // Trim trailing inside a raw block
                                     if hint.before {
                                         if let Some(ref content) = text {
-                                            text = Some(content.trim_end());
+                                            text =
+                                                Some(trim::trim_end(content));
                                         }
                                     }
 
@@ -983,11 +1724,12 @@ impl<'render> Render<'render> {
                     } else {
                         return Err(RenderError::BlockIdentifier(
                             path.as_str().to_string(),
+                            None,
                         ));
                     }
                 }
                 CallTarget::SubExpr(ref _call) => {
-                    return Err(RenderError::BlockTargetSubExpr)
+                    return Err(RenderError::BlockTargetSubExpr(None))
                 }
             }
         }
@@ -1043,6 +1785,155 @@ impl<'render> Render<'render> {
         &mut self,
         node: &'render Node<'render>,
         trim: TrimState,
+    ) -> RenderResult<()> {
+        // NOTE: partials and helpers that render templates recurse
+        // NOTE: so limit the depth rather than overflow the stack
+        if self.depth >= DEPTH_MAX {
+            return Err(RenderError::DepthLimit(DEPTH_MAX, None)
+                .locate(|| self.location(node)));
+        }
+        self.depth += 1;
+        let result = if self.errors.is_some() {
+            self.recover_node(node, trim)
+        } else {
+            self.locate_node(node, trim)
+        };
+        self.depth -= 1;
+        result
+    }
+
+    /// Write a node and assign the location of the node to errors.
+    fn locate_node(
+        &mut self,
+        node: &'render Node<'render>,
+        trim: TrimState,
+    ) -> RenderResult<()> {
+        #[cfg(feature = "std")]
+        if self.profiler.is_some() {
+            return self.profile_node(node, trim);
+        }
+        self.write_node(node, trim)
+            .map_err(|e| e.locate(|| self.location(node)))
+    }
+
+    /// Write a node collecting an error and writing the fallback
+    /// output when the node fails to render.
+    ///
+    /// Output and IO errors cannot be recovered so they are returned.
+    fn recover_node(
+        &mut self,
+        node: &'render Node<'render>,
+        trim: TrimState,
+    ) -> RenderResult<()> {
+        let stack = self.stack.len();
+        let scopes = self.scopes.len();
+        let partials = self.current_partial_name.len();
+        let err = match self.locate_node(node, trim) {
+            Ok(_) => return Ok(()),
+            Err(e @ RenderError::OutputLimit(..))
+            | Err(e @ RenderError::Io(..)) => return Err(e),
+            Err(e) => e,
+        };
+
+        // NOTE: restore the state that was not unwound
+        // NOTE: and never cache output that contains a fallback
+        self.stack.truncate(stack);
+        self.scopes.truncate(scopes);
+        self.current_partial_name.truncate(partials);
+        self.captures.iter_mut().for_each(|c| *c = None);
+
+        let fallback = match self.registry.error_fallback() {
+            ErrorFallback::Empty => None,
+            // Messages may contain template data so they are escaped
+            // and hyphens are written as entities so that the message
+            // can never close the comment
+            ErrorFallback::Comment => Some(format!(
+                "<!-- error: {} -->",
+                escape::html(&err.to_string()).replace('-', "&#45;")
+            )),
+            ErrorFallback::Text(ref text) => Some(text.clone()),
+        };
+        if let Some(fallback) = fallback {
+            self.write_str(&fallback, false)?;
+        }
+        if let Some(errors) = self.errors.as_ref() {
+            errors.borrow_mut().push(err);
+        }
+        Ok(())
+    }
+
+    /// Render a node inside a profiler frame.
+    #[cfg(feature = "std")]
+    fn profile_node(
+        &mut self,
+        node: &'render Node<'render>,
+        trim: TrimState,
+    ) -> RenderResult<()> {
+        let kind = match node {
+            Node::Document(_) => "document",
+            Node::Text(_) => "text",
+            Node::Statement(_) => "statement",
+            Node::Block(_) => "block",
+            Node::RawStatement(_) => "raw_statement",
+            Node::RawComment(_) | Node::Comment(_) => "comment",
+            Node::Link(_) => "link",
+        };
+        let location = self.location(node);
+        let label = match node {
+            Node::Statement(_) | Node::Block(_) | Node::Link(_) => {
+                location.source().to_string()
+            }
+            _ => kind.to_string(),
+        };
+        if let Some(profiler) = self.profiler.as_mut() {
+            profiler.enter(
+                kind,
+                label,
+                location.name().to_string(),
+                location.span().clone(),
+                location.lines().clone(),
+            );
+        }
+        let result = self
+            .write_node(node, trim)
+            .map_err(|e| e.locate(|| location));
+        if let Some(profiler) = self.profiler.as_mut() {
+            profiler.exit();
+        }
+        result
+    }
+
+    /// Location of a node used to assign a position to render errors.
+    ///
+    /// For blocks the location is the open tag.
+    fn location(&self, node: &Node<'_>) -> Location {
+        let (span, lines, source) = match node {
+            Node::Document(ref n) => (0..n.as_str().len(), 0..0, n.as_str()),
+            Node::Text(ref n) => {
+                (n.span().clone(), n.lines().clone(), n.as_str())
+            }
+            Node::RawStatement(ref n)
+            | Node::RawComment(ref n)
+            | Node::Comment(ref n) => (n.span(), n.lines().clone(), n.as_str()),
+            Node::Statement(ref n) => (n.span(), n.lines().clone(), n.as_str()),
+            Node::Block(ref n) => {
+                let call = n.call();
+                (call.span(), call.lines().clone(), call.as_str())
+            }
+            Node::Link(ref n) => (n.span(), n.lines().clone(), n.as_str()),
+        };
+        Location::new(
+            self.current_name().to_string(),
+            span,
+            lines,
+            source.to_string(),
+        )
+    }
+
+    fn write_node(
+        &mut self,
+        node: &'render Node<'render>,
+        trim: TrimState,
     ) -> RenderResult<()> {
         self.trim = trim;
         self.hint = Some(node.trim());
@@ -1055,9 +1946,10 @@ impl<'render> Render<'render> {
 
         match node {
             Node::Text(ref n) => {
-                self.write_str(n.as_str(), false)?;
+                self.write_str(n.value(), false)?;
             }
             Node::RawStatement(ref n) => {
+                self.assert_raw(n.as_str())?;
                 let raw = &n.as_str()[1..];
                 self.write_str(raw, false)?;
             }
@@ -1080,31 +1972,243 @@ impl<'render> Render<'render> {
             Node::Comment(_) => {}
             Node::Document(_) => {}
             Node::Statement(ref call) => {
-                if let Some(ref value) = self.statement(call)? {
-                    let val = json::stringify(value);
-                    self.write_str(&val, call.is_escaped())?;
+                if !call.is_escaped() && !call.is_partial() {
+                    self.assert_raw(call.as_str())?;
+                }
+                let value = self.statement(call)?;
+                if self.registry.last_result() && self.calls_helper(call) {
+                    self.set_last_result(value.clone().unwrap_or(Value::Null));
+                }
+                if let Some(ref val) = self.display(call, value)? {
+                    self.write_str(val, call.is_escaped())?;
                 }
             }
             Node::Block(ref block) => {
+                let call = block.call();
+                let trace = self
+                    .registry
+                    .trace()
+                    .filter(|trace| trace.blocks && !call.is_partial())
+                    .map(|trace| {
+                        let name = call
+                            .as_str()
+                            .trim_start_matches(['{', '~'])
+                            .trim_end_matches(['}', '~'])
+                            .trim();
+                        (trace.begin(BLOCK, name), trace.end(BLOCK, name))
+                    });
+                if let Some((ref begin, _)) = trace {
+                    self.write_marker(begin)?;
+                }
                 self.block(node, block)?;
+                if let Some((_, ref end)) = trace {
+                    self.write_marker(end)?;
+                }
             }
         }
 
         Ok(())
     }
 
+    /// Write a trace marker without escaping or whitespace trimming.
+    fn write_marker(&mut self, marker: &str) -> RenderResult<()> {
+        self.assert_output(marker.len())?;
+        self.written +=
+            self.writer.write_str(marker).map_err(RenderError::from)?;
+        self.capture(marker);
+        Ok(())
+    }
+
     fn write_str(&mut self, s: &str, escape: bool) -> RenderResult<usize> {
-        let val = if self.trim.start { s.trim_start() } else { s };
-        let val = if self.trim.end { val.trim_end() } else { val };
+        let start = if self.trim.start {
+            trim::trim_start(s)
+        } else {
+            s
+        };
+        let val = if self.trim.end {
+            trim::trim_end(start)
+        } else {
+            start
+        };
+        if self.report.is_some() && val.len() < s.len() {
+            let leading = &s[..s.len() - start.len()];
+            let trailing = &start[val.len()..];
+            for removed in [leading, trailing] {
+                if !removed.is_empty() {
+                    self.record(ReportKind::Trimmed, removed, None);
+                }
+            }
+        }
+        if val.is_empty() {
+            return Ok(0);
+        }
+        let val = self.registry.newline().normalize(val, &mut self.after_cr);
+        let val = val.as_ref();
         if val.is_empty() {
             return Ok(0);
         }
 
-        if escape {
-            let escaped = (self.registry.escape())(val);
-            Ok(self.writer.write_str(&escaped).map_err(RenderError::from)?)
+        // NOTE: escaped output is buffered when capturing or when the
+        // NOTE: output is limited so the limit is checked before writing
+        let limited =
+            self.registry.sandbox().and_then(|p| p.max_output).is_some();
+        let written = if escape && (limited || !self.captures.is_empty()) {
+            let mut buffer = BytesOutput::with_capacity(val.len());
+            (self.escape)(val, &mut buffer).map_err(RenderError::from)?;
+            let escaped: Vec<u8> = buffer.into();
+            self.assert_output(escaped.len())?;
+            match core::str::from_utf8(&escaped) {
+                Ok(escaped) => {
+                    let written = self
+                        .writer
+                        .write_str(escaped)
+                        .map_err(RenderError::from)?;
+                    self.capture(escaped);
+                    written
+                }
+                Err(_) => {
+                    // NOTE: output that is not valid UTF-8 cannot be cached
+                    self.captures.iter_mut().for_each(|c| *c = None);
+                    self.writer
+                        .write_bytes(&escaped)
+                        .map_err(RenderError::from)?
+                }
+            }
+        } else if escape {
+            let escape = self.escape;
+            escape(val, &mut **self.writer).map_err(RenderError::from)?
         } else {
-            Ok(self.writer.write_str(val).map_err(RenderError::from)?)
+            self.assert_output(val.len())?;
+            let written =
+                self.writer.write_str(val).map_err(RenderError::from)?;
+            self.capture(val);
+            written
+        };
+        self.written += written;
+        self.assert_output(0)?;
+        Ok(written)
+    }
+
+    /// Append written output to the active capture buffers.
+    fn capture(&mut self, s: &str) {
+        for buffer in self.captures.iter_mut().flatten() {
+            buffer.push_str(s);
         }
     }
+
+    /// Verify the sandbox permits unescaped output.
+    fn assert_raw(&self, source: &str) -> RenderResult<()> {
+        if let Some(profile) = self.registry.sandbox() {
+            if !profile.raw {
+                return Err(RenderError::RawNotAllowed(
+                    source.to_string(),
+                    None,
+                ));
+            }
+        }
+        Ok(())
+    }
+
+    /// Verify the sandbox output limit permits writing
+    /// an additional amount of bytes.
+    fn assert_output(&self, amount: usize) -> RenderResult<()> {
+        if let Some(max) = self.registry.sandbox().and_then(|p| p.max_output) {
+            if self.written.saturating_add(amount) > max {
+                return Err(RenderError::OutputLimit(max, None));
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Determine if a partial reads values other than the context passed
+/// to the partial: the root data, parent scopes, local variables or
+/// helpers that are not pure, including the partials it calls.
+///
+/// Unless the partial is isolated a variable that is not found in
+/// the context is resolved using the outer scopes and the root data
+/// so any variable other than `this` reads outer values.
+#[cfg(feature = "std")]
+fn partial_reads_outer(
+    registry: &Registry<'_>,
+    name: &str,
+    isolated: bool,
+    visited: &mut Vec<String>,
+) -> bool {
+    if visited.iter().any(|n| n == name) {
+        return false;
+    }
+    visited.push(name.to_string());
+    let template = match registry.get(name) {
+        Some(template) => template,
+        None => return true,
+    };
+    let node = template.node();
+    core::iter::once(node)
+        .chain(node.descendants())
+        .any(|node| match node {
+            Node::Statement(ref call) => {
+                call_reads_outer(registry, call, isolated, visited)
+            }
+            Node::Block(ref block) => {
+                call_reads_outer(registry, block.call(), isolated, visited)
+            }
+            _ => false,
+        })
+}
+
+/// Determine if a call reads values other than the current context.
+#[cfg(feature = "std")]
+fn call_reads_outer(
+    registry: &Registry<'_>,
+    call: &Call<'_>,
+    isolated: bool,
+    visited: &mut Vec<String>,
+) -> bool {
+    let outer = |path: &Path<'_>| {
+        path.is_root()
+            || path.is_local()
+            || path.parents() > 0
+            || !(isolated || path.is_explicit())
+    };
+    let target = if call.is_partial() {
+        match crate::dependency::partial_name(call) {
+            Some(name) => {
+                partial_reads_outer(registry, name, isolated, visited)
+            }
+            None => true,
+        }
+    } else {
+        match call.target() {
+            // NOTE: helpers that are not pure may read the clock,
+            // NOTE: a nonce for the render or other state and
+            // NOTE: arguments for an unknown name are passed
+            // NOTE: to a local or missing helper
+            CallTarget::Path(ref path) => {
+                match registry.helper(path.as_str()) {
+                    Some(helper) if path.is_simple() => !helper.pure(),
+                    _ => {
+                        outer(path)
+                            || !call.arguments().is_empty()
+                            || !call.parameters().is_empty()
+                    }
+                }
+            }
+            CallTarget::SubExpr(ref call) => {
+                call_reads_outer(registry, call, isolated, visited)
+            }
+        }
+    };
+    target
+        || call
+            .arguments()
+            .iter()
+            .chain(call.parameters().values())
+            .any(|value| match value {
+                ParameterValue::Path(ref path) => outer(path),
+                ParameterValue::SubExpr(ref call) => {
+                    call_reads_outer(registry, call, isolated, visited)
+                }
+                ParameterValue::Json { .. } => false,
+            })
 }
@@ -1,4 +1,8 @@
 //! Scopes define the evaluation context for variable paths.
+use alloc::{
+    format,
+    string::{String, ToString},
+};
 use serde_json::{Map, Value};
 
 /// A scope encapsulates a base value (lookup object) used when
@@ -11,6 +15,7 @@ use serde_json::{Map, Value};
 pub struct Scope {
     value: Option<Value>,
     locals: Value,
+    isolated: bool,
 }
 
 impl Scope {
@@ -19,6 +24,7 @@ impl Scope {
         Self {
             locals: Value::Object(Map::new()),
             value: None,
+            isolated: false,
         }
     }
 
@@ -38,6 +44,26 @@ impl Scope {
             .insert(format!("@{}", name), value);
     }
 
+    /// Set a named variable.
+    ///
+    /// Variables are stored with the locals but without an `@` prefix
+    /// so they are resolved like properties of the base value; they
+    /// take precedence over the base value and are visible to nested
+    /// scopes.
+    ///
+    /// Returns the previous value of the variable.
+    pub fn set_variable(&mut self, name: &str, value: Value) -> Option<Value> {
+        self.locals
+            .as_object_mut()
+            .unwrap()
+            .insert(name.to_string(), value)
+    }
+
+    /// Remove a named variable.
+    pub fn remove_variable(&mut self, name: &str) -> Option<Value> {
+        self.locals.as_object_mut().unwrap().remove(name)
+    }
+
     /// Get a named local.
     ///
     /// Locals should have the `@` prefix.
@@ -58,6 +84,19 @@ impl Scope {
     pub fn base_value(&self) -> &Option<Value> {
         &self.value
     }
+
+    /// Set the isolated flag.
+    ///
+    /// Variable paths that cannot be resolved in an isolated scope
+    /// are not resolved using outer scopes or the root object.
+    pub fn set_isolated(&mut self, isolated: bool) {
+        self.isolated = isolated;
+    }
+
+    /// Get the isolated flag.
+    pub fn is_isolated(&self) -> bool {
+        self.isolated
+    }
 }
 
 /// Create a scope from hash parameters.
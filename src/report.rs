@@ -0,0 +1,79 @@
+//! Report the health of a template render.
+//!
+//! Use [render_with_report()](crate::Registry#method.render_with_report)
+//! to render a named template and collect the problems that do not
+//! abort a render so they can be logged without failing the request:
+//!
+//! ```ignore
+//! let (output, report) = registry.render_with_report("page", &data)?;
+//! for entry in report.missing_variables.iter() {
+//!     log::warn!("{}: missing variable {}", entry.name, entry.subject);
+//! }
+//! ```
+//!
+//! When collecting a report a partial that does not exist is recorded
+//! and rendered as the empty string rather than returning an error.
+use alloc::{string::String, vec::Vec};
+use core::ops::Range;
+
+/// Problem recorded while rendering.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ReportEntry {
+    /// Name of the template or partial being rendered.
+    pub name: String,
+    /// Path, partial name, message or trimmed text for the entry.
+    pub subject: String,
+    /// Zero-based line range of the statement when known.
+    pub lines: Option<Range<usize>>,
+}
+
+/// Report collected for a render.
+///
+/// Entries are recorded once for each template and location so a
+/// statement in a loop does not repeat the same entry.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct RenderReport {
+    /// Variables that could not be resolved.
+    pub missing_variables: Vec<ReportEntry>,
+    /// Partials that do not exist.
+    pub missing_partials: Vec<ReportEntry>,
+    /// Warnings for helper calls such as calls to deprecated helpers.
+    pub helper_warnings: Vec<ReportEntry>,
+    /// Text removed by whitespace control.
+    pub trimmed: Vec<ReportEntry>,
+}
+
+impl RenderReport {
+    /// Determine if no problems were recorded.
+    ///
+    /// Trimmed text is expected so it is not considered a problem.
+    pub fn is_empty(&self) -> bool {
+        self.missing_variables.is_empty()
+            && self.missing_partials.is_empty()
+            && self.helper_warnings.is_empty()
+    }
+}
+
+/// Kind of report entry.
+#[derive(Clone, Copy, Debug)]
+pub(crate) enum ReportKind {
+    MissingVariable,
+    MissingPartial,
+    HelperWarning,
+    Trimmed,
+}
+
+impl RenderReport {
+    /// Record an entry unless an equal entry exists.
+    pub(crate) fn record(&mut self, kind: ReportKind, entry: ReportEntry) {
+        let entries = match kind {
+            ReportKind::MissingVariable => &mut self.missing_variables,
+            ReportKind::MissingPartial => &mut self.missing_partials,
+            ReportKind::HelperWarning => &mut self.helper_warnings,
+            ReportKind::Trimmed => &mut self.trimmed,
+        };
+        if !entries.contains(&entry) {
+            entries.push(entry);
+        }
+    }
+}
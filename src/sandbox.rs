@@ -0,0 +1,64 @@
+//! Sandbox profiles restrict what templates may do.
+//!
+//! Platforms that render templates supplied by their users can assign
+//! a profile to a registry using
+//! [set_sandbox()](crate::Registry#method.set_sandbox) to restrict
+//! which helpers may be called, limit partial depth and output size
+//! and disallow unescaped interpolation.
+//!
+//! Assigning a profile also sandboxes the registry so helpers that use
+//! ambient authority (such as `env`) are disabled.
+//!
+//! ```ignore
+//! let mut profile = Profile::new();
+//! profile.allow_helpers(&["if", "each"]);
+//! profile.max_partial_depth = Some(4);
+//! profile.max_output = Some(1024 * 1024);
+//! registry.set_sandbox(profile);
+//! ```
+use crate::collections::HashSet;
+use alloc::string::{String, ToString};
+
+/// Restrictions applied when rendering templates.
+///
+/// The default profile does not restrict helpers, partial depth or
+/// output size but does not permit unescaped interpolation.
+#[derive(Debug, Clone, Default, Eq, PartialEq)]
+pub struct Profile {
+    /// Names of the helpers that may be called; when `None` all
+    /// helpers that do not use ambient authority may be called.
+    pub helpers: Option<HashSet<String>>,
+    /// Maximum depth for nested partials.
+    pub max_partial_depth: Option<usize>,
+    /// Maximum number of bytes written to the output destination.
+    pub max_output: Option<usize>,
+    /// Allow unescaped interpolation (`{{{ }}}`) and raw
+    /// statements (`\{{ }}`).
+    pub raw: bool,
+}
+
+impl Profile {
+    /// Create a new profile.
+    pub fn new() -> Self {
+        Default::default()
+    }
+
+    /// Allow the named helpers to be called.
+    pub fn allow_helpers<S>(&mut self, names: &[S])
+    where
+        S: AsRef<str>,
+    {
+        let helpers = self.helpers.get_or_insert_with(Default::default);
+        for name in names {
+            helpers.insert(name.as_ref().to_string());
+        }
+    }
+
+    /// Determine if a helper may be called.
+    pub fn is_helper_allowed(&self, name: &str) -> bool {
+        self.helpers
+            .as_ref()
+            .map(|helpers| helpers.contains(name))
+            .unwrap_or(true)
+    }
+}
@@ -1,21 +1,38 @@
 //! Templates add rendering capability to nodes.
-use std::collections::HashMap;
+use crate::collections::HashMap;
+use alloc::{borrow::ToOwned, boxed::Box, string::String, vec::Vec};
+use core::hash::{Hash, Hasher};
 
+use core::fmt;
 use serde::Serialize;
-use std::fmt;
+use serde_json::Value;
 
 use crate::{
-    output::Output,
-    parser::{ast::Node, Parser, ParserOptions},
+    error::RenderError,
+    minify::HtmlMinify,
+    output::{Newline, Output, TrailingNewline, TrailingOutput},
+    parser::{
+        ast::{CallTarget, Node, ParameterValue, Slice, Text},
+        Parser, ParserOptions,
+    },
     render::{CallSite, Render},
+    variables::{self, Variable},
     Registry, RenderResult, SyntaxResult,
 };
 
 use self_cell::self_cell;
 
+/// Name of the helper that marks a fragment.
+const FRAGMENT: &str = "fragment";
+
 /// Collection of named templates.
 pub type Templates = HashMap<String, Template>;
 
+/// Function that maps a template name to a list of candidate names
+/// in order of preference using the data for the render.
+pub type TemplateResolver =
+    Box<dyn Fn(&str, &Value) -> Vec<String> + Send + Sync>;
+
 self_cell!(
     struct Ast {
         owner: String,
@@ -30,9 +47,14 @@ self_cell!(
 // SEE: https://github.com/projectfluent/fluent-rs/blob/master/fluent-bundle/src/resource.rs#L5-L14
 
 /// Template that owns the underlying string and a corresponding document node.
+///
+/// Templates are compared and hashed by their document node so
+/// identical templates are equal regardless of the file name.
 #[derive(Debug)]
 pub struct Template {
     file_name: Option<String>,
+    trailing_newline: Option<TrailingNewline>,
+    text_only: bool,
     ast: Ast,
 }
 
@@ -42,42 +64,179 @@ impl Template {
         source: String,
         options: ParserOptions,
     ) -> SyntaxResult<Self> {
-        let mut err = None;
-
         let file_name = if options.file_name != crate::parser::UNKNOWN {
             Some(options.file_name.clone())
         } else {
             None
         };
 
-        let ast = Ast::new(source, |s: &String| {
-            match Parser::new(s, options).parse() {
-                Ok(ast) => ast,
-                Err(e) => {
-                    err = Some(e);
-                    Default::default()
-                }
+        Template::build(source, file_name, |s| Parser::new(s, options).parse())
+    }
+
+    /// Create a template from a source string and a function that
+    /// creates the document node for the source.
+    pub(crate) fn build<F>(
+        source: String,
+        file_name: Option<String>,
+        parse: F,
+    ) -> SyntaxResult<Self>
+    where
+        F: for<'a> FnOnce(&'a str) -> SyntaxResult<Node<'a>>,
+    {
+        let mut err = None;
+        let ast = Ast::new(source, |s: &String| match parse(s) {
+            Ok(ast) => ast,
+            Err(e) => {
+                err = Some(e);
+                Default::default()
             }
         });
 
         if let Some(e) = err {
             Err(e)
         } else {
-            Ok(Self { file_name, ast })
+            let text_only = match ast.borrow_dependent() {
+                Node::Document(ref doc) => doc.nodes().iter().all(|n| {
+                    matches!(
+                        n,
+                        Node::Text(_) | Node::Comment(_) | Node::RawComment(_)
+                    )
+                }),
+                _ => false,
+            };
+            Ok(Self {
+                file_name,
+                trailing_newline: None,
+                text_only,
+                ast,
+            })
         }
     }
 
+    /// Compile a fragment extracted from a larger template.
+    ///
+    /// Raw block helpers can use this to interpret the raw content
+    /// of a block as a template on demand.
+    ///
+    /// Set `line_offset` on the options to the line where the fragment
+    /// begins so that syntax errors report line numbers relative to the
+    /// parent template; the fragment is the source for error snippets
+    /// so any `byte_offset` is ignored.
+    pub fn compile_fragment(
+        fragment: &str,
+        mut options: ParserOptions,
+    ) -> SyntaxResult<Self> {
+        options.byte_offset = 0;
+        Template::compile(fragment.to_owned(), options)
+    }
+
     /// The document node for the template.
     pub fn node(&self) -> &Node<'_> {
         self.ast.borrow_dependent()
     }
 
+    /// Call a function for every text node in the template so
+    /// that passes such as minification can replace the text
+    /// written when rendering.
+    ///
+    /// ```ignore
+    /// template.for_each_text(|text| {
+    ///     let value = text.value().replace("\t", "  ");
+    ///     text.set_value(value);
+    /// });
+    /// ```
+    pub fn for_each_text<F>(&mut self, mut f: F)
+    where
+        F: FnMut(&mut Text<'_>),
+    {
+        self.ast
+            .with_dependent_mut(|_, node| node.for_each_text(&mut f));
+    }
+
+    /// The source string for the template.
+    pub fn source(&self) -> &str {
+        self.ast.borrow_owner()
+    }
+
+    /// Estimate the size of the rendered output.
+    ///
+    /// Used to pre-allocate output buffers; the estimate is the
+    /// source length plus half again to allow for variable expansion.
+    pub fn size_hint(&self) -> usize {
+        let len = self.source().len();
+        len.saturating_add(len / 2)
+    }
+
     /// Get the file name given when this template was compiled.
     pub fn file_name(&self) -> Option<&str> {
         self.file_name.as_ref().map(|s| s.as_str())
     }
 
+    /// List the variables referenced by this template.
+    ///
+    /// See the [variables](crate::variables) module for how paths
+    /// are resolved.
+    pub fn variables(&self) -> Vec<Variable> {
+        variables::collect(self.node())
+    }
+
+    /// Generate a data value with placeholders for the variables
+    /// referenced by this template so that it can be previewed
+    /// without real data.
+    pub fn skeleton(&self) -> Value {
+        variables::skeleton(&self.variables())
+    }
+
+    /// Find the block for a fragment.
+    ///
+    /// Fragments are blocks that call the `fragment` helper with a
+    /// string literal id such as `{{#fragment "row"}}`; when more than
+    /// one block uses the same id the first block is returned.
+    pub fn fragment(&self, id: &str) -> Option<&Node<'_>> {
+        self.node().descendants().find(|node| match node {
+            Node::Block(ref block) => {
+                let call = block.call();
+                let name = match call.target() {
+                    CallTarget::Path(ref path) => path.as_str(),
+                    CallTarget::SubExpr(_) => return false,
+                };
+                let value = match call.arguments().first() {
+                    Some(ParameterValue::Json { ref value, .. }) => value,
+                    _ => return false,
+                };
+                !call.is_partial()
+                    && name == FRAGMENT
+                    && value.as_str() == Some(id)
+            }
+            _ => false,
+        })
+    }
+
+    /// Determine if this template only contains text and comments.
+    ///
+    /// Rendering a template that only contains text writes the text
+    /// directly to the output destination without serializing the data
+    /// unless the registry normalizes newlines or limits the output size.
+    pub fn is_text_only(&self) -> bool {
+        self.text_only
+    }
+
+    /// Set the trailing newline policy for this template.
+    ///
+    /// When `None` the policy for the registry is used.
+    pub fn set_trailing_newline(&mut self, policy: Option<TrailingNewline>) {
+        self.trailing_newline = policy;
+    }
+
+    /// Get the trailing newline policy for this template.
+    pub fn trailing_newline(&self) -> Option<TrailingNewline> {
+        self.trailing_newline
+    }
+
     /// Render this template to the given writer.
+    ///
+    /// The trailing newline policy for the template, or the registry
+    /// when the template does not have a policy, is applied to the output.
     pub fn render<'a, T>(
         &self,
         registry: &'a Registry<'a>,
@@ -89,12 +248,159 @@ impl Template {
     where
         T: Serialize,
     {
+        self.render_output(registry, name, writer, |writer| {
+            self.render_nested(registry, name, data, writer, stack)
+        })
+    }
+
+    /// Render this template with a function that configures the
+    /// renderer, renders the document node and collects any results.
+    ///
+    /// The `state` is passed to the function with the lifetime of the
+    /// renderer so it can lend partials or an escape function; the
+    /// output is processed in the same way as `render()`.
+    pub(crate) fn render_with<'a, T, S, R, F>(
+        &self,
+        registry: &'a Registry<'a>,
+        name: &str,
+        data: &T,
+        writer: &'a mut dyn Output,
+        state: &S,
+        render: F,
+    ) -> RenderResult<R>
+    where
+        T: Serialize,
+        S: ?Sized,
+        F: for<'r> FnOnce(
+            &mut Render<'r>,
+            &'r Node<'r>,
+            &'r S,
+        ) -> RenderResult<R>,
+    {
+        self.render_output(registry, name, writer, |writer| {
+            let mut rc = Render::new(
+                registry,
+                name,
+                data,
+                Box::new(writer),
+                Default::default(),
+            )?;
+            render(&mut rc, self.node(), state)
+        })
+    }
+
+    /// Render to the given writer recording metrics when enabled.
+    #[cfg_attr(not(feature = "metrics"), allow(unused_variables))]
+    fn render_output<R, F>(
+        &self,
+        registry: &Registry<'_>,
+        name: &str,
+        writer: &mut dyn Output,
+        render: F,
+    ) -> RenderResult<R>
+    where
+        F: FnOnce(&mut dyn Output) -> RenderResult<R>,
+    {
+        #[cfg(feature = "metrics")]
+        return crate::metrics::render(name, writer, |writer| {
+            self.render_trailing(registry, writer, render)
+        });
+        #[cfg(not(feature = "metrics"))]
+        self.render_trailing(registry, writer, render)
+    }
+
+    /// Render to the given writer applying the trailing newline
+    /// policy and minification.
+    fn render_trailing<R, F>(
+        &self,
+        registry: &Registry<'_>,
+        writer: &mut dyn Output,
+        render: F,
+    ) -> RenderResult<R>
+    where
+        F: FnOnce(&mut dyn Output) -> RenderResult<R>,
+    {
+        let policy = self
+            .trailing_newline
+            .unwrap_or_else(|| registry.trailing_newline());
+        if let TrailingNewline::Preserve = policy {
+            return render_minify(registry, writer, render);
+        }
+        let mut output =
+            TrailingOutput::new(writer, policy, registry.newline());
+        let result = render_minify(registry, &mut output, render)?;
+        output.finish().map_err(RenderError::from)?;
+        Ok(result)
+    }
+
+    /// Render this template to the given writer without applying
+    /// a trailing newline policy.
+    pub(crate) fn render_nested<'a, T>(
+        &self,
+        registry: &'a Registry<'a>,
+        name: &str,
+        data: &T,
+        writer: &'a mut dyn Output,
+        stack: Vec<CallSite>,
+    ) -> RenderResult<()>
+    where
+        T: Serialize,
+    {
+        let fast = self.text_only
+            && registry.newline() == Newline::PassThrough
+            && registry.sandbox().and_then(|p| p.max_output).is_none();
+        if fast {
+            if let Node::Document(ref doc) = self.node() {
+                for node in doc.nodes() {
+                    if let Node::Text(ref text) = node {
+                        writer
+                            .write_str(text.value())
+                            .map_err(RenderError::from)?;
+                    }
+                }
+            }
+            return Ok(());
+        }
+
         let mut rc =
             Render::new(registry, name, data, Box::new(writer), stack)?;
         rc.render(self.node())
     }
 }
 
+/// Render to the given writer minifying the output when
+/// minification is enabled for the registry.
+fn render_minify<R, F>(
+    registry: &Registry<'_>,
+    writer: &mut dyn Output,
+    render: F,
+) -> RenderResult<R>
+where
+    F: FnOnce(&mut dyn Output) -> RenderResult<R>,
+{
+    if !registry.minify() {
+        return render(writer);
+    }
+    let mut output = HtmlMinify::new(writer);
+    let result = render(&mut output)?;
+    output.finish().map_err(RenderError::from)?;
+    Ok(result)
+}
+
+impl PartialEq for Template {
+    fn eq(&self, other: &Self) -> bool {
+        self.node() == other.node()
+    }
+}
+
+impl Eq for Template {}
+
+impl Hash for Template {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.node().hash(state)
+    }
+}
+
 impl fmt::Display for Template {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         self.node().fmt(f)
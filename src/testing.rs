@@ -0,0 +1,212 @@
+//! Snapshot testing for template libraries.
+//!
+//! Requires the `testing` feature. Render templates against fixture
+//! data and compare the output to snapshots stored as files; when the
+//! output changes the assertion fails with a line diff:
+//!
+//! ```ignore
+//! use bracket::testing::Snapshots;
+//!
+//! #[test]
+//! fn card() -> bracket::Result<()> {
+//!     let mut registry = Registry::new();
+//!     registry.read_dir("templates", "hbs")?;
+//!     let snapshots = Snapshots::new("tests/snapshots");
+//!     snapshots.assert_render(&registry, "card", &json!({"title": "Hello"}))?;
+//!     snapshots.assert_fixture(&registry, "card", "tests/fixtures/empty.json")?;
+//!     Ok(())
+//! }
+//! ```
+//!
+//! Snapshots are written to `{name}.snap` in the snapshot directory;
+//! to create or accept snapshots run the tests with the
+//! `BRACKET_UPDATE_SNAPSHOTS` environment variable set to `1`:
+//!
+//! ```text
+//! BRACKET_UPDATE_SNAPSHOTS=1 cargo test
+//! ```
+use std::{
+    fmt::Write,
+    path::{Path, PathBuf},
+};
+
+use serde::Serialize;
+use serde_json::Value;
+
+use crate::{
+    diff::{lcs, Edit},
+    error::RenderError,
+    Error, Registry, Result,
+};
+
+/// Environment variable that enables updating snapshots.
+pub const UPDATE_ENV: &str = "BRACKET_UPDATE_SNAPSHOTS";
+
+/// File extension for snapshot files.
+pub const EXTENSION: &str = "snap";
+
+/// Snapshots stored in a directory.
+#[derive(Debug, Clone)]
+pub struct Snapshots {
+    dir: PathBuf,
+    update: bool,
+}
+
+impl Snapshots {
+    /// Create snapshots stored in a directory.
+    ///
+    /// Snapshots are updated when the `BRACKET_UPDATE_SNAPSHOTS`
+    /// environment variable is set to `1` or `true`.
+    pub fn new<P: AsRef<Path>>(dir: P) -> Self {
+        let update = std::env::var(UPDATE_ENV)
+            .map(|v| v == "1" || v == "true")
+            .unwrap_or(false);
+        Self {
+            dir: dir.as_ref().to_path_buf(),
+            update,
+        }
+    }
+
+    /// Set whether snapshots are updated rather than compared.
+    pub fn set_update(&mut self, update: bool) {
+        self.update = update;
+    }
+
+    /// Get whether snapshots are updated rather than compared.
+    pub fn update(&self) -> bool {
+        self.update
+    }
+
+    /// Path to the file for a named snapshot.
+    pub fn path(&self, name: &str) -> PathBuf {
+        self.dir.join(format!("{}.{}", name, EXTENSION))
+    }
+
+    /// Compare a value with a named snapshot.
+    ///
+    /// Returns a diff when the value does not match the snapshot or
+    /// the snapshot does not exist; when updating the snapshot is
+    /// written and `None` is returned.
+    pub fn check(&self, name: &str, actual: &str) -> Result<Option<String>> {
+        let path = self.path(name);
+        if self.update {
+            if let Some(parent) = path.parent() {
+                std::fs::create_dir_all(parent)?;
+            }
+            std::fs::write(&path, actual)?;
+            return Ok(None);
+        }
+        if !path.exists() {
+            return Ok(Some(format!(
+                "Snapshot '{}' does not exist, set {}=1 to create it",
+                path.display(),
+                UPDATE_ENV
+            )));
+        }
+        let expected = std::fs::read_to_string(&path)?;
+        if expected == actual {
+            Ok(None)
+        } else {
+            Ok(Some(format!(
+                "Snapshot '{}' does not match, set {}=1 to accept the changes\n{}",
+                path.display(),
+                UPDATE_ENV,
+                diff_lines(&expected, actual)
+            )))
+        }
+    }
+
+    /// Assert that a value matches a named snapshot.
+    ///
+    /// # Panics
+    ///
+    /// If the value does not match the snapshot.
+    pub fn assert(&self, name: &str, actual: &str) -> Result<()> {
+        if let Some(diff) = self.check(name, actual)? {
+            panic!("{}", diff);
+        }
+        Ok(())
+    }
+
+    /// Render a named template and assert that the output matches
+    /// the snapshot with the same name.
+    ///
+    /// # Panics
+    ///
+    /// If the output does not match the snapshot.
+    pub fn assert_render<T>(
+        &self,
+        registry: &Registry<'_>,
+        name: &str,
+        data: &T,
+    ) -> Result<()>
+    where
+        T: Serialize,
+    {
+        let output = registry.render(name, data)?;
+        self.assert(name, &output)
+    }
+
+    /// Render a named template using the data in a JSON fixture file
+    /// and assert that the output matches the snapshot named after
+    /// the template and the fixture, for example `card@empty`.
+    ///
+    /// # Panics
+    ///
+    /// If the output does not match the snapshot.
+    pub fn assert_fixture<P>(
+        &self,
+        registry: &Registry<'_>,
+        name: &str,
+        fixture: P,
+    ) -> Result<()>
+    where
+        P: AsRef<Path>,
+    {
+        let fixture = fixture.as_ref();
+        let data = read_fixture(fixture)?;
+        let output = registry.render(name, &data)?;
+        let stem = fixture
+            .file_stem()
+            .map(|s| s.to_string_lossy().into_owned())
+            .unwrap_or_default();
+        self.assert(&format!("{}@{}", name, stem), &output)
+    }
+}
+
+/// Read the data for a render from a JSON fixture file.
+pub fn read_fixture<P: AsRef<Path>>(fixture: P) -> Result<Value> {
+    let content = std::fs::read_to_string(fixture)?;
+    serde_json::from_str(&content)
+        .map_err(|e| Error::from(RenderError::from(e)))
+}
+
+/// Format the difference between the lines of two strings.
+///
+/// Lines only in the expected value are prefixed with `-` and lines
+/// only in the actual value are prefixed with `+`; unchanged lines
+/// are omitted except for a line of context either side of a change.
+pub fn diff_lines(expected: &str, actual: &str) -> String {
+    let a: Vec<&str> = expected.split('\n').collect();
+    let b: Vec<&str> = actual.split('\n').collect();
+
+    let lines: Vec<(char, usize, &str)> = lcs(&a, &b)
+        .into_iter()
+        .map(|edit| match edit {
+            Edit::Same(_, j) => (' ', j, b[j]),
+            Edit::Removed(i) => ('-', i, a[i]),
+            Edit::Added(j) => ('+', j, b[j]),
+        })
+        .collect();
+
+    let changed =
+        |index: usize| lines.get(index).map(|l| l.0 != ' ').unwrap_or(false);
+    let mut out = String::new();
+    for (index, (marker, line, text)) in lines.iter().enumerate() {
+        let context = changed(index + 1) || index > 0 && changed(index - 1);
+        if *marker != ' ' || context {
+            let _ = writeln!(out, "{}{:>4} | {}", marker, line + 1, text);
+        }
+    }
+    out
+}
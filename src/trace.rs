@@ -0,0 +1,85 @@
+//! Annotate rendered output with template boundaries.
+//!
+//! Assign a trace to a registry when debugging to wrap the output of
+//! each partial and block in markers so that the template which
+//! produced a fragment of a rendered page can be identified:
+//!
+//! ```ignore
+//! use bracket::trace::Trace;
+//! registry.set_trace(Some(Trace::html()));
+//! ```
+//!
+//! Renders `{{> card}}` as:
+//!
+//! ```html
+//! <!-- begin: card -->...<!-- end: card -->
+//! ```
+//!
+//! Markers are patterns where `{kind}` is replaced with `partial` or
+//! `block` and `{name}` is replaced with the name of the partial or
+//! the open tag of the block without the braces, for example
+//! `#each items`.
+//!
+//! Markers are written without escaping; when the registry also
+//! [minifies](crate::minify) output the HTML comments are removed.
+use alloc::string::String;
+
+/// Placeholder for the kind of template boundary.
+const KIND: &str = "{kind}";
+/// Placeholder for the name of the partial or block.
+const NAME: &str = "{name}";
+
+/// Markers written around the output of partials and blocks.
+#[derive(Debug, Clone, Eq, PartialEq)]
+pub struct Trace {
+    /// Pattern written before the output.
+    pub begin: String,
+    /// Pattern written after the output.
+    pub end: String,
+    /// Whether to annotate partials.
+    pub partials: bool,
+    /// Whether to annotate blocks.
+    pub blocks: bool,
+}
+
+impl Trace {
+    /// Create a trace with begin and end patterns that
+    /// annotates partials and blocks.
+    pub fn new<B, E>(begin: B, end: E) -> Self
+    where
+        B: Into<String>,
+        E: Into<String>,
+    {
+        Self {
+            begin: begin.into(),
+            end: end.into(),
+            partials: true,
+            blocks: true,
+        }
+    }
+
+    /// Create a trace that writes HTML comments.
+    pub fn html() -> Self {
+        Self::new("<!-- begin: {name} -->", "<!-- end: {name} -->")
+    }
+
+    /// Marker written before the output of a partial or block.
+    pub(crate) fn begin(&self, kind: &str, name: &str) -> String {
+        marker(&self.begin, kind, name)
+    }
+
+    /// Marker written after the output of a partial or block.
+    pub(crate) fn end(&self, kind: &str, name: &str) -> String {
+        marker(&self.end, kind, name)
+    }
+}
+
+impl Default for Trace {
+    fn default() -> Self {
+        Self::html()
+    }
+}
+
+fn marker(pattern: &str, kind: &str, name: &str) -> String {
+    pattern.replace(KIND, kind).replace(NAME, name)
+}
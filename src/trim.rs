@@ -1,4 +1,10 @@
 //! Types that control how whitespace is trimmed.
+//!
+//! Whitespace is trimmed by grapheme cluster so that combining marks
+//! are never separated from the character they modify when a
+//! whitespace control (`~`) removes adjacent whitespace.
+use unicode_segmentation::UnicodeSegmentation;
+
 use crate::parser::ast::Node;
 
 /// State that indicates how whitespace should be trimmed
@@ -41,3 +47,39 @@ pub struct TrimHint {
     /// Whether the next node should have leading whitespace removed.
     pub after: bool,
 }
+
+fn is_whitespace(grapheme: &str) -> bool {
+    grapheme.chars().all(char::is_whitespace)
+}
+
+/// Remove leading whitespace grapheme clusters.
+pub fn trim_start(s: &str) -> &str {
+    let trimmed = s.trim_start();
+    match trimmed.chars().next() {
+        Some(c) if !c.is_ascii() && trimmed.len() < s.len() => {
+            for (index, grapheme) in s.grapheme_indices(true) {
+                if !is_whitespace(grapheme) {
+                    return &s[index..];
+                }
+            }
+            ""
+        }
+        _ => trimmed,
+    }
+}
+
+/// Remove trailing whitespace grapheme clusters.
+pub fn trim_end(s: &str) -> &str {
+    let trimmed = s.trim_end();
+    match trimmed.chars().next_back() {
+        Some(c) if !c.is_ascii() && trimmed.len() < s.len() => {
+            for (index, grapheme) in s.grapheme_indices(true).rev() {
+                if !is_whitespace(grapheme) {
+                    return &s[..index + grapheme.len()];
+                }
+            }
+            ""
+        }
+        _ => trimmed,
+    }
+}
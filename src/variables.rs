@@ -0,0 +1,278 @@
+//! Static analysis of the variables referenced by a template.
+//!
+//! Use [variables()](crate::Template#method.variables) to list the
+//! data paths a template reads and
+//! [skeleton()](crate::Template#method.skeleton) to generate a
+//! plausible data value so that templates can be previewed without
+//! real data:
+//!
+//! ```ignore
+//! let template = registry.parse("card", "{{#each items}}<li>{{name}}</li>{{/each}}")?;
+//! let data = template.skeleton();
+//! // {"items": [{"name": "name"}]}
+//! let preview = template.render(&registry, "card", &data, &mut writer, Vec::new())?;
+//! ```
+//!
+//! Paths are resolved relative to the root of the template data;
+//! inside an `each` block the element is represented by the index `0`
+//! so `{{#each items}}{{name}}{{/each}}` references `items.0.name`.
+//!
+//! Statements without arguments are assumed to be variables; helpers
+//! with the same name as a variable cannot be distinguished without
+//! a registry. Local variables such as `@index` are not included.
+use alloc::{string::ToString, vec, vec::Vec};
+use core::ops::Range;
+
+use serde_json::{Map, Value};
+
+use crate::{
+    parser::ast::{Call, CallTarget, Lines, Node, ParameterValue, Path},
+    query::ValuePath,
+};
+
+/// Segment used for the element of an array.
+const ELEMENT: &str = "0";
+
+/// How a template uses a variable.
+#[derive(Clone, Copy, Debug, Eq, PartialEq, Hash)]
+pub enum Usage {
+    /// Value that is written or passed to a helper.
+    Value,
+    /// Condition for an `if` or `unless` block.
+    Condition,
+    /// Array iterated by an `each` block.
+    Iterated,
+    /// Object that is the context for a `with` block.
+    Scoped,
+}
+
+/// Variable referenced by a template.
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Variable {
+    /// Path from the root of the template data.
+    pub path: ValuePath,
+    /// How the variable is used.
+    pub usage: Usage,
+    /// Byte range of the path in the template.
+    pub span: Range<usize>,
+    /// Zero-based line range of the path in the template.
+    pub lines: Range<usize>,
+}
+
+/// Collect the variables referenced by a node and the nodes it contains.
+pub fn collect(node: &Node<'_>) -> Vec<Variable> {
+    let mut out = Vec::new();
+    let mut stack = vec![ValuePath::new()];
+    visit(node, &mut stack, &mut out);
+    out
+}
+
+fn visit(node: &Node<'_>, stack: &mut Vec<ValuePath>, out: &mut Vec<Variable>) {
+    match node {
+        Node::Document(ref doc) => {
+            doc.nodes().iter().for_each(|n| visit(n, stack, out));
+        }
+        Node::Statement(ref call) => {
+            let variable = !call.is_partial()
+                && call.arguments().is_empty()
+                && call.parameters().is_empty();
+            match call.target() {
+                CallTarget::Path(ref path) if variable => {
+                    push(path, Usage::Value, stack, out)
+                }
+                _ => call_arguments(call, stack, out),
+            }
+        }
+        Node::Block(ref block) => {
+            let call = block.call();
+            let (usage, scope) = match block.name() {
+                _ if call.is_partial() => (Usage::Value, None),
+                Some("each") => (Usage::Iterated, Some(true)),
+                Some("with") => (Usage::Scoped, Some(false)),
+                Some("if") | Some("unless") => (Usage::Condition, None),
+                _ => (Usage::Value, None),
+            };
+            let (target, skip) = match call.arguments().first() {
+                Some(ParameterValue::Path(ref path)) => {
+                    push(path, usage, stack, out);
+                    (resolve(path, stack).filter(|_| scope.is_some()), 1)
+                }
+                _ => (None, 0),
+            };
+            call_arguments_from(call, skip, stack, out);
+            if let (Some(each), Some(mut target)) = (scope, target) {
+                if each {
+                    target.push(ELEMENT);
+                }
+                stack.push(target);
+                block.nodes().iter().for_each(|n| visit(n, stack, out));
+                stack.pop();
+            } else {
+                block.nodes().iter().for_each(|n| visit(n, stack, out));
+            }
+            for condition in block.conditions() {
+                visit(condition, stack, out);
+            }
+        }
+        _ => {}
+    }
+}
+
+/// Collect variables from the arguments and hash parameters of a call.
+fn call_arguments(
+    call: &Call<'_>,
+    stack: &mut Vec<ValuePath>,
+    out: &mut Vec<Variable>,
+) {
+    if let CallTarget::SubExpr(ref call) = call.target() {
+        call_arguments(call, stack, out);
+    }
+    call_arguments_from(call, 0, stack, out);
+}
+
+fn call_arguments_from(
+    call: &Call<'_>,
+    skip: usize,
+    stack: &mut Vec<ValuePath>,
+    out: &mut Vec<Variable>,
+) {
+    let values = call
+        .arguments()
+        .iter()
+        .skip(skip)
+        .chain(call.parameters().values());
+    for value in values {
+        match value {
+            ParameterValue::Path(ref path) => {
+                push(path, Usage::Value, stack, out)
+            }
+            ParameterValue::SubExpr(ref call) => {
+                call_arguments(call, stack, out)
+            }
+            ParameterValue::Json { .. } => {}
+        }
+    }
+}
+
+fn push(
+    path: &Path<'_>,
+    usage: Usage,
+    stack: &[ValuePath],
+    out: &mut Vec<Variable>,
+) {
+    if let Some(resolved) = resolve(path, stack) {
+        out.push(Variable {
+            path: resolved,
+            usage,
+            span: path.span().clone(),
+            lines: path.lines().clone(),
+        });
+    }
+}
+
+/// Resolve a path relative to the current context.
+fn resolve(path: &Path<'_>, stack: &[ValuePath]) -> Option<ValuePath> {
+    if path.absolute() || path.is_empty() {
+        return None;
+    }
+    let (mut resolved, skip) = if path.is_root() {
+        (ValuePath::new(), 1)
+    } else if path.is_local() {
+        return None;
+    } else if path.parents() > 0 {
+        let index = stack.len().checked_sub(path.parents() as usize + 1)?;
+        (stack[index].clone(), 0)
+    } else if path.is_explicit() {
+        (stack.last().cloned().unwrap_or_default(), 1)
+    } else {
+        (stack.last().cloned().unwrap_or_default(), 0)
+    };
+    for component in path.components().iter().skip(skip) {
+        resolved.push(component.as_value());
+    }
+    Some(resolved)
+}
+
+/// Generate a data value that satisfies the variables.
+///
+/// Values are strings containing the name of the variable, conditions
+/// are `true`, iterated variables are arrays with a single element
+/// and scoped variables are objects.
+pub fn skeleton(variables: &[Variable]) -> Value {
+    let mut sorted: Vec<&Variable> = variables.iter().collect();
+    sorted.sort_by_key(|v| v.path.len());
+    let mut root = Value::Object(Map::new());
+    for variable in sorted {
+        let segments: Vec<&str> = variable.path.segments().collect();
+        if segments.is_empty() {
+            continue;
+        }
+        insert(&mut root, &segments, variable.usage, "");
+    }
+    fill(&mut root, "");
+    root
+}
+
+fn insert(target: &mut Value, segments: &[&str], usage: Usage, name: &str) {
+    let segment = segments[0];
+    let rest = &segments[1..];
+    let name = if segment == ELEMENT { name } else { segment };
+    let child = match target {
+        Value::Array(ref mut list) if segment == ELEMENT => {
+            if list.is_empty() {
+                list.push(Value::Null);
+            }
+            &mut list[0]
+        }
+        _ => {
+            if !target.is_object() {
+                *target = Value::Object(Map::new());
+            }
+            target
+                .as_object_mut()
+                .unwrap()
+                .entry(segment.to_string())
+                .or_insert(Value::Null)
+        }
+    };
+    if rest.is_empty() {
+        let value = match usage {
+            Usage::Value => Value::String(name.to_string()),
+            Usage::Condition => Value::Bool(true),
+            Usage::Iterated => Value::Array(Vec::new()),
+            Usage::Scoped => Value::Object(Map::new()),
+        };
+        // NOTE: a variable used as a container takes precedence
+        if !(child.is_object() || child.is_array()) {
+            *child = value;
+        }
+    } else {
+        if rest[0] == ELEMENT && !child.is_array() {
+            *child = Value::Array(Vec::new());
+        }
+        insert(child, rest, usage, name);
+    }
+}
+
+/// Replace empty arrays and null elements with a placeholder.
+fn fill(target: &mut Value, name: &str) {
+    match target {
+        Value::Array(ref mut list) => {
+            if list.is_empty() {
+                list.push(Value::Null);
+            }
+            for item in list.iter_mut() {
+                if item.is_null() {
+                    *item = Value::String(name.to_string());
+                }
+                fill(item, name);
+            }
+        }
+        Value::Object(ref mut map) => {
+            for (key, value) in map.iter_mut() {
+                fill(value, key);
+            }
+        }
+        _ => {}
+    }
+}
@@ -0,0 +1,163 @@
+//! Template variants for experiments.
+//!
+//! Multiple variants of a template can be registered under a single
+//! logical name using
+//! [register_variant()](crate::Registry#method.register_variant);
+//! each time the logical name is rendered a selector chooses which
+//! variant to use:
+//!
+//! ```ignore
+//! registry.register_variant("email/welcome", "control", control, 90)?;
+//! registry.register_variant("email/welcome", "v2", v2, 10)?;
+//! registry.set_variant_selector(Box::new(|_name, variants, data| {
+//!     let bucket = data.get("userId").and_then(|v| v.as_u64())?;
+//!     variants.pick(bucket).map(|v| v.name().to_string())
+//! }));
+//! let result = registry.render("email/welcome", &data)?;
+//! ```
+//!
+//! When no selector is assigned, or the selector does not return the
+//! name of a registered variant, the variant with the largest weight
+//! is used.
+//!
+//! Variants are also resolved for partials so the selector may be
+//! invoked more than once for a render; selectors should be
+//! deterministic for the render data so that the same variant
+//! is chosen each time.
+use alloc::{boxed::Box, string::String, vec::Vec};
+use serde_json::Value;
+
+use crate::template::Template;
+
+/// Function that selects a variant by name.
+///
+/// The function is passed the logical template name, the registered
+/// variants and the data for the render.
+pub type VariantSelector =
+    Box<dyn Fn(&str, &Variants, &Value) -> Option<String> + Send + Sync>;
+
+/// Template registered as a variant of a logical name.
+#[derive(Debug)]
+pub struct Variant {
+    name: String,
+    weight: u32,
+    template: Template,
+}
+
+impl Variant {
+    /// The name of the variant.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The relative weight of the variant.
+    pub fn weight(&self) -> u32 {
+        self.weight
+    }
+
+    /// The compiled template for the variant.
+    pub fn template(&self) -> &Template {
+        &self.template
+    }
+}
+
+/// Collection of the variants for a logical template name
+/// in the order they were registered.
+#[derive(Debug, Default)]
+pub struct Variants {
+    variants: Vec<Variant>,
+}
+
+impl Variants {
+    /// Add a variant replacing any existing variant with the same name.
+    pub(crate) fn insert(
+        &mut self,
+        name: String,
+        weight: u32,
+        template: Template,
+    ) {
+        let variant = Variant {
+            name,
+            weight,
+            template,
+        };
+        if let Some(existing) =
+            self.variants.iter_mut().find(|v| v.name == variant.name)
+        {
+            *existing = variant;
+        } else {
+            self.variants.push(variant);
+        }
+    }
+
+    /// Get a variant by name.
+    pub fn get(&self, name: &str) -> Option<&Variant> {
+        self.variants.iter().find(|v| v.name == name)
+    }
+
+    /// Iterate the variants.
+    pub fn iter(&self) -> impl Iterator<Item = &Variant> {
+        self.variants.iter()
+    }
+
+    /// Number of variants.
+    pub fn len(&self) -> usize {
+        self.variants.len()
+    }
+
+    /// Determine if there are no variants.
+    pub fn is_empty(&self) -> bool {
+        self.variants.is_empty()
+    }
+
+    /// Sum of the weights of all variants.
+    pub fn total_weight(&self) -> u64 {
+        self.variants.iter().map(|v| v.weight as u64).sum()
+    }
+
+    /// Pick a variant in proportion to the weights using a
+    /// bucket such as a random number or a hash of a user identifier.
+    ///
+    /// Returns `None` when the total weight is zero.
+    pub fn pick(&self, bucket: u64) -> Option<&Variant> {
+        let total = self.total_weight();
+        if total == 0 {
+            return None;
+        }
+        let mut point = bucket % total;
+        for variant in self.variants.iter() {
+            let weight = variant.weight as u64;
+            if point < weight {
+                return Some(variant);
+            }
+            point -= weight;
+        }
+        None
+    }
+
+    /// The variant with the largest weight; when weights are equal
+    /// the variant registered first is preferred.
+    pub fn heaviest(&self) -> Option<&Variant> {
+        self.variants.iter().fold(
+            None,
+            |best: Option<&Variant>, v| match best {
+                Some(b) if b.weight >= v.weight => Some(b),
+                _ => Some(v),
+            },
+        )
+    }
+
+    /// Select a variant using a selector falling back to the
+    /// variant with the largest weight.
+    pub(crate) fn select(
+        &self,
+        name: &str,
+        selector: Option<&VariantSelector>,
+        data: &Value,
+    ) -> Option<&Variant> {
+        selector
+            .and_then(|f| f(name, self, data))
+            .and_then(|selected| self.get(&selected))
+            .or_else(|| self.heaviest())
+    }
+}
@@ -0,0 +1,17 @@
+//! Responder for [actix-web](https://docs.rs/actix-web).
+use actix_web::{body::BoxBody, HttpRequest, HttpResponse, Responder};
+
+use super::Template;
+
+impl Responder for Template {
+    type Body = BoxBody;
+
+    fn respond_to(self, _req: &HttpRequest) -> HttpResponse<Self::Body> {
+        match self.respond() {
+            Some(body) => HttpResponse::Ok()
+                .content_type(self.content_type())
+                .body(body),
+            None => HttpResponse::InternalServerError().finish(),
+        }
+    }
+}
@@ -0,0 +1,17 @@
+//! Response conversion for [axum](https://docs.rs/axum).
+use axum::{
+    http::{header, StatusCode},
+    response::{IntoResponse, Response},
+};
+
+use super::Template;
+
+impl IntoResponse for Template {
+    fn into_response(self) -> Response {
+        match self.respond() {
+            Some(body) => ([(header::CONTENT_TYPE, self.content_type())], body)
+                .into_response(),
+            None => StatusCode::INTERNAL_SERVER_ERROR.into_response(),
+        }
+    }
+}
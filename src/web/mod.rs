@@ -0,0 +1,85 @@
+//! Responders for web frameworks.
+//!
+//! Enable the `axum`, `actix-web` or `rocket` feature to return a
+//! [Template](web::Template) from a request handler; the named template
+//! is rendered using a shared registry:
+//!
+//! ```ignore
+//! async fn index(State(registry): State<Arc<Registry<'static>>>) -> Template {
+//!     Template::new(registry, "index", json!({"title": "Home"}))
+//! }
+//! ```
+//!
+//! The `Content-Type` is `text/html` when the registry uses the default
+//! HTML escape function otherwise `text/plain`. Render errors are logged
+//! and converted to a `500 Internal Server Error` response without
+//! exposing the error detail to the client.
+use std::sync::Arc;
+
+use serde_json::Value;
+
+use crate::Registry;
+
+#[cfg(feature = "actix-web")]
+pub mod actix;
+#[cfg(feature = "axum")]
+pub mod axum;
+#[cfg(feature = "rocket")]
+pub mod rocket;
+
+/// Content type for templates rendered with HTML escaping.
+pub const HTML: &str = "text/html; charset=utf-8";
+
+/// Content type for templates rendered without HTML escaping.
+pub const TEXT: &str = "text/plain; charset=utf-8";
+
+/// Named template to render as a response.
+pub struct Template {
+    registry: Arc<Registry<'static>>,
+    name: String,
+    data: Value,
+}
+
+impl Template {
+    /// Create a response for the named template.
+    pub fn new<N>(
+        registry: Arc<Registry<'static>>,
+        name: N,
+        data: Value,
+    ) -> Self
+    where
+        N: Into<String>,
+    {
+        Self {
+            registry,
+            name: name.into(),
+            data,
+        }
+    }
+
+    /// The name of the template.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The content type for the response.
+    pub fn content_type(&self) -> &'static str {
+        if self.registry.escape_html() {
+            HTML
+        } else {
+            TEXT
+        }
+    }
+
+    /// Render the template, errors are logged and `None` is returned.
+    pub(crate) fn respond(&self) -> Option<String> {
+        match self.registry.render(&self.name, &self.data) {
+            Ok(body) => Some(body),
+            Err(e) => {
+                log::error!("Failed to render template '{}'", self.name);
+                log::error!("{:?}", e);
+                None
+            }
+        }
+    }
+}
@@ -0,0 +1,66 @@
+//! Responder and fairing for [rocket](https://docs.rs/rocket).
+//!
+//! Attach the fairing to manage the registry as state so handlers
+//! can access it:
+//!
+//! ```ignore
+//! #[get("/")]
+//! fn index(registry: &State<Arc<Registry<'static>>>) -> Template {
+//!     Template::new(Arc::clone(registry), "index", json!({}))
+//! }
+//!
+//! rocket::build()
+//!     .attach(Templates::new(Arc::new(registry)))
+//!     .mount("/", routes![index])
+//! ```
+use std::sync::Arc;
+
+use rocket::{
+    fairing::{self, Fairing, Info, Kind},
+    http::{ContentType, Status},
+    response::{self, Responder, Response},
+    Build, Request, Rocket,
+};
+
+use super::{Template, HTML};
+use crate::Registry;
+
+impl<'r> Responder<'r, 'static> for Template {
+    fn respond_to(self, req: &'r Request<'_>) -> response::Result<'static> {
+        let body = self.respond().ok_or(Status::InternalServerError)?;
+        let content_type = if self.content_type() == HTML {
+            ContentType::HTML
+        } else {
+            ContentType::Plain
+        };
+        Response::build_from(body.respond_to(req)?)
+            .header(content_type)
+            .ok()
+    }
+}
+
+/// Fairing that manages a shared registry.
+pub struct Templates {
+    registry: Arc<Registry<'static>>,
+}
+
+impl Templates {
+    /// Create a fairing for the registry.
+    pub fn new(registry: Arc<Registry<'static>>) -> Self {
+        Self { registry }
+    }
+}
+
+#[rocket::async_trait]
+impl Fairing for Templates {
+    fn info(&self) -> Info {
+        Info {
+            name: "Bracket Templates",
+            kind: Kind::Ignite,
+        }
+    }
+
+    async fn on_ignite(&self, rocket: Rocket<Build>) -> fairing::Result {
+        Ok(rocket.manage(Arc::clone(&self.registry)))
+    }
+}
@@ -0,0 +1,58 @@
+#![cfg(feature = "asset-helper")]
+use bracket::{asset::JsonManifest, Error, Registry, Result};
+use serde_json::json;
+
+const NAME: &str = "asset.rs";
+
+#[test]
+fn asset_json_manifest() -> Result<()> {
+    let mut manifest = JsonManifest::parse(
+        r#"{
+            "css/app.css": "css/app.3f2a9c.css",
+            "src/main.js": {"file": "assets/main.4b1e7d.js"}
+        }"#,
+    )?;
+    manifest.set_base("/static/");
+    let mut registry = Registry::new();
+    registry.set_manifest(Box::new(manifest));
+    let data = json!({"script": "src/main.js"});
+    let value = r#"{{asset "css/app.css"}} {{asset script}}"#;
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!(
+        "/static/css/app.3f2a9c.css /static/assets/main.4b1e7d.js",
+        &result
+    );
+    Ok(())
+}
+
+#[test]
+fn asset_callback() -> Result<()> {
+    let mut registry = Registry::new();
+    registry
+        .set_manifest(Box::new(|path: &str| Some(format!("/{}?v=2", path))));
+    let child = registry.child();
+    let result = child.once(NAME, r#"{{asset "app.css"}}"#, &json!({}))?;
+    assert_eq!("/app.css?v=2", &result);
+    Ok(())
+}
+
+#[test]
+fn asset_error() -> Result<()> {
+    let mut registry = Registry::new();
+    match registry.once(NAME, r#"{{asset "app.css"}}"#, &json!({})) {
+        Err(Error::Render(e)) => assert_eq!(
+            "Helper 'asset' requires an asset manifest",
+            e.to_string()
+        ),
+        _ => panic!("expected a render error"),
+    }
+    registry.set_manifest(Box::new(JsonManifest::new()));
+    match registry.once(NAME, r#"{{asset "app.css"}}"#, &json!({})) {
+        Err(Error::Render(e)) => assert_eq!(
+            "Helper 'asset' could not find 'app.css' in the asset manifest",
+            e.to_string()
+        ),
+        _ => panic!("expected a render error"),
+    }
+    Ok(())
+}
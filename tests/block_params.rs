@@ -0,0 +1,105 @@
+use bracket::{
+    error::{ErrorInfo, SourcePos, SyntaxError},
+    helper::*,
+    parser::ast::Node,
+    render::{Context, Render, Type},
+    Error, Registry, Result,
+};
+use serde_json::json;
+
+const NAME: &str = "block_params.rs";
+
+pub struct FetchHelper;
+
+impl Helper for FetchHelper {
+    fn call<'render, 'call>(
+        &self,
+        rc: &mut Render<'render>,
+        ctx: &Context<'call>,
+        _template: Option<&'render Node<'render>>,
+    ) -> HelperValue {
+        let key = ctx.try_get(0, &[Type::String])?.as_str().unwrap();
+        Ok(rc.evaluate(&format!("@root.store.{}", key))?.cloned())
+    }
+}
+
+fn registry<'reg>() -> Registry<'reg> {
+    let mut registry = Registry::new();
+    registry
+        .helpers_mut()
+        .insert("fetch", Box::new(FetchHelper {}));
+    registry
+}
+
+#[test]
+fn block_params_with() -> Result<()> {
+    let registry = registry();
+    let data = json!({"store": {"config": {"debug": true, "name": "app"}}});
+    let value = r#"{{#with (fetch "config") as |cfg|}}{{cfg.name}} {{name}} {{cfg.debug}}{{/with}}"#;
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!("app app true", &result);
+    Ok(())
+}
+
+#[test]
+fn block_params_each() -> Result<()> {
+    let registry = registry();
+    let data = json!({
+        "store": {"users": [{"name": "a"}, {"name": "b"}]},
+        "roles": {"admin": "x"}
+    });
+    let value = r#"{{#each (fetch "users") as |user index|}}{{index}}={{user.name}};{{/each}}{{#each roles as | role key |}}{{key}}:{{role}}{{/each}}"#;
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!("0=a;1=b;admin:x", &result);
+    Ok(())
+}
+
+#[test]
+fn block_params_nested() -> Result<()> {
+    let registry = registry();
+    let data = json!({"rows": [{"id": 1, "cells": ["a", "b"]}]});
+    let value = r#"{{#each rows as |row|}}{{#each row.cells as |cell|}}{{row.id}}{{cell}}{{/each}}{{/each}}"#;
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!("1a1b", &result);
+    Ok(())
+}
+
+#[test]
+fn block_params_parse() -> Result<()> {
+    let registry = registry();
+    let template = registry
+        .parse(NAME, r#"{{#each items key="id" as |item i|}}{{/each}}"#)?;
+    match template.node() {
+        Node::Document(doc) => match doc.nodes().first() {
+            Some(Node::Block(block)) => {
+                assert_eq!(&["item", "i"], block.call().block_params());
+                assert_eq!(1, block.call().arguments().len());
+                assert_eq!(1, block.call().parameters().len());
+            }
+            _ => panic!("expected a block"),
+        },
+        _ => panic!("expected a document"),
+    }
+
+    // Without pipes `as` is a path
+    let data = json!({"as": [1]});
+    let result =
+        registry.once(NAME, r#"{{#each as}}{{this}}{{/each}}"#, &data)?;
+    assert_eq!("1", &result);
+    Ok(())
+}
+
+#[test]
+fn block_params_error() -> Result<()> {
+    let registry = registry();
+    let value = r"{{#each items as |item.name|}}{{/each}}";
+    match registry.parse(NAME, value) {
+        Ok(_) => panic!("block params error expected"),
+        Err(e) => {
+            let pos = SourcePos(0, 22);
+            let info = ErrorInfo::new(value, NAME, pos, vec![]);
+            assert_eq!(Error::Syntax(SyntaxError::BlockParams(info.into())), e);
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,121 @@
+use bracket::{
+    builder::{Argument, CallBuilder, DocumentBuilder},
+    parser::ast::{Element, Lines, Node, Slice},
+    Registry, Result,
+};
+use serde_json::json;
+
+const NAME: &str = "builder.rs";
+
+#[test]
+fn builder_render() -> Result<()> {
+    let mut registry = Registry::new();
+    let mut doc = DocumentBuilder::new();
+    doc.text("<ul>{{\n")
+        .block(CallBuilder::new("each").arg(Argument::path("items")), |b| {
+            b.text("<li>")
+                .statement(CallBuilder::new("this"))
+                .text("</li>\n");
+        })
+        .block_else(
+            CallBuilder::new("if").arg(Argument::path("admin")),
+            |b| {
+                b.unescaped(CallBuilder::new("html"));
+            },
+            |b| {
+                b.statement(CallBuilder::new("html"));
+            },
+        )
+        .text("</ul>");
+    registry
+        .templates_mut()
+        .insert(NAME.to_string(), doc.build()?);
+    let data = json!({"items": ["a", "b"], "html": "<b>", "admin": false});
+    let result = registry.render(NAME, &data)?;
+    assert_eq!("<ul>{{\n<li>a</li>\n<li>b</li>\n&lt;b&gt;</ul>", &result);
+    Ok(())
+}
+
+#[test]
+fn builder_arguments() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.insert("card", "{{title}}: {{json value}}")?;
+    let mut doc = DocumentBuilder::new();
+    doc.unescaped(
+        CallBuilder::new("json")
+            .arg(json!({"path": "a\\b", "list": [1, "\"x\"\n"]})),
+    )
+    .text("|")
+    .statement(
+        CallBuilder::new("lookup")
+            .arg(Argument::path("names"))
+            .arg(Argument::value("1")),
+    )
+    .text("|")
+    .partial(
+        CallBuilder::new("card")
+            .hash(
+                "title",
+                CallBuilder::new("lookup")
+                    .arg(Argument::path("this"))
+                    .arg(json!("x")),
+            )
+            .hash("value", json!([true])),
+    );
+    let template = doc.build()?;
+    registry.templates_mut().insert(NAME.to_string(), template);
+    let data = json!({"names": ["a", "b"], "x": "X"});
+    let result = registry.render(NAME, &data)?;
+    assert_eq!(
+        r#"{"list":[1,"\"x\"\n"],"path":"a\\b"}|b|X: [true]"#,
+        &result
+    );
+    Ok(())
+}
+
+#[test]
+fn builder_block_span() -> Result<()> {
+    let mut registry = Registry::new();
+    let mut doc = DocumentBuilder::new();
+    doc.text("<p>").block_else(
+        CallBuilder::new("if").arg(Argument::path("admin")),
+        |b| {
+            b.text("\n").statement(CallBuilder::new("name")).text("\n");
+        },
+        |b| {
+            b.text("-");
+        },
+    );
+    let template = doc.build()?;
+    let block = template
+        .node()
+        .into_iter()
+        .find_map(|node| match node {
+            Node::Block(block) => Some(block),
+            _ => None,
+        })
+        .unwrap();
+    assert_eq!(3..42, block.span());
+    assert_eq!("{{#if admin}}\n{{name}}\n{{else}}-{{/if}}", block.as_str());
+    assert_eq!(0..3, block.lines().clone());
+    registry.templates_mut().insert(NAME.to_string(), template);
+    let result = registry.render(NAME, &json!({"admin": true, "name": "a"}))?;
+    assert_eq!("<p>\na\n", &result);
+    let result = registry.render(NAME, &json!({"admin": false}))?;
+    assert_eq!("<p>-", &result);
+    Ok(())
+}
+
+#[test]
+fn builder_error() -> Result<()> {
+    let mut doc = DocumentBuilder::new();
+    doc.statement(CallBuilder::new("a}}{{b"));
+    match doc.build() {
+        Err(e) => assert_eq!(
+            "Syntax error, call 'a}}{{b' must produce a single tag",
+            e.to_string()
+        ),
+        Ok(_) => panic!("expected a syntax error"),
+    }
+    Ok(())
+}
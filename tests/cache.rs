@@ -0,0 +1,190 @@
+use bracket::{cache::PartialCache, Registry, Result};
+use serde_json::json;
+
+const NAME: &str = "cache.rs";
+
+fn registry(capacity: usize) -> Result<Registry<'static>> {
+    let mut registry = Registry::new();
+    registry.insert("card", "<b>{{name}}</b>")?;
+    let mut cache = PartialCache::new(capacity);
+    cache.allow_partials(&["card"]);
+    registry.set_partial_cache(cache);
+    Ok(registry)
+}
+
+#[test]
+fn cache_partial_hit() -> Result<()> {
+    let registry = registry(8)?;
+    let value = json!({"items": [{"name": "a"}, {"name": "b"}, {"name": "a"}]});
+    let result = registry.once(
+        NAME,
+        r"{{#each items}}{{> card this isolated=true}}{{/each}}",
+        &value,
+    )?;
+    assert_eq!("<b>a</b><b>b</b><b>a</b>", &result);
+    let cache = registry.partial_cache().unwrap();
+    assert_eq!(1, cache.hits());
+    assert_eq!(2, cache.misses());
+    assert_eq!(2, cache.len());
+    Ok(())
+}
+
+#[test]
+fn cache_partial_across_renders() -> Result<()> {
+    let registry = registry(8)?;
+    let value = json!({"user": {"name": "<a>"}});
+    let template = r"{{> card user isolated=true}}";
+    let first = registry.once(NAME, template, &value)?;
+    let second = registry.once(NAME, template, &value)?;
+    assert_eq!("<b>&lt;a&gt;</b>", &first);
+    assert_eq!(first, second);
+    let cache = registry.partial_cache().unwrap();
+    assert_eq!(1, cache.hits());
+    cache.clear();
+    assert!(cache.is_empty());
+    assert_eq!(0, cache.hits());
+    Ok(())
+}
+
+#[test]
+fn cache_partial_hash() -> Result<()> {
+    let registry = registry(8)?;
+    let value = json!({});
+    let result = registry.once(
+        NAME,
+        r#"{{> card name="a" isolated=true}}{{> card name="b" isolated=true}}{{> card name="a" isolated=true}}"#,
+        &value,
+    )?;
+    assert_eq!("<b>a</b><b>b</b><b>a</b>", &result);
+    assert_eq!(1, registry.partial_cache().unwrap().hits());
+    Ok(())
+}
+
+#[test]
+fn cache_partial_evict() -> Result<()> {
+    let registry = registry(2)?;
+    let value =
+        json!({"a": {"name": "a"}, "b": {"name": "b"}, "c": {"name": "c"}});
+    // Using a keeps it recent so b is evicted when c is inserted
+    let template = ["a", "b", "a", "c", "a", "b"]
+        .iter()
+        .map(|key| format!("{{{{> card {} isolated=true}}}}", key))
+        .collect::<String>();
+    let result = registry.once(NAME, &template, &value)?;
+    assert_eq!("<b>a</b><b>b</b><b>a</b><b>c</b><b>a</b><b>b</b>", &result);
+    let cache = registry.partial_cache().unwrap();
+    assert_eq!(2, cache.capacity());
+    assert_eq!(2, cache.len());
+    assert_eq!(2, cache.hits());
+    assert_eq!(4, cache.misses());
+    Ok(())
+}
+
+#[test]
+fn cache_partial_nested() -> Result<()> {
+    let mut registry = registry(8)?;
+    registry.insert(
+        "list",
+        "<ul>{{#each items}}{{> card this isolated=true}}{{/each}}</ul>",
+    )?;
+    let mut cache = PartialCache::new(8);
+    cache.allow_partials(&["card", "list"]);
+    registry.set_partial_cache(cache);
+    let value = json!({"items": [{"name": "a"}, {"name": "a"}]});
+    let first =
+        registry.once(NAME, r"{{> list this isolated=true}}", &value)?;
+    let second =
+        registry.once(NAME, r"{{> list this isolated=true}}", &value)?;
+    assert_eq!("<ul><b>a</b><b>a</b></ul>", &first);
+    assert_eq!(first, second);
+    let cache = registry.partial_cache().unwrap();
+    assert_eq!(2, cache.hits());
+    assert_eq!(2, cache.misses());
+    Ok(())
+}
+
+#[test]
+fn cache_partial_not_allowed() -> Result<()> {
+    let mut registry = registry(8)?;
+    registry.insert("other", "{{name}}")?;
+    let value = json!({"name": "a"});
+    let result =
+        registry.once(NAME, r"{{> other this}}{{> other this}}", &value)?;
+    assert_eq!("aa", &result);
+    let cache = registry.partial_cache().unwrap();
+    assert!(!cache.is_cached("other"));
+    assert!(cache.is_empty());
+    assert_eq!(0, cache.misses());
+    Ok(())
+}
+
+#[test]
+fn cache_partial_parent() -> Result<()> {
+    let mut registry = registry(8)?;
+    registry.insert("card", "<b>{{name}}{{../suffix}}</b>")?;
+    let value = r"{{> card user}}";
+    let first = registry.once(
+        NAME,
+        value,
+        &json!({"user": {"name": "a"}, "suffix": "!"}),
+    )?;
+    let second = registry.once(
+        NAME,
+        value,
+        &json!({"user": {"name": "a"}, "suffix": "?"}),
+    )?;
+    assert_eq!("<b>a!</b>", &first);
+    assert_eq!("<b>a?</b>", &second);
+    let cache = registry.partial_cache().unwrap();
+    assert!(cache.is_empty());
+    assert_eq!(0, cache.misses());
+    Ok(())
+}
+
+#[cfg(feature = "csp-helper")]
+#[test]
+fn cache_partial_nonce() -> Result<()> {
+    let mut registry = registry(8)?;
+    registry.insert("b", r#"<script nonce="{{csp_nonce}}">"#)?;
+    let mut cache = PartialCache::new(8);
+    cache.allow_partials(&["b"]);
+    registry.set_partial_cache(cache);
+    registry.insert(NAME, r"{{> b this isolated=true}}")?;
+    let value = json!({});
+    for nonce in ["n1", "n2"] {
+        let mut locals = serde_json::Map::new();
+        locals.insert("nonce".to_string(), json!(nonce));
+        let result = registry.render_with_locals(NAME, &value, &locals)?;
+        assert_eq!(format!(r#"<script nonce="{}">"#, nonce), result);
+    }
+    let cache = registry.partial_cache().unwrap();
+    assert!(cache.is_empty());
+    assert_eq!(0, cache.misses());
+    Ok(())
+}
+
+#[test]
+fn cache_partial_root() -> Result<()> {
+    let mut registry = registry(8)?;
+    registry.insert("card", "[{{name}} @ {{site}}]")?;
+    registry.insert("page", r"{{> card item}}")?;
+    let first = registry
+        .render("page", &json!({"site": "A", "item": {"name": "x"}}))?;
+    let second = registry
+        .render("page", &json!({"site": "B", "item": {"name": "x"}}))?;
+    assert_eq!("[x @ A]", &first);
+    assert_eq!("[x @ B]", &second);
+    let cache = registry.partial_cache().unwrap();
+    assert!(cache.is_empty());
+    assert_eq!(0, cache.misses());
+
+    registry.insert("page", r"{{> card item isolated=true}}")?;
+    let first = registry
+        .render("page", &json!({"site": "A", "item": {"name": "x"}}))?;
+    let second = registry
+        .render("page", &json!({"site": "B", "item": {"name": "x"}}))?;
+    assert_eq!("[x @ ]", &first);
+    assert_eq!(first, second);
+    assert_eq!(1, registry.partial_cache().unwrap().hits());
+    Ok(())
+}
@@ -0,0 +1,50 @@
+#![cfg(feature = "call-helper")]
+use bracket::{Error, Registry, Result};
+use serde_json::json;
+
+const NAME: &str = "call.rs";
+
+#[test]
+fn call_allowed_helper() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.allow_call("json");
+    registry.allow_call("with");
+    let data = json!({
+        "widget": {"helper": "json", "value": {"a": 1}},
+        "block": "with"
+    });
+    let value = r#"{{call widget.helper widget.value}}|{{#call block widget.value}}{{a}}{{/call}}"#;
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!(r#"{&quot;a&quot;:1}|1"#, &result);
+
+    let child = registry.child();
+    let result = child.once(NAME, r#"{{call "json" 2}}"#, &data)?;
+    assert_eq!("2", &result);
+    Ok(())
+}
+
+#[test]
+fn call_not_allowed() -> Result<()> {
+    let registry = Registry::new();
+    match registry.once(NAME, r#"{{call "json" 1}}"#, &json!({})) {
+        Err(Error::Render(e)) => assert_eq!(
+            "Helper 'call' is not allowed to call 'json'",
+            e.to_string()
+        ),
+        _ => panic!("expected a render error"),
+    }
+    Ok(())
+}
+
+#[test]
+fn call_missing_helper() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.allow_call("missing");
+    match registry.once(NAME, r#"{{call "missing"}}"#, &json!({})) {
+        Err(Error::Render(e)) => {
+            assert_eq!("Helper 'missing' does not exist", e.to_string())
+        }
+        _ => panic!("expected a render error"),
+    }
+    Ok(())
+}
@@ -0,0 +1,89 @@
+use bracket::{escape, helper::prelude::*, Registry, Result};
+use serde_json::{json, Value};
+
+#[derive(Clone)]
+struct TenantHelper;
+impl Helper for TenantHelper {
+    fn call<'render, 'call>(
+        &self,
+        _rc: &mut Render<'render>,
+        _ctx: &Context<'call>,
+        _template: Option<&'render Node<'render>>,
+    ) -> HelperValue {
+        Ok(Some(Value::String("tenant".to_string())))
+    }
+}
+
+#[test]
+fn child_templates() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.insert("page", "{{> header}}|{{> footer}}")?;
+    registry.insert("header", "Header")?;
+    registry.insert("footer", "Footer")?;
+
+    let mut child = registry.child();
+    child.insert("footer", "Acme")?;
+    assert!(child.parent().is_some());
+    assert!(child.get("header").is_some());
+    assert_eq!("Header|Acme", child.render("page", &json!({}))?);
+    assert_eq!("Header|Footer", registry.render("page", &json!({}))?);
+    Ok(())
+}
+
+#[test]
+fn child_helpers() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.insert("page", "{{#if true}}{{name}}{{/if}}")?;
+    let mut child = registry.child();
+    child
+        .helpers_mut()
+        .insert("name", Box::new(TenantHelper {}));
+    assert!(child.helper("if").is_some());
+    assert_eq!("tenant", child.render("page", &json!({"name": "root"}))?);
+    assert_eq!("root", registry.render("page", &json!({"name": "root"}))?);
+    Ok(())
+}
+
+#[test]
+fn child_helper_alias() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.insert("page", "{{j x}}")?;
+    let mut child = registry.child();
+    child.alias_helper("j", "json");
+    assert!(child.helper("j").is_some());
+    assert_eq!("[1]", child.render("page", &json!({"x": [1]}))?);
+    Ok(())
+}
+
+#[test]
+fn child_escape() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.insert("page", "{{value}}")?;
+    let data = json!({"value": "<b>"});
+
+    let child = registry.child();
+    assert_eq!("&lt;b&gt;", child.render("page", &data)?);
+
+    let mut child = registry.child();
+    child.set_escape_write(Box::new(escape::noop_write));
+    assert_eq!("<b>", child.render("page", &data)?);
+    assert_eq!("&lt;b&gt;", registry.render("page", &data)?);
+    Ok(())
+}
+
+#[test]
+fn child_nested() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.insert("page", "{{> a}}{{> b}}{{> c}}")?;
+    registry.insert("a", "1")?;
+    registry.insert("b", "1")?;
+    registry.insert("c", "1")?;
+    let mut child = registry.child();
+    child.insert("b", "2")?;
+    let mut grandchild = child.child();
+    grandchild.insert("c", "3")?;
+    assert_eq!("123", grandchild.render("page", &json!({}))?);
+    let graph = grandchild.dependencies("page")?;
+    assert_eq!(0, graph.missing().count());
+    Ok(())
+}
@@ -0,0 +1,29 @@
+#![cfg(feature = "chunk-helper")]
+use bracket::{Error, Registry, Result};
+use serde_json::json;
+
+const NAME: &str = "chunk.rs";
+
+#[test]
+fn chunk_rows() -> Result<()> {
+    let registry = Registry::new();
+    let data = json!({"items": [1, 2, 3, 4, 5, 6, 7]});
+    let value = r#"{{#chunk items size=3}}{{@row}}{{#if @isLastRow}}!{{/if}}[{{#each this}}{{this}}{{/each}}]{{/chunk}}"#;
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!("0[123]1[456]2![7]", &result);
+    Ok(())
+}
+
+#[test]
+fn chunk_size_error() -> Result<()> {
+    let registry = Registry::new();
+    let data = json!({"items": [1]});
+    match registry.once(NAME, r#"{{#chunk items size=0}}{{/chunk}}"#, &data) {
+        Err(Error::Render(e)) => assert_eq!(
+            "Helper 'chunk' size must be a positive integer",
+            e.to_string()
+        ),
+        _ => panic!("expected a render error"),
+    }
+    Ok(())
+}
@@ -93,3 +93,72 @@ fn if_not_block() -> Result<()> {
     assert_eq!("bar", &result);
     Ok(())
 }
+
+#[test]
+fn if_else_if_chain() -> Result<()> {
+    let registry = Registry::new();
+    let value = r"{{#if (eq n 1)}}one{{else if (eq n 2)}}two{{else if (eq n 3)}}three{{else}}many{{/if}}";
+    let result = registry.once(NAME, value, &json!({"n": 1}))?;
+    assert_eq!("one", &result);
+    let result = registry.once(NAME, value, &json!({"n": 2}))?;
+    assert_eq!("two", &result);
+    let result = registry.once(NAME, value, &json!({"n": 3}))?;
+    assert_eq!("three", &result);
+    let result = registry.once(NAME, value, &json!({"n": 4}))?;
+    assert_eq!("many", &result);
+    Ok(())
+}
+
+#[test]
+fn if_else_if_first_truthy() -> Result<()> {
+    let registry = Registry::new();
+    let value = r"{{#if a}}a{{else if b}}b{{else if c}}c{{/if}}";
+    let data = json!({"a": false, "b": true, "c": true});
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!("b", &result);
+    let data = json!({"a": false, "b": false, "c": false});
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!("", &result);
+    Ok(())
+}
+
+#[test]
+fn if_else_if_chain_display() -> Result<()> {
+    let registry = Registry::new();
+    let value = r"{{#if a}}a{{else if b}}b{{else if c}}c{{else}}d{{/if}}";
+    let template = registry.parse(NAME, value)?;
+    assert_eq!(value, template.to_string());
+    Ok(())
+}
+
+#[test]
+fn inverted_section() -> Result<()> {
+    let registry = Registry::new();
+    let value = r"{{^user}}guest{{/user}}";
+    let result = registry.once(NAME, value, &json!({"user": null}))?;
+    assert_eq!("guest", &result);
+    let result = registry.once(NAME, value, &json!({"user": {}}))?;
+    assert_eq!("", &result);
+    let result = registry.once(NAME, value, &json!({}))?;
+    assert_eq!("guest", &result);
+    Ok(())
+}
+
+#[test]
+fn inverted_section_else() -> Result<()> {
+    let registry = Registry::new();
+    let value = r"{{~ ^flag ~}} no {{~else~}} {{foo}} {{~/flag}}";
+    let data = json!({"flag": true, "foo": "bar"});
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!("bar", &result);
+    Ok(())
+}
+
+#[test]
+fn inverted_section_display() -> Result<()> {
+    let registry = Registry::new();
+    let value = r"{{^items}}empty{{/items}}";
+    let template = registry.parse(NAME, value)?;
+    assert_eq!(value, template.to_string());
+    Ok(())
+}
@@ -0,0 +1,108 @@
+use bracket::{
+    contract::{Contract, Violation},
+    error::{RenderError, SyntaxError},
+    render::Type,
+    Error, Registry, Result,
+};
+use serde_json::json;
+
+const NAME: &str = "contract.rs";
+
+fn button() -> Contract {
+    Contract::new()
+        .required("label", &[Type::String])
+        .optional("kind", &[Type::String])
+        .optional("data", &[])
+}
+
+#[test]
+fn contract_check() -> Result<()> {
+    let contract = button();
+    let label = json!("OK");
+    let kind = json!(1);
+    let violations = contract.check(vec![
+        ("kind", Some(&kind)),
+        ("lable", Some(&label)),
+        ("isolated", None),
+    ]);
+    assert_eq!(
+        vec![
+            Violation::Type(
+                "kind".to_string(),
+                "string".to_string(),
+                Type::Number
+            ),
+            Violation::Unknown("lable".to_string()),
+            Violation::Missing("label".to_string()),
+        ],
+        violations
+    );
+    Ok(())
+}
+
+#[test]
+fn contract_lint() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.insert("button", "{{label}}")?;
+    registry.set_contract("button", button());
+    let value =
+        r#"{{> button label="OK" data=items}}{{> button lable="OK" kind=1}}"#;
+    let warnings = registry.lint(NAME, value)?;
+    let messages: Vec<String> =
+        warnings.iter().map(|e| e.to_string()).collect();
+    assert_eq!(
+        vec![
+            "Partial 'button' contract violation, unknown parameter 'lable'",
+            "Partial 'button' contract violation, parameter 'kind' expects string, got number",
+            "Partial 'button' contract violation, missing required parameter 'label'",
+        ],
+        messages
+    );
+    Ok(())
+}
+
+#[test]
+fn contract_builder() -> Result<()> {
+    let result = Registry::builder()
+        .template("page", r#"{{> button kind="primary"}}"#)
+        .template("button", "{{label}}")
+        .contract("button", button())
+        .build();
+    match result {
+        Err(errors) => match errors.first() {
+            Some(Error::Syntax(SyntaxError::PartialContract(name, _, _))) => {
+                assert_eq!("button", name);
+            }
+            _ => panic!("Expected contract violation"),
+        },
+        Ok(_) => panic!("Expected contract violation"),
+    }
+    Ok(())
+}
+
+#[test]
+fn contract_strict() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.insert("button", "{{label}}")?;
+    registry.set_contract("button", button());
+    let value = r#"{{> button label=title}}"#;
+    let result = registry.once(NAME, value, &json!({"title": "OK"}))?;
+    assert_eq!("OK", &result);
+
+    // Not checked without strict mode
+    let result = registry.once(NAME, value, &json!({"title": 1}))?;
+    assert_eq!("1", &result);
+
+    registry.set_strict(true);
+    match registry.once(NAME, value, &json!({"title": 1})) {
+        Err(Error::Render(RenderError::PartialContract(name, message, _))) => {
+            assert_eq!("button", &name);
+            assert_eq!(
+                "parameter 'label' expects string, got number",
+                &message
+            );
+        }
+        _ => panic!("Expected contract violation"),
+    }
+    Ok(())
+}
@@ -0,0 +1,41 @@
+#![cfg(feature = "csp-helper")]
+use bracket::{Error, Registry, Result};
+use serde_json::{json, Map};
+
+const NAME: &str = "csp.rs";
+
+#[test]
+fn csp_nonce() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.insert(
+        NAME,
+        r#"{{#each items}}<script nonce="{{csp_nonce}}">{{@nonce}}</script>{{/each}}"#,
+    )?;
+    let mut locals = Map::new();
+    locals.insert("nonce".to_string(), json!("r4nd0m"));
+    let data = json!({"items": [1, 2]});
+    let result = registry.render_with_locals(NAME, &data, &locals)?;
+    assert_eq!(
+        r#"<script nonce="r4nd0m">r4nd0m</script><script nonce="r4nd0m">r4nd0m</script>"#,
+        &result
+    );
+    Ok(())
+}
+
+#[test]
+fn csp_nonce_missing() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.insert(NAME, r#"<script nonce="{{csp_nonce}}">"#)?;
+    let result = registry.render(NAME, &json!({}))?;
+    assert_eq!(r#"<script nonce="">"#, &result);
+
+    registry.set_strict(true);
+    match registry.render(NAME, &json!({})) {
+        Err(Error::Render(e)) => assert_eq!(
+            "Helper 'csp_nonce' requires the 'nonce' render local",
+            e.to_string()
+        ),
+        _ => panic!("expected a render error"),
+    }
+    Ok(())
+}
@@ -46,3 +46,26 @@ fn defaults_block_strict() -> Result<()> {
     }
     Ok(())
 }
+
+#[cfg(not(feature = "extra-helpers"))]
+#[test]
+fn defaults_data_not_shadowed() -> Result<()> {
+    let registry = Registry::new();
+    let value = r"{{duration}} {{range}} {{form}} {{helpers}}";
+    let data =
+        json!({"duration": 5, "range": "a-z", "form": "f", "helpers": 2});
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!("5 a-z f 2", &result);
+    Ok(())
+}
+
+#[cfg(not(feature = "extra-helpers"))]
+#[test]
+fn defaults_random_not_shadowed() -> Result<()> {
+    let registry = Registry::new();
+    let value = r"{{uuid}} {{random}} {{now}}";
+    let data = json!({"uuid": "u", "random": 4, "now": "today"});
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!("u 4 today", &result);
+    Ok(())
+}
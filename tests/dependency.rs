@@ -0,0 +1,81 @@
+use bracket::{Error, Registry, Result};
+
+#[test]
+fn dependency_partials() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.insert("header", "{{> logo}}")?;
+    registry.insert("logo", "<img>")?;
+    registry.insert("footer", "footer")?;
+    registry.insert("layout", "{{> @partial-block}}")?;
+    registry.insert(
+        "index",
+        "{{> header}}{{#> layout}}{{#if x}}{{> footer}}{{/if}}{{/layout}}{{> header}}",
+    )?;
+    let graph = registry.dependencies("index")?;
+    assert_eq!("index", graph.root());
+    assert_eq!(
+        Some(
+            &[
+                "header".to_string(),
+                "layout".to_string(),
+                "footer".to_string()
+            ][..]
+        ),
+        graph.get("index")
+    );
+    assert_eq!(Some(&["logo".to_string()][..]), graph.get("header"));
+    assert!(graph.contains("logo"));
+    assert!(!graph.contains("missing"));
+    assert_eq!(
+        vec!["footer", "header", "index", "layout", "logo"],
+        graph.names().collect::<Vec<_>>()
+    );
+    Ok(())
+}
+
+#[test]
+fn dependency_order() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.insert("a", "{{> b}}{{> c}}")?;
+    registry.insert("b", "{{> c}}")?;
+    registry.insert("c", "c")?;
+    let graph = registry.dependencies("a")?;
+    assert!(!graph.is_cyclic());
+    assert_eq!(Some(vec!["c", "b", "a"]), graph.order());
+    Ok(())
+}
+
+#[test]
+fn dependency_cycle() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.insert("a", "{{> b}}")?;
+    registry.insert("b", "{{#if x}}{{> c}}{{/if}}")?;
+    registry.insert("c", "{{> b}}")?;
+    let graph = registry.dependencies("a")?;
+    assert!(graph.is_cyclic());
+    assert_eq!(Some(vec!["b", "c", "b"]), graph.cycle());
+    assert_eq!(None, graph.order());
+    Ok(())
+}
+
+#[test]
+fn dependency_missing() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.insert("page", "{{> missing}}{{> (dynamic)}}")?;
+    let graph = registry.dependencies("page")?;
+    assert_eq!(vec!["missing"], graph.missing().collect::<Vec<_>>());
+    assert_eq!(Some(vec!["page"]), graph.order());
+    Ok(())
+}
+
+#[test]
+fn dependency_template_not_found() -> Result<()> {
+    let registry = Registry::new();
+    match registry.dependencies("missing") {
+        Err(e) => {
+            assert_eq!(Error::TemplateNotFound("missing".to_string()), e)
+        }
+        Ok(_) => panic!("Template not found error expected"),
+    }
+    Ok(())
+}
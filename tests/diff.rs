@@ -0,0 +1,66 @@
+use bracket::{diff::Change, Registry, Result};
+
+const NAME: &str = "diff.rs";
+
+#[test]
+fn diff_whitespace() -> Result<()> {
+    let registry = Registry::new();
+    let before = registry
+        .parse(NAME, "<p>\n  {{ title }}\n</p>{{#if  a}}{{> foo}}{{/if}}")?;
+    let after = registry
+        .parse(NAME, "<p>{{title}}</p>\n{{#if a}}\n  {{>foo}}\n{{/if}}")?;
+    assert!(bracket::diff(&before, &after).is_empty());
+    Ok(())
+}
+
+#[test]
+fn diff_string_literal() -> Result<()> {
+    let registry = Registry::new();
+    let before = registry.parse(NAME, r#"{{join list "a  b"}}"#)?;
+    let after = registry.parse(NAME, r#"{{join list "a b"}}"#)?;
+    let changes = bracket::diff(&before, &after);
+    assert_eq!(1, changes.len());
+    Ok(())
+}
+
+#[test]
+fn diff_changes() -> Result<()> {
+    let registry = Registry::new();
+    let before =
+        registry.parse(NAME, "{{title}}\n{{#if a}}{{foo}}{{/if}}\n{{bar}}")?;
+    let after = registry
+        .parse(NAME, "{{title}}\n{{#if b}}{{foo}}{{/if}}\n{{qux}}\n{{baz}}")?;
+    let changes = bracket::diff(&before, &after);
+    let lines: Vec<String> = changes.iter().map(|c| c.to_string()).collect();
+    assert_eq!(
+        vec![
+            "~ 2:2: {{#if a}} -> {{#if b}}",
+            "- 3: {{bar}}",
+            "+ 3: {{qux}}",
+            "+ 4: {{baz}}",
+        ],
+        lines
+    );
+    match changes.first() {
+        Some(Change::Changed(before, after)) => {
+            assert_eq!("block", before.kind);
+            assert_eq!(10, before.span.start);
+            assert_eq!(10, after.span.start);
+        }
+        _ => panic!("Expected changed block"),
+    }
+    Ok(())
+}
+
+#[test]
+fn diff_text() -> Result<()> {
+    let registry = Registry::new();
+    let before = registry.parse(NAME, "Hello {{name}}")?;
+    let after = registry.parse(NAME, "Hi {{name}}")?;
+    let lines: Vec<String> = bracket::diff(&before, &after)
+        .iter()
+        .map(|c| c.to_string())
+        .collect();
+    assert_eq!(vec!["~ 1:1: Hello  -> Hi "], lines);
+    Ok(())
+}
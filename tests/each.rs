@@ -13,6 +13,16 @@ fn each_array() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn each_array_dot() -> Result<()> {
+    let registry = Registry::new();
+    let value = r"{{#each foo}}{{~ . ~}} {{#if .}}!{{/if}}{{/each}}";
+    let data = json!({"foo": [1, 0, 2]});
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!("1!02!", &result);
+    Ok(())
+}
+
 #[test]
 fn each_array_index() -> Result<()> {
     let registry = Registry::new();
@@ -0,0 +1,107 @@
+use bracket::{
+    error::EditError,
+    parser::{ast::*, edit::Rewriter, Parser},
+    Result,
+};
+
+fn statements<'a>(node: &'a Node<'a>) -> Vec<&'a Call<'a>> {
+    node.descendants()
+        .filter_map(|n| match n {
+            Node::Statement(ref call) => Some(call),
+            _ => None,
+        })
+        .collect()
+}
+
+#[test]
+fn edit_rename_call() -> Result<()> {
+    let value = "{{fmt this}} {{fmt name}} {{other}}";
+    let mut parser = Parser::new(value, Default::default());
+    let node = parser.parse()?;
+    let mut rewriter = Rewriter::new(value);
+    for call in statements(&node) {
+        if call.target().as_str() == "fmt" {
+            rewriter.rename_call(call, "format").unwrap();
+        }
+    }
+    assert_eq!(2, rewriter.len());
+    assert_eq!(
+        "{{format this}} {{format name}} {{other}}",
+        rewriter.finish()
+    );
+    Ok(())
+}
+
+#[test]
+fn edit_rename_block() -> Result<()> {
+    let value = "{{#each items}}{{this}}{{~/ each ~}}";
+    let mut parser = Parser::new(value, Default::default());
+    let node = parser.parse()?;
+    let mut rewriter = Rewriter::new(value);
+    for node in node.descendants() {
+        if let Node::Block(ref block) = node {
+            rewriter.rename_block(block, "loop").unwrap();
+        }
+    }
+    assert_eq!("{{#loop items}}{{this}}{{~/ loop ~}}", rewriter.finish());
+    Ok(())
+}
+
+#[test]
+fn edit_add_parameter() -> Result<()> {
+    let value = r#"{{a}} {{b x ~}} {{c y z="1"}} {{c lang="de"}}"#;
+    let mut parser = Parser::new(value, Default::default());
+    let node = parser.parse()?;
+    let mut rewriter = Rewriter::new(value);
+    for call in statements(&node) {
+        rewriter.add_parameter(call, "lang", r#""en""#).unwrap();
+    }
+    assert_eq!(
+        r#"{{a lang="en"}} {{b x lang="en" ~}} {{c y z="1" lang="en"}} {{c lang="en"}}"#,
+        rewriter.finish()
+    );
+    Ok(())
+}
+
+#[test]
+fn edit_insert_order() {
+    let value = "{{foo}}";
+    let mut rewriter = Rewriter::new(value);
+    rewriter.replace(2..5, "bar").unwrap();
+    rewriter.insert(2, "[").unwrap();
+    rewriter.insert(2, "(").unwrap();
+    rewriter.insert(0, "<").unwrap();
+    rewriter.remove(5..7).unwrap();
+    assert_eq!("<{{[(bar", rewriter.finish());
+}
+
+#[test]
+fn edit_errors() {
+    let value = "{{föo}}";
+    let mut rewriter = Rewriter::new(value);
+    assert_eq!(Err(EditError::OutOfBounds(6..12)), rewriter.remove(6..12));
+    assert_eq!(Err(EditError::CharBoundary(3..4)), rewriter.remove(3..4));
+    rewriter.replace(2..6, "bar").unwrap();
+    assert_eq!(
+        Err(EditError::Overlap(5..7, 2..6)),
+        rewriter.replace(5..7, "x")
+    );
+    assert_eq!(Err(EditError::Overlap(3..3, 2..6)), rewriter.insert(3, "x"));
+    rewriter.insert(6, "x").unwrap();
+    assert_eq!("{{barx}}", rewriter.finish());
+}
+
+#[test]
+fn edit_rename_sub_expr() -> Result<()> {
+    let value = "{{> (name)}}";
+    let mut parser = Parser::new(value, Default::default());
+    let node = parser.parse()?;
+    let mut rewriter = Rewriter::new(value);
+    let calls = statements(&node);
+    assert_eq!(
+        Err(EditError::Rename(String::from("(name)"))),
+        rewriter.rename_call(calls[0], "other")
+    );
+    assert!(rewriter.is_empty());
+    Ok(())
+}
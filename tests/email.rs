@@ -0,0 +1,57 @@
+use bracket::{cache::PartialCache, email::Email, Error, Registry, Result};
+use serde_json::json;
+
+#[test]
+fn email_render() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.insert("welcome.html", "<p>Hello {{name}}</p>")?;
+    registry.insert("welcome.txt", "Hello {{name}}")?;
+    let data = json!({"name": "<Jo & Sam>"});
+    let email = registry.render_email("welcome", &data)?;
+    assert_eq!(
+        Email {
+            html: "<p>Hello &lt;Jo &amp; Sam&gt;</p>".to_string(),
+            text: "Hello <Jo & Sam>".to_string(),
+        },
+        email
+    );
+    Ok(())
+}
+
+#[test]
+fn email_partial_escape() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.insert("footer", "{{company}}")?;
+    registry.insert("welcome.html", "{{> footer}}")?;
+    registry.insert("welcome.txt", "{{> footer}}")?;
+    let data = json!({"company": "Smith & Co"});
+    let email = registry.render_email("welcome", &data)?;
+    assert_eq!("Smith &amp; Co", &email.html);
+    assert_eq!("Smith & Co", &email.text);
+    Ok(())
+}
+
+#[test]
+fn email_missing_text() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.insert("welcome.html", "<p>Hello</p>")?;
+    let result = registry.render_email("welcome", &json!({}));
+    assert!(matches!(result, Err(Error::TemplateNotFound(_))));
+    Ok(())
+}
+
+#[test]
+fn email_partial_cache() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.insert("sig", "{{name}}")?;
+    registry.insert("welcome.html", "{{> sig this isolated=true}}")?;
+    registry.insert("welcome.txt", "{{> sig this isolated=true}}")?;
+    let mut cache = PartialCache::new(8);
+    cache.allow_partials(&["sig"]);
+    registry.set_partial_cache(cache);
+    let data = json!({"name": "Tom & Jerry"});
+    let email = registry.render_email("welcome", &data)?;
+    assert_eq!("Tom &amp; Jerry", &email.html);
+    assert_eq!("Tom & Jerry", &email.text);
+    Ok(())
+}
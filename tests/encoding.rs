@@ -0,0 +1,42 @@
+#![cfg(feature = "encoding-helper")]
+use bracket::{Error, Registry, Result};
+use serde_json::json;
+
+const NAME: &str = "encoding.rs";
+
+#[test]
+fn encoding_base64() -> Result<()> {
+    let registry = Registry::new();
+    let data = json!({"s": "user:pa?ss>"});
+    let value = r#"{{base64 s}} {{base64url s}} {{hex "hi"}}"#;
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!("dXNlcjpwYT9zcz4= dXNlcjpwYT9zcz4 6869", &result);
+    Ok(())
+}
+
+#[test]
+fn encoding_sha256() -> Result<()> {
+    let registry = Registry::new();
+    let data = json!({"s": "abc", "o": {"a": 1}});
+    let result = registry.once(NAME, r#"{{sha256 s}}"#, &data)?;
+    assert_eq!(
+        "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad",
+        &result
+    );
+    let result =
+        registry.once(NAME, r#"{{sha256 s encoding="base64"}}"#, &data)?;
+    assert_eq!("ungWv48Bz+pBQUDeXa4iI7ADYaOWF3qctBD/YfIAFa0=", &result);
+    let result = registry.once(NAME, r#"{{sha256 o}}|{{hex o}}"#, &data)?;
+    assert_eq!(
+        "015abd7f5cc57a2dd94b7590f04ad8084273905ee33ec5cebeae62276a97f862|7b2261223a317d",
+        &result
+    );
+    match registry.once(NAME, r#"{{sha256 s encoding="md5"}}"#, &data) {
+        Err(Error::Render(e)) => assert_eq!(
+            "Helper 'sha256' got unknown encoding 'md5', expected 'hex' or 'base64'",
+            e.to_string()
+        ),
+        _ => panic!("expected a render error"),
+    }
+    Ok(())
+}
@@ -0,0 +1,50 @@
+use bracket::{error::RenderError, Error, Registry, Result};
+use serde_json::json;
+
+const NAME: &str = "env.rs";
+
+#[test]
+fn env_allowed() -> Result<()> {
+    std::env::set_var("BRACKET_ENV_ALLOWED", "1.0.0");
+    let mut registry = Registry::new();
+    registry.allow_env("BRACKET_ENV_ALLOWED");
+    let value = r#"{{env "BRACKET_ENV_ALLOWED"}}"#;
+    let result = registry.once(NAME, value, &json!({}))?;
+    assert_eq!("1.0.0", &result);
+    Ok(())
+}
+
+#[test]
+fn env_unset() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.allow_env("BRACKET_ENV_UNSET");
+    let value = r#"{{#if (env "BRACKET_ENV_UNSET")}}set{{else}}unset{{/if}}"#;
+    let result = registry.once(NAME, value, &json!({}))?;
+    assert_eq!("unset", &result);
+    Ok(())
+}
+
+#[test]
+fn env_not_allowed() -> Result<()> {
+    std::env::set_var("BRACKET_ENV_DENIED", "secret");
+    let registry = Registry::new();
+    let value = r#"{{env "BRACKET_ENV_DENIED"}}"#;
+    assert!(registry.once(NAME, value, &json!({})).is_err());
+    Ok(())
+}
+
+#[test]
+fn env_sandboxed() -> Result<()> {
+    std::env::set_var("BRACKET_ENV_SANDBOX", "1.0.0");
+    let mut registry = Registry::new();
+    registry.allow_env("BRACKET_ENV_SANDBOX");
+    registry.set_sandboxed(true);
+    let value = r#"{{env "BRACKET_ENV_SANDBOX"}}"#;
+    match registry.once(NAME, value, &json!({})) {
+        Err(Error::Render(RenderError::HelperSandboxed(name, _))) => {
+            assert_eq!("env", &name);
+        }
+        _ => panic!("Expected sandbox error"),
+    }
+    Ok(())
+}
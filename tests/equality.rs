@@ -0,0 +1,47 @@
+use bracket::{
+    parser::{ast::Node, ParserOptions},
+    template::Template,
+    Registry, Result,
+};
+use std::collections::HashSet;
+
+const NAME: &str = "equality.rs";
+
+fn compile(name: &str, value: &str) -> Result<Template> {
+    let options = ParserOptions::new(name.to_string(), 0, 0);
+    Ok(Template::compile(value.to_string(), options)?)
+}
+
+#[test]
+fn equality_template() -> Result<()> {
+    let value = r"a{{#if b}}{{c}}{{else}}{{{d}}}{{/if}}{{!-- e --}}";
+    let a = compile("a.hbs", value)?;
+    let b = compile("b.hbs", value)?;
+    assert_eq!(a, b);
+
+    let c = compile("c.hbs", r"a{{#if b}}{{c}}{{else}}{{d}}{{/if}}")?;
+    assert_ne!(a, c);
+    Ok(())
+}
+
+#[test]
+fn equality_ignore_spans() -> Result<()> {
+    let registry = Registry::new();
+    let a = registry.parse(NAME, r"{{#each list}}{{this}}{{/each}}")?;
+    let b = registry.parse(NAME, r"foo{{#each list}}{{this}}{{/each}}")?;
+    let block_a = a.node().children().next().unwrap();
+    let block_b = b.node().children().nth(1).unwrap();
+    assert!(matches!(block_a, Node::Block(_)));
+    assert_eq!(block_a, block_b);
+    Ok(())
+}
+
+#[test]
+fn equality_hash_dedup() -> Result<()> {
+    let mut set = HashSet::new();
+    set.insert(compile("a.hbs", r"{{> header}}{{title}}")?);
+    set.insert(compile("b.hbs", r"{{> header}}{{title}}")?);
+    set.insert(compile("c.hbs", r"{{> footer}}")?);
+    assert_eq!(2, set.len());
+    Ok(())
+}
@@ -0,0 +1,184 @@
+use bracket::{escape, output::StringOutput, Registry, Result};
+use serde_json::json;
+
+const NAME: &str = "escape.rs";
+
+#[test]
+fn escape_html() -> Result<()> {
+    assert_eq!("", &escape::html(""));
+    assert_eq!("foo", &escape::html("foo"));
+    assert_eq!(
+        "&lt;a href=&quot;#&quot;&gt;&#x27;&amp;&#x27;&lt;/a&gt;",
+        &escape::html(r##"<a href="#">'&'</a>"##)
+    );
+    assert_eq!("&lt;☃&gt;", &escape::html("<☃>"));
+    Ok(())
+}
+
+#[test]
+fn escape_html_into() -> Result<()> {
+    let mut output = String::from("foo ");
+    escape::html_into("<bar>", &mut output);
+    assert_eq!("foo &lt;bar&gt;", &output);
+    Ok(())
+}
+
+#[test]
+fn escape_render() -> Result<()> {
+    let registry = Registry::new();
+    let value = r"{{foo}}";
+    let data = json!({"foo": "<bar & baz>"});
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!("&lt;bar &amp; baz&gt;", &result);
+    Ok(())
+}
+
+#[test]
+fn escape_html_write() -> Result<()> {
+    let mut writer = StringOutput::new();
+    let written = escape::html_write("<bar>", &mut writer)?;
+    let result: String = writer.into();
+    assert_eq!(11, written);
+    assert_eq!("&lt;bar&gt;", &result);
+    Ok(())
+}
+
+#[test]
+fn escape_render_noop() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.set_escape_write(Box::new(escape::noop_write));
+    let value = r"{{foo}}";
+    let data = json!({"foo": "<bar>"});
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!("<bar>", &result);
+    Ok(())
+}
+
+#[test]
+fn escape_render_adapt() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.set_escape(Box::new(|s: &str| s.to_uppercase()));
+    let value = r"{{foo}}";
+    let data = json!({"foo": "bar"});
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!("BAR", &result);
+    Ok(())
+}
+
+#[test]
+fn escape_csv() -> Result<()> {
+    assert_eq!("foo", &escape::csv("foo"));
+    assert_eq!(r#""a,b""#, &escape::csv("a,b"));
+    assert_eq!(r#""say ""hi""""#, &escape::csv(r#"say "hi""#));
+    assert_eq!("\"a\nb\"", &escape::csv("a\nb"));
+    assert_eq!("'=SUM(A1:A2)", &escape::csv("=SUM(A1:A2)"));
+    assert_eq!(r#""'=1,2""#, &escape::csv("=1,2"));
+    assert_eq!("-5", &escape::csv("-5"));
+    assert_eq!("+1.5", &escape::csv("+1.5"));
+    assert_eq!("'-2+3", &escape::csv("-2+3"));
+    assert_eq!("a,b", &escape::tsv("a,b"));
+    assert_eq!("\"a\tb\"", &escape::tsv("a\tb"));
+
+    let mut registry = Registry::new();
+    registry.set_escape_write(Box::new(escape::csv_write));
+    let value = r"{{name}},{{note}}";
+    let data = json!({"name": "-1", "note": "a, b"});
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!(r#"-1,"a, b""#, &result);
+    Ok(())
+}
+
+#[cfg(feature = "csv-helper")]
+#[test]
+fn escape_csv_helper() -> Result<()> {
+    let registry = Registry::new();
+    let value = r#"{{{csv name 42 note}}}|{{{csv row delimiter=";"}}}"#;
+    let data = json!({
+        "name": "@cmd",
+        "note": "x \"y\"",
+        "row": ["a;b", true, null],
+    });
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!(r#"'@cmd,42,"x ""y"""|"a;b";true;"#, &result);
+    match registry.once(NAME, "{{csv}}", &data) {
+        Ok(_) => panic!("Arity error expected"),
+        Err(e) => assert_eq!(
+            "Helper 'csv' got invalid arity expects at least 1 argument(s)",
+            e.to_string()
+        ),
+    }
+    Ok(())
+}
+
+#[cfg(feature = "cdata-helper")]
+#[test]
+fn escape_xml() -> Result<()> {
+    assert_eq!(
+        "&lt;a title=&quot;x&quot;&gt;&apos;&amp;&apos;&lt;/a&gt;",
+        &escape::xml(r#"<a title="x">'&'</a>"#)
+    );
+    let mut registry = Registry::new();
+    registry.set_escape_write(Box::new(escape::xml_write));
+    let value = r#"<title>{{title}}</title>{{#cdata}}{{{body}}}{{/cdata}}"#;
+    let data = json!({"title": "Tom's <feed>", "body": "<p>a]]>b</p>"});
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!(
+        "<title>Tom&apos;s &lt;feed&gt;</title><![CDATA[<p>a]]]]><![CDATA[>b</p>]]>",
+        &result
+    );
+    Ok(())
+}
+
+#[test]
+fn escape_latex() -> Result<()> {
+    assert_eq!(
+        r"50\% \& \#1 a\_b \{x\} \$5 \textasciitilde{}\textasciicircum{} \textbackslash{}n",
+        &escape::latex(r"50% & #1 a_b {x} $5 ~^ \n")
+    );
+    let mut registry = Registry::new();
+    registry.set_escape(Box::new(escape::latex));
+    let value = r"\textbf{ {{name}} }";
+    let data = json!({"name": "R&D_2"});
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!(r"\textbf{ R\&D\_2 }", &result);
+    Ok(())
+}
+
+#[cfg(feature = "escape-helper")]
+#[test]
+fn escape_override() -> Result<()> {
+    let mut registry = Registry::new();
+    let value = r"{{a | safe}}|{{{a | escape}}}|{{safe a}}|{{n | safe}}";
+    let data = json!({"a": "<b>", "n": 1});
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!("<b>|&lt;b&gt;|<b>|1", &result);
+
+    registry.set_sandbox(Default::default());
+    match registry.once(NAME, r"{{a | safe}}", &data) {
+        Err(bracket::Error::Render(e)) => assert_eq!(
+            "Unescaped output is not allowed in a sandbox, got '{{a | safe}}'",
+            e.to_string()
+        ),
+        _ => panic!("expected a render error"),
+    }
+    Ok(())
+}
+
+#[test]
+fn escape_registry_fn() -> Result<()> {
+    let mut registry = Registry::new();
+    assert_eq!("&lt;b&gt;", (registry.escape())("<b>"));
+
+    registry.set_escape_write(Box::new(escape::xml_write));
+    assert_eq!("&lt;&apos;&gt;", (registry.escape())("<'>"));
+    let mut out = StringOutput::new();
+    (registry.escape_write())("<'>", &mut out)?;
+    assert_eq!("&lt;&apos;&gt;", out.as_str());
+
+    registry.set_escape(Box::new(|s: &str| s.to_uppercase()));
+    assert_eq!("ABC", (registry.escape())("abc"));
+    let child = registry.child();
+    assert_eq!("ABC", (child.escape())("abc"));
+    assert_eq!("ABC", &child.once(NAME, "{{a}}", &json!({"a": "abc"}))?);
+    Ok(())
+}
@@ -2,7 +2,7 @@ use bracket::{
     helper::*,
     parser::ast::Node,
     render::{Context, Render},
-    Registry, Result,
+    Error, Registry, Result,
 };
 use serde_json::json;
 
@@ -34,3 +34,32 @@ fn helper_evaluate_path() -> Result<()> {
     assert_eq!("bar", &result);
     Ok(())
 }
+
+#[test]
+fn registry_evaluate_path() -> Result<()> {
+    let mut registry = Registry::new();
+    let data = json!({"user": {"name": "alice", "tags": ["a", "b"]}});
+    assert_eq!(json!("alice"), registry.evaluate("user.name", &data)?);
+    assert_eq!(json!("b"), registry.evaluate("user.tags.[1]", &data)?);
+    assert_eq!(json!(null), registry.evaluate("user.missing", &data)?);
+
+    registry.set_strict(true);
+    assert!(registry.evaluate("user.missing", &data).is_err());
+    Ok(())
+}
+
+#[test]
+fn registry_evaluate_sub_expr() -> Result<()> {
+    let registry = Registry::new();
+    let data = json!({"count": 3});
+    assert_eq!(json!(true), registry.evaluate("(eq count 3)", &data)?);
+    assert_eq!(
+        json!(false),
+        registry.evaluate("(and (gt count 1) (lt count 2))", &data)?
+    );
+    match registry.evaluate("(eq a b)}}{{c", &data) {
+        Err(Error::Render(_)) => {}
+        _ => panic!("expected evaluate error"),
+    }
+    Ok(())
+}
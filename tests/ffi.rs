@@ -0,0 +1,76 @@
+#![cfg(feature = "ffi")]
+use bracket::ffi::*;
+use std::ffi::{CStr, CString};
+
+fn last_error() -> String {
+    let message = bracket_last_error();
+    assert!(!message.is_null());
+    unsafe { CStr::from_ptr(message) }
+        .to_string_lossy()
+        .into_owned()
+}
+
+#[test]
+fn ffi_render() {
+    let name = CString::new("greeting").unwrap();
+    let source = CString::new("Hello {{name}}!").unwrap();
+    let json = CString::new(r#"{"name": "world"}"#).unwrap();
+    unsafe {
+        let registry = bracket_registry_new();
+        assert_eq!(
+            0,
+            bracket_register_template(registry, name.as_ptr(), source.as_ptr())
+        );
+        let result =
+            bracket_render_json(registry, name.as_ptr(), json.as_ptr());
+        assert!(!result.is_null());
+        assert_eq!("Hello world!", CStr::from_ptr(result).to_str().unwrap());
+        bracket_string_free(result);
+        bracket_registry_free(registry);
+    }
+}
+
+#[test]
+fn ffi_syntax_error() {
+    let name = CString::new("bad").unwrap();
+    let source = CString::new("{{}}").unwrap();
+    unsafe {
+        let registry = bracket_registry_new();
+        assert_eq!(
+            -1,
+            bracket_register_template(registry, name.as_ptr(), source.as_ptr())
+        );
+        assert!(last_error().contains("expecting identifier"));
+        bracket_registry_free(registry);
+    }
+}
+
+#[test]
+fn ffi_invalid_json() {
+    let name = CString::new("greeting").unwrap();
+    let source = CString::new("{{name}}").unwrap();
+    let json = CString::new("{").unwrap();
+    unsafe {
+        let registry = bracket_registry_new();
+        bracket_register_template(registry, name.as_ptr(), source.as_ptr());
+        let result =
+            bracket_render_json(registry, name.as_ptr(), json.as_ptr());
+        assert!(result.is_null());
+        assert!(last_error().starts_with("Invalid JSON data"));
+        bracket_registry_free(registry);
+    }
+}
+
+#[test]
+fn ffi_template_not_found() {
+    let name = CString::new("missing").unwrap();
+    let json = CString::new("{}").unwrap();
+    unsafe {
+        let registry = bracket_registry_new();
+        let result =
+            bracket_render_json(registry, name.as_ptr(), json.as_ptr());
+        assert!(result.is_null());
+        assert_eq!("Template not found 'missing'", last_error());
+        bracket_registry_free(registry);
+    }
+}
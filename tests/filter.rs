@@ -0,0 +1,56 @@
+#![cfg(feature = "filter-helper")]
+use bracket::{Error, Registry, Result};
+use serde_json::json;
+
+const NAME: &str = "filter.rs";
+
+fn data() -> serde_json::Value {
+    json!({
+        "orders": [
+            {"id": 1, "status": "paid", "total": 50, "tags": ["gift"]},
+            {"id": 2, "status": "open", "total": 150.0},
+            {"id": 3, "status": "paid", "total": 100, "customer": {"vip": true}}
+        ],
+        "wanted": [1, 2]
+    })
+}
+
+fn ids(registry: &Registry<'_>, expr: &str) -> Result<String> {
+    let value = format!("{{{{#each ({})}}}}{{{{id}}}}{{{{/each}}}}", expr);
+    registry.once(NAME, &value, &data())
+}
+
+#[test]
+fn where_operators() -> Result<()> {
+    let registry = Registry::new();
+    assert_eq!("13", ids(&registry, r#"where orders "status" "paid""#)?);
+    assert_eq!(
+        "2",
+        ids(&registry, r#"where orders "status" "paid" op="ne""#)?
+    );
+    assert_eq!(
+        "23",
+        ids(&registry, r#"where orders "total" 100 op="gte""#)?
+    );
+    assert_eq!("1", ids(&registry, r#"where orders "total" 100 op="lt""#)?);
+    assert_eq!(
+        "1",
+        ids(&registry, r#"where orders "tags" "gift" op="contains""#)?
+    );
+    assert_eq!("12", ids(&registry, r#"where orders "id" wanted op="in""#)?);
+    assert_eq!("3", ids(&registry, r#"where orders "customer.vip""#)?);
+    Ok(())
+}
+
+#[test]
+fn where_unknown_operator() -> Result<()> {
+    let registry = Registry::new();
+    match ids(&registry, r#"where orders "id" 1 op="like""#) {
+        Err(Error::Render(e)) => assert_eq!(
+            "Helper 'where' got unknown operator 'like'",
+            e.to_string()
+        ),
+        _ => panic!("expected a render error"),
+    }
+    Ok(())
+}
@@ -0,0 +1,71 @@
+#![cfg(feature = "form-helper")]
+use bracket::{Registry, Result};
+use serde_json::json;
+
+const NAME: &str = "form.rs";
+
+#[test]
+fn form_model_and_errors() -> Result<()> {
+    let registry = Registry::new();
+    let value = r#"{{#form action="/users/1" method="PUT" model=user errors=errors}}{{input name="email" type="email" class="field"}}{{input name="password" type="password"}}{{/form}}"#;
+    let data = json!({
+        "user": {"email": "a@b.c", "password": "secret"},
+        "errors": {"email": ["Invalid", "Taken <sic>"]}
+    });
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!(
+        concat!(
+            r#"<form action="/users/1" method="post">"#,
+            r#"<input type="hidden" name="_method" value="put">"#,
+            r#"<input type="email" name="email" value="a@b.c" aria-invalid="true" class="field">"#,
+            r#"<span class="error">Invalid</span><span class="error">Taken &lt;sic&gt;</span>"#,
+            r#"<input type="password" name="password">"#,
+            r#"</form>"#
+        ),
+        &result
+    );
+    Ok(())
+}
+
+#[test]
+fn form_checked() -> Result<()> {
+    let registry = Registry::new();
+    let value = r#"{{input type="checkbox" name="admin"}}{{input type="checkbox" name="tags" value="b"}}{{input type="radio" name="size" value="m"}}{{input type="radio" name="size" value="l"}}"#;
+    let data = json!({"admin": true, "tags": ["a", "b"], "size": "m"});
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!(
+        concat!(
+            r#"<input type="checkbox" name="admin" checked>"#,
+            r#"<input type="checkbox" name="tags" value="b" checked>"#,
+            r#"<input type="radio" name="size" value="m" checked>"#,
+            r#"<input type="radio" name="size" value="l">"#,
+        ),
+        &result
+    );
+    Ok(())
+}
+
+#[test]
+fn form_select() -> Result<()> {
+    let registry = Registry::new();
+    let value = r#"{{select name="role" options=roles}}|{{select name="level" options=levels}}"#;
+    let data = json!({
+        "role": "admin",
+        "roles": [{"value": "user", "label": "User"}, {"value": "admin", "label": "Admin"}],
+        "level": 2,
+        "levels": [1, 2],
+        "errors": {"level": "Too low"}
+    });
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!(
+        concat!(
+            r#"<select name="role"><option value="user">User</option>"#,
+            r#"<option value="admin" selected>Admin</option></select>|"#,
+            r#"<select name="level" aria-invalid="true"><option value="1">1</option>"#,
+            r#"<option value="2" selected>2</option></select>"#,
+            r#"<span class="error">Too low</span>"#,
+        ),
+        &result
+    );
+    Ok(())
+}
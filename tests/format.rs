@@ -0,0 +1,113 @@
+use bracket::{
+    format::{DisplayValue, NullPolicy, NumberFormat},
+    render::Type,
+    Error, Registry, Result,
+};
+use serde_json::{json, Value};
+
+const NAME: &str = "format.rs";
+
+#[test]
+fn format_number() -> Result<()> {
+    let format = NumberFormat::new().precision(2);
+    assert_eq!(Some("1.50".to_string()), format.display(&json!(1.5)));
+    assert_eq!(Some("3.00".to_string()), format.display(&json!(3)));
+    assert_eq!(None, format.display(&json!("3")));
+    let format = NumberFormat::new().exponent(true);
+    assert_eq!(Some("1.5e3".to_string()), format.display(&json!(1500)));
+    let format = NumberFormat::new().exponent(true).precision(1);
+    assert_eq!(Some("1.2e-3".to_string()), format.display(&json!(0.00123)));
+    Ok(())
+}
+
+#[test]
+fn format_types() -> Result<()> {
+    let mut registry = Registry::new();
+    let formatters = registry.formatters_mut();
+    formatters
+        .insert_type(Type::Number, Box::new(NumberFormat::new().precision(1)));
+    formatters.insert_type(
+        Type::Bool,
+        Box::new(|value: &Value| {
+            Some(if value.as_bool() == Some(true) {
+                "yes".to_string()
+            } else {
+                "no".to_string()
+            })
+        }),
+    );
+    formatters
+        .insert_type(Type::Null, Box::new(|_: &Value| Some(String::new())));
+    let value = r#"{{price}}|{{active}}|{{deleted}}|{{missing}}|{{name}}"#;
+    let data = json!({
+        "price": 2,
+        "active": true,
+        "deleted": false,
+        "missing": null,
+        "name": "x"
+    });
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!("2.0|yes|no||x", &result);
+
+    let mut child = registry.child();
+    child.formatters_mut().insert_type(
+        Type::String,
+        Box::new(|value: &Value| value.as_str().map(|s| s.to_uppercase())),
+    );
+    let result = child.once(NAME, value, &data)?;
+    assert_eq!("2.0|yes|no||X", &result);
+    Ok(())
+}
+
+#[cfg(feature = "format-helper")]
+#[test]
+fn format_helper() -> Result<()> {
+    let mut registry = Registry::new();
+    registry
+        .formatters_mut()
+        .insert_tag("sci", Box::new(NumberFormat::new().exponent(true)));
+    let value = r#"{{format distance "sci"}} {{format name "sci"}}"#;
+    let data = json!({"distance": 42000, "name": "x"});
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!("4.2e4 x", &result);
+
+    let value = r#"{{format distance "money"}}"#;
+    match registry.once(NAME, value, &data) {
+        Err(Error::Render(e)) => {
+            assert!(e
+                .to_string()
+                .contains("Formatter for tag 'money' not found"))
+        }
+        _ => panic!("expected a render error"),
+    }
+    Ok(())
+}
+
+#[test]
+fn format_null_policy() -> Result<()> {
+    let mut registry = Registry::new();
+    let value = r#"[{{missing}}|{{nothing}}|{{log "x"}}|{{this.missing}}]"#;
+    let data = json!({"nothing": null});
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!("[|||]", &result);
+
+    registry.set_null_policy(NullPolicy::Null);
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!("[null|null||null]", &result);
+
+    registry.set_null_policy(NullPolicy::Placeholder("<n/a>".to_string()));
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!("[&lt;n/a&gt;|&lt;n/a&gt;||&lt;n/a&gt;]", &result);
+
+    registry.set_null_policy(NullPolicy::Error);
+    match registry.once(NAME, "{{nothing}}", &data) {
+        Err(Error::Render(e)) => assert_eq!(
+            "Variable 'nothing' is null or missing in format.rs",
+            e.to_string()
+        ),
+        _ => panic!("expected a render error"),
+    }
+    let result = registry.once(NAME, r#"{{log "x"}}"#, &data)?;
+    assert_eq!("", &result);
+    Ok(())
+}
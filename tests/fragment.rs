@@ -0,0 +1,98 @@
+use bracket::{
+    error::{ErrorInfo, SourcePos, SyntaxError},
+    helper::prelude::*,
+    parser::{ast::Lines, ParserOptions},
+    Error, Registry, Result,
+};
+use serde_json::json;
+
+const NAME: &str = "fragment.rs";
+
+pub struct FragmentHelper;
+
+impl Helper for FragmentHelper {
+    fn call<'render, 'call>(
+        &self,
+        rc: &mut Render<'render>,
+        ctx: &Context<'call>,
+        _template: Option<&'render Node<'render>>,
+    ) -> HelperValue {
+        if let Some(text) = ctx.text() {
+            rc.fragment(text, ctx.call().lines().start)?;
+        }
+        Ok(None)
+    }
+}
+
+#[test]
+fn fragment_render() -> Result<()> {
+    let mut registry = Registry::new();
+    registry
+        .helpers_mut()
+        .insert("fragment", Box::new(FragmentHelper {}));
+    let value = r"{{{{fragment}}}}Hello {{name}}!{{{{/fragment}}}}";
+    let data = json!({"name": "world"});
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!("Hello world!", &result);
+    Ok(())
+}
+
+#[test]
+fn fragment_render_scope() -> Result<()> {
+    let mut registry = Registry::new();
+    registry
+        .helpers_mut()
+        .insert("fragment", Box::new(FragmentHelper {}));
+    let value =
+        r"{{#each list}}{{{{fragment}}}}{{this}}{{{{/fragment}}}}{{/each}}";
+    let data = json!({"list": [1, 2, 3]});
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!("123", &result);
+    Ok(())
+}
+
+#[test]
+fn fragment_line_offset() -> Result<()> {
+    let fragment = "\n{{}}";
+    let options = ParserOptions::new(NAME.to_string(), 4, 128);
+    match Template::compile_fragment(fragment, options) {
+        Ok(_) => panic!("Identifier error expected in fragment"),
+        Err(e) => {
+            let pos = SourcePos(5, 3);
+            let info = ErrorInfo::new(fragment, NAME, pos, vec![]);
+            assert_eq!(SyntaxError::ExpectedIdentifier(info.into()), e);
+        }
+    }
+    Ok(())
+}
+
+#[cfg(feature = "fragment-helper")]
+#[test]
+fn fragment_helper() -> Result<()> {
+    let mut registry = Registry::new();
+    let value = r#"<ul>{{#each items}}{{#fragment "item"}}<li>{{name}}</li>{{/fragment}}{{/each}}</ul>"#;
+    registry.insert(NAME, value)?;
+    let data = json!({"items": [{"name": "a"}, {"name": "b"}]});
+    assert_eq!(
+        "<ul><li>a</li><li>b</li></ul>",
+        registry.render(NAME, &data)?
+    );
+    let data = json!({"name": "c"});
+    assert_eq!("<li>c</li>", registry.render_fragment(NAME, "item", &data)?);
+    Ok(())
+}
+
+#[test]
+fn fragment_not_found() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.insert(NAME, r#"{{#fragment "item"}}{{name}}{{/fragment}}"#)?;
+    let data = json!({"name": "c"});
+    match registry.render_fragment(NAME, "row", &data) {
+        Ok(_) => panic!("Fragment not found error expected"),
+        Err(e) => assert_eq!(
+            Error::FragmentNotFound(NAME.to_string(), "row".to_string()),
+            e
+        ),
+    }
+    Ok(())
+}
@@ -0,0 +1,38 @@
+#![cfg(feature = "group-helper")]
+use bracket::{Registry, Result};
+use serde_json::json;
+
+const NAME: &str = "group.rs";
+
+#[test]
+fn group_by_key() -> Result<()> {
+    let registry = Registry::new();
+    let data = json!({
+        "products": [
+            {"name": "Apple", "category": "fruit"},
+            {"name": "Carrot", "category": "vegetable"},
+            {"name": "Pear", "category": "fruit"},
+            {"name": "Salt"}
+        ]
+    });
+    let value = r#"{{#group products by="category"}}{{@index}}:{{@key}}={{#each this}}{{name}}{{#unless @last}},{{/unless}}{{/each}}{{#unless @last}};{{/unless}}{{/group}}"#;
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!("0:fruit=Apple,Pear;1:vegetable=Carrot;2:=Salt", &result);
+    Ok(())
+}
+
+#[test]
+fn group_by_path() -> Result<()> {
+    let registry = Registry::new();
+    let data = json!({
+        "posts": [
+            {"title": "a", "author": {"id": 1}},
+            {"title": "b", "author": {"id": 2}},
+            {"title": "c", "author": {"id": 1}}
+        ]
+    });
+    let value = r#"{{#group posts by="author.id"}}[{{@key}} {{#each this}}{{title}}{{/each}}]{{/group}}"#;
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!("[1 ac][2 b]", &result);
+    Ok(())
+}
@@ -1,4 +1,8 @@
-use bracket::{helper::prelude::*, Registry, Result};
+use bracket::{
+    error::{Error, RenderError},
+    helper::prelude::*,
+    Registry, Result,
+};
 use serde_json::{json, Value};
 
 const NAME: &str = "helper.rs";
@@ -140,3 +144,85 @@ fn helper_block_missing() -> Result<()> {
     assert_eq!("bar", &result);
     Ok(())
 }
+
+#[test]
+fn helper_error_location() -> Result<()> {
+    let registry = Registry::new();
+    let value = "{{foo}}\n{{json obj 1 2}}";
+    let data = json!({"obj": {}});
+    match registry.once(NAME, value, &data) {
+        Err(Error::Render(e @ RenderError::Helper(..))) => {
+            let location = e.location().unwrap();
+            assert_eq!(NAME, location.name());
+            assert_eq!(&(1..2), location.lines());
+            assert_eq!("{{json obj 1 2}}", location.source());
+            assert_eq!("helper.rs:2", location.to_string());
+            assert!(format!("{:?}", e).contains("{{json obj 1 2}}"));
+        }
+        _ => panic!("Helper error expected"),
+    }
+    Ok(())
+}
+
+#[test]
+fn helper_error_location_nested() -> Result<()> {
+    let registry = Registry::new();
+    let value = "{{#each list}}\n  {{#if true}}\n    {{json this 1 2}}\n  {{/if}}\n{{/each}}";
+    let data = json!({"list": [1]});
+    match registry.once(NAME, value, &data) {
+        Err(Error::Render(e)) => {
+            let location = e.location().unwrap();
+            assert_eq!(&(2..3), location.lines());
+            assert_eq!("{{json this 1 2}}", location.source());
+        }
+        _ => panic!("Helper error expected"),
+    }
+    Ok(())
+}
+
+#[test]
+fn helper_alias() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.helpers_mut().insert("foo", Box::new(FooHelper {}));
+    registry.alias_helper("old_foo", "foo");
+    let value = json!({});
+    let result = registry.once(NAME, r"{{old_foo}} {{foo}}", &value)?;
+    assert_eq!("bar bar", &result);
+    Ok(())
+}
+
+#[test]
+fn helper_deprecated_lint() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.helpers_mut().insert("foo", Box::new(FooHelper {}));
+    registry.alias_helper("old_foo", "foo");
+    registry.deprecate_helper("old_foo", "use 'foo' instead");
+    let errors = registry.lint(
+        NAME,
+        "{{foo}}\n{{#if (old_foo)}}{{old_foo}}{{/if}}{{> old_foo}}",
+    )?;
+    assert_eq!(2, errors.len());
+    assert_eq!(
+        "Helper 'old_foo' is deprecated, use 'foo' instead",
+        errors[0].to_string()
+    );
+    assert_eq!("warning", errors[0].diagnostic().kind.as_str());
+    let debug = format!("{:?}", errors[1]);
+    assert!(debug.contains("--> helper.rs:2:"));
+    assert_eq!("bar", registry.once(NAME, r"{{old_foo}}", &json!({}))?);
+    Ok(())
+}
+
+#[test]
+fn helper_last_result() -> Result<()> {
+    let mut registry = Registry::new();
+    let data = json!({"user": {"name": "x"}, "items": [1, 2]});
+    let value = r#"{{lookup user "name"}}={{@last_result}};{{#each items}}{{eq this 1}}/{{@last_result}},{{/each}}{{@last_result}}"#;
+    assert_eq!("x=;true/,false/,", registry.once(NAME, value, &data)?);
+    registry.set_last_result(true);
+    assert_eq!(
+        "x=x;true/true,false/false,x",
+        registry.once(NAME, value, &data)?
+    );
+    Ok(())
+}
@@ -0,0 +1,69 @@
+use bracket::highlight::{classify, TokenClass};
+
+fn classes(source: &str) -> Vec<(&str, TokenClass)> {
+    classify(source)
+        .into_iter()
+        .map(|(span, class)| (&source[span], class))
+        .collect()
+}
+
+#[test]
+fn highlight_statement() {
+    let value = r#"Hi {{~ user.name "x" size=2 ~}}!"#;
+    let expect = vec![
+        ("Hi ", TokenClass::Text),
+        ("{{", TokenClass::TagDelimiter),
+        ("~", TokenClass::WhitespaceControl),
+        (" ", TokenClass::TagDelimiter),
+        ("user", TokenClass::Identifier),
+        (".", TokenClass::Punctuation),
+        ("name", TokenClass::Identifier),
+        (" ", TokenClass::Whitespace),
+        (r#""x""#, TokenClass::String),
+        (" ", TokenClass::Whitespace),
+        ("size=", TokenClass::HashKey),
+        ("2", TokenClass::Number),
+        (" ", TokenClass::Whitespace),
+        ("~", TokenClass::WhitespaceControl),
+        ("}}", TokenClass::TagDelimiter),
+        ("!", TokenClass::Text),
+    ];
+    assert_eq!(expect, classes(value));
+}
+
+#[test]
+fn highlight_block_comment() {
+    let value = "{{!-- {{x}} --}}{{#if true}}a{{else}}b{{/if}}";
+    let expect = vec![
+        ("{{!-- {{x}} --}}", TokenClass::Comment),
+        ("{{#", TokenClass::TagDelimiter),
+        ("if", TokenClass::Identifier),
+        (" ", TokenClass::Whitespace),
+        ("true", TokenClass::Keyword),
+        ("}}", TokenClass::TagDelimiter),
+        ("a", TokenClass::Text),
+        ("{{", TokenClass::TagDelimiter),
+        ("else", TokenClass::Keyword),
+        ("}}", TokenClass::TagDelimiter),
+        ("b", TokenClass::Text),
+        ("{{/", TokenClass::TagDelimiter),
+        ("if", TokenClass::Identifier),
+        ("}}", TokenClass::TagDelimiter),
+    ];
+    assert_eq!(expect, classes(value));
+    assert_eq!("tag-delimiter", TokenClass::TagDelimiter.as_str());
+}
+
+#[test]
+fn highlight_adjacent_tags() {
+    let value = "{{a}}{{[b c]}}";
+    let expect = vec![
+        ("{{", TokenClass::TagDelimiter),
+        ("a", TokenClass::Identifier),
+        ("}}", TokenClass::TagDelimiter),
+        ("{{", TokenClass::TagDelimiter),
+        ("[b c]", TokenClass::Identifier),
+        ("}}", TokenClass::TagDelimiter),
+    ];
+    assert_eq!(expect, classes(value));
+}
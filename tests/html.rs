@@ -0,0 +1,77 @@
+#![cfg(feature = "html-helper")]
+use bracket::{Error, Registry, Result};
+use serde_json::json;
+
+const NAME: &str = "html.rs";
+
+#[test]
+fn html_link_to() -> Result<()> {
+    let registry = Registry::new();
+    let data = json!({"label": "Tom & Jerry", "url": "/search?a=1&b=\"2\""});
+    let value = r#"{{link_to label url class="nav" rel=null}}"#;
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!(
+        r#"<a href="/search?a=1&amp;b=&quot;2&quot;" class="nav">Tom &amp; Jerry</a>"#,
+        &result
+    );
+    Ok(())
+}
+
+#[test]
+fn html_img() -> Result<()> {
+    let registry = Registry::new();
+    let value = r#"{{img "/logo.png" alt="Logo" width=120 title=""}}"#;
+    let result = registry.once(NAME, value, &json!({}))?;
+    assert_eq!(r#"<img src="/logo.png" alt="Logo" width="120">"#, &result);
+    Ok(())
+}
+
+#[test]
+fn html_attr() -> Result<()> {
+    let registry = Registry::new();
+    let value = r#"<input{{attr "disabled" off}}{{attr "checked" on}}{{attr title=hint}}>"#;
+    let data = json!({"off": false, "on": true, "hint": "<b>"});
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!(r#"<input checked title="&lt;b&gt;">"#, &result);
+
+    match registry.once(NAME, r#"{{attr "checked"}}"#, &data) {
+        Err(Error::Render(e)) => assert_eq!(
+            "Helper 'attr' expects a name and value or hash parameters",
+            e.to_string()
+        ),
+        _ => panic!("expected a render error"),
+    }
+    Ok(())
+}
+
+#[test]
+fn html_class_list() -> Result<()> {
+    let registry = Registry::new();
+    let value = r#"<b class="{{class_list "btn" extra missing active=on hidden=off}}">"#;
+    let data = json!({"extra": ["btn", "large"], "on": 1, "off": 0});
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!(r#"<b class="btn large active">"#, &result);
+    Ok(())
+}
+
+#[test]
+fn html_attr_invalid_name() -> Result<()> {
+    let registry = Registry::new();
+    let data = json!({"k": "onmouseover=alert(1) x", "v": "1"});
+    match registry.once(NAME, r#"<div{{attr k v}}>"#, &data) {
+        Err(Error::Render(e)) => assert_eq!(
+            "Helper 'attr' got invalid attribute name 'onmouseover=alert(1) x'",
+            e.to_string()
+        ),
+        _ => panic!("expected a render error"),
+    }
+    let value = "{{link_to \"Home\" \"/\" a\u{1}b=1}}";
+    match registry.once(NAME, value, &data) {
+        Err(Error::Render(e)) => assert_eq!(
+            "Helper 'link_to' got invalid attribute name 'a\u{1}b'",
+            e.to_string()
+        ),
+        _ => panic!("expected a render error"),
+    }
+    Ok(())
+}
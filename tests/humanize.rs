@@ -0,0 +1,55 @@
+#![cfg(feature = "humanize-helper")]
+use bracket::{Error, Registry, Result};
+use serde_json::json;
+
+const NAME: &str = "humanize.rs";
+
+#[test]
+fn humanize_filesize() -> Result<()> {
+    let registry = Registry::new();
+    let data = json!({"sizes": [0, 512, 1024, 1_468_006, 5_000_000_000u64]});
+    let value = r#"{{#each sizes}}{{filesize this}}|{{filesize this si=true precision=2}};{{/each}}"#;
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!(
+        "0 B|0 B;512 B|512 B;1.0 KiB|1.02 kB;1.4 MiB|1.47 MB;4.7 GiB|5.00 GB;",
+        &result
+    );
+    Ok(())
+}
+
+#[test]
+fn humanize_duration() -> Result<()> {
+    let registry = Registry::new();
+    let data = json!({"times": [0, 0.5, 45, 7980, 3601, 93_784, -90]});
+    let value = r#"{{#each times}}{{duration this}}|{{duration this units=4}};{{/each}}"#;
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!(
+        "0s|0s;0s|0s;45s|45s;2h 13m|2h 13m;1h|1h 1s;1d 2h|1d 2h 3m 4s;-1m 30s|-1m 30s;",
+        &result
+    );
+    Ok(())
+}
+
+#[test]
+fn humanize_error() -> Result<()> {
+    let registry = Registry::new();
+    match registry.once(NAME, r#"{{filesize -1}}"#, &json!({})) {
+        Err(Error::Render(e)) => assert_eq!(
+            "Helper 'filesize' expects a positive number of bytes",
+            e.to_string()
+        ),
+        _ => panic!("expected a render error"),
+    }
+    match registry.once(
+        NAME,
+        r#"{{filesize 2048 precision=70000}}"#,
+        &json!({}),
+    ) {
+        Err(Error::Render(e)) => assert_eq!(
+            "Helper 'filesize' expects a precision of at most 20",
+            e.to_string()
+        ),
+        _ => panic!("expected a render error"),
+    }
+    Ok(())
+}
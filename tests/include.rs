@@ -0,0 +1,52 @@
+#![cfg(feature = "include-helper")]
+use bracket::{Error, Registry, Result};
+use serde_json::json;
+
+const NAME: &str = "include.rs";
+
+#[test]
+fn include_data() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.insert("card", "{{name}} {{@root.name}} {{title}}|")?;
+    let value = r#"{{#each users}}{{include "card" data=this}}{{/each}}"#;
+    let data = json!({
+        "title": "Users",
+        "users": [{"name": "alice"}, {"name": "bob"}]
+    });
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!("alice alice |bob bob |", &result);
+    Ok(())
+}
+
+#[test]
+fn include_no_data() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.insert("card", "[{{name}}{{title}}]")?;
+    let value = r#"{{include "card"}}{{title}}"#;
+    let data = json!({"name": "alice", "title": "x"});
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!("[]x", &result);
+    Ok(())
+}
+
+#[test]
+fn include_errors() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.insert("loop", r#"{{include "loop" data=this}}"#)?;
+    match registry.render("loop", &json!({})) {
+        Err(Error::Render(e)) => {
+            assert_eq!(
+                "Cycle detected whilst processing partial 'loop'",
+                e.to_string()
+            )
+        }
+        _ => panic!("partial cycle error expected"),
+    }
+    match registry.once(NAME, r#"{{include "missing"}}"#, &json!({})) {
+        Err(Error::Render(e)) => {
+            assert_eq!("Partial 'missing' not found", e.to_string())
+        }
+        _ => panic!("partial not found error expected"),
+    }
+    Ok(())
+}
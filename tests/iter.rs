@@ -0,0 +1,80 @@
+use bracket::{
+    parser::ast::{Node, Slice},
+    Registry, Result,
+};
+
+const NAME: &str = "iter.rs";
+
+#[test]
+fn iter_children() -> Result<()> {
+    let registry = Registry::new();
+    let value = r"a{{#if b}}c{{else}}d{{/if}}e";
+    let template = registry.parse(NAME, value)?;
+    let nodes: Vec<&str> =
+        template.node().children().map(|n| n.as_str()).collect();
+    assert_eq!(vec!["a", "{{#if b}}c{{else}}d{{/if}}", "e"], nodes);
+
+    let block = template.node().children().nth(1).unwrap();
+    let nodes: Vec<&str> = block.children().map(|n| n.as_str()).collect();
+    assert_eq!(vec!["c", "{{else}}d{{/if}}"], nodes);
+    Ok(())
+}
+
+#[test]
+fn iter_depth_first() -> Result<()> {
+    let registry = Registry::new();
+    let value = r"{{#if a}}{{#each b}}{{c}}{{/each}}{{else}}{{d}}{{/if}}{{e}}";
+    let template = registry.parse(NAME, value)?;
+    let statements: Vec<&str> = template
+        .node()
+        .iter_depth_first()
+        .filter(|n| matches!(n, Node::Statement(_)))
+        .map(|n| n.as_str())
+        .collect();
+    assert_eq!(vec!["{{c}}", "{{d}}", "{{e}}"], statements);
+    Ok(())
+}
+
+#[test]
+fn iter_breadth_first() -> Result<()> {
+    let registry = Registry::new();
+    let value = r"{{#if a}}{{#each b}}{{c}}{{/each}}{{else}}{{d}}{{/if}}{{e}}";
+    let template = registry.parse(NAME, value)?;
+    let statements: Vec<&str> = template
+        .node()
+        .iter()
+        .filter(|n| matches!(n, Node::Statement(_)))
+        .map(|n| n.as_str())
+        .collect();
+    assert_eq!(vec!["{{e}}", "{{c}}", "{{d}}"], statements);
+    Ok(())
+}
+
+#[test]
+fn iter_descendants() -> Result<()> {
+    let registry = Registry::new();
+    let value = r"{{#each a}}{{b}}{{/each}}";
+    let template = registry.parse(NAME, value)?;
+    let block = template.node().children().next().unwrap();
+    let nodes: Vec<&str> = block.descendants().map(|n| n.as_str()).collect();
+    assert_eq!(vec!["{{b}}"], nodes);
+    Ok(())
+}
+
+#[test]
+fn iter_max_depth() -> Result<()> {
+    let registry = Registry::new();
+    let value = r"{{#if a}}{{#each b}}{{c}}{{/each}}{{/if}}";
+    let template = registry.parse(NAME, value)?;
+    let depth_first: Vec<&str> = template
+        .node()
+        .iter_depth_first()
+        .max_depth(2)
+        .filter(|n| matches!(n, Node::Statement(_)))
+        .map(|n| n.as_str())
+        .collect();
+    assert!(depth_first.is_empty());
+    let breadth_first = template.node().iter().max_depth(3).count();
+    assert_eq!(4, breadth_first);
+    Ok(())
+}
@@ -0,0 +1,31 @@
+use bracket::{Registry, Result};
+use serde_json::json;
+
+const NAME: &str = "json.rs";
+
+#[test]
+fn json_pointer() -> Result<()> {
+    let registry = Registry::new();
+    let data = json!({"app": {"user": {"name": "foo", "id": 1}}});
+    let value = r#"{{{json this pointer="/app/user/name"}}}"#;
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!(r#""foo""#, &result);
+    let value = r#"{{{json this pointer="/app/missing"}}}"#;
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!("null", &result);
+    Ok(())
+}
+
+#[test]
+fn json_only() -> Result<()> {
+    let registry = Registry::new();
+    let data = json!({"user": {"name": "foo", "id": 1, "secret": "bar"}});
+    let value = r#"{{{json this pointer="/user" only="id, name, missing"}}}"#;
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!(r#"{"id":1,"name":"foo"}"#, &result);
+    let data = json!({"user": {"name": "foo", "id": 1}, "keys": ["id"]});
+    let value = r#"{{{json user only=keys}}}"#;
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!(r#"{"id":1}"#, &result);
+    Ok(())
+}
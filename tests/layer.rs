@@ -0,0 +1,46 @@
+use bracket::{Registry, Result};
+use serde_json::json;
+
+#[test]
+fn layer_priority() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.add_layer("site");
+    registry.add_layer("theme");
+    registry.insert_layer("core", "page", "{{> header}}|{{> footer}}")?;
+    registry.insert_layer("core", "header", "core header")?;
+    registry.insert_layer("core", "footer", "core footer")?;
+    registry.insert_layer("theme", "header", "theme header")?;
+    registry.insert_layer("site", "footer", "site footer")?;
+    assert_eq!(
+        vec!["site", "theme", "core"],
+        registry.layers().collect::<Vec<_>>()
+    );
+    assert_eq!(
+        "theme header|site footer",
+        registry.render("page", &json!({}))?
+    );
+
+    let (layer, _) = registry.effective_template("header").unwrap();
+    assert_eq!(Some("theme"), layer);
+    let (layer, _) = registry.effective_template("page").unwrap();
+    assert_eq!(Some("core"), layer);
+
+    registry.insert("header", "header")?;
+    let (layer, _) = registry.effective_template("header").unwrap();
+    assert_eq!(None, layer);
+    assert_eq!("header|site footer", registry.render("page", &json!({}))?);
+    Ok(())
+}
+
+#[test]
+fn layer_child() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.insert_layer("core", "page", "{{> header}}")?;
+    registry.insert_layer("core", "header", "core")?;
+    let mut child = registry.child();
+    child.insert_layer("tenant", "header", "tenant")?;
+    assert_eq!("tenant", child.render("page", &json!({}))?);
+    let (layer, _) = child.effective_template("page").unwrap();
+    assert_eq!(Some("core"), layer);
+    Ok(())
+}
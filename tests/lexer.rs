@@ -1,6 +1,6 @@
 use bracket::lexer::{
-    collect as lex, Array, Block, Comment, DoubleQuoteString, Link, Parameters,
-    RawComment, RawStatement, SingleQuoteString, Token,
+    collect as lex, Array, Block, Comment, DoubleQuoteString, Lexer, Link,
+    Mode, Parameters, RawComment, RawStatement, SingleQuoteString, Token,
 };
 
 #[test]
@@ -336,6 +336,23 @@ fn lex_block_scope_partial() {
     assert_eq!(expect, tokens);
 }
 
+#[test]
+fn lex_inverted_scope() {
+    let value = "{{^foo}}bar{{/foo}}";
+    let tokens = lex(value, true);
+
+    let expect = vec![
+        Token::Block(Block::StartInvertedScope, 0..3),
+        Token::Parameters(Parameters::Identifier, 3..6),
+        Token::Parameters(Parameters::End, 6..8),
+        Token::Block(Block::Text, 8..11),
+        Token::Block(Block::EndBlockScope, 11..14),
+        Token::Parameters(Parameters::Identifier, 14..17),
+        Token::Parameters(Parameters::End, 17..19),
+    ];
+    assert_eq!(expect, tokens);
+}
+
 #[test]
 fn lex_link() {
     let value = "[[/foo/bar|label]]";
@@ -349,3 +366,86 @@ fn lex_link() {
     ];
     assert_eq!(expect, tokens);
 }
+
+#[test]
+fn lex_spanned() {
+    let value = r#"{{foo bar="baz"}}"#;
+    let tokens: Vec<(Mode, &str)> = Lexer::new(value)
+        .spanned()
+        .map(|t| (t.token().mode(), t.as_str()))
+        .collect();
+    let expect = vec![
+        (Mode::Block, "{{"),
+        (Mode::Parameters, "foo"),
+        (Mode::Parameters, " "),
+        (Mode::Parameters, "bar="),
+        (Mode::Parameters, "\""),
+        (Mode::DoubleQuoteString, "baz"),
+        (Mode::DoubleQuoteString, "\""),
+        (Mode::Parameters, "}}"),
+    ];
+    assert_eq!(expect, tokens);
+}
+
+#[test]
+fn lex_categories() {
+    let value = r#"{{!x}}{{#if @first 1 "a"}}{{else}}x{{/if}}"#;
+    let tokens: Vec<Token> = Lexer::new(value).collect();
+    let select = |f: fn(&Token) -> bool| -> Vec<&str> {
+        tokens
+            .iter()
+            .filter(|t| f(t))
+            .map(|t| &value[t.span().clone()])
+            .collect()
+    };
+    assert_eq!(vec!["{{!", "x", "}}"], select(Token::is_comment));
+    assert_eq!(vec!["if", "@first", "if"], select(Token::is_identifier));
+    assert_eq!(vec!["else"], select(Token::is_keyword));
+    assert_eq!(vec!["1"], select(Token::is_literal));
+    assert_eq!(vec!["\"", "a", "\""], select(Token::is_string));
+    assert_eq!(vec![" ", " ", " "], select(Token::is_whitespace));
+    assert_eq!(vec!["{{!", "{{#", "{{", "{{/"], select(Token::is_open));
+    assert_eq!(vec!["}}", "}}", "}}", "}}"], select(Token::is_close));
+    assert!(!tokens.iter().any(Token::is_error));
+}
+
+#[test]
+fn lex_set_mode() {
+    // Resume lexing a fragment inside a statement
+    let value = r#"name "text"}} after"#;
+    let mut lexer = Lexer::new(value);
+    assert_eq!(Mode::Block, lexer.mode());
+    lexer.set_mode(Mode::Parameters);
+    assert_eq!(Mode::Parameters, lexer.mode());
+    let tokens: Vec<Token> = lexer.collect();
+    let expect = vec![
+        Token::Parameters(Parameters::Identifier, 0..4),
+        Token::Parameters(Parameters::WhiteSpace, 4..5),
+        Token::Parameters(Parameters::DoubleQuoteString, 5..6),
+        Token::DoubleQuoteString(DoubleQuoteString::Text, 6..10),
+        Token::DoubleQuoteString(DoubleQuoteString::End, 10..11),
+        Token::Parameters(Parameters::End, 11..13),
+        Token::Block(Block::Text, 13..14),
+        Token::Block(Block::Text, 14..15),
+        Token::Block(Block::Text, 15..16),
+        Token::Block(Block::Text, 16..17),
+        Token::Block(Block::Text, 17..18),
+        Token::Block(Block::Text, 18..19),
+    ];
+    assert_eq!(expect, tokens);
+}
+
+#[test]
+fn lex_crlf_bom() {
+    let value = "\u{feff}a\r\n{{\r\nb}}";
+    let tokens = lex(value, false);
+    let expect = vec![
+        Token::Block(Block::Text, 3..4),
+        Token::Block(Block::Newline, 4..6),
+        Token::Block(Block::StartStatement, 6..8),
+        Token::Parameters(Parameters::Newline, 8..10),
+        Token::Parameters(Parameters::Identifier, 10..11),
+        Token::Parameters(Parameters::End, 11..13),
+    ];
+    assert_eq!(expect, tokens);
+}
@@ -111,3 +111,43 @@ If can have other {{foo}} statements.
     }
     Ok(())
 }
+
+#[test]
+fn lines_crlf() -> Result<()> {
+    let registry = Registry::new();
+    let value =
+        "{{\r\nfoo\r\n\"message\"\r\n}}\r\n{{#block}}\r\ntext\r\n{{/block}}";
+    let template = registry.parse(NAME, value)?;
+    let mut nodes = template.node().into_iter();
+    if let Some(Node::Statement(call)) = nodes.next() {
+        assert_eq!(0..4, call.lines().clone());
+        assert_eq!(1..2, call.target().lines().clone());
+    } else {
+        panic!("Expecting statement node.");
+    }
+    nodes.next();
+    if let Some(Node::Block(block)) = nodes.next() {
+        assert_eq!(4..7, block.lines().clone());
+    } else {
+        panic!("Expecting block node.");
+    }
+    Ok(())
+}
+
+#[test]
+fn lines_crlf_raw() -> Result<()> {
+    let registry = Registry::new();
+    let value =
+        "\u{feff}{{{{raw}}}}\r\n{{foo}}\r\n{{{{/raw}}}}\r\n{{!--\r\n--}}";
+    let template = registry.parse(NAME, value)?;
+    let data = serde_json::json!({});
+    let result = registry.render_template(NAME, &template, &data)?;
+    assert_eq!("\r\n{{foo}}\r\n\r\n", &result);
+    let mut nodes = template.node().into_iter();
+    if let Some(Node::Block(block)) = nodes.next() {
+        assert_eq!(0..3, block.lines().clone());
+    } else {
+        panic!("Expecting raw block node.");
+    }
+    Ok(())
+}
@@ -0,0 +1,68 @@
+use bracket::{error::SyntaxError, Error, Registry, Result};
+
+const NAME: &str = "lint.rs";
+
+fn messages(errors: &[Error]) -> Vec<String> {
+    errors.iter().map(|e| e.to_string()).collect()
+}
+
+#[test]
+fn lint_trim_no_effect() -> Result<()> {
+    let registry = Registry::new();
+    let errors = registry.lint(NAME, "{{~a}} {{b~}}{{c}}x{{~d}}\n{{e~}}")?;
+    assert_eq!(
+        vec![
+            "Whitespace control has no effect, the tag is at the start of the template",
+            "Whitespace control has no effect, there is another tag after the tag",
+            "Whitespace control has no effect, there is no whitespace before the tag",
+            "Whitespace control has no effect, the tag is at the end of the template",
+        ],
+        messages(&errors)
+    );
+    let debug = format!("{:?}", errors[3]);
+    assert!(debug.contains("--> lint.rs:2:"));
+    Ok(())
+}
+
+#[test]
+fn lint_trim_joins_text() -> Result<()> {
+    let registry = Registry::new();
+    let errors =
+        registry.lint(NAME, "Hello {{~name}}, {{#if x~}} y {{/if}}")?;
+    assert_eq!(1, errors.len());
+    match errors[0] {
+        Error::Syntax(SyntaxError::TrimJoinsText(ref info)) => {
+            assert!(info.contains("remove the '~' to keep the space"))
+        }
+        _ => panic!("expected whitespace lint warning"),
+    }
+
+    let value =
+        "<ul>\n  {{~#each items}}\n    <li>{{this}}</li>\n  {{~/each}}\n</ul>";
+    assert!(registry.lint(NAME, value)?.is_empty());
+    Ok(())
+}
+
+#[test]
+fn lint_unreachable_block() -> Result<()> {
+    let registry = Registry::new();
+    let value = r#"{{#if false}}a{{/if}}
+{{#if 1}}b{{else}}c{{/if}}
+{{#unless "x"}}d{{/unless}}
+{{#each []}}{{#with null}}e{{/with}}{{/each}}
+{{#if flag}}f{{/if}}{{#each items}}g{{/each}}"#;
+    let errors = registry.lint(NAME, value)?;
+    assert_eq!(
+        vec![
+            "Unreachable template in block 'if', the condition is always false",
+            "Unreachable template in block 'if', the else branch is never rendered as the condition is always true",
+            "Unreachable template in block 'unless', the condition is always true",
+            "Unreachable template in block 'each', the value to iterate is always empty",
+            "Unreachable template in block 'with', the value is always null",
+        ],
+        messages(&errors)
+    );
+    let debug = format!("{:?}", errors[3]);
+    assert!(debug.contains("--> lint.rs:4:"));
+    Ok(())
+}
@@ -0,0 +1,133 @@
+#![cfg(feature = "metrics")]
+use bracket::{Registry, Result};
+use metrics::{
+    Counter, Gauge, Histogram, HistogramFn, Key, KeyName, Metadata, Recorder,
+    SharedString, Unit,
+};
+use serde_json::json;
+use std::{
+    collections::HashMap,
+    sync::{
+        atomic::{AtomicU64, Ordering},
+        Arc, Mutex,
+    },
+};
+
+#[derive(Default)]
+struct Samples(Mutex<Vec<f64>>);
+
+impl HistogramFn for Samples {
+    fn record(&self, value: f64) {
+        self.0.lock().unwrap().push(value);
+    }
+}
+
+#[derive(Default)]
+struct TestRecorder {
+    counters: Mutex<HashMap<String, Arc<AtomicU64>>>,
+    histograms: Mutex<HashMap<String, Arc<Samples>>>,
+}
+
+impl TestRecorder {
+    fn counter(&self, key: &str) -> u64 {
+        self.counters
+            .lock()
+            .unwrap()
+            .get(key)
+            .map(|c| c.load(Ordering::SeqCst))
+            .unwrap_or(0)
+    }
+
+    fn samples(&self, key: &str) -> Vec<f64> {
+        self.histograms
+            .lock()
+            .unwrap()
+            .get(key)
+            .map(|h| h.0.lock().unwrap().clone())
+            .unwrap_or_default()
+    }
+}
+
+fn key_name(key: &Key) -> String {
+    let labels: Vec<String> = key
+        .labels()
+        .map(|l| format!("{}={}", l.key(), l.value()))
+        .collect();
+    format!("{}{{{}}}", key.name(), labels.join(","))
+}
+
+impl Recorder for TestRecorder {
+    fn describe_counter(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+    fn describe_gauge(&self, _: KeyName, _: Option<Unit>, _: SharedString) {}
+    fn describe_histogram(&self, _: KeyName, _: Option<Unit>, _: SharedString) {
+    }
+
+    fn register_counter(&self, key: &Key, _: &Metadata<'_>) -> Counter {
+        let counter = Arc::clone(
+            self.counters
+                .lock()
+                .unwrap()
+                .entry(key_name(key))
+                .or_default(),
+        );
+        Counter::from_arc(counter)
+    }
+
+    fn register_gauge(&self, _: &Key, _: &Metadata<'_>) -> Gauge {
+        Gauge::noop()
+    }
+
+    fn register_histogram(&self, key: &Key, _: &Metadata<'_>) -> Histogram {
+        let samples = Arc::clone(
+            self.histograms
+                .lock()
+                .unwrap()
+                .entry(key_name(key))
+                .or_default(),
+        );
+        Histogram::from_arc(samples)
+    }
+}
+
+#[test]
+fn metrics_render() -> Result<()> {
+    let recorder = TestRecorder::default();
+    let mut registry = Registry::new();
+    registry.insert("page", "Hello {{name}}")?;
+    registry.insert("broken", "{{lookup}}")?;
+    metrics::with_local_recorder(&recorder, || -> Result<()> {
+        registry.render("page", &json!({"name": "world"}))?;
+        registry.render("page", &json!({"name": "bracket"}))?;
+        assert!(registry.render("broken", &json!({})).is_err());
+        Ok(())
+    })?;
+
+    let page = "{template=page}";
+    assert_eq!(
+        2,
+        recorder.counter(&format!("bracket_renders_started_total{}", page))
+    );
+    assert_eq!(
+        2,
+        recorder.counter(&format!("bracket_renders_completed_total{}", page))
+    );
+    assert_eq!(
+        vec![11.0, 13.0],
+        recorder.samples(&format!("bracket_render_output_bytes{}", page))
+    );
+    assert_eq!(
+        2,
+        recorder
+            .samples(&format!("bracket_render_duration_seconds{}", page))
+            .len()
+    );
+    assert_eq!(
+        1,
+        recorder.counter("bracket_renders_failed_total{template=broken}")
+    );
+    assert_eq!(
+        1,
+        recorder.counter("bracket_helper_errors_total{helper=lookup}")
+    );
+    Ok(())
+}
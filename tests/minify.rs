@@ -0,0 +1,117 @@
+use bracket::{
+    minify::HtmlMinify,
+    output::{Output, StringOutput},
+    Registry, Result,
+};
+use serde_json::{json, Map};
+
+const NAME: &str = "minify.rs";
+
+#[test]
+fn minify_whitespace() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.set_minify(true);
+    let value = r#"
+<ul>
+    <li class="a  b">{{first}}  and
+        {{second}}</li>
+    <li>x < y</li>
+</ul>
+"#;
+    registry.insert(NAME, value)?;
+    let data = json!({"first": "One", "second": "Two"});
+    let result = registry.render(NAME, &data)?;
+    assert_eq!(
+        r#"<ul> <li class="a  b">One and Two</li> <li>x < y</li> </ul>"#,
+        &result
+    );
+    Ok(())
+}
+
+#[test]
+fn minify_inline_elements() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.set_minify(true);
+    registry.insert(NAME, "<a>One</a>\n<a>Two</a>")?;
+    let result = registry.render(NAME, &json!({}))?;
+    assert_eq!("<a>One</a> <a>Two</a>", &result);
+    Ok(())
+}
+
+#[test]
+fn minify_comments() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.set_minify(true);
+    let value = r#"<p>a <!-- note --> b</p>
+<!--[if IE]><p>IE</p><![endif]-->
+<!--[if !IE]><!--><p>Other</p><!--<![endif]-->"#;
+    registry.insert(NAME, value)?;
+    let result = registry.render(NAME, &json!({}))?;
+    assert_eq!(
+        "<p>a b</p> <!--[if IE]><p>IE</p><![endif]--> \
+         <!--[if !IE]><!--><p>Other</p><!--<![endif]-->",
+        &result
+    );
+    Ok(())
+}
+
+#[test]
+fn minify_raw_elements() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.set_minify(true);
+    let value = "<div>\n  <pre>\n  a  b\n  <b>c</b></pre>\n  \
+                 <textarea>  x  </TEXTAREA>\n  \
+                 <script>// {{code}}\nrun();</script>\n</div>";
+    registry.insert(NAME, value)?;
+    let result = registry.render(NAME, &json!({"code": "a  b"}))?;
+    assert_eq!(
+        "<div> <pre>\n  a  b\n  <b>c</b></pre> <textarea>  x  </TEXTAREA> \
+         <script>// a  b\nrun();</script> </div>",
+        &result
+    );
+    Ok(())
+}
+
+#[test]
+fn minify_output_chunks() -> Result<()> {
+    let mut writer = StringOutput::new();
+    let mut output = HtmlMinify::new(&mut writer);
+    for chunk in ["<p", ">\n  a", "  b <!-", "- c -", "->\n", "</p>\n", "<b"] {
+        output.write_str(chunk)?;
+    }
+    output.finish()?;
+    assert_eq!("<p> a b </p> <b", writer.as_str());
+    Ok(())
+}
+
+#[test]
+fn minify_render_variants() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.set_minify(true);
+    registry.insert(NAME, "<p>\n    {{name}}\n</p>\n")?;
+    let data = json!({"name": "a"});
+    let expected = "<p> a </p>";
+    assert_eq!(expected, &registry.render(NAME, &data)?);
+    assert_eq!(
+        expected,
+        &registry.render_with_locals(NAME, &data, &Map::new())?
+    );
+    assert_eq!(expected, &registry.render_graceful(NAME, &data)?.0);
+    Ok(())
+}
+
+#[cfg(feature = "fragment-helper")]
+#[test]
+fn minify_render_fragment() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.set_minify(true);
+    let value = r#"<ul>{{#fragment "item"}}<li>
+    {{name}}
+</li>{{/fragment}}</ul>"#;
+    registry.insert(NAME, value)?;
+    let data = json!({"name": "a"});
+    assert_eq!("<ul><li> a </li></ul>", &registry.render(NAME, &data)?);
+    let result = registry.render_fragment(NAME, "item", &data)?;
+    assert_eq!("<li> a </li>", &result);
+    Ok(())
+}
@@ -0,0 +1,44 @@
+#![cfg(feature = "now-helper")]
+use bracket::{clock::FixedClock, Error, Registry, Result};
+use serde_json::json;
+
+const NAME: &str = "now.rs";
+
+#[test]
+fn now_fixed() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.set_clock(Box::new(FixedClock::new(1_709_285_400)));
+    let data = json!({});
+    let result = registry.once(NAME, r#"{{now}}"#, &data)?;
+    assert_eq!("2024-03-01T09:30:00Z", &result);
+    let value = r#"{{now format="%d/%m/%Y %H:%M" add_days=-1}}"#;
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!("29/02/2024 09:30", &result);
+    let value = r#"{{now format="%s %%" add_days=7 add_seconds=30}}"#;
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!("1709890230 %", &result);
+    Ok(())
+}
+
+#[test]
+fn now_before_epoch() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.set_clock(Box::new(FixedClock::new(-1)));
+    let result = registry.once(NAME, r#"{{now}}"#, &json!({}))?;
+    assert_eq!("1969-12-31T23:59:59Z", &result);
+    Ok(())
+}
+
+#[test]
+fn now_format_error() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.set_clock(Box::new(FixedClock::new(0)));
+    match registry.once(NAME, r#"{{now format="%Q"}}"#, &json!({})) {
+        Err(Error::Render(e)) => assert_eq!(
+            "Helper 'now' got unknown format specifier '%Q'",
+            e.to_string()
+        ),
+        _ => panic!("expected a render error"),
+    }
+    Ok(())
+}
@@ -0,0 +1,43 @@
+#![cfg(feature = "once-helper")]
+use bracket::{Registry, Result};
+use serde_json::json;
+
+const NAME: &str = "once.rs";
+
+#[test]
+fn once_partial() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.insert(
+        "picker",
+        r#"{{#once "picker"}}<script src="picker.js"></script>{{/once}}<input name="{{name}}">"#,
+    )?;
+    let value = r#"{{#each fields}}{{> picker}}{{/each}}"#;
+    let data = json!({"fields": [{"name": "from"}, {"name": "to"}]});
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!(
+        r#"<script src="picker.js"></script><input name="from"><input name="to">"#,
+        &result
+    );
+    Ok(())
+}
+
+#[test]
+fn once_else() -> Result<()> {
+    let registry = Registry::new();
+    let value = r#"{{#each items}}{{#once "head"}}[{{else}},{{/once}}{{this}}{{/each}}"#;
+    let data = json!({"items": [1, 2, 3]});
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!("[1,2,3", &result);
+    Ok(())
+}
+
+#[test]
+fn once_per_render() -> Result<()> {
+    let mut registry = Registry::new();
+    registry
+        .insert("page", r#"{{#once "a"}}A{{/once}}{{#once "a"}}A{{/once}}"#)?;
+    let data = json!({});
+    assert_eq!("A", &registry.render("page", &data)?);
+    assert_eq!("A", &registry.render("page", &data)?);
+    Ok(())
+}
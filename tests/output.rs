@@ -0,0 +1,157 @@
+use bracket::{
+    error::RenderError,
+    helper::prelude::*,
+    output::{
+        BytesOutput, Newline, Output, StringOutput, TrailingNewline, Writer,
+    },
+    Error, Registry, Result,
+};
+use serde_json::json;
+
+const NAME: &str = "output.rs";
+
+pub struct BytesHelper;
+
+impl Helper for BytesHelper {
+    fn call<'render, 'call>(
+        &self,
+        rc: &mut Render<'render>,
+        _ctx: &Context<'call>,
+        _template: Option<&'render Node<'render>>,
+    ) -> HelperValue {
+        rc.write_bytes(b"<bytes>")?;
+        Ok(None)
+    }
+}
+
+#[test]
+fn output_write_bytes_writer() -> Result<()> {
+    let mut buffer: Vec<u8> = Vec::new();
+    let mut writer = Writer::new(&mut buffer);
+    writer.write_bytes(&[0xff, 0x00])?;
+    writer.write_str("foo")?;
+    assert_eq!(vec![0xff, 0x00, b'f', b'o', b'o'], buffer);
+    Ok(())
+}
+
+#[test]
+fn output_write_bytes_string() -> Result<()> {
+    let mut writer = StringOutput::new();
+    writer.write_bytes(b"foo")?;
+    assert!(writer.write_bytes(&[0xff, 0x00]).is_err());
+    let result: String = writer.into();
+    assert_eq!("foo", &result);
+    Ok(())
+}
+
+#[test]
+fn output_write_bytes_helper() -> Result<()> {
+    let mut registry = Registry::new();
+    registry
+        .helpers_mut()
+        .insert("bytes", Box::new(BytesHelper {}));
+    let value = r"{{bytes}}";
+    let data = json!({});
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!("<bytes>", &result);
+    Ok(())
+}
+
+#[test]
+fn output_bytes_escape() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.set_escape_write(Box::new(|s, out| {
+        let mut written = 0;
+        for b in s.bytes() {
+            written += match b {
+                b'\n' => out.write_bytes(&[0xff, b'n'])?,
+                _ => out.write_bytes(&[b])?,
+            }
+        }
+        Ok(written)
+    }));
+    registry.insert(NAME, "<{{foo}}>")?;
+    let data = json!({"foo": "a\nb"});
+    let result = registry.render_bytes(NAME, &data)?;
+    assert_eq!(b"<a\xffnb>".to_vec(), result);
+    assert!(registry.render(NAME, &data).is_err());
+    Ok(())
+}
+
+#[test]
+fn output_bytes_latin1() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.insert(NAME, "Grüße {{name}}")?;
+    let mut writer = BytesOutput::latin1();
+    registry.render_to_write(NAME, &json!({"name": "Zoë"}), &mut writer)?;
+    assert_eq!(b"Gr\xfc\xdfe Zo\xeb", writer.as_bytes());
+
+    let mut writer = BytesOutput::latin1();
+    let result =
+        registry.render_to_write(NAME, &json!({"name": "☃"}), &mut writer);
+    match result {
+        Err(Error::Render(RenderError::Io(_, location))) => {
+            assert!(location.is_some())
+        }
+        _ => panic!("expected encoding error"),
+    }
+    Ok(())
+}
+
+#[test]
+fn output_newline_normalize() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.insert("footer", "c\r\nd\re")?;
+    registry.insert(NAME, "a\r\n{{foo}}\n{{> footer}}\n")?;
+    let data = json!({"foo": "x\ry\nz\r"});
+    assert_eq!("a\r\nx\ry\nz\r\nc\r\nd\re\n", registry.render(NAME, &data)?);
+
+    registry.set_newline(Newline::Lf);
+    assert_eq!("a\nx\ny\nz\nc\nd\ne\n", registry.render(NAME, &data)?);
+
+    registry.set_newline(Newline::CrLf);
+    assert_eq!(
+        "a\r\nx\r\ny\r\nz\r\nc\r\nd\r\ne\r\n",
+        registry.render(NAME, &data)?
+    );
+    Ok(())
+}
+
+#[test]
+fn output_newline_split() {
+    let mut after_cr = false;
+    let newline = Newline::Lf;
+    assert_eq!("a\n", newline.normalize("a\r", &mut after_cr));
+    assert!(after_cr);
+    assert_eq!("b", newline.normalize("\nb", &mut after_cr));
+    assert!(!after_cr);
+    assert_eq!("\nc", newline.normalize("\nc", &mut after_cr));
+    assert_eq!("\n", Newline::PassThrough.normalize("\n", &mut after_cr));
+}
+
+#[test]
+fn output_trailing_newline() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.insert("footer", "{{foo}}\n\n")?;
+    registry.insert(NAME, "a\n\n{{> footer}}")?;
+    registry.insert("empty", "{{foo}}")?;
+    let data = json!({"foo": "b\r\n"});
+    assert_eq!("a\n\nb\r\n\n\n", registry.render(NAME, &data)?);
+
+    registry.set_trailing_newline(TrailingNewline::Single);
+    assert_eq!("a\n\nb\r\n", registry.render(NAME, &data)?);
+    assert_eq!(b"a\n\nb\r\n".to_vec(), registry.render_bytes(NAME, &data)?);
+    assert_eq!("", registry.render("empty", &json!({}))?);
+    assert_eq!("x\n", registry.once("once", "x", &data)?);
+
+    registry.set_trailing_newline(TrailingNewline::Strip);
+    assert_eq!("a\n\nb", registry.render(NAME, &data)?);
+
+    registry
+        .templates_mut()
+        .get_mut(NAME)
+        .unwrap()
+        .set_trailing_newline(Some(TrailingNewline::Preserve));
+    assert_eq!("a\n\nb\r\n\n\n", registry.render(NAME, &data)?);
+    Ok(())
+}
@@ -0,0 +1,103 @@
+use bracket::{
+    helper::paginate::{Pagination, MAX_WINDOW},
+    Registry, Result,
+};
+use serde_json::json;
+
+const NAME: &str = "paginate.rs";
+
+#[test]
+fn paginate_values() -> Result<()> {
+    let registry = Registry::new();
+    let value = r"{{#paginate total 10 page}}{{@page}}/{{@totalPages}} {{@offset}} {{@hasPrev}} {{@hasNext}} {{@prev}} {{@next}}{{/paginate}}";
+    let data = json!({"total": 95, "page": 3});
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!("3/10 20 true true 2 4", &result);
+    Ok(())
+}
+
+#[test]
+fn paginate_window() -> Result<()> {
+    let registry = Registry::new();
+    let value = r"{{#paginate 200 10 page window=3}}{{#each @pages}}{{this}} {{/each}}{{/paginate}}";
+    let result = registry.once(NAME, value, &json!({"page": 1}))?;
+    assert_eq!("1 2 3 ", &result);
+    let result = registry.once(NAME, value, &json!({"page": 7}))?;
+    assert_eq!("6 7 8 ", &result);
+    let result = registry.once(NAME, value, &json!({"page": 20}))?;
+    assert_eq!("18 19 20 ", &result);
+    Ok(())
+}
+
+#[test]
+fn paginate_bounds() -> Result<()> {
+    let registry = Registry::new();
+    let value = r"{{#paginate total 10 99}}{{@page}} {{#unless @hasNext}}last{{/unless}}{{/paginate}}";
+    let data = json!({"total": 0});
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!("1 last", &result);
+    Ok(())
+}
+
+#[test]
+fn paginate_this() -> Result<()> {
+    let registry = Registry::new();
+    let value = r"{{#paginate count 2}}{{title}}{{/paginate}}";
+    let data = json!({"title": "List", "count": 3});
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!("List", &result);
+    Ok(())
+}
+
+#[test]
+fn paginate_size_err() -> Result<()> {
+    let registry = Registry::new();
+    let value = r"{{#paginate 10 0}}{{/paginate}}";
+    let data = json!({});
+    match registry.once(NAME, value, &data) {
+        Ok(_) => panic!("Numerical operand error expected"),
+        Err(e) => assert_eq!(
+            "Helper 'paginate' got invalid numerical operand",
+            e.to_string()
+        ),
+    }
+    Ok(())
+}
+
+#[test]
+fn paginate_overflow() -> Result<()> {
+    let registry = Registry::new();
+    let value = r"{{#paginate 18446744073709551615 1 18446744073709551615 window=3}}{{#each @pages}}{{this}} {{/each}}{{/paginate}}";
+    let result = registry.once(NAME, value, &json!({}))?;
+    assert_eq!(
+        "18446744073709551613 18446744073709551614 18446744073709551615 ",
+        &result
+    );
+    Ok(())
+}
+
+#[test]
+fn paginate_window_err() -> Result<()> {
+    let registry = Registry::new();
+    let value = r"{{#paginate 18446744073709551615 1 18446744073709551615 window=18446744073709551615}}{{/paginate}}";
+    match registry.once(NAME, value, &json!({})) {
+        Ok(_) => panic!("Window error expected"),
+        Err(e) => assert_eq!(
+            "Helper 'paginate' window exceeds 1000 pages",
+            e.to_string()
+        ),
+    }
+    Ok(())
+}
+
+#[test]
+fn paginate_struct() {
+    let pagination = Pagination::new(25, 10, 3, 5).unwrap();
+    assert_eq!(3, pagination.total_pages);
+    assert_eq!(vec![1, 2, 3], pagination.pages);
+    assert!(pagination.has_prev());
+    assert!(!pagination.has_next());
+    assert!(Pagination::new(25, 0, 1, 5).is_none());
+    let pagination = Pagination::new(u64::MAX, 1, 1, u64::MAX).unwrap();
+    assert_eq!(MAX_WINDOW as usize, pagination.pages.len());
+}
@@ -283,6 +283,34 @@ fn parse_arg_string() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn parse_hash_order() -> Result<()> {
+    let value = r#"{{foo zeta=1 alpha=2 mid=3 alpha=4}}"#;
+    let mut parser = Parser::new(value, Default::default());
+    let node = parser.parse()?;
+
+    match node {
+        Node::Document(doc) => match doc.nodes().first().unwrap() {
+            Node::Statement(ref call) => {
+                let hash = call.parameters();
+                assert_eq!(3, hash.len());
+                assert_eq!(
+                    vec!["zeta", "alpha", "mid"],
+                    hash.keys().copied().collect::<Vec<_>>()
+                );
+                assert_eq!("4", hash.get("alpha").unwrap().as_str());
+                let values: Vec<&str> =
+                    hash.iter().map(|(_, v)| v.as_str()).collect();
+                assert_eq!(vec!["1", "4", "3"], values);
+            }
+            _ => panic!("Expecting statement node."),
+        },
+        _ => panic!("Bad root node type for parser()."),
+    }
+
+    Ok(())
+}
+
 #[test]
 fn parse_hash_string() -> Result<()> {
     let value = r#"{{foo bar="baz"}}"#;
@@ -691,3 +719,38 @@ fn parse_raw_block() -> Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn parse_raw_block_parameters() -> Result<()> {
+    let value =
+        r#"{{{{highlight code lang="rust"}}}}{{foo bar=1}}{{{{/highlight}}}}"#;
+    let mut parser = Parser::new(value, Default::default());
+    let node = parser.parse()?;
+
+    match node {
+        Node::Document(doc) => match doc.nodes().first().unwrap() {
+            Node::Block(ref block) => {
+                assert!(block.is_raw());
+                let call = block.call();
+                assert_eq!("highlight", call.target().as_str());
+                assert_eq!(1, call.arguments().len());
+                assert_eq!("code", call.arguments()[0].as_str());
+                assert_eq!(
+                    r#""rust""#,
+                    call.parameters().get("lang").unwrap().as_str()
+                );
+                assert_eq!(1, block.nodes().len());
+                match block.nodes().first().unwrap() {
+                    Node::Text(ref text) => {
+                        assert_eq!("{{foo bar=1}}", text.as_str())
+                    }
+                    _ => panic!("Expecting text node."),
+                }
+            }
+            _ => panic!("Expecting block node."),
+        },
+        _ => panic!("Bad root node type for parser()."),
+    }
+
+    Ok(())
+}
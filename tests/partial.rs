@@ -1,5 +1,6 @@
-use bracket::{Registry, Result};
+use bracket::{parser::ParserOptions, Registry, Result};
 use serde_json::json;
+use std::collections::HashMap;
 
 const NAME: &str = "partial.rs";
 
@@ -62,3 +63,93 @@ fn partial_context_parameter() -> Result<()> {
     assert_eq!("xyz", &result);
     Ok(())
 }
+
+#[test]
+fn partial_template_string() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.insert("footer", "registered")?;
+
+    let mut partials = HashMap::new();
+    partials.insert("header", "Hello {{name}}");
+    partials.insert("footer", "{{> signature}}");
+    partials.insert("signature", "Sent to {{email}}");
+
+    let value = r"{{> header}}, {{> footer}}";
+    let data = json!({"name": "world", "email": "foo@example.com"});
+    let options = ParserOptions::new(NAME.to_string(), 0, 0);
+    let result =
+        registry.render_template_string(value, &data, options, partials)?;
+    assert_eq!("Hello world, Sent to foo@example.com", &result);
+    assert!(registry.get("header").is_none());
+    Ok(())
+}
+
+#[test]
+fn partial_hash_merge() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.insert("button", r#"<a class="{{kind}}">{{label}}</a>"#)?;
+
+    let value = r#"{{> button label="OK"}}"#;
+    let data = json!({"kind": "primary", "label": "Cancel"});
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!(r#"<a class="primary">OK</a>"#, &result);
+    Ok(())
+}
+
+#[test]
+fn partial_hash_this() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.insert("fields", "{{#each this}}{{@key}}={{this}};{{/each}}")?;
+
+    let value = r#"{{#with item}}{{> fields label="OK"}}{{/with}}"#;
+    let data = json!({"item": {"kind": "primary"}});
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!("kind=primary;label=OK;", &result);
+    Ok(())
+}
+
+#[test]
+fn partial_current_context() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.insert("item", "{{this}}:{{.}};")?;
+
+    let value = r#"{{#each items}}{{> item}}{{/each}}"#;
+    let data = json!({"items": ["a", "b"]});
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!("a:a;b:b;", &result);
+    Ok(())
+}
+
+#[test]
+fn partial_hash_isolated() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.insert("button", r#"<a class="{{kind}}">{{label}}</a>"#)?;
+
+    let value = r#"{{> button label="OK" isolated=true}}"#;
+    let data = json!({"kind": "primary"});
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!(r#"<a class="">OK</a>"#, &result);
+    Ok(())
+}
+
+#[test]
+fn partial_hash_isolated_context() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.insert("foo", "{{bar}}-{{baz}}-{{@root.baz}}")?;
+
+    let value = r#"{{> foo ctx isolated=true}}"#;
+    let data = json!({"baz": "qux", "ctx": {"bar": "baz"}});
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!("baz--qux", &result);
+    Ok(())
+}
+
+#[test]
+fn partial_hash_isolated_type() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.insert("foo", "{{bar}}")?;
+
+    let value = r#"{{> foo isolated="yes"}}"#;
+    assert!(registry.once(NAME, value, &json!({})).is_err());
+    Ok(())
+}
@@ -0,0 +1,166 @@
+use bracket::{
+    error::{Error, ErrorInfo, SourcePos, SyntaxError},
+    helper::prelude::*,
+    Registry, Result,
+};
+use serde_json::{json, Value};
+
+const NAME: &str = "pipe.rs";
+
+pub struct UpperHelper;
+
+impl Helper for UpperHelper {
+    fn call<'render, 'call>(
+        &self,
+        _rc: &mut Render<'render>,
+        ctx: &Context<'call>,
+        _template: Option<&'render Node<'render>>,
+    ) -> HelperValue {
+        ctx.arity(1..1)?;
+        let value = ctx.try_get(0, &[Type::String])?.as_str().unwrap();
+        Ok(Some(Value::String(value.to_uppercase())))
+    }
+}
+
+pub struct TruncateHelper;
+
+impl Helper for TruncateHelper {
+    fn call<'render, 'call>(
+        &self,
+        _rc: &mut Render<'render>,
+        ctx: &Context<'call>,
+        _template: Option<&'render Node<'render>>,
+    ) -> HelperValue {
+        ctx.arity(2..2)?;
+        let value = ctx.try_get(0, &[Type::String])?.as_str().unwrap();
+        let amount = ctx.try_get(1, &[Type::Number])?.as_u64().unwrap();
+        Ok(Some(Value::String(
+            value.chars().take(amount as usize).collect(),
+        )))
+    }
+}
+
+fn registry<'reg>() -> Registry<'reg> {
+    let mut registry = Registry::new();
+    registry
+        .helpers_mut()
+        .insert("upper", Box::new(UpperHelper {}));
+    registry
+        .helpers_mut()
+        .insert("truncate", Box::new(TruncateHelper {}));
+    registry
+}
+
+#[test]
+fn pipe_filter() -> Result<()> {
+    let registry = registry();
+    let value = r"{{ name | upper }}";
+    let data = json!({"name": "world"});
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!("WORLD", &result);
+    Ok(())
+}
+
+#[test]
+fn pipe_chain_arguments() -> Result<()> {
+    let registry = registry();
+    let value = r"{{name|upper|truncate 3}}";
+    let data = json!({"name": "world"});
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!("WOR", &result);
+    Ok(())
+}
+
+#[test]
+fn pipe_helper_call() -> Result<()> {
+    let registry = registry();
+    let value = r#"{{lookup map "key" | upper}}"#;
+    let data = json!({"map": {"key": "value"}});
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!("VALUE", &result);
+    Ok(())
+}
+
+#[test]
+fn pipe_sub_expr() -> Result<()> {
+    let registry = registry();
+    let value = r"{{#if (flag | not)}}{{name | upper}}{{/if}}";
+    let data = json!({"flag": false, "name": "world"});
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!("WORLD", &result);
+    Ok(())
+}
+
+#[test]
+fn pipe_escape() -> Result<()> {
+    let registry = registry();
+    let data = json!({"name": "<b>"});
+    let result = registry.once(NAME, r"{{name | upper}}", &data)?;
+    assert_eq!("&lt;B&gt;", &result);
+    let result = registry.once(NAME, r"{{{name | upper}}}", &data)?;
+    assert_eq!("<B>", &result);
+    Ok(())
+}
+
+#[test]
+fn pipe_display() -> Result<()> {
+    let registry = registry();
+    let value = r"{{ name | upper | truncate 3 }}";
+    let template = registry.parse(NAME, value)?;
+    assert_eq!(value, template.to_string());
+    Ok(())
+}
+
+#[test]
+fn pipe_partial_not_allowed() -> Result<()> {
+    let registry = registry();
+    let value = r"{{> partial | upper}}";
+    match registry.parse(NAME, value) {
+        Ok(_) => panic!("Pipe not allowed error expected (partial)"),
+        Err(e) => {
+            let pos = SourcePos(0, 12);
+            let info = ErrorInfo::new(value, NAME, pos, vec![]);
+            assert_eq!(
+                Error::Syntax(SyntaxError::PipeNotAllowed(info.into())),
+                e
+            );
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn pipe_block_not_allowed() -> Result<()> {
+    let registry = registry();
+    let value = r"{{#each list | upper}}{{/each}}";
+    match registry.parse(NAME, value) {
+        Ok(_) => panic!("Pipe not allowed error expected (block)"),
+        Err(e) => {
+            let pos = SourcePos(0, 13);
+            let info = ErrorInfo::new(value, NAME, pos, vec![]);
+            assert_eq!(
+                Error::Syntax(SyntaxError::PipeNotAllowed(info.into())),
+                e
+            );
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn pipe_filter_expected() -> Result<()> {
+    let registry = registry();
+    let value = r"{{name | }}";
+    match registry.parse(NAME, value) {
+        Ok(_) => panic!("Identifier error expected (empty filter)"),
+        Err(e) => {
+            let pos = SourcePos(0, 9);
+            let info = ErrorInfo::new(value, NAME, pos, vec![]);
+            assert_eq!(
+                Error::Syntax(SyntaxError::ExpectedIdentifier(info.into())),
+                e
+            );
+        }
+    }
+    Ok(())
+}
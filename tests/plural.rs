@@ -0,0 +1,64 @@
+#![cfg(feature = "plural-helper")]
+use bracket::{Error, Registry, Result};
+use serde_json::json;
+
+const NAME: &str = "plural.rs";
+
+#[test]
+fn plural_forms() -> Result<()> {
+    let registry = Registry::new();
+    let value = r#"{{#each counts}}{{this}} {{plural this "item" "items"}}/{{plural this "file"}};{{/each}}"#;
+    let data = json!({"counts": [0, 1, 2, 1.5]});
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!(
+        "0 items/files;1 item/file;2 items/files;1.5 items/files;",
+        &result
+    );
+    Ok(())
+}
+
+#[cfg(not(feature = "i18n"))]
+#[test]
+fn plural_locale_requires_feature() -> Result<()> {
+    let registry = Registry::new();
+    match registry.once(
+        NAME,
+        r#"{{plural 1 locale="ru" other="x"}}"#,
+        &json!({}),
+    ) {
+        Err(Error::Render(e)) => assert_eq!(
+            "Helper 'plural' requires the i18n feature for locale rules",
+            e.to_string()
+        ),
+        _ => panic!("expected a render error"),
+    }
+    Ok(())
+}
+
+#[cfg(feature = "i18n")]
+#[test]
+fn plural_locale() -> Result<()> {
+    use bracket::helper::plural::category;
+    assert_eq!("one", category("ru", 21));
+    assert_eq!("few", category("ru-RU", 22));
+    assert_eq!("many", category("ru", 12));
+    assert_eq!("one", category("fr", 0));
+    assert_eq!("other", category("pt-PT", 0));
+    assert_eq!("other", category("ja", 1));
+    assert_eq!("many", category("ar", 11));
+
+    let registry = Registry::new();
+    let value = r#"{{#each counts}}{{plural this locale="pl" one="plik" few="pliki" many="plików"}},{{/each}}"#;
+    let data = json!({"counts": [1, 3, 5, 22]});
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!("plik,pliki,plików,pliki,", &result);
+
+    match registry.once(NAME, r#"{{plural 2 locale="ru" one="a"}}"#, &data) {
+        Err(Error::Render(e)) => assert_eq!(
+            "Helper 'plural' requires the 'few' or 'other' form",
+            e.to_string()
+        ),
+        _ => panic!("expected a render error"),
+    }
+    Ok(())
+}
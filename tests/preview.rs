@@ -0,0 +1,70 @@
+#![cfg(feature = "preview")]
+use bracket::preview::{Preview, RELOAD};
+use std::path::PathBuf;
+
+fn directory(name: &str) -> PathBuf {
+    let dir = std::env::temp_dir().join(format!("bracket-preview-{}", name));
+    let _ = std::fs::remove_dir_all(&dir);
+    std::fs::create_dir_all(&dir).unwrap();
+    dir
+}
+
+#[test]
+fn preview_data_file() {
+    let dir = directory("data");
+    std::fs::write(dir.join("card.hbs"), "<h1>{{title}}</h1>").unwrap();
+    std::fs::write(dir.join("card.json"), r#"{"title": "Hello"}"#).unwrap();
+    let preview = Preview::new(&dir);
+    let page = preview.respond("/card");
+    assert_eq!(200, page.status);
+    assert!(page.body.starts_with("<h1>Hello</h1><script>"));
+    assert!(page.body.contains(RELOAD));
+}
+
+#[test]
+fn preview_skeleton() {
+    let dir = directory("skeleton");
+    std::fs::write(dir.join("card.hbs"), "<h1>{{title}}</h1>").unwrap();
+    let preview = Preview::new(&dir);
+    let page = preview.respond("/card");
+    assert!(page.body.starts_with("<h1>title</h1>"));
+}
+
+#[test]
+fn preview_index_and_missing() {
+    let dir = directory("index");
+    std::fs::write(dir.join("card.hbs"), "").unwrap();
+    std::fs::write(dir.join("list.hbs"), "").unwrap();
+    let preview = Preview::new(&dir);
+    let page = preview.respond("/");
+    assert!(page.body.contains(r#"<a href="/card">card</a>"#));
+    assert!(page.body.contains(r#"<a href="/list">list</a>"#));
+    assert_eq!(404, preview.respond("/page").status);
+}
+
+#[test]
+fn preview_reload() {
+    let dir = directory("reload");
+    std::fs::write(dir.join("card.hbs"), "{{title}}").unwrap();
+    let preview = Preview::new(&dir);
+    let stamp = preview.respond(RELOAD).body;
+    assert!(preview.respond("/page").status == 404);
+
+    std::fs::write(dir.join("page.hbs"), "<p>{{> card}}</p>").unwrap();
+    std::fs::write(dir.join("page.json"), r#"{"title": "Hello"}"#).unwrap();
+    assert_ne!(stamp, preview.respond(RELOAD).body);
+    let page = preview.respond("/page");
+    assert_eq!(200, page.status);
+    assert!(page.body.starts_with("<p>Hello</p>"));
+}
+
+#[test]
+fn preview_error() {
+    let dir = directory("error");
+    std::fs::write(dir.join("card.hbs"), "{{title}}").unwrap();
+    std::fs::write(dir.join("card.json"), "{").unwrap();
+    let preview = Preview::new(&dir);
+    let page = preview.respond("/card");
+    assert_eq!(500, page.status);
+    assert!(page.body.starts_with("<!doctype html>\n<pre>"));
+}
@@ -0,0 +1,56 @@
+use bracket::{Registry, Result};
+use serde_json::json;
+
+#[test]
+fn profile_render() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.insert("item", "<li>{{this}}</li>")?;
+    registry.insert(
+        "page",
+        "<ul>{{#each items}}{{> item}}{{/each}}</ul>{{json items}}",
+    )?;
+    let data = json!({"items": [1, 2, 3]});
+    let (output, profile) = registry.render_profiled("page", &data)?;
+    assert_eq!(registry.render("page", &data)?, output);
+
+    assert_eq!(3, profile.partials.get("item").unwrap().count);
+    assert_eq!(1, profile.helpers.get("each").unwrap().count);
+    assert_eq!(1, profile.helpers.get("json").unwrap().count);
+    assert_eq!(1, profile.nodes.get("block").unwrap().count);
+    assert_eq!(7, profile.nodes.get("statement").unwrap().count);
+
+    assert_eq!(4, profile.frames.len());
+    let block = &profile.frames[1];
+    assert_eq!("block", block.kind);
+    assert_eq!("{{#each items}}", block.label);
+    assert_eq!("page", block.name);
+    assert_eq!(3, block.children.len());
+    let partial = &block.children[0];
+    assert_eq!("{{> item}}", partial.label);
+    assert_eq!("item", partial.children[1].name);
+    assert!(block.elapsed >= partial.elapsed);
+
+    let folded = profile.folded();
+    assert!(folded.lines().any(|l| l.starts_with(
+        "page:1 {{#each items}};page:1 {{> item}};item:1 {{this}} "
+    )));
+    Ok(())
+}
+
+#[test]
+fn profile_missing() {
+    let registry = Registry::new();
+    assert!(registry.render_profiled("page", &json!({})).is_err());
+}
+
+#[test]
+fn profile_minify() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.set_minify(true);
+    registry.insert("page", "<p>\n    {{name}}\n</p>\n")?;
+    let data = json!({"name": "a"});
+    let (output, profile) = registry.render_profiled("page", &data)?;
+    assert_eq!("<p> a </p>", &output);
+    assert_eq!(1, profile.nodes.get("statement").unwrap().count);
+    Ok(())
+}
@@ -0,0 +1,63 @@
+use bracket::{
+    error::PathError,
+    query::{self, ValuePath},
+};
+use serde_json::{json, Value};
+
+#[test]
+fn query_parse() -> Result<(), PathError> {
+    let path: ValuePath = r"users[0].[first name].a\.b.[x\]\\y]".parse()?;
+    assert_eq!(
+        vec!["users", "0", "first name", "a.b", r"x]\y"],
+        path.segments().collect::<Vec<_>>()
+    );
+    assert_eq!(r"users.0[first name][a.b][x\]\\y]", path.to_string());
+    assert_eq!(path, path.to_string().parse()?);
+    assert!(ValuePath::parse("")?.is_empty());
+
+    assert_eq!(Err(PathError::EmptySegment(2)), ValuePath::parse("a..b"));
+    assert_eq!(Err(PathError::EmptySegment(2)), ValuePath::parse("a."));
+    assert_eq!(
+        Err(PathError::UnterminatedBracket(1)),
+        ValuePath::parse("a[b")
+    );
+    assert_eq!(
+        Err(PathError::ExpectedDelimiter(4)),
+        ValuePath::parse("a[b]c")
+    );
+    assert_eq!(Err(PathError::TrailingEscape(1)), ValuePath::parse(r"a\"));
+    Ok(())
+}
+
+#[test]
+fn query_get() -> Result<(), PathError> {
+    let data = json!({
+        "users": [{"first name": "alice", "tags": ["a"]}],
+        "a.b": true
+    });
+    assert_eq!(
+        Some(&json!("alice")),
+        query::get(&data, "users.0.[first name]")?
+    );
+    assert_eq!(Some(&json!("a")), query::get(&data, "users[0].tags[0]")?);
+    assert_eq!(Some(&json!(true)), query::get(&data, r"a\.b")?);
+    assert_eq!(Some(&data), query::get(&data, "")?);
+    assert_eq!(None, query::get(&data, "users.1")?);
+    assert_eq!(None, query::get(&data, "users.0.tags.0.length")?);
+    Ok(())
+}
+
+#[test]
+fn query_get_mut() -> Result<(), PathError> {
+    let mut data = json!({"users": [{"active": true}]});
+    let path = ValuePath::parse("users.0.active")?;
+    if let Some(active) = path.get_mut(&mut data) {
+        *active = Value::Bool(false);
+    }
+    assert_eq!(json!({"users": [{"active": false}]}), data);
+    if let Some(users) = query::get_mut(&mut data, "users")? {
+        users.as_array_mut().unwrap().clear();
+    }
+    assert_eq!(json!({"users": []}), data);
+    Ok(())
+}
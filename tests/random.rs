@@ -0,0 +1,54 @@
+#![cfg(feature = "random-helper")]
+use bracket::{random::SeededRng, Error, Registry, Result};
+use serde_json::json;
+
+const NAME: &str = "random.rs";
+
+#[test]
+fn random_seeded() -> Result<()> {
+    let mut registry = Registry::new();
+    let data = json!({"colors": ["red", "green", "blue"]});
+    let value = r#"{{uuid}}|{{random}}|{{random 1 6}}|{{random colors}}"#;
+    registry.set_rng(Box::new(SeededRng::new(42)));
+    let first = registry.once(NAME, value, &data)?;
+    registry.set_rng(Box::new(SeededRng::new(42)));
+    let second = registry.once(NAME, value, &data)?;
+    assert_eq!(first, second);
+
+    let parts: Vec<&str> = first.split('|').collect();
+    let uuid = parts[0];
+    assert_eq!(36, uuid.len());
+    assert_eq!(Some('4'), uuid.chars().nth(14));
+    assert!("89ab".contains(uuid.chars().nth(19).unwrap()));
+    let float: f64 = parts[1].parse().unwrap();
+    assert!((0.0..1.0).contains(&float));
+    let die: i64 = parts[2].parse().unwrap();
+    assert!((1..=6).contains(&die));
+    assert!(["red", "green", "blue"].contains(&parts[3]));
+    Ok(())
+}
+
+#[test]
+fn random_child() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.set_rng(Box::new(SeededRng::new(7)));
+    let expected = registry.once(NAME, "{{uuid}}", &json!({}))?;
+    registry.set_rng(Box::new(SeededRng::new(7)));
+    let child = registry.child();
+    let result = child.once(NAME, "{{uuid}}", &json!({}))?;
+    assert_eq!(expected, result);
+    Ok(())
+}
+
+#[test]
+fn random_range_error() -> Result<()> {
+    let registry = Registry::new();
+    match registry.once(NAME, r#"{{random 6 1}}"#, &json!({})) {
+        Err(Error::Render(e)) => assert_eq!(
+            "Helper 'random' got a minimum greater than the maximum",
+            e.to_string()
+        ),
+        _ => panic!("expected a render error"),
+    }
+    Ok(())
+}
@@ -0,0 +1,44 @@
+#![cfg(feature = "range-helper")]
+use bracket::{Error, Registry, Result};
+use serde_json::json;
+
+const NAME: &str = "range.rs";
+
+#[test]
+fn range_numbers() -> Result<()> {
+    let registry = Registry::new();
+    let data = json!({"pages": 4});
+    let each = |expr: &str| {
+        let value =
+            format!("{{{{#each ({})}}}}{{{{this}}}},{{{{/each}}}}", expr);
+        registry.once(NAME, &value, &data)
+    };
+    assert_eq!("1,2,3,4,", each("range 1 pages")?);
+    assert_eq!("1,3,5,7,9,", each("range 1 10 step=2")?);
+    assert_eq!("3,1,-1,", each("range 3 -1 step=-2")?);
+    assert_eq!("5,", each("range 5 5")?);
+    Ok(())
+}
+
+#[test]
+fn range_errors() -> Result<()> {
+    let registry = Registry::new();
+    let data = json!({});
+    let message = |value: &str| match registry.once(NAME, value, &data) {
+        Err(Error::Render(e)) => e.to_string(),
+        _ => panic!("expected a render error"),
+    };
+    assert_eq!(
+        "Helper 'range' step must not be zero",
+        message("{{range 1 2 step=0}}")
+    );
+    assert_eq!(
+        "Helper 'range' expects integer arguments",
+        message("{{range 1.5 2}}")
+    );
+    assert_eq!(
+        "Helper 'range' range exceeds 10000 numbers",
+        message("{{range 0 10000}}")
+    );
+    Ok(())
+}
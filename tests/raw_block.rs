@@ -68,3 +68,51 @@ text";
     assert_eq!(expected, result);
     Ok(())
 }
+
+#[derive(Clone)]
+pub struct FenceHelper;
+
+impl Helper for FenceHelper {
+    fn call<'render, 'call>(
+        &self,
+        rc: &mut Render<'render>,
+        ctx: &Context<'call>,
+        _template: Option<&'render Node<'render>>,
+    ) -> HelperValue {
+        let lang = ctx
+            .param("lang")
+            .and_then(|v| v.as_str())
+            .unwrap_or_default();
+        let title = ctx.get(0).and_then(|v| v.as_str()).unwrap_or_default();
+        rc.write(&format!("```{} {}\n", lang, title))?;
+        if let Some(text) = ctx.text() {
+            rc.write(text)?;
+        }
+        rc.write("\n```")?;
+        Ok(None)
+    }
+}
+
+#[test]
+fn raw_block_parameters() -> Result<()> {
+    let mut registry = Registry::new();
+    registry
+        .helpers_mut()
+        .insert("fence", Box::new(FenceHelper {}));
+    let value = r#"{{{{fence title lang="rust"}}}}{{foo}}{{{{/fence}}}}"#;
+    let expected = "```rust main.rs\n{{foo}}\n```";
+    let data = json!({"title": "main.rs"});
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!(expected, result);
+    Ok(())
+}
+
+#[test]
+fn raw_block_parameters_missing_helper() -> Result<()> {
+    let registry = Registry::new();
+    let value = r#"{{{{raw lang="rust"}}}}{{foo}}{{{{/raw}}}}"#;
+    let data = json!({});
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!("{{foo}}", result);
+    Ok(())
+}
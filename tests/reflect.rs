@@ -0,0 +1,78 @@
+#![cfg(feature = "reflect-helper")]
+use bracket::{
+    error::RenderError,
+    helper::{Helper, HelperValue},
+    parser::ast::Node,
+    render::{Context, Render},
+    Error, Registry, Result,
+};
+use serde_json::{json, Value};
+
+const NAME: &str = "reflect.rs";
+
+struct Greet;
+
+impl Helper for Greet {
+    fn call<'render, 'call>(
+        &self,
+        _rc: &mut Render<'render>,
+        _ctx: &Context<'call>,
+        _template: Option<&'render Node<'render>>,
+    ) -> HelperValue {
+        Ok(Some(Value::String("hello".to_string())))
+    }
+
+    fn description(&self) -> Option<&str> {
+        Some("Say hello.")
+    }
+}
+
+#[test]
+fn reflect_names() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.helpers_mut().insert("greet", Box::new(Greet {}));
+    let value = r#"{{#each (helpers)}}{{name}} {{/each}}"#;
+    let result = registry.once(NAME, value, &json!({}))?;
+    assert!(result.contains("greet "));
+    assert!(result.contains("helpers "));
+    assert!(result.contains("with "));
+    Ok(())
+}
+
+#[test]
+fn reflect_metadata() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.helpers_mut().insert("greet", Box::new(Greet {}));
+    registry.helpers_mut().alias("hi", "greet");
+    registry.helpers_mut().deprecate("hi", "Use greet");
+    let value = r#"{{#each (helpers)}}{{#if description}}{{name}}={{alias}}:{{deprecated}}:{{description}};{{/if}}{{/each}}"#;
+    let result = registry.once(NAME, value, &json!({}))?;
+    assert!(result.contains("greet=::Say hello.;"));
+    assert!(result.contains("hi=greet:Use greet:Say hello.;"));
+    Ok(())
+}
+
+#[test]
+fn reflect_child() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.helpers_mut().insert("greet", Box::new(Greet {}));
+    let child = registry.child();
+    let names = child.helper_names();
+    assert!(names.contains(&"greet"));
+    assert!(names.windows(2).all(|w| w[0] < w[1]));
+    Ok(())
+}
+
+#[test]
+fn reflect_sandboxed() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.set_sandboxed(true);
+    let value = r#"{{#each (helpers)}}{{name}}{{/each}}"#;
+    match registry.once(NAME, value, &json!({})) {
+        Err(Error::Render(RenderError::HelperSandboxed(name, _))) => {
+            assert_eq!("helpers", &name);
+        }
+        _ => panic!("Expected sandbox error"),
+    }
+    Ok(())
+}
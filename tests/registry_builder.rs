@@ -0,0 +1,63 @@
+use bracket::{
+    error::SyntaxError, helper::prelude::*, Error, Registry, Result,
+};
+use serde_json::{json, Value};
+
+pub struct Shout;
+
+impl Helper for Shout {
+    fn call<'render, 'call>(
+        &self,
+        _rc: &mut Render<'render>,
+        ctx: &Context<'call>,
+        _template: Option<&'render Node<'render>>,
+    ) -> HelperValue {
+        ctx.arity(1..1)?;
+        let value = ctx.try_get(0, &[Type::String])?.as_str().unwrap();
+        Ok(Some(Value::String(value.to_uppercase())))
+    }
+}
+
+#[test]
+fn registry_builder() -> Result<()> {
+    let registry = Registry::builder()
+        .template("page", "{{> header}}{{shout (shout name)}}")
+        .template("header", "<h1>{{title}}</h1>")
+        .helper("shout", Box::new(Shout))
+        .strict(true)
+        .build()
+        .unwrap();
+    assert!(registry.strict());
+    let data = json!({"title": "Hi", "name": "bob"});
+    assert_eq!("<h1>Hi</h1>BOB", registry.render("page", &data)?);
+    Ok(())
+}
+
+#[test]
+fn registry_builder_errors() -> Result<()> {
+    let errors = match Registry::builder()
+        .template("broken", "{{/if}}")
+        .template("page", "{{> header}}{{> @partial-block}}{{name}}")
+        .template("list", "{{#each items}}{{shout this}}{{/each}}")
+        .template("nested", "{{lookup (missing a) b}}")
+        .build()
+    {
+        Ok(_) => panic!("Validation errors expected"),
+        Err(errors) => errors,
+    };
+    assert_eq!(4, errors.len());
+    assert!(matches!(errors[0], Error::Syntax(_)));
+    assert!(matches!(
+        errors[1],
+        Error::Syntax(SyntaxError::MissingHelper(ref name, _)) if name == "shout"
+    ));
+    assert!(matches!(
+        errors[2],
+        Error::Syntax(SyntaxError::MissingHelper(ref name, _)) if name == "missing"
+    ));
+    assert!(matches!(
+        errors[3],
+        Error::Syntax(SyntaxError::MissingPartial(ref name, _)) if name == "header"
+    ));
+    Ok(())
+}
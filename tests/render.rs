@@ -1,4 +1,4 @@
-use bracket::{Registry, Result};
+use bracket::{output::Newline, render::NumericKeys, Error, Registry, Result};
 use serde_json::json;
 
 const NAME: &str = "render.rs";
@@ -66,3 +66,93 @@ fn render_statement() -> Result<()> {
     assert_eq!(expected, result);
     Ok(())
 }
+
+#[test]
+fn render_batch() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.insert("greeting", "Hello {{name}}")?;
+    let items = vec![json!({"name": "a"}), json!({"name": "b"})];
+    let result = registry.render_batch("greeting", items)?;
+    assert_eq!(vec!["Hello a", "Hello b"], result);
+    Ok(())
+}
+
+#[test]
+fn render_batch_with() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.insert("greeting", "Hello {{this}}")?;
+    let mut output = Vec::new();
+    let result = registry.render_batch_with(
+        "greeting",
+        ["a", "bb", "c"],
+        |index, value| {
+            if index == 2 {
+                return Err(Error::TemplateNotFound(value.to_string()));
+            }
+            output.push((index, value.to_string()));
+            Ok(())
+        },
+    );
+    assert_eq!(
+        Err(Error::TemplateNotFound(String::from("Hello c"))),
+        result
+    );
+    assert_eq!(
+        vec![(0, String::from("Hello a")), (1, String::from("Hello bb"))],
+        output
+    );
+    assert_eq!(
+        Err(Error::TemplateNotFound(String::from("missing"))),
+        registry.render_batch("missing", vec![json!({})])
+    );
+    Ok(())
+}
+
+#[test]
+fn render_text_only() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.insert(NAME, "a\n{{!-- {{b}} --}}c{{! d }}\r\n")?;
+    registry.insert("statement", "a {{b}}")?;
+    assert!(registry.get(NAME).unwrap().is_text_only());
+    assert!(!registry.get("statement").unwrap().is_text_only());
+    assert_eq!("a\nc\r\n", registry.render(NAME, &json!({}))?);
+
+    registry.set_newline(Newline::Lf);
+    assert_eq!("a\nc\n", registry.render(NAME, &json!({}))?);
+    Ok(())
+}
+
+#[test]
+fn render_text_value() -> Result<()> {
+    let mut registry = Registry::new();
+    let data = json!({"name": "world"});
+    registry.insert(NAME, "a  b {{name}}  {{#if true}}c  d{{/if}}")?;
+    let template = registry.templates_mut().get_mut(NAME).unwrap();
+    template.for_each_text(|text| {
+        let value = text.value().replace("  ", " ");
+        text.set_value(value);
+    });
+    let template = registry.get(NAME).unwrap();
+    assert_eq!("a  b {{name}}  {{#if true}}c  d{{/if}}", template.source());
+    assert_eq!("a b world c d", registry.render(NAME, &data)?);
+    Ok(())
+}
+
+#[test]
+fn render_numeric_keys() -> Result<()> {
+    let mut registry = Registry::new();
+    let data = json!({
+        "list": ["a", "b"],
+        "map": {"0": "x", "01": "y"},
+        "rows": [[1, 2], [3, 4]],
+    });
+    let value = r"{{list.0}}{{list.[1]}}{{list.01}}{{map.0}}{{map.01}}";
+    assert_eq!("abxy", registry.once(NAME, value, &data)?);
+    assert_eq!("3", registry.once(NAME, r"{{rows.1.0}}", &data)?);
+
+    registry.set_numeric_keys(NumericKeys::Key);
+    assert_eq!("xy", registry.once(NAME, value, &data)?);
+    let value = r#"{{lookup list "1"}}"#;
+    assert_eq!("b", registry.once(NAME, value, &data)?);
+    Ok(())
+}
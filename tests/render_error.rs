@@ -0,0 +1,206 @@
+use bracket::{
+    error::{Error, RenderError},
+    escape,
+    render::ErrorFallback,
+    Registry, Result,
+};
+use serde_json::json;
+
+const NAME: &str = "render_error.rs";
+
+#[test]
+fn render_err_variable_strict() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.set_strict(true);
+    let value = "foo\n  {{qux}}";
+    let data = json!({});
+    match registry.once(NAME, value, &data) {
+        Err(Error::Render(e @ RenderError::VariableNotFound(..))) => {
+            let location = e.location().unwrap();
+            assert_eq!(NAME, location.name());
+            assert_eq!(&(6..13), location.span());
+            assert_eq!(&(1..2), location.lines());
+            assert_eq!("{{qux}}", location.source());
+        }
+        _ => panic!("Variable not found error expected"),
+    }
+    Ok(())
+}
+
+#[test]
+fn render_err_variable_nested() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.set_strict(true);
+    let value = "{{#each list}}\n{{#if true}}\n{{qux}}\n{{/if}}\n{{/each}}";
+    let data = json!({"list": [{}]});
+    match registry.once(NAME, value, &data) {
+        Err(Error::Render(e)) => {
+            let location = e.location().unwrap();
+            assert_eq!(&(2..3), location.lines());
+            assert_eq!("{{qux}}", location.source());
+        }
+        _ => panic!("Variable not found error expected"),
+    }
+    Ok(())
+}
+
+#[test]
+fn render_err_partial_not_found() -> Result<()> {
+    let registry = Registry::new();
+    let value = "{{> missing}}";
+    let data = json!({});
+    match registry.once(NAME, value, &data) {
+        Err(Error::Render(e)) => {
+            assert_eq!(
+                RenderError::PartialNotFound("missing".to_string(), None),
+                e
+            );
+            let location = e.location().unwrap();
+            assert_eq!(NAME, location.name());
+            assert_eq!("{{> missing}}", location.source());
+        }
+        _ => panic!("Partial not found error expected"),
+    }
+    Ok(())
+}
+
+#[test]
+fn render_err_partial_location() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.insert("inner", "inner\n{{> missing}}")?;
+    registry.insert("outer", "{{> inner}}")?;
+    let data = json!({});
+    match registry.render("outer", &data) {
+        Err(Error::Render(e)) => {
+            let location = e.location().unwrap();
+            assert_eq!("inner", location.name());
+            assert_eq!(&(1..2), location.lines());
+            assert_eq!("{{> missing}}", location.source());
+        }
+        _ => panic!("Partial not found error expected"),
+    }
+    Ok(())
+}
+
+#[test]
+fn render_err_debug() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.set_strict(true);
+    let value = "foo\n{{qux}}";
+    let data = json!({});
+    let err = registry.once(NAME, value, &data).unwrap_err();
+    let debug = format!("{:?}", err);
+    assert!(debug.starts_with(&err.to_string()));
+    assert!(debug.contains("--> render_error.rs:2"));
+    assert!(debug.contains("| {{qux}}"));
+    Ok(())
+}
+
+#[test]
+fn render_err_graceful() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.set_strict(true);
+    registry.insert("card", "[{{title}}{{missing}}]")?;
+    registry.insert(
+        "page",
+        "a{{foo}}b{{#each items}}{{> card}}{{/each}}c{{> nope}}d",
+    )?;
+    let data = json!({"items": [{"title": "x"}, {"title": "y"}]});
+    assert!(registry.render("page", &data).is_err());
+
+    let (output, errors) = registry.render_graceful("page", &data)?;
+    assert_eq!("ab[x][y]cd", &output);
+    assert_eq!(4, errors.len());
+    assert_eq!("{{foo}}", errors[0].location().unwrap().source());
+    assert_eq!("card", errors[1].location().unwrap().name());
+    assert_eq!("{{> nope}}", errors[3].location().unwrap().source());
+    Ok(())
+}
+
+#[test]
+fn render_err_graceful_fallback() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.insert("page", "<p>{{lookup}}</p>{{#if}}x{{/if}}")?;
+    registry.set_error_fallback(ErrorFallback::Comment);
+    let (output, errors) = registry.render_graceful("page", &json!({}))?;
+    assert_eq!(2, errors.len());
+    assert_eq!(
+        format!(
+            "<p><!-- error: {} --></p><!-- error: {} -->",
+            escape::html(&errors[0].to_string()),
+            escape::html(&errors[1].to_string())
+        ),
+        output
+    );
+    registry.set_error_fallback(ErrorFallback::Text("?".to_string()));
+    let (output, _) = registry.render_graceful("page", &json!({}))?;
+    assert_eq!("<p>?</p>?", &output);
+    Ok(())
+}
+
+#[test]
+fn render_err_graceful_fallback_comment_escaped() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.insert("page", r#"{{lookup o "---><script>x</script>"}}"#)?;
+    registry.set_error_fallback(ErrorFallback::Comment);
+    let (output, errors) =
+        registry.render_graceful("page", &json!({"o": {}}))?;
+    assert_eq!(1, errors.len());
+    assert_eq!(
+        "<!-- error: Helper &#x27;lookup&#x27; failed to resolve field \
+         &#x27;&#45;&#45;&#45;&gt;&lt;script&gt;x&lt;/script&gt;&#x27; -->",
+        output
+    );
+    assert_eq!(2, output.matches("--").count());
+    assert!(!output.contains("<script>"));
+    Ok(())
+}
+
+#[test]
+fn render_err_depth_limit() -> Result<()> {
+    let mut registry = Registry::new();
+    for i in 0..200 {
+        registry.insert(format!("p{}", i), format!("{{{{> p{}}}}}", i + 1))?;
+    }
+    registry.insert("p200", "end")?;
+    match registry.render("p0", &json!({})) {
+        Ok(_) => panic!("Depth limit error expected"),
+        Err(e) => {
+            assert!(matches!(e, Error::Render(RenderError::DepthLimit(..))));
+        }
+    }
+    assert_eq!("end", registry.render("p150", &json!({}))?);
+    Ok(())
+}
+
+#[test]
+fn render_err_json() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.set_strict(true);
+    let value = "foo\n  {{qux}}";
+    match registry.once(NAME, value, &json!({})) {
+        Err(e) => assert_eq!(
+            json!({
+                "kind": "render",
+                "code": "variable_not_found",
+                "message": e.to_string(),
+                "file": NAME,
+                "line": 1,
+                "span": {"start": 6, "end": 13},
+                "notes": [],
+            }),
+            e.to_json()
+        ),
+        _ => panic!("Variable not found error expected"),
+    }
+    match registry.once(NAME, "{{#with}}{{/with}}", &json!({})) {
+        Err(e) => {
+            let diagnostic = e.diagnostic();
+            assert_eq!("helper", diagnostic.kind.as_str());
+            assert_eq!("arity_exact", diagnostic.code);
+            assert_eq!(Some(0), diagnostic.line);
+        }
+        _ => panic!("Helper arity error expected"),
+    }
+    Ok(())
+}
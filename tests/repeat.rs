@@ -0,0 +1,115 @@
+use bracket::{Registry, Result};
+use serde_json::json;
+
+const NAME: &str = "repeat.rs";
+
+#[test]
+fn repeat_index() -> Result<()> {
+    let registry = Registry::new();
+    let value = r"{{#repeat 5}}{{@index}}{{/repeat}}";
+    let data = json!({});
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!("01234", &result);
+    Ok(())
+}
+
+#[test]
+fn repeat_start_step() -> Result<()> {
+    let registry = Registry::new();
+    let value = r"{{#repeat 3 start=10 step=-5}}{{@index}} {{/repeat}}";
+    let data = json!({});
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!("10 5 0 ", &result);
+    Ok(())
+}
+
+#[test]
+fn repeat_first_last() -> Result<()> {
+    let registry = Registry::new();
+    let value = r"{{#repeat count}}{{#if @first}}[{{/if}}{{name}}{{#if @last}}]{{/if}}{{/repeat}}";
+    let data = json!({"count": 3, "name": "x"});
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!("[xxx]", &result);
+    Ok(())
+}
+
+#[test]
+fn repeat_this() -> Result<()> {
+    let registry = Registry::new();
+    let value = r"{{#with obj}}{{#repeat 2}}{{this.value}}{{/repeat}}{{/with}}";
+    let data = json!({"obj": {"value": "y"}});
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!("yy", &result);
+    Ok(())
+}
+
+#[test]
+fn repeat_zero() -> Result<()> {
+    let registry = Registry::new();
+    let value = r"{{#repeat 0}}{{@index}}{{/repeat}}";
+    let data = json!({});
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!("", &result);
+    Ok(())
+}
+
+#[test]
+fn repeat_negative_err() -> Result<()> {
+    let registry = Registry::new();
+    let value = r"{{#repeat -1}}{{@index}}{{/repeat}}";
+    let data = json!({});
+    match registry.once(NAME, value, &data) {
+        Ok(_) => panic!("Numerical operand error expected"),
+        Err(e) => assert_eq!(
+            "Helper 'repeat' got invalid numerical operand",
+            e.to_string()
+        ),
+    }
+    Ok(())
+}
+
+#[test]
+fn repeat_statement_err() -> Result<()> {
+    let registry = Registry::new();
+    let value = r"{{repeat 2}}";
+    let data = json!({});
+    match registry.once(NAME, value, &data) {
+        Ok(_) => panic!("Block template error expected"),
+        Err(e) => assert_eq!(
+            "Helper 'repeat' expects a block template",
+            e.to_string()
+        ),
+    }
+    Ok(())
+}
+
+#[test]
+fn repeat_overflow_err() -> Result<()> {
+    let registry = Registry::new();
+    let value =
+        r"{{#repeat 3 start=1 step=9223372036854775807}}{{@index}}{{/repeat}}";
+    let data = json!({});
+    match registry.once(NAME, value, &data) {
+        Ok(_) => panic!("Overflow error expected"),
+        Err(e) => assert_eq!(
+            "Helper 'repeat' index overflows for step 9223372036854775807",
+            e.to_string()
+        ),
+    }
+    Ok(())
+}
+
+#[test]
+fn repeat_count_err() -> Result<()> {
+    let registry = Registry::new();
+    let value = r"{{#repeat 10001}}{{/repeat}}";
+    let data = json!({});
+    match registry.once(NAME, value, &data) {
+        Ok(_) => panic!("Count error expected"),
+        Err(e) => assert_eq!(
+            "Helper 'repeat' count exceeds 10000 iterations",
+            e.to_string()
+        ),
+    }
+    Ok(())
+}
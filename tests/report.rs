@@ -0,0 +1,69 @@
+use bracket::{report::ReportEntry, Registry, Result};
+use serde_json::json;
+
+#[test]
+fn report_missing() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.insert(
+        "page",
+        "{{title}}{{#each items}}{{this.label}}{{/each}}{{> footer}}{{> card}}",
+    )?;
+    registry.insert("card", "[{{user.name}}]")?;
+    let data = json!({"title": "Home", "items": [1, 2]});
+    let (result, report) = registry.render_with_report("page", &data)?;
+    assert_eq!("Home[]", &result);
+    assert!(!report.is_empty());
+    assert_eq!(
+        vec![
+            ReportEntry {
+                name: "page".to_string(),
+                subject: "this.label".to_string(),
+                lines: Some(0..1),
+            },
+            ReportEntry {
+                name: "card".to_string(),
+                subject: "user.name".to_string(),
+                lines: Some(0..1),
+            },
+        ],
+        report.missing_variables
+    );
+    assert_eq!(1, report.missing_partials.len());
+    assert_eq!("footer", &report.missing_partials[0].subject);
+
+    // Without a report a missing partial is an error
+    assert!(registry.render("page", &data).is_err());
+    Ok(())
+}
+
+#[test]
+fn report_helper_warnings_and_trim() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.deprecate_helper("json", "use `format` instead");
+    registry.insert("page", "{{{json name}}}\n  {{~name}}")?;
+    let data = json!({"name": "a"});
+    let (result, report) = registry.render_with_report("page", &data)?;
+    assert_eq!(r#""a"a"#, &result);
+    assert_eq!(1, report.helper_warnings.len());
+    assert_eq!(
+        "Helper 'json' is deprecated, use `format` instead",
+        &report.helper_warnings[0].subject
+    );
+    assert_eq!(1, report.trimmed.len());
+    assert_eq!("\n  ", &report.trimmed[0].subject);
+    assert!(report.missing_variables.is_empty());
+    Ok(())
+}
+
+#[test]
+fn report_minify() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.set_minify(true);
+    registry.insert("page", "<p>\n  {{name}}  {{missing}}\n</p>\n")?;
+    let data = json!({"name": "a"});
+    let (result, report) = registry.render_with_report("page", &data)?;
+    assert_eq!(registry.render("page", &data)?, result);
+    assert_eq!("<p> a </p>", &result);
+    assert_eq!(1, report.missing_variables.len());
+    Ok(())
+}
@@ -0,0 +1,79 @@
+use bracket::{Error, Registry, Result};
+use serde_json::json;
+
+#[test]
+fn resolve_fallback() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.insert("emails/en/welcome", "Welcome")?;
+    registry.insert("emails/welcome", "Default")?;
+    let (name, template) = registry
+        .get_fallback(&[
+            "emails/fr/welcome",
+            "emails/en/welcome",
+            "emails/welcome",
+        ])
+        .unwrap();
+    assert_eq!("emails/en/welcome", *name);
+    assert_eq!(
+        "Welcome",
+        registry.render_template("welcome", template, &json!({}))?
+    );
+    assert!(registry.get_fallback(vec!["a", "b"]).is_none());
+    Ok(())
+}
+
+#[test]
+fn resolve_locale() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.insert("welcome", "Welcome {{> footer}}")?;
+    registry.insert("fr/welcome", "Bienvenue {{> footer}}")?;
+    registry.insert("footer", "footer")?;
+    registry.insert("fr/footer", "pied de page")?;
+    registry.set_resolver(Box::new(|name, data| {
+        let mut names = Vec::new();
+        if let Some(locale) = data.get("locale").and_then(|v| v.as_str()) {
+            names.push(format!("{}/{}", locale, name));
+        }
+        names
+    }));
+    assert!(registry.has_resolver());
+    assert_eq!(
+        "Bienvenue pied de page",
+        registry.render("welcome", &json!({"locale": "fr"}))?
+    );
+    assert_eq!(
+        "Welcome footer",
+        registry.render("welcome", &json!({"locale": "de"}))?
+    );
+    assert_eq!("Welcome footer", registry.render("welcome", &json!({}))?);
+    assert_eq!(
+        vec!["Bienvenue pied de page", "Welcome footer"],
+        registry.render_batch(
+            "welcome",
+            vec![json!({"locale": "fr"}), json!({})]
+        )?
+    );
+    match registry.render("missing", &json!({"locale": "fr"})) {
+        Err(Error::TemplateNotFound(name)) => assert_eq!("missing", name),
+        _ => panic!("expected template not found"),
+    }
+    Ok(())
+}
+
+#[test]
+fn resolve_child() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.insert("dark/page", "dark")?;
+    registry.insert("page", "light")?;
+    registry.set_resolver(Box::new(|name, data| {
+        data.get("theme")
+            .and_then(|v| v.as_str())
+            .map(|theme| vec![format!("{}/{}", theme, name)])
+            .unwrap_or_default()
+    }));
+    let mut child = registry.child();
+    child.insert("page", "tenant")?;
+    assert_eq!("dark", child.render("page", &json!({"theme": "dark"}))?);
+    assert_eq!("tenant", child.render("page", &json!({}))?);
+    Ok(())
+}
@@ -0,0 +1,184 @@
+use bracket::{Registry, Result};
+use proptest::prelude::*;
+
+const NAME: &str = "round_trip.rs";
+
+fn text() -> impl Strategy<Value = String> {
+    "[a-zA-Z0-9 \n\t.,:;!?<>&'\"/=-]{0,12}"
+}
+
+fn path() -> impl Strategy<Value = String> {
+    prop_oneof![
+        "[a-z][a-z0-9_]{0,6}",
+        "[a-z]{1,4}\\.[a-z]{1,4}",
+        "(\\.\\./){1,2}[a-z]{1,4}",
+        Just("this".to_string()),
+        Just("@index".to_string()),
+        Just("@root.list".to_string()),
+    ]
+}
+
+fn parameter() -> impl Strategy<Value = String> {
+    prop_oneof![
+        path(),
+        "\"[a-z ]{0,6}\"",
+        "'[a-z ]{0,6}'",
+        "-?(0|[1-9][0-9]{0,3})",
+        Just("true".to_string()),
+        Just("null".to_string()),
+    ]
+}
+
+fn hash() -> impl Strategy<Value = String> {
+    ("[a-z]{1,4}", parameter()).prop_map(|(k, v)| format!("{}={}", k, v))
+}
+
+fn trim() -> impl Strategy<Value = &'static str> {
+    prop_oneof![Just(""), Just("~")]
+}
+
+fn leaf() -> impl Strategy<Value = String> {
+    prop_oneof![
+        text(),
+        (trim(), path(), trim())
+            .prop_map(|(a, p, b)| format!("{{{{{}{}{}}}}}", a, p, b)),
+        path().prop_map(|p| format!("{{{{{{{}}}}}}}", p)),
+        (
+            path(),
+            prop::collection::vec(parameter(), 1..4),
+            prop::collection::vec(hash(), 0..3),
+        )
+            .prop_map(|(p, a, h)| {
+                format!("{{{{{} {}}}}}", p, [a, h].concat().join(" "))
+            }),
+        "[a-z ]{0,8}".prop_map(|c| format!("{{{{!{}}}}}", c)),
+        "[a-z ]{0,4}(\\{\\{[a-z]{1,3}\\}\\})?"
+            .prop_map(|c| format!("{{{{!--{}--}}}}", c)),
+        path().prop_map(|p| format!("\\{{{{{}}}}}", p)),
+        "[a-z ]{0,4}(\\{\\{[a-z]{1,3}\\}\\})?[a-z ]{0,4}".prop_map(
+            |c| format!("{{{{{{{{raw}}}}}}}}{}{{{{{{{{/raw}}}}}}}}", c)
+        ),
+    ]
+}
+
+fn template() -> impl Strategy<Value = String> {
+    leaf().prop_recursive(4, 32, 4, |inner| {
+        let body = prop::collection::vec(inner, 0..4).prop_map(|v| v.concat());
+        prop_oneof![
+            prop::collection::vec(body.clone(), 1..4).prop_map(|v| v.concat()),
+            (path(), body.clone(), body.clone()).prop_map(|(p, a, b)| {
+                format!("{{{{#if {}}}}}{}{{{{else}}}}{}{{{{/if}}}}", p, a, b)
+            }),
+            (path(), body.clone(), path(), body.clone(), body.clone())
+                .prop_map(|(p, a, q, b, c)| {
+                    format!(
+                        "{{{{#if {}}}}}{}{{{{else if {}}}}}{}{{{{else}}}}{}{{{{/if}}}}",
+                        p, a, q, b, c
+                    )
+                }),
+            (trim(), path(), body.clone(), trim()).prop_map(|(a, p, t, b)| {
+                format!("{{{{{}#each {}}}}}{}{{{{/each{}}}}}", a, p, t, b)
+            }),
+            (path(), body).prop_map(|(p, t)| {
+                format!("{{{{#> layout {}}}}}{}{{{{/layout}}}}", p, t)
+            }),
+        ]
+    })
+}
+
+/// Templates that may be malformed: a well formed template that is
+/// truncated, has a fragment of markup inserted or starts with byte
+/// order marks.
+fn malformed() -> impl Strategy<Value = String> {
+    let fragment = prop_oneof![
+        Just("{{"),
+        Just("}}"),
+        Just("{{{"),
+        Just("{{#"),
+        Just("{{/"),
+        Just("{{!"),
+        Just("{{!--"),
+        Just("\\{{"),
+        Just("[["),
+        Just("("),
+        Just("\u{feff}"),
+    ];
+    (
+        prop::collection::vec(Just("\u{feff}"), 0..3),
+        template(),
+        any::<prop::sample::Index>(),
+        any::<prop::sample::Index>(),
+        prop::option::of(fragment),
+    )
+        .prop_map(|(bom, source, cut, at, fragment)| {
+            let boundary = |index: prop::sample::Index| {
+                let boundaries: Vec<usize> = source
+                    .char_indices()
+                    .map(|(i, _)| i)
+                    .chain(Some(source.len()))
+                    .collect();
+                boundaries[index.index(boundaries.len())]
+            };
+            let mut source = match fragment {
+                Some(fragment) => {
+                    let at = boundary(at);
+                    format!("{}{}{}", &source[..at], fragment, &source[at..])
+                }
+                None => source[..boundary(cut)].to_string(),
+            };
+            source.insert_str(0, &bom.concat());
+            source
+        })
+}
+
+proptest! {
+    #[test]
+    fn round_trip_display(source in template()) {
+        let registry = Registry::new();
+        let template = registry.parse(NAME, &source).unwrap();
+        let printed = template.to_string();
+        prop_assert_eq!(&source, &printed);
+
+        let reparsed = registry.parse(NAME, &printed).unwrap();
+        prop_assert_eq!(printed, reparsed.to_string());
+    }
+
+    #[test]
+    fn round_trip_display_malformed(source in malformed()) {
+        let registry = Registry::new();
+        if let Ok(template) = registry.parse(NAME, &source) {
+            let printed = template.to_string();
+            prop_assert_eq!(&source, &printed);
+
+            let reparsed = registry.parse(NAME, &printed).unwrap();
+            prop_assert_eq!(printed, reparsed.to_string());
+        }
+    }
+}
+
+#[test]
+fn round_trip_block_conditionals() -> Result<()> {
+    let registry = Registry::new();
+    let value = r"{{#if a}}b{{else if c}}d{{else}}e{{/if}}";
+    let template = registry.parse(NAME, value)?;
+    assert_eq!(value, template.node().to_string());
+    Ok(())
+}
+
+#[test]
+fn round_trip_block_raw() -> Result<()> {
+    let registry = Registry::new();
+    let value = r"{{{{raw}}}}{{foo}}{{{{/raw}}}}";
+    let template = registry.parse(NAME, value)?;
+    assert_eq!(value, template.node().to_string());
+    Ok(())
+}
+
+#[test]
+fn round_trip_byte_order_mark() -> Result<()> {
+    let registry = Registry::new();
+    let value = "\u{feff}\u{feff}{{foo}}";
+    let template = registry.parse(NAME, value)?;
+    assert_eq!(value, template.node().to_string());
+    Ok(())
+}
@@ -0,0 +1,211 @@
+use bracket::{
+    error::RenderError, helper::prelude::*, output::StringOutput,
+    sandbox::Profile, Error, Registry, Result,
+};
+use serde_json::{json, Value};
+
+const NAME: &str = "sandbox.rs";
+
+#[test]
+fn sandbox_helpers() -> Result<()> {
+    let mut registry = Registry::new();
+    let mut profile = Profile::new();
+    profile.allow_helpers(&["if"]);
+    registry.set_sandbox(profile);
+    let data = json!({"list": [1, 2]});
+
+    let value = r"{{#if list}}yes{{/if}}";
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!("yes", &result);
+
+    let value = r"{{#each list}}{{this}}{{/each}}";
+    match registry.once(NAME, value, &data) {
+        Err(Error::Render(RenderError::HelperSandboxed(name, _))) => {
+            assert_eq!("each", &name);
+        }
+        _ => panic!("Expected sandbox helper error"),
+    }
+    Ok(())
+}
+
+#[derive(Clone)]
+struct LocalFoo;
+impl Helper for LocalFoo {
+    fn call<'render, 'call>(
+        &self,
+        _rc: &mut Render<'render>,
+        _ctx: &Context<'call>,
+        _template: Option<&'render Node<'render>>,
+    ) -> HelperValue {
+        Ok(Some(Value::String("foo".to_string())))
+    }
+}
+impl LocalHelper for LocalFoo {}
+
+struct Outer;
+impl Helper for Outer {
+    fn call<'render, 'call>(
+        &self,
+        rc: &mut Render<'render>,
+        _ctx: &Context<'call>,
+        template: Option<&'render Node<'render>>,
+    ) -> HelperValue {
+        rc.register_local_helper("foo", Box::new(LocalFoo));
+        let result = rc.template(template.unwrap());
+        rc.unregister_local_helper("foo");
+        result?;
+        Ok(None)
+    }
+}
+
+#[test]
+fn sandbox_local_helper() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.helpers_mut().insert("outer", Box::new(Outer));
+    let mut profile = Profile::new();
+    profile.allow_helpers(&["outer"]);
+    registry.set_sandbox(profile);
+    match registry.once(NAME, r"{{#outer}}{{foo}}{{/outer}}", &json!({})) {
+        Err(Error::Render(RenderError::HelperSandboxed(name, _))) => {
+            assert_eq!("foo", &name);
+        }
+        _ => panic!("Expected sandbox helper error"),
+    }
+    Ok(())
+}
+
+#[test]
+fn sandbox_partial_depth() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.insert("a", "a{{> b}}")?;
+    registry.insert("b", "b{{> c}}")?;
+    registry.insert("c", "c")?;
+    let mut profile = Profile::new();
+    profile.max_partial_depth = Some(2);
+    registry.set_sandbox(profile);
+
+    let result = registry.once(NAME, r"{{> b}}", &json!({}))?;
+    assert_eq!("bc", &result);
+
+    match registry.once(NAME, r"{{> a}}", &json!({})) {
+        Err(Error::Render(RenderError::PartialDepth(name, max, _))) => {
+            assert_eq!("c", &name);
+            assert_eq!(2, max);
+        }
+        _ => panic!("Expected sandbox partial depth error"),
+    }
+    Ok(())
+}
+
+#[test]
+fn sandbox_output_limit() -> Result<()> {
+    let mut registry = Registry::new();
+    let mut profile = Profile::new();
+    profile.max_output = Some(8);
+    registry.set_sandbox(profile);
+
+    let result =
+        registry.once(NAME, r"{{foo}}", &json!({"foo": "12345678"}))?;
+    assert_eq!("12345678", &result);
+
+    let value = r"{{#each list}}{{this}}{{/each}}";
+    let data = json!({"list": ["1234", "5678", "9"]});
+    // NOTE: the error is wrapped by the each helper
+    let err = registry.once(NAME, value, &data).unwrap_err();
+    assert_eq!(
+        RenderError::OutputLimit(8, None).to_string(),
+        err.to_string()
+    );
+    Ok(())
+}
+
+#[test]
+fn sandbox_output_limit_escaped() -> Result<()> {
+    let mut registry = Registry::new();
+    let mut profile = Profile::new();
+    profile.max_output = Some(8);
+    registry.set_sandbox(profile);
+    registry.insert(NAME, r"{{foo}}")?;
+
+    let mut writer = StringOutput::new();
+    let data = json!({"foo": "<<<<"});
+    let err = registry
+        .render_to_write(NAME, &data, &mut writer)
+        .unwrap_err();
+    assert_eq!(
+        RenderError::OutputLimit(8, None).to_string(),
+        err.to_string()
+    );
+    assert!(writer.as_str().is_empty());
+    Ok(())
+}
+
+#[test]
+fn sandbox_raw() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.set_sandbox(Profile::new());
+    let data = json!({"foo": "<bar>"});
+
+    let result = registry.once(NAME, r"{{foo}}", &data)?;
+    assert_eq!("&lt;bar&gt;", &result);
+    assert!(registry.once(NAME, r"{{{foo}}}", &data).is_err());
+    assert!(registry.once(NAME, r"\{{foo}}", &data).is_err());
+
+    let mut profile = Profile::new();
+    profile.raw = true;
+    registry.set_sandbox(profile);
+    let result = registry.once(NAME, r"{{{foo}}}", &data)?;
+    assert_eq!("<bar>", &result);
+    Ok(())
+}
+
+#[cfg(feature = "truncate-helper")]
+#[test]
+fn sandbox_raw_truncate_html() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.set_sandbox(Profile::new());
+    let data = json!({"x": "<script>alert(1)</script>"});
+    match registry.once(NAME, r"{{truncate_html x 100}}", &data) {
+        Err(Error::Render(RenderError::Helper(HelperError::Render(e), _))) => {
+            assert!(matches!(*e, RenderError::RawNotAllowed(..)));
+        }
+        result => panic!("expected raw output error, got {:?}", result),
+    }
+
+    let mut profile = Profile::new();
+    profile.raw = true;
+    registry.set_sandbox(profile);
+    let result = registry.once(NAME, r"{{truncate_html x 100}}", &data)?;
+    assert_eq!("<script>alert(1)</script>", &result);
+    Ok(())
+}
+
+#[test]
+fn sandbox_ambient() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.allow_env("PATH");
+    registry.set_sandbox(Profile::new());
+    assert!(registry.sandboxed());
+    assert!(registry
+        .once(NAME, r#"{{env "PATH"}}"#, &json!({}))
+        .is_err());
+    Ok(())
+}
+
+#[cfg(feature = "now-helper")]
+#[test]
+fn sandbox_clock() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.set_sandbox(Profile::new());
+    match registry.once(NAME, r"{{now}}", &json!({})) {
+        Err(Error::Render(RenderError::Helper(e, _))) => {
+            assert_eq!("Helper 'now' requires a clock", e.to_string());
+        }
+        result => panic!("expected a clock error, got {:?}", result),
+    }
+
+    registry.set_clock(Box::new(bracket::clock::FixedClock::new(0)));
+    let result = registry.once(NAME, r"{{now}}", &json!({}))?;
+    assert_eq!("1970-01-01T00:00:00Z", &result);
+    Ok(())
+}
@@ -0,0 +1,80 @@
+use bracket::{Registry, Result};
+use serde_json::json;
+
+const NAME: &str = "set.rs";
+
+#[test]
+fn set_statement() -> Result<()> {
+    let registry = Registry::new();
+    let value = r#"{{set title="Home" count=2}}{{title}} {{count}}"#;
+    let data = json!({"title": "Ignored"});
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!("Home 2", &result);
+    Ok(())
+}
+
+#[test]
+fn set_statement_sub_expr() -> Result<()> {
+    let registry = Registry::new();
+    let value = r#"{{set active=(not hidden)}}{{#if active}}yes{{/if}}"#;
+    let data = json!({"hidden": false});
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!("yes", &result);
+    Ok(())
+}
+
+#[test]
+fn set_nested_blocks() -> Result<()> {
+    let registry = Registry::new();
+    let value = r#"{{set sep=", "}}{{#each list}}{{this}}{{#unless @last}}{{sep}}{{/unless}}{{/each}}"#;
+    let data = json!({"list": [1, 2, 3]});
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!("1, 2, 3", &result);
+    Ok(())
+}
+
+#[test]
+fn set_parent_reference() -> Result<()> {
+    let registry = Registry::new();
+    let value =
+        r#"{{set name="set"}}{{#with obj}}{{../name}} {{value}}{{/with}}"#;
+    let data = json!({"obj": {"value": "with"}});
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!("set with", &result);
+    Ok(())
+}
+
+#[test]
+fn set_block() -> Result<()> {
+    let registry = Registry::new();
+    let value = r#"{{#set name="world"}}Hello {{name}}!{{/set}} {{name}}"#;
+    let data = json!({"name": "root"});
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!("Hello world! root", &result);
+    Ok(())
+}
+
+#[test]
+fn set_block_restore() -> Result<()> {
+    let registry = Registry::new();
+    let value = r#"{{#each list}}{{#set value="inner"}}{{@index}}:{{value}} {{/set}}{{value}} {{/each}}"#;
+    let data = json!({"list": [{"value": "a"}]});
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!("0:inner a ", &result);
+    Ok(())
+}
+
+#[test]
+fn set_arguments_err() -> Result<()> {
+    let registry = Registry::new();
+    let value = r#"{{set "name"}}"#;
+    let data = json!({});
+    match registry.once(NAME, value, &data) {
+        Ok(_) => panic!("Arity error expected"),
+        Err(e) => assert_eq!(
+            "Helper 'set' got invalid arity expects 0 arguments(s)",
+            e.to_string()
+        ),
+    }
+    Ok(())
+}
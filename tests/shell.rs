@@ -0,0 +1,47 @@
+#![cfg(feature = "shell-helper")]
+use bracket::{Error, Registry, Result};
+use serde_json::json;
+
+const NAME: &str = "shell.rs";
+
+#[test]
+fn shell_quote_posix() -> Result<()> {
+    let registry = Registry::new();
+    let value = r#"{{{shell_quote a}}} {{{shell_quote b}}} {{{shell_quote c}}} {{{shell_quote d}}}"#;
+    let data = json!({"a": "file.txt", "b": "it's $HOME", "c": "", "d": 42});
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!(r#"file.txt 'it'"'"'s $HOME' '' 42"#, &result);
+    Ok(())
+}
+
+#[test]
+fn shell_quote_powershell() -> Result<()> {
+    let registry = Registry::new();
+    let value = r#"{{{shell_quote a mode="powershell"}}}"#;
+    let data = json!({"a": "it's $env:PATH ‘x’"});
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!("'it''s $env:PATH ‘‘x’’'", &result);
+    Ok(())
+}
+
+#[test]
+fn shell_quote_errors() -> Result<()> {
+    let registry = Registry::new();
+    let data = json!({"a": "a\u{0}b"});
+    match registry.once(NAME, r#"{{{shell_quote a}}}"#, &data) {
+        Err(Error::Render(e)) => assert_eq!(
+            "Helper 'shell_quote' cannot quote a value that contains a NUL byte",
+            e.to_string()
+        ),
+        _ => panic!("expected a render error"),
+    }
+    let value = r#"{{{shell_quote "x" mode="cmd"}}}"#;
+    match registry.once(NAME, value, &data) {
+        Err(Error::Render(e)) => assert_eq!(
+            "Helper 'shell_quote' got unknown mode 'cmd', expected 'posix' or 'powershell'",
+            e.to_string()
+        ),
+        _ => panic!("expected a render error"),
+    }
+    Ok(())
+}
@@ -0,0 +1,45 @@
+#![cfg(feature = "sql-helper")]
+use bracket::{Error, Registry, Result};
+use serde_json::json;
+
+const NAME: &str = "sql.rs";
+
+#[test]
+fn sql_str() -> Result<()> {
+    let registry = Registry::new();
+    let data = json!({"s": "it's a\\b\n", "n": 1.5, "b": true, "z": null});
+    let value =
+        r#"{{{sql_str s}}} {{{sql_str n}}} {{{sql_str b}}} {{{sql_str z}}}"#;
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!("'it''s a\\b\n' 1.5 TRUE NULL", &result);
+    let value = r#"{{{sql_str s dialect="mysql"}}}"#;
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!(r"'it''s a\\b\n'", &result);
+    let data = json!({"s": "a\u{0}"});
+    match registry.once(NAME, r#"{{{sql_str s dialect="sqlite"}}}"#, &data) {
+        Err(Error::Render(e)) => assert_eq!(
+            "Helper 'sql_str' cannot quote a value that contains a NUL byte",
+            e.to_string()
+        ),
+        _ => panic!("expected a render error"),
+    }
+    Ok(())
+}
+
+#[test]
+fn sql_ident() -> Result<()> {
+    let registry = Registry::new();
+    let data = json!({"t": "my \"table\"", "c": "col`x"});
+    let value = r#"{{{sql_ident t}}} {{{sql_ident c dialect="mysql"}}}"#;
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!(r#""my ""table""" `col``x`"#, &result);
+    match registry.once(NAME, r#"{{{sql_ident t dialect="oracle"}}}"#, &data)
+    {
+        Err(Error::Render(e)) => assert_eq!(
+            "Helper 'sql_ident' got unknown dialect 'oracle', expected 'postgres', 'mysql' or 'sqlite'",
+            e.to_string()
+        ),
+        _ => panic!("expected a render error"),
+    }
+    Ok(())
+}
@@ -1,6 +1,7 @@
 use bracket::{
     error::{Error, ErrorInfo, SourcePos, SyntaxError},
-    Registry, Result,
+    parser::{ParserOptions, MAX_DEPTH},
+    Registry, Result, Template,
 };
 
 const NAME: &str = "syntax_error.rs";
@@ -24,6 +25,25 @@ fn syntax_err_empty_statement() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn syntax_err_path_trailing_delimiter() -> Result<()> {
+    let registry = Registry::new();
+    for value in [r"{{items.1.}}", r"{{items.}}"] {
+        match registry.parse(NAME, value) {
+            Ok(_) => panic!("Identifier error expected (trailing delimiter)"),
+            Err(e) => {
+                let pos = SourcePos(0, value.len() - 2);
+                let info = ErrorInfo::new(value, NAME, pos, vec![]);
+                assert_eq!(
+                    Error::Syntax(SyntaxError::ExpectedIdentifier(info.into())),
+                    e
+                );
+            }
+        }
+    }
+    Ok(())
+}
+
 #[test]
 fn syntax_err_identifier_expected() -> Result<()> {
     let registry = Registry::new();
@@ -159,6 +179,63 @@ fn syntax_err_link() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn syntax_err_block_not_terminated() -> Result<()> {
+    let registry = Registry::new();
+    let value = r#"x{{#if true}}y"#;
+    match registry.parse(NAME, value) {
+        Ok(_) => panic!("Block not terminated error expected"),
+        Err(e) => {
+            println!("{:?}", e);
+            let pos = SourcePos(0, 1);
+            let info = ErrorInfo::new(value, NAME, pos, vec![]);
+            assert_eq!(
+                Error::Syntax(SyntaxError::BlockNotTerminated(info.into())),
+                e
+            );
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn syntax_err_statement_not_terminated() -> Result<()> {
+    let registry = Registry::new();
+    let value = r#"a{{{x"#;
+    match registry.parse(NAME, value) {
+        Ok(_) => panic!("Statement not terminated error expected"),
+        Err(e) => {
+            println!("{:?}", e);
+            let pos = SourcePos(0, 4);
+            let info = ErrorInfo::new(value, NAME, pos, vec![]);
+            assert_eq!(
+                Error::Syntax(SyntaxError::StatementNotTerminated(info.into())),
+                e
+            );
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn syntax_err_raw_block_not_open() -> Result<()> {
+    let registry = Registry::new();
+    let value = r#"x{{{{/raw}}}}y"#;
+    match registry.parse(NAME, value) {
+        Ok(_) => panic!("Block not open error expected"),
+        Err(e) => {
+            println!("{:?}", e);
+            let pos = SourcePos(0, 1);
+            let info = ErrorInfo::new(value, NAME, pos, vec![]);
+            assert_eq!(
+                Error::Syntax(SyntaxError::BlockNotOpen(info.into())),
+                e
+            );
+        }
+    }
+    Ok(())
+}
+
 #[test]
 fn syntax_err_raw_block_open() -> Result<()> {
     let registry = Registry::new();
@@ -217,3 +294,223 @@ fn syntax_err_raw_block_half_open() -> Result<()> {
     }
     Ok(())
 }
+
+#[test]
+fn syntax_err_number_literal() -> Result<()> {
+    let registry = Registry::new();
+    let value = r"{{foo 01}}";
+    match registry.parse(NAME, value) {
+        Ok(_) => panic!("Number literal error expected"),
+        Err(e) => {
+            println!("{:?}", e);
+            let pos = SourcePos(0, 6);
+            let info = ErrorInfo::new(value, NAME, pos, vec![]);
+            assert_eq!(
+                Error::Syntax(SyntaxError::NumberLiteral(info.into())),
+                e
+            );
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn syntax_err_source_boundaries() -> Result<()> {
+    // Error positions at a newline, inside multi-byte characters
+    // or at the end of the source must not panic.
+    let registry = Registry::new();
+    let values =
+        vec!["#null{{{{\n", "-1.5e{{{{é", "é}}}}{{#>", "\r{{!--*.\"\n"];
+    for value in values {
+        assert!(registry.parse(NAME, value).is_err());
+    }
+    Ok(())
+}
+
+#[test]
+fn syntax_err_inverted_block_name() -> Result<()> {
+    let registry = Registry::new();
+    let value = r#"{{^foo.bar}}{{/foo.bar}}"#;
+    match registry.parse(NAME, value) {
+        Ok(_) => panic!("Block name error expected (inverted section)"),
+        Err(e) => {
+            let pos = SourcePos(0, 3);
+            let info = ErrorInfo::new(value, NAME, pos, vec![]);
+            assert_eq!(Error::Syntax(SyntaxError::BlockName(info.into())), e);
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn syntax_err_inverted_tag_mismatch() -> Result<()> {
+    let registry = Registry::new();
+    let value = r#"{{^foo}}{{/unless}}"#;
+    match registry.parse(NAME, value) {
+        Ok(_) => panic!("Tag name mismatch error expected (inverted section)"),
+        Err(e) => {
+            let pos = SourcePos(0, 17);
+            let notes = vec!["opening name is 'foo'".to_string()];
+            let info = ErrorInfo::new(value, NAME, pos, notes);
+            assert_eq!(
+                Error::Syntax(SyntaxError::TagNameMismatch(info.into())),
+                e
+            );
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn syntax_err_hint_single_brace() -> Result<()> {
+    let registry = Registry::new();
+    let value = r#"{{#if x}foo{{/if}}"#;
+    match registry.parse(NAME, value) {
+        Ok(_) => panic!("Token error expected (single brace)"),
+        Err(e) => {
+            let pos = SourcePos(0, 7);
+            let notes = vec![
+                "did you mean '{{#if x}}'? tags must be closed with two braces"
+                    .to_string(),
+            ];
+            let info = ErrorInfo::new(value, NAME, pos, notes);
+            assert_eq!(
+                Error::Syntax(SyntaxError::TokenError(
+                    "parameters".to_string(),
+                    info.into()
+                )),
+                e
+            );
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn syntax_err_hint_unterminated_string() -> Result<()> {
+    let registry = Registry::new();
+    let value = r#"{{foo bar="baz}}"#;
+    match registry.parse(NAME, value) {
+        Ok(_) => panic!("Raw literal error expected (unterminated string)"),
+        Err(e) => {
+            let pos = SourcePos(0, 15);
+            let notes = vec![
+                r#"did you mean "baz"? the string literal is not terminated"#
+                    .to_string(),
+            ];
+            let info = ErrorInfo::new(value, NAME, pos, notes);
+            assert_eq!(
+                Error::Syntax(SyntaxError::TokenRawLiteral(info.into())),
+                e
+            );
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn syntax_err_hint_block_not_open() -> Result<()> {
+    let registry = Registry::new();
+    let value = r#"{{if x}}foo{{/if}}"#;
+    match registry.parse(NAME, value) {
+        Ok(_) => panic!("Block not open error expected"),
+        Err(e) => {
+            let pos = SourcePos(0, 11);
+            let notes = vec![
+                "perhaps open the block 'if'".to_string(),
+                "did you mean '{{#if'? blocks are opened with '#'".to_string(),
+            ];
+            let info = ErrorInfo::new(value, NAME, pos, notes);
+            assert_eq!(
+                Error::Syntax(SyntaxError::BlockNotOpen(info.into())),
+                e
+            );
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn syntax_err_identifier_character() -> Result<()> {
+    let registry = Registry::new();
+    registry.parse(NAME, "{{名前.prénom}}{{@índice}}")?;
+    let value = "{{a\u{200b}b}}";
+    match registry.parse(NAME, value) {
+        Ok(_) => panic!("Identifier character error expected"),
+        Err(e) => {
+            let pos = SourcePos(0, 3);
+            let notes = vec![format!(
+                "use a raw literal such as [{}] for names that are not identifiers",
+                "a\u{200b}b"
+            )];
+            let info = ErrorInfo::new(value, NAME, pos, notes);
+            assert_eq!(
+                Error::Syntax(SyntaxError::IdentifierCharacter(
+                    '\u{200b}',
+                    info.into()
+                )),
+                e
+            );
+        }
+    }
+    registry.parse(NAME, "{{this.[a\u{200b}b]}}")?;
+    Ok(())
+}
+
+#[test]
+fn syntax_err_nesting_depth() -> Result<()> {
+    let registry = Registry::new();
+    let nested = |depth: usize| {
+        format!(
+            "{}x{}",
+            "{{#if true}}".repeat(depth),
+            "{{/if}}".repeat(depth)
+        )
+    };
+    registry.parse(NAME, nested(MAX_DEPTH))?;
+    match registry.parse(NAME, nested(MAX_DEPTH + 1)) {
+        Ok(_) => panic!("Nesting depth error expected"),
+        Err(e) => {
+            assert!(matches!(
+                e,
+                Error::Syntax(SyntaxError::NestingDepth(MAX_DEPTH, _))
+            ));
+        }
+    }
+
+    let value = format!("{{{{not {}1{}}}}}", "(not ".repeat(4), ")".repeat(4));
+    let options = ParserOptions {
+        max_depth: 3,
+        ..Default::default()
+    };
+    match Template::compile(value, options) {
+        Ok(_) => panic!("Nesting depth error expected"),
+        Err(e) => {
+            assert!(matches!(e, SyntaxError::NestingDepth(3, _)));
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn syntax_err_json() -> Result<()> {
+    let registry = Registry::new();
+    let value = "foo\n{{# }}";
+    match registry.parse(NAME, value) {
+        Ok(_) => panic!("Identifier error expected (empty block)"),
+        Err(e) => {
+            let expected = serde_json::json!({
+                "kind": "syntax",
+                "code": "expected_identifier",
+                "message": "Syntax error, expecting identifier",
+                "file": NAME,
+                "line": 1,
+                "span": {"start": 8, "end": 9},
+                "notes": [],
+            });
+            assert_eq!(expected, e.to_json());
+            assert_eq!(expected, serde_json::to_value(&e).unwrap());
+        }
+    }
+    Ok(())
+}
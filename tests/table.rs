@@ -0,0 +1,72 @@
+#![cfg(feature = "table-helper")]
+use bracket::{format::NumberFormat, Registry, Result};
+use serde_json::json;
+
+const NAME: &str = "table.rs";
+
+#[test]
+fn table_columns() -> Result<()> {
+    let mut registry = Registry::new();
+    registry
+        .formatters_mut()
+        .insert_tag("money", Box::new(NumberFormat::new().precision(2)));
+    let data = json!({
+        "rows": [
+            {"id": 1, "total": 9.5, "customer": {"name": "Tom & Jerry"}},
+            {"id": 2, "total": 12}
+        ],
+        "columns": [
+            "id",
+            {"key": "customer.name", "label": "Customer"},
+            {"key": "total", "label": "Total", "format": "money"}
+        ]
+    });
+    let value = r#"{{table rows columns=columns class="report"}}"#;
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!(
+        concat!(
+            r#"<table class="report"><thead><tr>"#,
+            "<th>id</th><th>Customer</th><th>Total</th></tr></thead><tbody>",
+            "<tr><td>1</td><td>Tom &amp; Jerry</td><td>9.50</td></tr>",
+            "<tr><td>2</td><td></td><td>12.00</td></tr>",
+            "</tbody></table>"
+        ),
+        &result
+    );
+    Ok(())
+}
+
+#[test]
+fn table_default_columns() -> Result<()> {
+    let registry = Registry::new();
+    let data = json!({"rows": [{"a": 1, "b": 2}]});
+    let result = registry.once(NAME, r#"{{table rows}}"#, &data)?;
+    assert_eq!(
+        concat!(
+            "<table><thead><tr><th>a</th><th>b</th></tr></thead>",
+            "<tbody><tr><td>1</td><td>2</td></tr></tbody></table>"
+        ),
+        &result
+    );
+    Ok(())
+}
+
+#[test]
+fn table_block() -> Result<()> {
+    let registry = Registry::new();
+    let data = json!({
+        "rows": [{"name": "a"}, {"name": "b"}],
+        "columns": {"index": "#", "name": "Name"}
+    });
+    let value = r#"{{#table rows columns=columns}}<td>{{@index}}</td><td>{{name}}</td>{{/table}}"#;
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!(
+        concat!(
+            "<table><thead><tr><th>#</th><th>Name</th></tr></thead><tbody>",
+            "<tr><td>0</td><td>a</td></tr><tr><td>1</td><td>b</td></tr>",
+            "</tbody></table>"
+        ),
+        &result
+    );
+    Ok(())
+}
@@ -0,0 +1,76 @@
+#![cfg(feature = "testing")]
+use bracket::{
+    testing::{self, Snapshots},
+    Registry, Result,
+};
+use serde_json::json;
+use std::path::PathBuf;
+
+fn snapshots(name: &str) -> (PathBuf, Snapshots) {
+    let dir = std::env::temp_dir().join(format!("bracket-testing-{}", name));
+    let _ = std::fs::remove_dir_all(&dir);
+    let mut snapshots = Snapshots::new(&dir);
+    snapshots.set_update(false);
+    (dir, snapshots)
+}
+
+#[test]
+fn testing_update_and_match() -> Result<()> {
+    let (dir, mut snapshots) = snapshots("update");
+    let mut registry = Registry::new();
+    registry.insert("card", "<h1>{{title}}</h1>")?;
+    let data = json!({"title": "Hello"});
+
+    assert!(snapshots.check("card", "<h1>Hello</h1>")?.is_some());
+
+    snapshots.set_update(true);
+    snapshots.assert_render(&registry, "card", &data)?;
+    assert!(dir.join("card.snap").exists());
+
+    snapshots.set_update(false);
+    snapshots.assert_render(&registry, "card", &data)?;
+    Ok(())
+}
+
+#[test]
+fn testing_mismatch() -> Result<()> {
+    let (_, mut snapshots) = snapshots("mismatch");
+    snapshots.set_update(true);
+    snapshots.assert("page", "a\nb\nc")?;
+    snapshots.set_update(false);
+    let diff = snapshots.check("page", "a\nB\nc")?.unwrap();
+    assert!(diff.contains("-   2 | b\n+   2 | B\n"));
+    Ok(())
+}
+
+#[test]
+#[should_panic]
+fn testing_assert_panics() {
+    let (_, mut snapshots) = snapshots("panics");
+    snapshots.set_update(true);
+    snapshots.assert("page", "foo").unwrap();
+    snapshots.set_update(false);
+    snapshots.assert("page", "bar").unwrap();
+}
+
+#[test]
+fn testing_fixture() -> Result<()> {
+    let (dir, mut snapshots) = snapshots("fixture");
+    let mut registry = Registry::new();
+    registry.insert("card", "<h1>{{title}}</h1>")?;
+    snapshots.set_update(true);
+    snapshots.assert_fixture(
+        &registry,
+        "card",
+        "tests/fixtures/testing/hello.json",
+    )?;
+    let output = std::fs::read_to_string(dir.join("card@hello.snap"))?;
+    assert_eq!("<h1>Hello</h1>", &output);
+    Ok(())
+}
+
+#[test]
+fn testing_diff_lines() {
+    let diff = testing::diff_lines("a\nb\nc\nd\ne", "a\nb\nC\nd\ne");
+    assert_eq!("    2 | b\n-   3 | c\n+   3 | C\n    4 | d\n", &diff);
+}
@@ -0,0 +1,83 @@
+#![cfg(feature = "timeago-helper")]
+use bracket::{
+    clock::FixedClock,
+    helper::timeago::{Timeago, Unit},
+    Error, Registry, Result,
+};
+use serde_json::json;
+
+const NAME: &str = "timeago.rs";
+
+#[test]
+fn timeago_past_and_future() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.set_clock(Box::new(FixedClock::new(1_709_285_400)));
+    let data = json!({
+        "times": [
+            1_709_285_390,
+            1_709_285_340,
+            1_709_274_600,
+            1_709_285_400 + 2 * 86_400,
+            1_709_285_400 - 90 * 86_400,
+            1_709_285_400 - 800 * 86_400,
+            "2024-03-01T06:30:00Z",
+            "2024-03-01T10:30:00+04:00",
+            "2024-03-01T09:00:00.250Z"
+        ]
+    });
+    let value = r#"{{#each times}}{{timeago this}};{{/each}}"#;
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!(
+        "just now;1 minute ago;3 hours ago;in 2 days;3 months ago;\
+        2 years ago;3 hours ago;3 hours ago;30 minutes ago;",
+        &result
+    );
+    Ok(())
+}
+
+#[test]
+fn timeago_thresholds() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.set_clock(Box::new(FixedClock::new(100_000)));
+    let value = r#"{{timeago 20000}} {{timeago 20000 hours=24}}"#;
+    let result = registry.once(NAME, value, &json!({}))?;
+    assert_eq!("1 day ago 22 hours ago", &result);
+    Ok(())
+}
+
+#[test]
+fn timeago_locale() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.set_clock(Box::new(FixedClock::new(7_200)));
+    let locale = |unit: Unit, count: u64, future: bool| {
+        let name = match unit {
+            Unit::Hour => "Stunden",
+            _ => "?",
+        };
+        if future {
+            format!("in {} {}", count, name)
+        } else {
+            format!("vor {} {}", count, name)
+        }
+    };
+    registry
+        .helpers_mut()
+        .insert("timeago", Box::new(Timeago::with_locale(Box::new(locale))));
+    let result = registry.once(NAME, r#"{{timeago 0}}"#, &json!({}))?;
+    assert_eq!("vor 2 Stunden", &result);
+    Ok(())
+}
+
+#[test]
+fn timeago_error() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.set_clock(Box::new(FixedClock::new(0)));
+    match registry.once(NAME, r#"{{timeago "yesterday"}}"#, &json!({})) {
+        Err(Error::Render(e)) => assert_eq!(
+            "Helper 'timeago' expects an integer timestamp or an RFC 3339 string",
+            e.to_string()
+        ),
+        _ => panic!("expected a render error"),
+    }
+    Ok(())
+}
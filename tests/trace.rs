@@ -0,0 +1,51 @@
+use bracket::{trace::Trace, Registry, Result};
+use serde_json::json;
+
+const NAME: &str = "trace.rs";
+
+#[test]
+fn trace_partial() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.set_trace(Some(Trace::html()));
+    registry.insert("card", "<p>{{title}}</p>")?;
+    let value = r"{{> card}}";
+    let result = registry.once(NAME, value, &json!({"title": "foo"}))?;
+    assert_eq!("<!-- begin: card --><p>foo</p><!-- end: card -->", &result);
+    Ok(())
+}
+
+#[test]
+fn trace_block() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.set_trace(Some(Trace::html()));
+    let value = r"{{~#each items~}}{{this}}{{/each}}";
+    let result = registry.once(NAME, value, &json!({"items": [1, 2]}))?;
+    assert_eq!(
+        "<!-- begin: #each items -->12<!-- end: #each items -->",
+        &result
+    );
+    Ok(())
+}
+
+#[test]
+fn trace_custom() -> Result<()> {
+    let mut registry = Registry::new();
+    let mut trace = Trace::new("[{kind} {name}]", "[/{kind}]");
+    trace.blocks = false;
+    registry.set_trace(Some(trace));
+    registry.insert("card", "{{#if title}}{{title}}{{/if}}")?;
+    let value = r"{{#> card}}{{/card}}";
+    let result = registry.once(NAME, value, &json!({"title": "foo"}))?;
+    assert_eq!("[partial card]foo[/partial]", &result);
+    Ok(())
+}
+
+#[test]
+fn trace_disabled() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.insert("card", "{{title}}")?;
+    let value = r"{{> card}}";
+    let result = registry.once(NAME, value, &json!({"title": "foo"}))?;
+    assert_eq!("foo", &result);
+    Ok(())
+}
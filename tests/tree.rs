@@ -0,0 +1,73 @@
+#![cfg(feature = "tree-helper")]
+use bracket::{Registry, Result};
+use serde_json::json;
+
+const NAME: &str = "tree.rs";
+
+#[test]
+fn tree_nested() -> Result<()> {
+    let registry = Registry::new();
+    let data = json!({
+        "menu": [
+            {"title": "Home"},
+            {"title": "Docs", "items": [
+                {"title": "Guide"},
+                {"title": "API", "items": [{"title": "Registry"}]}
+            ]}
+        ]
+    });
+    let value = r#"<ul>{{#tree menu children="items"}}<li>{{title}}{{#unless @leaf}}<ul>{{{@children}}}</ul>{{/unless}}</li>{{/tree}}</ul>"#;
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!(
+        concat!(
+            "<ul><li>Home</li><li>Docs<ul><li>Guide</li>",
+            "<li>API<ul><li>Registry</li></ul></li></ul></li></ul>"
+        ),
+        &result
+    );
+    Ok(())
+}
+
+#[test]
+fn tree_locals() -> Result<()> {
+    let registry = Registry::new();
+    let data = json!({
+        "root": {"name": "a", "children": [
+            {"name": "b"},
+            {"name": "c", "children": [{"name": "d"}]}
+        ]}
+    });
+    let value = r#"{{#tree root}}[{{@path}}:{{@depth}}:{{name}}{{#if @last}}!{{/if}}]{{{@children}}}{{/tree}}"#;
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!("[0:0:a!][0.0:1:b][0.1:1:c!][0.1.0:2:d!]", &result);
+    Ok(())
+}
+
+#[test]
+fn tree_depth_err() -> Result<()> {
+    let registry = Registry::new();
+    let mut node = json!({"t": "leaf"});
+    for _ in 0..200 {
+        node = json!({"t": "node", "children": [node]});
+    }
+    let value = r#"{{#tree menu}}{{t}}{{{@children}}}{{/tree}}"#;
+    match registry.once(NAME, value, &json!({ "menu": node })) {
+        Ok(_) => panic!("Depth error expected"),
+        Err(e) => assert_eq!(
+            "Helper 'tree' nodes are nested deeper than 128",
+            e.to_string()
+        ),
+    }
+    Ok(())
+}
+
+#[test]
+fn tree_scope_children() -> Result<()> {
+    let registry = Registry::new();
+    let data = json!({"root": {"t": "a", "children": [{"t": "b"}]}});
+    let value =
+        r#"{{#tree root}}{{t}}:{{children.length}}[{{{@children}}}]{{/tree}}"#;
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!("a:[b:[]]", &result);
+    Ok(())
+}
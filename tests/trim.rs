@@ -124,3 +124,27 @@ bar
     assert_eq!("bar", &result);
     Ok(())
 }
+
+#[test]
+fn trim_grapheme() -> Result<()> {
+    let registry = Registry::new();
+    // The combining acute accent forms a single grapheme with the
+    // space so it must not be trimmed away from the accent
+    let value = "{{foo~}} \u{301}x";
+    let data = json!({"foo": "bar"});
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!("bar \u{301}x", &result);
+    let value = "\u{3000}名前\u{3000}\n{{~foo~}}\r\n\u{3000}";
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!("\u{3000}名前bar", &result);
+    Ok(())
+}
+
+#[test]
+fn trim_functions() {
+    assert_eq!("\u{301}x ", bracket::trim::trim_start("\u{301}x "));
+    assert_eq!(" \u{301}x", bracket::trim::trim_start(" \u{301}x"));
+    assert_eq!("x", bracket::trim::trim_start("\r\n\u{a0}x"));
+    assert_eq!("x", bracket::trim::trim_end("x\u{2003}\r\n"));
+    assert_eq!("", bracket::trim::trim_end(" \t\n"));
+}
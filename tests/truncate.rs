@@ -0,0 +1,60 @@
+#![cfg(feature = "truncate-helper")]
+use bracket::{Error, Registry, Result};
+use serde_json::json;
+
+const NAME: &str = "truncate.rs";
+
+#[test]
+fn truncate_html_characters() -> Result<()> {
+    let registry = Registry::new();
+    let data = json!({
+        "body": r#"<p>Fish &amp; <a href="/c?a=1&b=>">chips</a> are <em>great</em></p>"#
+    });
+    let result = registry.once(NAME, r#"{{truncate_html body 10}}"#, &data)?;
+    assert_eq!(
+        r#"<p>Fish &amp; <a href="/c?a=1&b=>">chi…</a></p>"#,
+        &result
+    );
+    let result = registry.once(NAME, r#"{{truncate_html body 24}}"#, &data)?;
+    assert_eq!(data["body"].as_str().unwrap(), &result);
+    let value = r#"{{truncate_html body 12 ellipsis="..."}}"#;
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!(
+        r#"<p>Fish &amp; <a href="/c?a=1&b=>">chips...</a></p>"#,
+        &result
+    );
+    Ok(())
+}
+
+#[test]
+fn truncate_html_words_block() -> Result<()> {
+    let registry = Registry::new();
+    let data = json!({"name": "world"});
+    let value = r#"{{#truncate_html 3 words=true}}<div>Hello <b>{{name}}</b><br> how <i>are</i> you</div>{{/truncate_html}}"#;
+    let result = registry.once(NAME, value, &data)?;
+    assert_eq!("<div>Hello <b>world</b><br> how…</div>", &result);
+    Ok(())
+}
+
+#[test]
+fn truncate_html_graphemes() -> Result<()> {
+    let registry = Registry::new();
+    let data = json!({"body": "<span>e\u{301}te\u{301}</span>"});
+    let result = registry.once(NAME, r#"{{truncate_html body 2}}"#, &data)?;
+    assert_eq!("<span>e\u{301}t…</span>", &result);
+    Ok(())
+}
+
+#[test]
+fn truncate_html_error() -> Result<()> {
+    let registry = Registry::new();
+    match registry.once(NAME, r#"{{truncate_html "<p>a</p>" -1}}"#, &json!({}))
+    {
+        Err(Error::Render(e)) => assert_eq!(
+            "Helper 'truncate_html' expects a positive integer limit",
+            e.to_string()
+        ),
+        _ => panic!("expected a render error"),
+    }
+    Ok(())
+}
@@ -0,0 +1,64 @@
+use bracket::{variables::Usage, Registry, Result};
+use serde_json::json;
+
+const NAME: &str = "variables.rs";
+
+#[test]
+fn variables_paths() -> Result<()> {
+    let registry = Registry::new();
+    let template = registry.parse(
+        NAME,
+        r#"{{title}}{{#each items}}{{name}}{{@index}}{{../title}}{{/each}}{{#with user}}{{this.email}}{{@root.site}}{{/with}}{{#if (eq kind "a")}}{{else}}{{fallback}}{{/if}}"#,
+    )?;
+    let vars: Vec<(String, Usage)> = template
+        .variables()
+        .iter()
+        .map(|v| (v.path.to_string(), v.usage))
+        .collect();
+    assert_eq!(
+        vec![
+            ("title".to_string(), Usage::Value),
+            ("items".to_string(), Usage::Iterated),
+            ("items.0.name".to_string(), Usage::Value),
+            ("title".to_string(), Usage::Value),
+            ("user".to_string(), Usage::Scoped),
+            ("user.email".to_string(), Usage::Value),
+            ("site".to_string(), Usage::Value),
+            ("kind".to_string(), Usage::Value),
+            ("fallback".to_string(), Usage::Value),
+        ],
+        vars
+    );
+    Ok(())
+}
+
+#[test]
+fn variables_skeleton() -> Result<()> {
+    let registry = Registry::new();
+    let template = registry.parse(
+        NAME,
+        r#"{{title}}{{#each items}}<li>{{name}}</li>{{/each}}{{#each tags}}{{this}}{{/each}}{{#if published}}{{#with author}}{{name}}{{/with}}{{/if}}{{format price}}"#,
+    )?;
+    assert_eq!(
+        json!({
+            "title": "title",
+            "items": [{"name": "name"}],
+            "tags": ["tags"],
+            "published": true,
+            "author": {"name": "name"},
+            "price": "price",
+        }),
+        template.skeleton()
+    );
+    Ok(())
+}
+
+#[test]
+fn variables_preview() -> Result<()> {
+    let registry = Registry::new();
+    let value = r"{{#each items}}<li>{{name}}</li>{{/each}}";
+    let template = registry.parse(NAME, value)?;
+    let result = registry.once(NAME, value, &template.skeleton())?;
+    assert_eq!("<li>name</li>", &result);
+    Ok(())
+}
@@ -0,0 +1,90 @@
+use bracket::{Error, Registry, Result};
+use serde_json::json;
+
+#[test]
+fn variant_heaviest() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.register_variant("welcome", "control", "Hello {{name}}", 90)?;
+    registry.register_variant("welcome", "v2", "Hi {{name}}!", 10)?;
+    let result = registry.render("welcome", &json!({"name": "world"}))?;
+    assert_eq!("Hello world", &result);
+    let variants = registry.variants("welcome").unwrap();
+    assert_eq!(2, variants.len());
+    assert_eq!(100, variants.total_weight());
+    Ok(())
+}
+
+#[test]
+fn variant_selector() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.register_variant("welcome", "control", "Hello {{name}}", 90)?;
+    registry.register_variant("welcome", "v2", "Hi {{name}}!", 10)?;
+    registry.set_variant_selector(Box::new(|name, variants, data| {
+        assert_eq!("welcome", name);
+        let bucket = data.get("bucket").and_then(|v| v.as_u64())?;
+        variants.pick(bucket).map(|v| v.name().to_string())
+    }));
+    let control = json!({"name": "a", "bucket": 89});
+    let v2 = json!({"name": "b", "bucket": 90});
+    let fallback = json!({"name": "c"});
+    assert_eq!("Hello a", registry.render("welcome", &control)?);
+    assert_eq!("Hi b!", registry.render("welcome", &v2)?);
+    assert_eq!("Hello c", registry.render("welcome", &fallback)?);
+    Ok(())
+}
+
+#[test]
+fn variant_partial() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.register_variant("header", "a", "<h1>{{title}}</h1>", 1)?;
+    registry.register_variant("header", "b", "<h2>{{title}}</h2>", 1)?;
+    registry.insert("page", "{{> header}}")?;
+    registry.set_variant_selector(Box::new(|_, _, data| {
+        data.get("variant")
+            .and_then(|v| v.as_str())
+            .map(String::from)
+    }));
+    let data = json!({"title": "Page", "variant": "b"});
+    assert_eq!("<h2>Page</h2>", registry.render("page", &data)?);
+    Ok(())
+}
+
+#[test]
+fn variant_template_precedence() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.insert("welcome", "template")?;
+    registry.register_variant("welcome", "v2", "variant", 1)?;
+    assert_eq!("template", registry.render("welcome", &json!({}))?);
+    registry.remove("welcome");
+    assert_eq!("variant", registry.render("welcome", &json!({}))?);
+    assert!(registry.remove_variants("welcome").is_some());
+    match registry.render("welcome", &json!({})) {
+        Err(Error::TemplateNotFound(name)) => assert_eq!("welcome", name),
+        _ => panic!("expected template not found"),
+    }
+    Ok(())
+}
+
+#[test]
+fn variant_replace() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.register_variant("welcome", "v1", "one", 1)?;
+    registry.register_variant("welcome", "v1", "two", 3)?;
+    let variants = registry.variants("welcome").unwrap();
+    assert_eq!(1, variants.len());
+    assert_eq!(3, variants.get("v1").unwrap().weight());
+    assert_eq!("two", registry.render("welcome", &json!({}))?);
+    Ok(())
+}
+
+#[test]
+fn variant_render_variants() -> Result<()> {
+    let mut registry = Registry::new();
+    registry.register_variant("w", "a", "A{{@n}}", 1)?;
+    let mut locals = serde_json::Map::new();
+    locals.insert("n".to_string(), json!(1));
+    assert_eq!("A1", registry.render_with_locals("w", &json!({}), &locals)?);
+    let (result, _) = registry.render_with_report("w", &json!({}))?;
+    assert_eq!("A", &result);
+    Ok(())
+}
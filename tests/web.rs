@@ -0,0 +1,91 @@
+#![cfg(any(feature = "axum", feature = "actix-web", feature = "rocket"))]
+use bracket::{escape, web::Template, Registry};
+use serde_json::json;
+use std::sync::Arc;
+
+fn registry(html: bool) -> Arc<Registry<'static>> {
+    let mut registry = Registry::new();
+    if !html {
+        registry.set_escape_write(Box::new(escape::noop_write));
+    }
+    registry.insert("index", "Hello {{name}}!").unwrap();
+    Arc::new(registry)
+}
+
+#[test]
+fn web_content_type() {
+    let html = Template::new(registry(true), "index", json!({}));
+    assert_eq!(bracket::web::HTML, html.content_type());
+    let text = Template::new(registry(false), "index", json!({}));
+    assert_eq!(bracket::web::TEXT, text.content_type());
+}
+
+#[cfg(feature = "axum")]
+#[test]
+fn web_axum() {
+    use axum::{http::header, http::StatusCode, response::IntoResponse};
+
+    let data = json!({"name": "world"});
+    let res = Template::new(registry(true), "index", data).into_response();
+    assert_eq!(StatusCode::OK, res.status());
+    assert_eq!(
+        bracket::web::HTML,
+        res.headers().get(header::CONTENT_TYPE).unwrap()
+    );
+
+    let res =
+        Template::new(registry(true), "missing", json!({})).into_response();
+    assert_eq!(StatusCode::INTERNAL_SERVER_ERROR, res.status());
+}
+
+#[cfg(feature = "actix-web")]
+#[test]
+fn web_actix() {
+    use actix_web::{
+        http::header, http::StatusCode, test::TestRequest, Responder,
+    };
+
+    let req = TestRequest::default().to_http_request();
+    let data = json!({"name": "world"});
+    let res = Template::new(registry(false), "index", data).respond_to(&req);
+    assert_eq!(StatusCode::OK, res.status());
+    assert_eq!(
+        bracket::web::TEXT,
+        res.headers().get(header::CONTENT_TYPE).unwrap()
+    );
+
+    let res =
+        Template::new(registry(false), "missing", json!({})).respond_to(&req);
+    assert_eq!(StatusCode::INTERNAL_SERVER_ERROR, res.status());
+}
+
+#[cfg(feature = "rocket")]
+#[rocket::get("/<name>")]
+fn page(
+    registry: &rocket::State<Arc<Registry<'static>>>,
+    name: &str,
+) -> Template {
+    Template::new(Arc::clone(registry), name, json!({"name": "world"}))
+}
+
+#[cfg(feature = "rocket")]
+#[test]
+fn web_rocket() {
+    use bracket::web::rocket::Templates;
+    use rocket::{
+        http::ContentType, http::Status, local::blocking::Client, routes,
+    };
+
+    let rocket = rocket::build()
+        .attach(Templates::new(registry(true)))
+        .mount("/", routes![page]);
+    let client = Client::tracked(rocket).unwrap();
+
+    let res = client.get("/index").dispatch();
+    assert_eq!(Status::Ok, res.status());
+    assert_eq!(Some(ContentType::HTML), res.content_type());
+    assert_eq!("Hello world!", res.into_string().unwrap());
+
+    let res = client.get("/missing").dispatch();
+    assert_eq!(Status::InternalServerError, res.status());
+}